@@ -4,7 +4,6 @@ import 'dart:io';
 import 'package:appflowy/generated/locale_keys.g.dart';
 import 'package:appflowy/plugins/document/application/document_data_pb_extension.dart';
 import 'package:appflowy/plugins/document/presentation/editor_plugins/migration/editor_migration.dart';
-import 'package:appflowy/shared/markdown_to_document.dart';
 import 'package:appflowy/startup/startup.dart';
 import 'package:appflowy/workspace/application/settings/share/import_service.dart';
 import 'package:appflowy/workspace/presentation/home/menu/sidebar/import/import_type.dart';
@@ -171,7 +170,6 @@ class _ImportPanelState extends State<ImportPanel> {
           );
           break;
         case ImportType.historyDocument:
-        case ImportType.markdownOrText:
           final data = await File(path).readAsString();
           final bytes = _documentDataFrom(importType, data);
           if (bytes != null) {
@@ -180,10 +178,20 @@ class _ImportPanelState extends State<ImportPanel> {
                 ..name = name
                 ..data = bytes
                 ..viewLayout = ViewLayoutPB.Document
-                ..importType = ImportTypePB.Markdown,
+                ..importType = ImportTypePB.HistoryDocument,
             );
           }
           break;
+        case ImportType.markdownOrText:
+          final data = await File(path).readAsString();
+          importValues.add(
+            ImportValuePayloadPB.create()
+              ..name = name
+              ..data = utf8.encode(data)
+              ..viewLayout = ViewLayoutPB.Document
+              ..importType = ImportTypePB.Markdown,
+          );
+          break;
         case ImportType.csv:
           final data = await File(path).readAsString();
           importValues.add(
@@ -226,9 +234,6 @@ Uint8List? _documentDataFrom(ImportType importType, String data) {
     case ImportType.historyDocument:
       final document = EditorMigration.migrateDocument(data);
       return DocumentDataPBFromTo.fromDocument(document)?.writeToBuffer();
-    case ImportType.markdownOrText:
-      final document = customMarkdownToDocument(data);
-      return DocumentDataPBFromTo.fromDocument(document)?.writeToBuffer();
     default:
       assert(false, 'Unsupported Type $importType');
       return null;
@@ -33,6 +33,29 @@ impl EventIntegrationTest {
       .unwrap()
   }
 
+  pub async fn export_xlsx(&self, view_id: &str) -> Vec<u8> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::ExportXLSX)
+      .payload(DatabaseViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<DatabaseExportDataPB>()
+      .bytes
+  }
+
+  pub async fn preview_csv_import(&self, content: &str) -> CSVImportPreviewPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::PreviewCSVImport)
+      .payload(CSVImportPreviewPayloadPB {
+        content: content.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<CSVImportPreviewPB>()
+  }
+
   /// The initial data can refer to the [FolderOperationHandler::create_view_with_view_data] method.
   pub async fn create_grid(&self, parent_id: &str, name: String, initial_data: Vec<u8>) -> ViewPB {
     let payload = CreateViewPayloadPB {
@@ -162,6 +185,18 @@ impl EventIntegrationTest {
       .await;
   }
 
+  pub async fn update_field_type_option(
+    &self,
+    changeset: TypeOptionChangesetPB,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::UpdateFieldTypeOption)
+      .payload(changeset)
+      .async_send()
+      .await
+      .error()
+  }
+
   pub async fn delete_field(&self, view_id: &str, field_id: &str) -> Option<FlowyError> {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::DeleteField)
@@ -516,6 +551,7 @@ impl EventIntegrationTest {
       .event(DatabaseEvent::GetAllCalendarEvents)
       .payload(CalendarEventRequestPB {
         view_id: view_id.to_string(),
+        ..Default::default()
       })
       .async_send()
       .await
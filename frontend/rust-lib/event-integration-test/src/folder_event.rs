@@ -328,6 +328,22 @@ impl EventIntegrationTest {
       .items
   }
 
+  pub async fn export_view_to_html(
+    &self,
+    view_id: &str,
+    output_dir: &str,
+  ) -> ExportViewToHtmlResponsePB {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::ExportViewToHtml)
+      .payload(ExportViewToHtmlPayloadPB {
+        view_id: view_id.to_string(),
+        output_dir: output_dir.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<ExportViewToHtmlResponsePB>()
+  }
+
   pub async fn get_view_ancestors(&self, view_id: &str) -> Vec<ViewPB> {
     EventBuilder::new(self.clone())
       .event(FolderEvent::GetViewAncestors)
@@ -1,4 +1,5 @@
 use std::convert::TryFrom;
+use std::time::Duration;
 
 use bytes::Bytes;
 
@@ -8,7 +9,8 @@ use flowy_database2::entities::{
   CellChangesetPB, CellIdPB, CheckboxCellDataPB, ChecklistCellDataChangesetPB,
   ChecklistCellInsertPB, DatabaseLayoutPB, DatabaseSettingChangesetPB, DatabaseViewIdPB,
   DateCellChangesetPB, FieldType, OrderObjectPositionPB, RelationCellChangesetPB,
-  SelectOptionCellDataPB, UpdateRowMetaChangesetPB,
+  RelationTypeOptionPB, RollupCalculationTypePB, RollupTypeOptionPB, SelectOptionCellDataPB,
+  TypeOptionChangesetPB, UpdateRowMetaChangesetPB,
 };
 use lib_infra::util::timestamp;
 
@@ -915,3 +917,107 @@ async fn get_detailed_relation_cell_data() {
   assert_eq!(rows.len(), 1);
   assert_eq!(rows[0].name, "hello world");
 }
+
+#[tokio::test]
+async fn rollup_related_rows_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+
+  let milestone_view = test
+    .create_grid(&current_workspace.id, "milestones".to_owned(), vec![])
+    .await;
+  let task_view = test
+    .create_grid(&current_workspace.id, "tasks".to_owned(), vec![])
+    .await;
+  let milestone_database = test.get_database(&milestone_view.id).await;
+  let estimate_field = test.create_field(&task_view.id, FieldType::Number).await;
+  let task_database = test.get_database(&task_view.id).await;
+
+  // link the milestone to the first two tasks
+  let relation_field = test
+    .create_field(&milestone_view.id, FieldType::Relation)
+    .await;
+  test
+    .update_field_type_option(TypeOptionChangesetPB {
+      view_id: milestone_view.id.clone(),
+      field_id: relation_field.id.clone(),
+      type_option_data: RelationTypeOptionPB {
+        database_id: task_database.id.clone(),
+      }
+      .try_into()
+      .unwrap(),
+    })
+    .await;
+  let rollup_field = test
+    .create_field(&milestone_view.id, FieldType::Rollup)
+    .await;
+  test
+    .update_field_type_option(TypeOptionChangesetPB {
+      view_id: milestone_view.id.clone(),
+      field_id: rollup_field.id.clone(),
+      type_option_data: RollupTypeOptionPB {
+        relation_field_id: relation_field.id.clone(),
+        target_field_id: estimate_field.id.clone(),
+        calculation_type: RollupCalculationTypePB::Sum,
+      }
+      .try_into()
+      .unwrap(),
+    })
+    .await;
+
+  for (row, estimate) in task_database.rows.iter().zip(["3", "4", "5"]) {
+    test
+      .update_cell(CellChangesetPB {
+        view_id: task_view.id.clone(),
+        row_id: row.id.clone(),
+        field_id: estimate_field.id.clone(),
+        cell_changeset: estimate.to_string(),
+      })
+      .await;
+  }
+  test
+    .update_relation_cell(RelationCellChangesetPB {
+      view_id: milestone_view.id.clone(),
+      cell_id: CellIdPB {
+        view_id: milestone_view.id.clone(),
+        field_id: relation_field.id.clone(),
+        row_id: milestone_database.rows[0].id.clone(),
+      },
+      inserted_row_ids: vec![
+        task_database.rows[0].id.clone(),
+        task_database.rows[1].id.clone(),
+      ],
+      ..Default::default()
+    })
+    .await;
+
+  // rollups are refreshed in the background
+  tokio::time::sleep(Duration::from_millis(500)).await;
+  let total = test
+    .get_text_cell(
+      &milestone_view.id,
+      &milestone_database.rows[0].id,
+      &rollup_field.id,
+    )
+    .await;
+  assert_eq!(total, "7");
+
+  // editing a related row invalidates the rollup
+  test
+    .update_cell(CellChangesetPB {
+      view_id: task_view.id.clone(),
+      row_id: task_database.rows[1].id.clone(),
+      field_id: estimate_field.id.clone(),
+      cell_changeset: "10".to_string(),
+    })
+    .await;
+  tokio::time::sleep(Duration::from_millis(500)).await;
+  let total = test
+    .get_text_cell(
+      &milestone_view.id,
+      &milestone_database.rows[0].id,
+      &rollup_field.id,
+    )
+    .await;
+  assert_eq!(total, "13");
+}
@@ -150,9 +150,9 @@ async fn apply_text_delta_test() {
 }
 
 macro_rules! generate_convert_document_test_cases {
-  ($($json:ident, $text:ident, $html:ident),*) => {
+  ($($json:ident, $text:ident, $html:ident, $markdown:ident),*) => {
     [
-        $((ParseTypePB { json: $json, text: $text, html: $html }, ($json, $text, $html))),*
+        $((ParseTypePB { json: $json, text: $text, html: $html, markdown: $markdown }, ($json, $text, $html, $markdown))),*
     ]
   };
 }
@@ -163,12 +163,14 @@ async fn convert_document_test() {
   let view = test.create_document().await;
 
   let test_cases = generate_convert_document_test_cases! {
-    true, true, true,
-    false, true, true,
-    false, false, false
+    true, true, true, true,
+    false, true, true, false,
+    false, false, false, true,
+    false, false, false, false
   };
 
-  for (export_types, (json_assert, text_assert, html_assert)) in test_cases.iter() {
+  for (export_types, (json_assert, text_assert, html_assert, markdown_assert)) in test_cases.iter()
+  {
     let copy_payload = ConvertDocumentPayloadPB {
       document_id: view.id.to_string(),
       range: None,
@@ -178,6 +180,7 @@ async fn convert_document_test() {
     assert_eq!(result.json.is_some(), *json_assert);
     assert_eq!(result.text.is_some(), *text_assert);
     assert_eq!(result.html.is_some(), *html_assert);
+    assert_eq!(result.markdown.is_some(), *markdown_assert);
   }
 }
 
@@ -226,6 +229,17 @@ async fn convert_data_to_json_test() {
   };
   let result = test.convert_data_to_json(payload).await;
 
+  assert!(serde_json::from_str::<NestedBlock>(&result.json)
+    .unwrap()
+    .eq(&expect_json));
+
+  let markdown = "Hello\n\nWorld!";
+  let payload = ConvertDataToJsonPayloadPB {
+    data: markdown.to_string(),
+    input_type: InputType::Markdown,
+  };
+  let result = test.convert_data_to_json(payload).await;
+
   assert!(serde_json::from_str::<NestedBlock>(&result.json)
     .unwrap()
     .eq(&expect_json));
@@ -0,0 +1,46 @@
+use std::env::temp_dir;
+use std::fs;
+
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::ViewLayoutPB;
+
+#[tokio::test]
+async fn export_view_to_html_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace = test.get_current_workspace().await;
+  let handbook = test
+    .create_and_open_document(&workspace.id, "Handbook".to_string(), vec![])
+    .await;
+  test
+    .insert_document_text(&handbook.id, "Welcome <everyone>", 0)
+    .await;
+  let onboarding = test
+    .create_and_open_document(&handbook.id, "Onboarding".to_string(), vec![])
+    .await;
+  let members = test
+    .create_view_with_layout(&handbook.id, "Members".to_string(), ViewLayoutPB::Grid)
+    .await;
+
+  let output_dir = temp_dir().join(uuid::Uuid::new_v4().to_string());
+  let response = test
+    .export_view_to_html(&handbook.id, output_dir.to_str().unwrap())
+    .await;
+  assert_eq!(response.page_count, 3);
+
+  // The exported view is the index page, whose navigation links to the child views
+  let index = fs::read_to_string(&response.index_file_path).unwrap();
+  assert!(response.index_file_path.ends_with("index.html"));
+  assert!(index.contains("<title>Handbook</title>"));
+  assert!(index.contains("Welcome &lt;everyone&gt;"));
+  let onboarding_file_name = format!("onboarding-{}.html", onboarding.id);
+  let members_file_name = format!("members-{}.html", members.id);
+  assert!(index.contains(&format!("<a href=\"{}\">", onboarding_file_name)));
+  assert!(index.contains(&format!("<a href=\"{}\">", members_file_name)));
+
+  // The database is exported as a table
+  let members_page = fs::read_to_string(output_dir.join(&members_file_name)).unwrap();
+  assert!(members_page.contains("<a href=\"index.html\">Handbook</a>"));
+  assert!(members_page.contains("<table>"));
+
+  fs::remove_dir_all(output_dir).unwrap();
+}
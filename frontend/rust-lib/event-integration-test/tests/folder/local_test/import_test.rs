@@ -1,7 +1,9 @@
 use crate::util::unzip;
 use event_integration_test::EventIntegrationTest;
 use flowy_core::DEFAULT_NAME;
+use flowy_database2::entities::{CellChangesetPB, FieldType};
 use flowy_folder::entities::{ImportPayloadPB, ImportTypePB, ImportValuePayloadPB, ViewLayoutPB};
+use lib_dispatch::prelude::ToBytes;
 
 #[tokio::test]
 async fn import_492_row_csv_file_test() {
@@ -44,6 +46,89 @@ async fn import_10240_row_csv_file_test() {
   drop(cleaner);
 }
 
+#[tokio::test]
+async fn import_csv_with_edited_preview_test() {
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+
+  let csv_string = "Name,Price,Status\nWrite,$1,Todo\nReview,$2,Todo\nShip,$3,Done";
+  let mut preview = test.preview_csv_import(csv_string).await;
+  assert_eq!(preview.columns[1].field_type, FieldType::Number);
+  assert_eq!(preview.columns[2].field_type, FieldType::SingleSelect);
+  preview.columns[1].field_type = FieldType::RichText;
+
+  let workspace_id = test.get_current_workspace().await.id;
+  let mut import_data = gen_import_data(
+    "Prices.csv".to_string(),
+    csv_string.to_string(),
+    workspace_id,
+  );
+  import_data.values[0].csv_preview = Some(preview.into_bytes().unwrap().to_vec());
+  let views = test.import_data(import_data).await;
+
+  let fields = test.get_all_database_fields(&views[0].id).await;
+  let field_types = fields
+    .items
+    .iter()
+    .map(|field| field.field_type)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    field_types,
+    vec![
+      FieldType::RichText,
+      FieldType::RichText,
+      FieldType::SingleSelect
+    ]
+  );
+}
+
+#[tokio::test]
+async fn export_and_import_xlsx_test() {
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+
+  let workspace_id = test.get_current_workspace().await.id;
+  let grid = test
+    .create_grid(&workspace_id, "Grid".to_string(), vec![])
+    .await;
+  let origin = test.get_database(&grid.id).await;
+  // Empty rows at the end of a sheet are dropped, so give every row a name.
+  let primary_field = test.get_primary_field(&grid.id).await;
+  for (index, row) in origin.rows.iter().enumerate() {
+    test
+      .update_cell(CellChangesetPB {
+        view_id: grid.id.clone(),
+        row_id: row.id.clone(),
+        field_id: primary_field.id.clone(),
+        cell_changeset: format!("row {}", index),
+      })
+      .await;
+  }
+  let bytes = test.export_xlsx(&grid.id).await;
+  assert!(!bytes.is_empty());
+
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    values: vec![ImportValuePayloadPB {
+      name: "Grid.xlsx".to_string(),
+      data: Some(bytes),
+      file_path: None,
+      view_layout: ViewLayoutPB::Grid,
+      import_type: ImportTypePB::XLSX,
+      ..Default::default()
+    }],
+  };
+  let views = test.import_data(import_data).await;
+  assert_eq!(views.len(), 1);
+  assert_eq!(views[0].name, "Grid.xlsx");
+
+  let database = test.get_database(&views[0].id).await;
+  assert_eq!(database.rows.len(), origin.rows.len());
+  let fields = test.get_all_database_fields(&views[0].id).await;
+  let origin_fields = test.get_all_database_fields(&grid.id).await;
+  assert_eq!(fields.items.len(), origin_fields.items.len());
+}
+
 fn gen_import_data(file_name: String, csv_string: String, workspace_id: String) -> ImportPayloadPB {
   ImportPayloadPB {
     parent_view_id: workspace_id.clone(),
@@ -53,6 +138,7 @@ fn gen_import_data(file_name: String, csv_string: String, workspace_id: String)
       file_path: None,
       view_layout: ViewLayoutPB::Grid,
       import_type: ImportTypePB::CSV,
+      ..Default::default()
     }],
   }
 }
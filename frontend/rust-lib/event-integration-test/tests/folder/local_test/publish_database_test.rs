@@ -150,6 +150,7 @@ fn gen_import_data(file_name: String, csv_string: String, workspace_id: String)
       file_path: None,
       view_layout: ViewLayoutPB::Grid,
       import_type: ImportTypePB::CSV,
+      ..Default::default()
     }],
   }
 }
@@ -3,3 +3,4 @@ mod helper;
 mod import_af_data_local_test;
 mod user_awareness_test;
 mod user_profile_test;
+mod workspace_test;
@@ -0,0 +1,51 @@
+use event_integration_test::EventIntegrationTest;
+
+#[tokio::test]
+async fn local_create_open_and_delete_workspace_test() {
+  let test = EventIntegrationTest::new().await;
+  let _ = test.init_anon_user().await;
+  let workspaces = test.get_all_workspaces().await.items;
+  assert_eq!(workspaces.len(), 1);
+  let first_workspace = workspaces[0].clone();
+
+  let created_workspace = test.create_workspace("client project").await;
+  assert_eq!(created_workspace.name, "client project");
+  assert_eq!(test.get_all_workspaces().await.items.len(), 2);
+
+  test
+    .rename_workspace(&created_workspace.workspace_id, "client project A")
+    .await
+    .unwrap();
+  test
+    .change_workspace_icon(&created_workspace.workspace_id, "🚀")
+    .await
+    .unwrap();
+  let workspace = test
+    .get_all_workspaces()
+    .await
+    .items
+    .into_iter()
+    .find(|workspace| workspace.workspace_id == created_workspace.workspace_id)
+    .unwrap();
+  assert_eq!(workspace.name, "client project A");
+  assert_eq!(workspace.icon, "🚀");
+
+  // the new workspace has its own folder
+  test.open_workspace(&created_workspace.workspace_id).await;
+  let current_workspace = test.folder_read_current_workspace().await;
+  assert_eq!(current_workspace.id, created_workspace.workspace_id);
+  assert!(test
+    .folder_read_current_workspace_views()
+    .await
+    .items
+    .is_empty());
+
+  // the open workspace can't be deleted
+  test.delete_workspace(&created_workspace.workspace_id).await;
+  assert_eq!(test.get_all_workspaces().await.items.len(), 2);
+
+  test.delete_workspace(&first_workspace.workspace_id).await;
+  let workspaces = test.get_all_workspaces().await.items;
+  assert_eq!(workspaces.len(), 1);
+  assert_eq!(workspaces[0].workspace_id, created_workspace.workspace_id);
+}
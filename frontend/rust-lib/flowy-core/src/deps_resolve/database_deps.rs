@@ -1,17 +1,21 @@
 use appflowy_local_ai::ai_ops::{LocalAITranslateItem, LocalAITranslateRowData};
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
+use collab_plugins::local_storage::kv::{KVEntry, KVStore, KVTransactionDB};
 use flowy_ai::ai_manager::AIManager;
-use flowy_database2::{DatabaseManager, DatabaseUser};
+use flowy_database2::services::snapshot::{DatabaseSnapshotData, DatabaseSnapshotMeta};
+use flowy_database2::{DatabaseManager, DatabaseSnapshotService, DatabaseUser};
 use flowy_database_pub::cloud::{
   DatabaseAIService, DatabaseCloudService, SummaryRowContent, TranslateRowContent,
   TranslateRowResponse,
 };
-use flowy_error::FlowyError;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::ContentIndexManager;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use lib_infra::async_trait::async_trait;
 use lib_infra::priority_task::TaskDispatcher;
 use std::sync::{Arc, Weak};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 
 pub struct DatabaseDepsResolver();
@@ -24,8 +28,9 @@ impl DatabaseDepsResolver {
     cloud_service: Arc<dyn DatabaseCloudService>,
     ai_service: Arc<dyn DatabaseAIService>,
     ai_manager: Arc<AIManager>,
+    database_indexer: Arc<dyn ContentIndexManager>,
   ) -> Arc<DatabaseManager> {
-    let user = Arc::new(DatabaseUserImpl(authenticate_user));
+    let user = Arc::new(DatabaseUserImpl(authenticate_user.clone()));
     Arc::new(DatabaseManager::new(
       user,
       task_scheduler,
@@ -35,6 +40,8 @@ impl DatabaseDepsResolver {
         ai_manager,
         ai_service,
       }),
+      Arc::new(DatabaseSnapshotImpl(authenticate_user)),
+      database_indexer,
     ))
   }
 }
@@ -101,6 +108,123 @@ impl DatabaseAIService for DatabaseAIServiceMiddleware {
   }
 }
 
+/// The number of snapshots kept for each database.
+const MAX_NUM_OF_DATABASE_SNAPSHOTS: usize = 20;
+const DATABASE_SNAPSHOT_KEY_PREFIX: &str = "database_snapshot/";
+
+struct DatabaseSnapshotImpl(Weak<AuthenticateUser>);
+
+impl DatabaseSnapshotImpl {
+  fn get_collab_db(&self) -> FlowyResult<Arc<CollabKVDB>> {
+    let authenticate_user = self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?;
+    let uid = authenticate_user.user_id()?;
+    authenticate_user
+      .get_collab_db(uid)?
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Collab db is not initialized"))
+  }
+}
+
+impl DatabaseSnapshotService for DatabaseSnapshotImpl {
+  fn create_snapshot(&self, database_id: &str, data: Vec<u8>) -> FlowyResult<()> {
+    create_database_snapshot(&self.get_collab_db()?, database_id, data)
+  }
+
+  fn get_snapshot_metas(&self, database_id: &str) -> FlowyResult<Vec<DatabaseSnapshotMeta>> {
+    get_database_snapshot_metas(&self.get_collab_db()?, database_id)
+  }
+
+  fn get_snapshot(&self, snapshot_id: &str) -> FlowyResult<DatabaseSnapshotData> {
+    get_database_snapshot(&self.get_collab_db()?, snapshot_id)?.ok_or(
+      FlowyError::record_not_found().with_context(format!("Snapshot {} not found", snapshot_id)),
+    )
+  }
+}
+
+/// The snapshots of a database are stored in the collab key-value store, next to the collabs of
+/// the database. The id of a snapshot is made of the id of the database and the time in
+/// milliseconds at which it was taken, so the keys of a database are sorted from the oldest
+/// snapshot to the latest.
+pub(crate) fn create_database_snapshot(
+  collab_db: &CollabKVDB,
+  database_id: &str,
+  data: Vec<u8>,
+) -> FlowyResult<()> {
+  let created_at = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_millis())
+    .unwrap_or_default();
+  let snapshot_id = format!("{}/{:020}", database_id, created_at);
+  let metas = get_database_snapshot_metas(collab_db, database_id)?;
+  let num_of_outdated = (metas.len() + 1).saturating_sub(MAX_NUM_OF_DATABASE_SNAPSHOTS);
+
+  let write_txn = collab_db.write_txn();
+  write_txn.insert(database_snapshot_key(&snapshot_id), data)?;
+  // The metas are sorted from the latest to the oldest
+  for meta in metas.iter().rev().take(num_of_outdated) {
+    write_txn.remove(database_snapshot_key(&meta.snapshot_id).as_bytes())?;
+  }
+  write_txn.commit_transaction()?;
+  Ok(())
+}
+
+/// Returns the snapshots of the database, the latest first.
+pub(crate) fn get_database_snapshot_metas(
+  collab_db: &CollabKVDB,
+  database_id: &str,
+) -> FlowyResult<Vec<DatabaseSnapshotMeta>> {
+  // All the keys of the database start with `{prefix}{database_id}/`, and '0' comes after '/'
+  let from = format!("{}{}/", DATABASE_SNAPSHOT_KEY_PREFIX, database_id);
+  let to = format!("{}{}0", DATABASE_SNAPSHOT_KEY_PREFIX, database_id);
+  let read_txn = collab_db.read_txn();
+  let mut metas = read_txn
+    .range(from.as_bytes()..to.as_bytes())?
+    .filter_map(|entry| {
+      let key = std::str::from_utf8(entry.key()).ok()?;
+      let snapshot_id = key.strip_prefix(DATABASE_SNAPSHOT_KEY_PREFIX)?;
+      let (object_id, _) = snapshot_id.rsplit_once('/')?;
+      Some(DatabaseSnapshotMeta {
+        snapshot_id: snapshot_id.to_string(),
+        object_id: object_id.to_string(),
+        created_at: database_snapshot_timestamp(snapshot_id) / 1000,
+      })
+    })
+    .collect::<Vec<_>>();
+  metas.reverse();
+  Ok(metas)
+}
+
+pub(crate) fn get_database_snapshot(
+  collab_db: &CollabKVDB,
+  snapshot_id: &str,
+) -> FlowyResult<Option<DatabaseSnapshotData>> {
+  let object_id = match snapshot_id.rsplit_once('/') {
+    Some((object_id, _)) => object_id.to_string(),
+    None => return Ok(None),
+  };
+  let read_txn = collab_db.read_txn();
+  let data = read_txn.get(database_snapshot_key(snapshot_id))?;
+  Ok(data.map(|data| DatabaseSnapshotData {
+    object_id,
+    data: data.as_ref().to_vec(),
+  }))
+}
+
+/// Returns the time in milliseconds at which the snapshot was taken.
+pub(crate) fn database_snapshot_timestamp(snapshot_id: &str) -> i64 {
+  snapshot_id
+    .rsplit_once('/')
+    .and_then(|(_, created_at)| created_at.parse::<i64>().ok())
+    .unwrap_or_default()
+}
+
+fn database_snapshot_key(snapshot_id: &str) -> String {
+  format!("{}{}", DATABASE_SNAPSHOT_KEY_PREFIX, snapshot_id)
+}
+
 struct DatabaseUserImpl(Weak<AuthenticateUser>);
 impl DatabaseUserImpl {
   fn upgrade_user(&self) -> Result<Arc<AuthenticateUser>, FlowyError> {
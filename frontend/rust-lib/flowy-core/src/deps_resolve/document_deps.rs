@@ -8,6 +8,7 @@ use flowy_document::entities::{DocumentSnapshotData, DocumentSnapshotMeta};
 use flowy_document::manager::{DocumentManager, DocumentSnapshotService, DocumentUserService};
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::ContentIndexManager;
 use flowy_storage_pub::storage::StorageService;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 
@@ -19,6 +20,7 @@ impl DocumentDepsResolver {
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DocumentCloudService>,
     storage_service: Weak<dyn StorageService>,
+    document_indexer: Arc<dyn ContentIndexManager>,
   ) -> Arc<DocumentManager> {
     let user_service: Arc<dyn DocumentUserService> =
       Arc::new(DocumentUserImpl(authenticate_user.clone()));
@@ -29,6 +31,7 @@ impl DocumentDepsResolver {
       cloud_service,
       storage_service,
       snapshot_service,
+      document_indexer,
     ))
   }
 }
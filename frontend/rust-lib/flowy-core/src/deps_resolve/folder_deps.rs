@@ -5,20 +5,24 @@ use collab_folder::hierarchy_builder::NestedViewBuilder;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_ai::ai_manager::AIManager;
-use flowy_database2::entities::DatabaseLayoutPB;
-use flowy_database2::services::share::csv::CSVFormat;
+use flowy_database2::entities::{CSVImportPreviewPB, CSVImportReportPB, DatabaseLayoutPB};
+use flowy_database2::notification::{send_notification, DatabaseNotification};
+use flowy_database2::services::share::csv::{CSVFormat, CSVImportPreview};
 use flowy_database2::template::{make_default_board, make_default_calendar, make_default_grid};
 use flowy_database2::DatabaseManager;
 use flowy_document::entities::DocumentDataPB;
 use flowy_document::manager::DocumentManager;
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::html_export::{image_urls, prepare_html_export, MentionedPage};
 use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_document::parser::markdown::parser::MarkdownToDocumentParser;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_folder::entities::{CreateViewParams, ViewLayoutPB};
 use flowy_folder::manager::{FolderManager, FolderUser};
-use flowy_folder::share::ImportType;
+use flowy_folder::share::{HtmlExportContext, ImportOptions, ImportType};
 use flowy_folder::view_operation::{
   DatabaseEncodedCollab, DocumentEncodedCollab, EncodedCollabWrapper, FolderOperationHandler,
-  FolderOperationHandlers, ImportedData, View, ViewData,
+  FolderOperationHandlers, ImportedData, ImportedView, View, ViewData,
 };
 use flowy_folder::ViewLayout;
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
@@ -28,6 +32,7 @@ use flowy_user::services::data_import::{load_collab_by_object_id, load_collab_by
 use lib_dispatch::prelude::ToBytes;
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
 
@@ -69,10 +74,14 @@ pub fn folder_operation_handlers(
   document_manager: Arc<DocumentManager>,
   database_manager: Arc<DatabaseManager>,
   chat_manager: Arc<AIManager>,
+  storage_path: &str,
 ) -> FolderOperationHandlers {
   let mut map: HashMap<ViewLayout, Arc<dyn FolderOperationHandler + Send + Sync>> = HashMap::new();
 
-  let document_folder_operation = Arc::new(DocumentFolderOperation(document_manager));
+  let document_folder_operation = Arc::new(DocumentFolderOperation {
+    manager: document_manager,
+    storage_path: PathBuf::from(storage_path),
+  });
   map.insert(ViewLayout::Document, document_folder_operation);
 
   let database_folder_operation = Arc::new(DatabaseFolderOperation(database_manager));
@@ -116,7 +125,50 @@ impl FolderUser for FolderUserImpl {
   }
 }
 
-struct DocumentFolderOperation(Arc<DocumentManager>);
+struct DocumentFolderOperation {
+  manager: Arc<DocumentManager>,
+  /// The directory where the application stores its data, including the images that were
+  /// inserted into documents from the local disk.
+  storage_path: PathBuf,
+}
+
+impl DocumentFolderOperation {
+  /// Copies an image of a document to the assets of the exported site. Only images stored in the
+  /// application's data directory are copied from the local disk, every other image is fetched
+  /// from the file storage.
+  async fn export_image(&self, url: &str, context: &mut HtmlExportContext) -> FlowyResult<()> {
+    if context.asset_href(url).is_some() {
+      return Ok(());
+    }
+
+    match self.local_image_path(url) {
+      Some(path) => context.copy_asset(url, &path)?,
+      None => {
+        let data = self.manager.get_file(url.to_string()).await?;
+        context.write_asset(url, &data)?;
+      },
+    }
+    Ok(())
+  }
+
+  /// Returns the path of the image if it is a file inside the application's data directory. The
+  /// paths are canonicalized so that `..` components or symlinks can't point outside of it.
+  fn local_image_path(&self, url: &str) -> Option<PathBuf> {
+    let path = Path::new(url.strip_prefix("file://").unwrap_or(url));
+    if !path.is_absolute() {
+      return None;
+    }
+
+    let path = path.canonicalize().ok()?;
+    let storage_path = self.storage_path.canonicalize().ok()?;
+    if path.starts_with(storage_path) && path.is_file() {
+      Some(path)
+    } else {
+      None
+    }
+  }
+}
+
 #[async_trait]
 impl FolderOperationHandler for DocumentFolderOperation {
   async fn create_workspace_view(
@@ -124,7 +176,7 @@ impl FolderOperationHandler for DocumentFolderOperation {
     uid: i64,
     workspace_view_builder: Arc<RwLock<NestedViewBuilder>>,
   ) -> Result<(), FlowyError> {
-    let manager = self.0.clone();
+    let manager = self.manager.clone();
     let mut write_guard = workspace_view_builder.write().await;
     // Create a view named "Getting started" with an icon ⭐️ and the built-in README data.
     // Don't modify this code unless you know what you are doing.
@@ -148,18 +200,18 @@ impl FolderOperationHandler for DocumentFolderOperation {
   }
 
   async fn open_view(&self, view_id: &str) -> Result<(), FlowyError> {
-    self.0.open_document(view_id).await?;
+    self.manager.open_document(view_id).await?;
     Ok(())
   }
 
   /// Close the document view.
   async fn close_view(&self, view_id: &str) -> Result<(), FlowyError> {
-    self.0.close_document(view_id).await?;
+    self.manager.close_document(view_id).await?;
     Ok(())
   }
 
   async fn delete_view(&self, view_id: &str) -> Result<(), FlowyError> {
-    match self.0.delete_document(view_id).await {
+    match self.manager.delete_document(view_id).await {
       Ok(_) => tracing::trace!("Delete document: {}", view_id),
       Err(e) => tracing::error!("🔴delete document failed: {}", e),
     }
@@ -167,7 +219,7 @@ impl FolderOperationHandler for DocumentFolderOperation {
   }
 
   async fn duplicate_view(&self, view_id: &str) -> Result<Bytes, FlowyError> {
-    let data: DocumentDataPB = self.0.get_document_data(view_id).await?.into();
+    let data: DocumentDataPB = self.manager.get_document_data(view_id).await?.into();
     let data_bytes = data.into_bytes().map_err(|_| FlowyError::invalid_data())?;
     Ok(data_bytes)
   }
@@ -184,7 +236,7 @@ impl FolderOperationHandler for DocumentFolderOperation {
       ViewData::Empty => None,
     };
     let encoded_collab = self
-      .0
+      .manager
       .create_document(user_id, &params.view_id, data.map(|d| d.into()))
       .await?;
     Ok(Some(encoded_collab))
@@ -230,6 +282,36 @@ impl FolderOperationHandler for DocumentFolderOperation {
     }))
   }
 
+  async fn export_view_to_html(
+    &self,
+    view_id: &str,
+    context: &mut HtmlExportContext,
+  ) -> Result<String, FlowyError> {
+    let data = self.manager.get_document_data(view_id).await?;
+    let parser = DocumentDataParser::new(Arc::new(data), None);
+    let mut json = match parser.to_json() {
+      Some(json) => json,
+      None => return Ok(String::new()),
+    };
+
+    for url in image_urls(&json) {
+      if let Err(err) = self.export_image(&url, context).await {
+        // The image keeps its original url, which may still be reachable from the site
+        tracing::warn!("[Export] failed to copy the image {}: {}", url, err);
+      }
+    }
+
+    let mentioned_page = |page_id: &str| {
+      context.view_name(page_id).map(|name| MentionedPage {
+        name,
+        href: context.page_href(page_id),
+      })
+    };
+    let asset_href = |url: &str| context.asset_href(url);
+    prepare_html_export(&mut json, &mentioned_page, &asset_href);
+    Ok(parser.to_html_with_json(&Some(json)))
+  }
+
   /// Create a view with built-in data.
   async fn create_view_with_default_data(
     &self,
@@ -239,7 +321,7 @@ impl FolderOperationHandler for DocumentFolderOperation {
     layout: ViewLayout,
   ) -> Result<(), FlowyError> {
     debug_assert_eq!(layout, ViewLayout::Document);
-    match self.0.create_document(user_id, view_id, None).await {
+    match self.manager.create_document(user_id, view_id, None).await {
       Ok(_) => Ok(()),
       Err(err) => {
         if err.is_already_exists() {
@@ -256,12 +338,16 @@ impl FolderOperationHandler for DocumentFolderOperation {
     uid: i64,
     view_id: &str,
     _name: &str,
-    _import_type: ImportType,
+    import_type: ImportType,
     bytes: Vec<u8>,
+    _options: ImportOptions,
   ) -> Result<Vec<ImportedData>, FlowyError> {
-    let data = DocumentDataPB::try_from(Bytes::from(bytes))?;
+    let data = match import_type {
+      ImportType::Markdown => markdown_document_data(bytes)?,
+      _ => DocumentDataPB::try_from(Bytes::from(bytes))?,
+    };
     let encoded_collab = self
-      .0
+      .manager
       .create_document(uid, view_id, Some(data.into()))
       .await?;
     Ok(vec![(
@@ -271,13 +357,24 @@ impl FolderOperationHandler for DocumentFolderOperation {
     )])
   }
 
-  // will implement soon
   async fn import_from_file_path(
     &self,
-    _view_id: &str,
+    view_id: &str,
     _name: &str,
-    _path: String,
+    import_type: ImportType,
+    path: String,
+    _options: ImportOptions,
   ) -> Result<(), FlowyError> {
+    if !matches!(import_type, ImportType::Markdown) {
+      return Err(FlowyError::not_support());
+    }
+    let markdown = tokio::fs::read_to_string(path).await?;
+    let data = MarkdownToDocumentParser::markdown_to_document(&markdown)?;
+    let uid = self.manager.user_service.user_id()?;
+    self
+      .manager
+      .create_document(uid, view_id, Some(data.into()))
+      .await?;
     Ok(())
   }
 
@@ -286,6 +383,13 @@ impl FolderOperationHandler for DocumentFolderOperation {
   }
 }
 
+/// The Markdown files are imported as they are and converted to the document data here
+fn markdown_document_data(bytes: Vec<u8>) -> FlowyResult<DocumentDataPB> {
+  let markdown = String::from_utf8(bytes)
+    .map_err(|_| FlowyError::invalid_data().with_context("The Markdown file is not UTF-8"))?;
+  MarkdownToDocumentParser::markdown_to_document(&markdown)
+}
+
 struct DatabaseFolderOperation(Arc<DatabaseManager>);
 
 #[async_trait]
@@ -412,6 +516,14 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     .await?
   }
 
+  async fn export_view_to_html(
+    &self,
+    view_id: &str,
+    _context: &mut HtmlExportContext,
+  ) -> Result<String, FlowyError> {
+    self.0.export_html(view_id).await
+  }
+
   async fn duplicate_view(&self, view_id: &str) -> Result<Bytes, FlowyError> {
     Ok(Bytes::from(view_id.to_string()))
   }
@@ -513,6 +625,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     _name: &str,
     import_type: ImportType,
     bytes: Vec<u8>,
+    options: ImportOptions,
   ) -> Result<Vec<ImportedData>, FlowyError> {
     let format = match import_type {
       ImportType::CSV => CSVFormat::Original,
@@ -523,10 +636,23 @@ impl FolderOperationHandler for DatabaseFolderOperation {
       String::from_utf8(bytes).map_err(|err| FlowyError::internal().with_context(err))
     })
     .await??;
-    let result = self
-      .0
-      .import_csv(view_id.to_string(), content, format)
-      .await?;
+    let preview = csv_preview_from_options(&options)?;
+    let result = match (import_type, preview) {
+      (ImportType::ICS, _) => self.0.import_ics(view_id.to_string(), content).await?,
+      // The field types of the columns were chosen by the user in the preview
+      (ImportType::CSV, Some(preview)) => {
+        self
+          .0
+          .import_csv_with_preview(view_id.to_string(), content, preview)
+          .await?
+      },
+      _ => {
+        self
+          .0
+          .import_csv(view_id.to_string(), content, format)
+          .await?
+      },
+    };
     Ok(
       result
         .encoded_collabs
@@ -544,14 +670,68 @@ impl FolderOperationHandler for DatabaseFolderOperation {
 
   async fn import_from_file_path(
     &self,
-    _view_id: &str,
+    view_id: &str,
     _name: &str,
+    import_type: ImportType,
     path: String,
+    options: ImportOptions,
   ) -> Result<(), FlowyError> {
-    self.0.import_csv_from_file(path, CSVFormat::META).await?;
+    if matches!(import_type, ImportType::ICS) {
+      let content = tokio::fs::read_to_string(path).await?;
+      self.0.import_ics(view_id.to_string(), content).await?;
+      return Ok(());
+    }
+
+    let format = match import_type {
+      ImportType::AFDatabase => CSVFormat::META,
+      _ => CSVFormat::Original,
+    };
+    let preview = csv_preview_from_options(&options)?;
+    let import_id = options.import_id.unwrap_or_else(|| view_id.to_string());
+    let report = self
+      .0
+      .import_csv_from_file(view_id.to_string(), &import_id, path, format, preview)
+      .await?;
+    // The view id is generated by the folder, so the report is sent to the id of the caller
+    send_notification(&import_id, DatabaseNotification::DidFinishCSVImport)
+      .payload(CSVImportReportPB::new(view_id, &report))
+      .send();
     Ok(())
   }
 
+  async fn import_views_from_bytes(
+    &self,
+    _uid: i64,
+    import_type: ImportType,
+    bytes: Vec<u8>,
+  ) -> Result<Vec<ImportedView>, FlowyError> {
+    if !matches!(import_type, ImportType::XLSX) {
+      return Err(FlowyError::not_support());
+    }
+
+    let results = self.0.import_xlsx(bytes).await?;
+    Ok(
+      results
+        .into_iter()
+        .map(|(name, result)| ImportedView {
+          view_id: result.view_id,
+          name,
+          imported_data: result
+            .encoded_collabs
+            .into_iter()
+            .map(|encoded| {
+              (
+                encoded.object_id,
+                encoded.collab_type,
+                encoded.encoded_collab,
+              )
+            })
+            .collect(),
+        })
+        .collect(),
+    )
+  }
+
   async fn did_update_view(&self, old: &View, new: &View) -> Result<(), FlowyError> {
     let database_layout = match new.layout {
       ViewLayout::Document | ViewLayout::Chat => {
@@ -578,6 +758,17 @@ impl FolderOperationHandler for DatabaseFolderOperation {
   }
 }
 
+/// Decodes the [CSVImportPreviewPB] that was edited by the user before the import.
+fn csv_preview_from_options(options: &ImportOptions) -> FlowyResult<Option<CSVImportPreview>> {
+  let preview = options
+    .csv_preview
+    .as_ref()
+    .map(|bytes| CSVImportPreviewPB::try_from(Bytes::from(bytes.clone())))
+    .transpose()?
+    .map(CSVImportPreview::from);
+  Ok(preview)
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct CreateDatabaseExtParams {
   database_id: String,
@@ -640,6 +831,7 @@ impl FolderOperationHandler for ChatFolderOperation {
     _name: &str,
     _import_type: ImportType,
     _bytes: Vec<u8>,
+    _options: ImportOptions,
   ) -> Result<Vec<ImportedData>, FlowyError> {
     Err(FlowyError::not_support())
   }
@@ -648,7 +840,9 @@ impl FolderOperationHandler for ChatFolderOperation {
     &self,
     _view_id: &str,
     _name: &str,
+    _import_type: ImportType,
     _path: String,
+    _options: ImportOptions,
   ) -> Result<(), FlowyError> {
     Err(FlowyError::not_support())
   }
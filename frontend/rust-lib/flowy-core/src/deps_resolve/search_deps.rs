@@ -1,4 +1,7 @@
 use flowy_folder::manager::FolderManager;
+use flowy_search::content::indexer::ContentIndexManagerImpl;
+use flowy_search::database::handler::DatabaseSearchHandler;
+use flowy_search::document::handler::DocumentSearchHandler;
 use flowy_search::folder::handler::FolderSearchHandler;
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
 use flowy_search::services::manager::SearchManager;
@@ -9,11 +12,22 @@ pub struct SearchDepsResolver();
 impl SearchDepsResolver {
   pub async fn resolve(
     folder_indexer: Arc<FolderIndexManagerImpl>,
-    _cloud_service: Arc<dyn SearchCloudService>,
-    _folder_manager: Arc<FolderManager>,
+    document_indexer: Arc<ContentIndexManagerImpl>,
+    database_indexer: Arc<ContentIndexManagerImpl>,
+    cloud_service: Arc<dyn SearchCloudService>,
+    folder_manager: Arc<FolderManager>,
   ) -> Arc<SearchManager> {
     let folder_handler = Arc::new(FolderSearchHandler::new(folder_indexer));
-    // let document_handler = Arc::new(DocumentSearchHandler::new(cloud_service, folder_manager));
-    Arc::new(SearchManager::new(vec![folder_handler]))
+    let document_handler = Arc::new(DocumentSearchHandler::new(
+      cloud_service,
+      folder_manager.clone(),
+      document_indexer,
+    ));
+    let database_handler = Arc::new(DatabaseSearchHandler::new(folder_manager, database_indexer));
+    Arc::new(SearchManager::new(vec![
+      folder_handler,
+      document_handler,
+      database_handler,
+    ]))
   }
 }
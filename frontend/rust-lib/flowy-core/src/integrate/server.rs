@@ -1,11 +1,17 @@
 use arc_swap::ArcSwapOption;
 use dashmap::DashMap;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::{Arc, Weak};
 
+use collab::entity::EncodedCollab;
+use collab::preclude::Collab;
+use collab_integrate::{CollabKVAction, CollabKVDB};
+use collab_plugins::local_storage::kv::KVTransactionDB;
 use serde_repr::*;
 
+use flowy_database_pub::cloud::DatabaseSnapshot;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_server::af_cloud::define::ServerUser;
 use flowy_server::af_cloud::AppFlowyCloudServer;
@@ -14,8 +20,15 @@ use flowy_server::{AppFlowyEncryption, AppFlowyServer, EncryptionImpl};
 use flowy_server_pub::af_cloud_config::AFCloudConfiguration;
 use flowy_server_pub::AuthenticatorType;
 use flowy_sqlite::kv::KVStorePreferences;
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use flowy_user::services::sqlite_sql::workspace_sql::{
+  get_all_user_workspace_op, get_user_workspace_op,
+};
 use flowy_user_pub::entities::*;
 
+use crate::deps_resolve::{
+  database_snapshot_timestamp, get_database_snapshot, get_database_snapshot_metas,
+};
 use crate::AppFlowyCoreConfig;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize_repr, Deserialize_repr)]
@@ -59,6 +72,7 @@ pub struct ServerProvider {
   /// The authenticator type of the user.
   authenticator: AtomicU8,
   user: Arc<dyn ServerUser>,
+  authenticate_user: Weak<AuthenticateUser>,
   pub(crate) uid: Arc<ArcSwapOption<i64>>,
 }
 
@@ -68,6 +82,7 @@ impl ServerProvider {
     server: Server,
     store_preferences: Weak<KVStorePreferences>,
     server_user: impl ServerUser + 'static,
+    authenticate_user: Weak<AuthenticateUser>,
   ) -> Self {
     let user = Arc::new(server_user);
     let encryption = EncryptionImpl::new(None);
@@ -80,6 +95,7 @@ impl ServerProvider {
       store_preferences,
       uid: Default::default(),
       user,
+      authenticate_user,
     }
   }
 
@@ -118,6 +134,7 @@ impl ServerProvider {
       Server::Local => {
         let local_db = Arc::new(LocalServerDBImpl {
           storage_path: self.config.storage_path.clone(),
+          authenticate_user: self.authenticate_user.clone(),
         });
         let server = Arc::new(LocalServer::new(local_db));
         Ok::<Arc<dyn AppFlowyServer>, FlowyError>(server)
@@ -174,6 +191,24 @@ pub fn current_server_type() -> Server {
 struct LocalServerDBImpl {
   #[allow(dead_code)]
   storage_path: String,
+  authenticate_user: Weak<AuthenticateUser>,
+}
+
+impl LocalServerDBImpl {
+  fn upgrade_user(&self) -> FlowyResult<Arc<AuthenticateUser>> {
+    self
+      .authenticate_user
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))
+  }
+
+  fn collab_db(&self, uid: i64) -> FlowyResult<Arc<CollabKVDB>> {
+    self
+      .upgrade_user()?
+      .get_collab_db(uid)?
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The collab db is already dropped"))
+  }
 }
 
 impl LocalServerDB for LocalServerDBImpl {
@@ -190,4 +225,90 @@ impl LocalServerDB for LocalServerDBImpl {
         .with_context("LocalServer doesn't support get_user_workspace"),
     )
   }
+
+  fn get_current_uid(&self) -> Result<i64, FlowyError> {
+    self.upgrade_user()?.user_id()
+  }
+
+  fn get_current_workspace_id(&self) -> Result<String, FlowyError> {
+    self.upgrade_user()?.workspace_id()
+  }
+
+  fn get_all_user_workspaces(&self, uid: i64) -> Result<Vec<UserWorkspace>, FlowyError> {
+    let conn = self.upgrade_user()?.get_sqlite_connection(uid)?;
+    get_all_user_workspace_op(uid, conn)
+  }
+
+  fn get_user_workspace_by_id(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+  ) -> Result<Option<UserWorkspace>, FlowyError> {
+    let conn = self.upgrade_user()?.get_sqlite_connection(uid)?;
+    Ok(get_user_workspace_op(workspace_id, conn))
+  }
+
+  fn save_collab(
+    &self,
+    uid: i64,
+    object_id: &str,
+    encoded_collab: EncodedCollab,
+  ) -> Result<(), FlowyError> {
+    let collab_db = self.collab_db(uid)?;
+    let write_txn = collab_db.write_txn();
+    write_txn.flush_doc(
+      uid,
+      object_id,
+      encoded_collab.state_vector.to_vec(),
+      encoded_collab.doc_state.to_vec(),
+    )?;
+    write_txn.commit_transaction()?;
+    Ok(())
+  }
+
+  fn get_collab(&self, uid: i64, object_id: &str) -> Result<Option<EncodedCollab>, FlowyError> {
+    let collab_db = self.collab_db(uid)?;
+    let read_txn = collab_db.read_txn();
+    if !read_txn.is_exist(uid, object_id) {
+      return Ok(None);
+    }
+    let mut collab = Collab::new(uid, object_id, "local_server", vec![], false);
+    read_txn.load_doc_with_txn(uid, object_id, &mut collab.transact_mut())?;
+    let encoded_collab = collab.encode_collab_v1(|_| Ok::<_, FlowyError>(()))?;
+    Ok(Some(encoded_collab))
+  }
+
+  fn delete_collab(&self, uid: i64, object_id: &str) -> Result<(), FlowyError> {
+    let collab_db = self.collab_db(uid)?;
+    let write_txn = collab_db.write_txn();
+    write_txn.delete_doc(uid, object_id)?;
+    write_txn.commit_transaction()?;
+    Ok(())
+  }
+
+  fn get_user_data_dir(&self) -> Result<PathBuf, FlowyError> {
+    self.upgrade_user()?.get_user_data_dir()
+  }
+
+  fn get_database_snapshots(
+    &self,
+    uid: i64,
+    database_id: &str,
+    limit: usize,
+  ) -> Result<Vec<DatabaseSnapshot>, FlowyError> {
+    let collab_db = self.collab_db(uid)?;
+    let metas = get_database_snapshot_metas(&collab_db, database_id)?;
+    let mut snapshots = vec![];
+    for meta in metas.into_iter().take(limit) {
+      if let Some(snapshot) = get_database_snapshot(&collab_db, &meta.snapshot_id)? {
+        snapshots.push(DatabaseSnapshot {
+          snapshot_id: database_snapshot_timestamp(&meta.snapshot_id),
+          database_id: snapshot.object_id,
+          data: snapshot.data,
+          created_at: meta.created_at,
+        });
+      }
+    }
+    Ok(snapshots)
+  }
 }
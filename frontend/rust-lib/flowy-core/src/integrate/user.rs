@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use client_api::entity::billing_dto::SubscriptionPlan;
-use tracing::{event, trace};
+use tracing::{error, event, trace};
 
 use collab_entity::CollabType;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
@@ -10,11 +10,13 @@ use flowy_ai::ai_manager::AIManager;
 use flowy_database2::DatabaseManager;
 use flowy_document::manager::DocumentManager;
 use flowy_error::FlowyResult;
+use flowy_folder::entities::ViewLayoutPB;
 use flowy_folder::manager::{FolderInitDataSource, FolderManager};
 use flowy_storage::manager::StorageManager;
 use flowy_user::event_map::UserStatusCallback;
 use flowy_user_pub::cloud::{UserCloudConfig, UserCloudServiceProvider};
 use flowy_user_pub::entities::{Authenticator, UserProfile, UserWorkspace};
+use lib_dispatch::prelude::af_spawn;
 use lib_infra::async_trait::async_trait;
 
 use crate::integrate::server::{Server, ServerProvider};
@@ -71,6 +73,7 @@ impl UserStatusCallback for UserStatusCallbackImpl {
       .await?;
     self.document_manager.initialize(user_id).await?;
     self.ai_manager.initialize(&user_workspace.id).await?;
+    self.index_local_views();
     Ok(())
   }
 
@@ -200,6 +203,7 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     self.document_manager.initialize(user_id).await?;
     self.ai_manager.initialize(&user_workspace.id).await?;
     self.storage_manager.initialize(&user_workspace.id).await;
+    self.index_local_views();
     Ok(())
   }
 
@@ -236,3 +240,34 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     }
   }
 }
+
+impl UserStatusCallbackImpl {
+  /// Indexes the documents and the databases of the workspace that are not indexed yet, e.g. the
+  /// ones that were never opened on this device, so the local search can find their content.
+  fn index_local_views(&self) {
+    let folder_manager = self.folder_manager.clone();
+    let document_manager = self.document_manager.clone();
+    let database_manager = self.database_manager.clone();
+    af_spawn(async move {
+      let views = match folder_manager.get_all_views_pb().await {
+        Ok(views) => views,
+        Err(err) => {
+          error!("Failed to get the views to index: {}", err);
+          return;
+        },
+      };
+      for view in views {
+        let result = if view.layout == ViewLayoutPB::Document {
+          document_manager.index_document_if_needed(&view.id).await
+        } else if view.layout.is_database() {
+          database_manager.index_database_if_needed(&view.id).await
+        } else {
+          continue;
+        };
+        if let Err(err) = result {
+          trace!("Failed to index view {}: {}", view.id, err);
+        }
+      }
+    });
+  }
+}
@@ -1,5 +1,8 @@
 #![allow(unused_doc_comments)]
 
+use flowy_search::content::indexer::{
+  ContentIndexManagerImpl, DATABASE_INDEX_DIR, DOCUMENT_INDEX_DIR,
+};
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
 use flowy_search::services::manager::SearchManager;
 use std::sync::{Arc, Weak};
@@ -132,6 +135,7 @@ impl AppFlowyCore {
       server_type,
       Arc::downgrade(&store_preference),
       ServerUserImpl(Arc::downgrade(&authenticate_user)),
+      Arc::downgrade(&authenticate_user),
     ));
 
     event!(tracing::Level::DEBUG, "Init managers",);
@@ -168,6 +172,15 @@ impl AppFlowyCore {
         Arc::downgrade(&storage_manager.storage_service),
       );
 
+      let document_indexer = Arc::new(ContentIndexManagerImpl::new(
+        Some(Arc::downgrade(&authenticate_user)),
+        DOCUMENT_INDEX_DIR,
+      ));
+      let database_indexer = Arc::new(ContentIndexManagerImpl::new(
+        Some(Arc::downgrade(&authenticate_user)),
+        DATABASE_INDEX_DIR,
+      ));
+
       let database_manager = DatabaseDepsResolver::resolve(
         Arc::downgrade(&authenticate_user),
         task_dispatcher.clone(),
@@ -175,6 +188,7 @@ impl AppFlowyCore {
         server_provider.clone(),
         server_provider.clone(),
         ai_manager.clone(),
+        database_indexer.clone(),
       )
       .await;
 
@@ -184,6 +198,7 @@ impl AppFlowyCore {
         collab_builder.clone(),
         server_provider.clone(),
         Arc::downgrade(&storage_manager.storage_service),
+        document_indexer.clone(),
       );
 
       let folder_indexer = Arc::new(FolderIndexManagerImpl::new(Some(Arc::downgrade(
@@ -194,6 +209,7 @@ impl AppFlowyCore {
         document_manager.clone(),
         database_manager.clone(),
         ai_manager.clone(),
+        &user_config.storage_path,
       );
 
       let folder_manager = FolderDepsResolver::resolve(
@@ -218,6 +234,8 @@ impl AppFlowyCore {
 
       let search_manager = SearchDepsResolver::resolve(
         folder_indexer,
+        document_indexer,
+        database_indexer,
         server_provider.clone(),
         folder_manager.clone(),
       )
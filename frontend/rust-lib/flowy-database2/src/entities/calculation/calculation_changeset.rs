@@ -20,6 +20,11 @@ pub struct UpdateCalculationChangesetPB {
 
   #[pb(index = 4)]
   pub calculation_type: CalculationType,
+
+  /// The percentile of the [CalculationType::Percentile] calculation, defaults to the median.
+  #[pb(index = 5, one_of)]
+  #[validate(range(min = 0, max = 100))]
+  pub percentile: Option<i64>,
 }
 
 #[derive(Default, ProtoBuf, Validate)]
@@ -21,6 +21,10 @@ pub struct CalculationPB {
 
   #[pb(index = 4)]
   pub value: String,
+
+  /// The percentile of the [CalculationType::Percentile] calculation, between 0 and 100.
+  #[pb(index = 5)]
+  pub percentile: i64,
 }
 
 impl std::convert::From<&CalculationPB> for Calculation {
@@ -32,6 +36,7 @@ impl std::convert::From<&CalculationPB> for Calculation {
       field_id: calculation.field_id.clone(),
       calculation_type,
       value: calculation.value.clone(),
+      percentile: calculation.percentile,
     }
   }
 }
@@ -45,6 +50,7 @@ impl std::convert::From<&Calculation> for CalculationPB {
       field_id: calculation.field_id.clone(),
       calculation_type,
       value: calculation.value.clone(),
+      percentile: calculation.percentile,
     }
   }
 }
@@ -58,6 +64,7 @@ impl std::convert::From<&Arc<Calculation>> for CalculationPB {
       field_id: calculation.field_id.clone(),
       calculation_type,
       value: calculation.value.clone(),
+      percentile: calculation.percentile,
     }
   }
 }
@@ -69,13 +76,24 @@ impl std::convert::From<&Arc<Calculation>> for CalculationPB {
 pub enum CalculationType {
   #[default]
   Average = 0, // Number
-  Max = 1,           // Number
-  Median = 2,        // Number
-  Min = 3,           // Number
-  Sum = 4,           // Number
-  Count = 5,         // All
-  CountEmpty = 6,    // All
-  CountNonEmpty = 7, // All
+  Max = 1,              // Number
+  Median = 2,           // Number
+  Min = 3,              // Number
+  Sum = 4,              // Number
+  Count = 5,            // All
+  CountEmpty = 6,       // All
+  CountNonEmpty = 7,    // All
+  CountUnique = 8,      // All
+  PercentEmpty = 9,     // All
+  PercentNotEmpty = 10, // All
+  CountChecked = 11,    // Checkbox
+  PercentChecked = 12,  // Checkbox
+  EarliestDate = 13,    // Date
+  LatestDate = 14,      // Date
+  DateRange = 15,       // Date
+  StdDev = 16,          // Number
+  Variance = 17,        // Number
+  Percentile = 18,      // Number
 }
 
 impl Display for CalculationType {
@@ -126,20 +144,49 @@ impl CalculationType {
       | CalculationType::Min
       | CalculationType::Average
       | CalculationType::Median
-      | CalculationType::Sum => {
+      | CalculationType::Sum
+      | CalculationType::StdDev
+      | CalculationType::Variance
+      | CalculationType::Percentile => {
         matches!(field_type, FieldType::Number)
       },
-      // Exclude some fields from CountNotEmpty & CountEmpty
-      CalculationType::CountEmpty | CalculationType::CountNonEmpty => !matches!(
+      // Exclude some fields from the empty and non empty calculations
+      CalculationType::CountEmpty
+      | CalculationType::CountNonEmpty
+      | CalculationType::PercentEmpty
+      | CalculationType::PercentNotEmpty => !matches!(
         field_type,
         FieldType::URL | FieldType::Checkbox | FieldType::CreatedTime | FieldType::LastEditedTime
       ),
+      // Checkbox fields only
+      CalculationType::CountChecked | CalculationType::PercentChecked => {
+        matches!(field_type, FieldType::Checkbox)
+      },
+      // Date fields only
+      CalculationType::EarliestDate | CalculationType::LatestDate | CalculationType::DateRange => {
+        matches!(
+          field_type,
+          FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime
+        )
+      },
+      // A checkbox only has two values
+      CalculationType::CountUnique => !matches!(field_type, FieldType::Checkbox),
       // All fields
       CalculationType::Count => true,
     }
   }
 }
 
+/// The calculations of the view evaluated over the rows of a group.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct GroupCalculationsPB {
+  #[pb(index = 1)]
+  pub group_id: String,
+
+  #[pb(index = 2)]
+  pub calculations: Vec<CalculationPB>,
+}
+
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct RepeatedCalculationsPB {
   #[pb(index = 1)]
@@ -2,7 +2,7 @@ use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
 
 use crate::entities::parser::NotEmptyStr;
-use crate::entities::RowMetaPB;
+use crate::entities::{CellIdParams, RowMetaPB};
 use crate::services::setting::{CalendarLayout, CalendarLayoutSetting};
 
 use super::CellIdPB;
@@ -81,11 +81,21 @@ impl std::convert::From<CalendarLayout> for CalendarLayoutPB {
 pub struct CalendarEventRequestPB {
   #[pb(index = 1)]
   pub view_id: String,
+
+  /// The start of the window, the recurring events are expanded to their occurrences in the
+  /// window. Both `start` and `end` are required to set the window.
+  #[pb(index = 2, one_of)]
+  pub start: Option<i64>,
+
+  #[pb(index = 3, one_of)]
+  pub end: Option<i64>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct CalendarEventRequestParams {
   pub view_id: String,
+  /// The start and end timestamps of the window
+  pub window: Option<(i64, i64)>,
 }
 
 impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
@@ -93,7 +103,15 @@ impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
 
   fn try_into(self) -> Result<CalendarEventRequestParams, Self::Error> {
     let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
-    Ok(CalendarEventRequestParams { view_id: view_id.0 })
+    let window = match (self.start, self.end) {
+      (Some(start), Some(end)) if start <= end => Some((start, end)),
+      (None, None) => None,
+      _ => return Err(ErrorCode::InvalidParams),
+    };
+    Ok(CalendarEventRequestParams {
+      view_id: view_id.0,
+      window,
+    })
   }
 }
 
@@ -110,6 +128,10 @@ pub struct CalendarEventPB {
 
   #[pb(index = 4, one_of)]
   pub timestamp: Option<i64>,
+
+  /// The event is an occurrence of a recurring date, the timestamp is the start of the occurrence
+  #[pb(index = 5)]
+  pub is_recurring: bool,
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -125,6 +147,43 @@ pub struct MoveCalendarEventPB {
 
   #[pb(index = 2)]
   pub timestamp: i64,
+
+  /// The start of the moved occurrence if the date is recurring
+  #[pb(index = 3, one_of)]
+  pub occurrence: Option<i64>,
+
+  #[pb(index = 4)]
+  pub scope: RecurrenceEditScopePB,
+}
+
+/// The occurrences of a recurring date that are changed when one of them is moved
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum RecurrenceEditScopePB {
+  #[default]
+  All = 0,
+  ThisOccurrence = 1,
+  AllFollowing = 2,
+}
+
+pub struct MoveCalendarEventParams {
+  pub cell_id: CellIdParams,
+  pub timestamp: i64,
+  pub occurrence: Option<i64>,
+  pub scope: RecurrenceEditScopePB,
+}
+
+impl TryInto<MoveCalendarEventParams> for MoveCalendarEventPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveCalendarEventParams, Self::Error> {
+    Ok(MoveCalendarEventParams {
+      cell_id: self.cell_path.try_into()?,
+      timestamp: self.timestamp,
+      occurrence: self.occurrence,
+      scope: self.scope,
+    })
+  }
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -172,6 +172,10 @@ pub struct MoveGroupRowPayloadPB {
 
   #[pb(index = 5)]
   pub from_group_id: String,
+
+  /// The sub group the row is moved to when the board is grouped by two fields.
+  #[pb(index = 6, one_of)]
+  pub to_sub_group_id: Option<String>,
 }
 
 pub struct MoveGroupRowParams {
@@ -180,6 +184,7 @@ pub struct MoveGroupRowParams {
   pub from_group_id: String,
   pub to_group_id: String,
   pub to_row_id: Option<RowId>,
+  pub to_sub_group_id: Option<String>,
 }
 
 impl TryInto<MoveGroupRowParams> for MoveGroupRowPayloadPB {
@@ -198,6 +203,7 @@ impl TryInto<MoveGroupRowParams> for MoveGroupRowPayloadPB {
       from_group_id: from_group_id.0,
       from_row_id: RowId::from(self.from_row_id),
       to_row_id: self.to_row_id.map(RowId::from),
+      to_sub_group_id: self.to_sub_group_id,
     })
   }
 }
@@ -441,6 +441,8 @@ pub enum FieldType {
   Translate = 12,
   Time = 13,
   Media = 14,
+  Formula = 15,
+  Rollup = 16,
 }
 
 impl Display for FieldType {
@@ -484,6 +486,8 @@ impl FieldType {
       FieldType::Translate => "Translate",
       FieldType::Time => "Time",
       FieldType::Media => "Media",
+      FieldType::Formula => "Formula",
+      FieldType::Rollup => "Rollup",
     };
     s.to_string()
   }
@@ -520,6 +524,11 @@ impl FieldType {
     matches!(self, FieldType::LastEditedTime)
   }
 
+  /// The created time and the last edited time are read from the row instead of its cells
+  pub fn is_timestamp(&self) -> bool {
+    self.is_created_time() || self.is_last_edited_time()
+  }
+
   pub fn is_created_time(&self) -> bool {
     matches!(self, FieldType::CreatedTime)
   }
@@ -548,6 +557,14 @@ impl FieldType {
     matches!(self, FieldType::Media)
   }
 
+  pub fn is_formula(&self) -> bool {
+    matches!(self, FieldType::Formula)
+  }
+
+  pub fn is_rollup(&self) -> bool {
+    matches!(self, FieldType::Rollup)
+  }
+
   pub fn can_be_group(&self) -> bool {
     self.is_select_option() || self.is_checkbox() || self.is_url()
   }
@@ -20,6 +20,11 @@ pub struct DateFilterPB {
 
   #[pb(index = 4, one_of)]
   pub timestamp: Option<i64>,
+
+  /// The number of days of the [DateFilterConditionPB::DateStartsWithinPastDays] and
+  /// [DateFilterConditionPB::DateStartsWithinNextDays] conditions
+  #[pb(index = 5, one_of)]
+  pub num_of_days: Option<i64>,
 }
 
 #[derive(Deserialize, Serialize, Default, Clone, Debug)]
@@ -27,6 +32,8 @@ pub struct DateFilterContent {
   pub start: Option<i64>,
   pub end: Option<i64>,
   pub timestamp: Option<i64>,
+  #[serde(default)]
+  pub num_of_days: Option<i64>,
 }
 
 impl ToString for DateFilterContent {
@@ -63,6 +70,22 @@ pub enum DateFilterConditionPB {
   DateEndsBetween = 13,
   DateEndIsEmpty = 14,
   DateEndIsNotEmpty = 15,
+  // The relative conditions compare the start date with the current local date, so they are
+  // re-evaluated when the day changes. The weeks start on Monday.
+  DateStartsToday = 16,
+  DateStartsTomorrow = 17,
+  DateStartsYesterday = 18,
+  DateStartsThisWeek = 19,
+  DateStartsNextWeek = 20,
+  DateStartsLastWeek = 21,
+  DateStartsThisMonth = 22,
+  DateStartsNextMonth = 23,
+  DateStartsLastMonth = 24,
+  DateStartsThisQuarter = 25,
+  DateStartsNextQuarter = 26,
+  DateStartsLastQuarter = 27,
+  DateStartsWithinPastDays = 28,
+  DateStartsWithinNextDays = 29,
 }
 
 impl DateFilterConditionPB {
@@ -77,6 +100,27 @@ impl DateFilterConditionPB {
         | Self::DateStartsBetween
         | Self::DateStartIsEmpty
         | Self::DateStartIsNotEmpty,
+    ) || self.is_relative()
+  }
+
+  /// Returns true if the condition depends on the current date
+  pub fn is_relative(&self) -> bool {
+    matches!(
+      self,
+      Self::DateStartsToday
+        | Self::DateStartsTomorrow
+        | Self::DateStartsYesterday
+        | Self::DateStartsThisWeek
+        | Self::DateStartsNextWeek
+        | Self::DateStartsLastWeek
+        | Self::DateStartsThisMonth
+        | Self::DateStartsNextMonth
+        | Self::DateStartsLastMonth
+        | Self::DateStartsThisQuarter
+        | Self::DateStartsNextQuarter
+        | Self::DateStartsLastQuarter
+        | Self::DateStartsWithinPastDays
+        | Self::DateStartsWithinNextDays,
     )
   }
 }
@@ -108,6 +152,20 @@ impl std::convert::TryFrom<u8> for DateFilterConditionPB {
       13 => Ok(Self::DateEndsBetween),
       14 => Ok(Self::DateEndIsEmpty),
       15 => Ok(Self::DateEndIsNotEmpty),
+      16 => Ok(Self::DateStartsToday),
+      17 => Ok(Self::DateStartsTomorrow),
+      18 => Ok(Self::DateStartsYesterday),
+      19 => Ok(Self::DateStartsThisWeek),
+      20 => Ok(Self::DateStartsNextWeek),
+      21 => Ok(Self::DateStartsLastWeek),
+      22 => Ok(Self::DateStartsThisMonth),
+      23 => Ok(Self::DateStartsNextMonth),
+      24 => Ok(Self::DateStartsLastMonth),
+      25 => Ok(Self::DateStartsThisQuarter),
+      26 => Ok(Self::DateStartsNextQuarter),
+      27 => Ok(Self::DateStartsLastQuarter),
+      28 => Ok(Self::DateStartsWithinPastDays),
+      29 => Ok(Self::DateStartsWithinNextDays),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
@@ -126,6 +184,7 @@ impl ParseFilterData for DateFilterPB {
       date_filter.start = content.start;
       date_filter.end = content.end;
       date_filter.timestamp = content.timestamp;
+      date_filter.num_of_days = content.num_of_days;
     };
 
     date_filter
@@ -0,0 +1,115 @@
+use std::cmp::Ordering;
+
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::services::field::{compare_values, FormulaValue};
+
+/// Compares the cells of two fields of the same row, e.g. `Actual > Estimate`. Dates are compared
+/// by their timestamps, so `Due date < Start date` keeps the rows that are due before they start.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FieldComparisonFilterPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub other_field_id: String,
+
+  #[pb(index = 3)]
+  pub condition: FieldComparisonConditionPB,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum FieldComparisonConditionPB {
+  #[default]
+  Equal = 0,
+  NotEqual = 1,
+  GreaterThan = 2,
+  LessThan = 3,
+  GreaterThanOrEqualTo = 4,
+  LessThanOrEqualTo = 5,
+}
+
+impl std::convert::From<FieldComparisonConditionPB> for u32 {
+  fn from(value: FieldComparisonConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl TryFrom<u8> for FieldComparisonConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(FieldComparisonConditionPB::Equal),
+      1 => Ok(FieldComparisonConditionPB::NotEqual),
+      2 => Ok(FieldComparisonConditionPB::GreaterThan),
+      3 => Ok(FieldComparisonConditionPB::LessThan),
+      4 => Ok(FieldComparisonConditionPB::GreaterThanOrEqualTo),
+      5 => Ok(FieldComparisonConditionPB::LessThanOrEqualTo),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+impl FieldComparisonConditionPB {
+  /// Returns true if the values satisfy the condition. An empty cell is only equal to another
+  /// empty cell and isn't greater or less than any value.
+  pub fn is_visible(&self, value: &FormulaValue, other_value: &FormulaValue) -> bool {
+    match (value.is_empty(), other_value.is_empty()) {
+      (false, false) => {},
+      (is_empty, other_is_empty) => {
+        return match self {
+          FieldComparisonConditionPB::Equal => is_empty && other_is_empty,
+          FieldComparisonConditionPB::NotEqual => is_empty != other_is_empty,
+          _ => false,
+        };
+      },
+    }
+
+    let ordering = compare_values(value, other_value);
+    match self {
+      FieldComparisonConditionPB::Equal => ordering == Ordering::Equal,
+      FieldComparisonConditionPB::NotEqual => ordering != Ordering::Equal,
+      FieldComparisonConditionPB::GreaterThan => ordering == Ordering::Greater,
+      FieldComparisonConditionPB::LessThan => ordering == Ordering::Less,
+      FieldComparisonConditionPB::GreaterThanOrEqualTo => ordering != Ordering::Less,
+      FieldComparisonConditionPB::LessThanOrEqualTo => ordering != Ordering::Greater,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::FieldComparisonConditionPB;
+  use crate::services::field::FormulaValue;
+
+  #[test]
+  fn field_comparison_test() {
+    let actual = FormulaValue::Number(5.5);
+    let estimate = FormulaValue::Number(3.0);
+    assert!(FieldComparisonConditionPB::GreaterThan.is_visible(&actual, &estimate));
+    assert!(!FieldComparisonConditionPB::LessThanOrEqualTo.is_visible(&actual, &estimate));
+    assert!(FieldComparisonConditionPB::NotEqual.is_visible(&actual, &estimate));
+
+    // 2024-01-01 and 2023-12-25
+    let due = FormulaValue::Date(1704067200);
+    let start = FormulaValue::Date(1703462400);
+    assert!(FieldComparisonConditionPB::LessThan.is_visible(&start, &due));
+    assert!(!FieldComparisonConditionPB::LessThan.is_visible(&due, &start));
+
+    let name = FormulaValue::Text("Task".to_string());
+    assert!(FieldComparisonConditionPB::Equal.is_visible(&name, &name.clone()));
+  }
+
+  #[test]
+  fn field_comparison_empty_cell_test() {
+    let empty = FormulaValue::Empty;
+    let number = FormulaValue::Number(1.0);
+    assert!(!FieldComparisonConditionPB::GreaterThan.is_visible(&number, &empty));
+    assert!(!FieldComparisonConditionPB::LessThan.is_visible(&empty, &number));
+    assert!(FieldComparisonConditionPB::NotEqual.is_visible(&number, &empty));
+    assert!(FieldComparisonConditionPB::Equal.is_visible(&empty, &FormulaValue::Empty));
+  }
+}
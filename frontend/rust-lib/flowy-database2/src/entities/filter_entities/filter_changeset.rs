@@ -1,7 +1,7 @@
 use flowy_derive::ProtoBuf;
 
-use crate::entities::RepeatedFilterPB;
-use crate::services::filter::Filter;
+use crate::entities::{RepeatedFilterPB, TextFilterPB};
+use crate::services::filter::{Filter, FilterInner};
 
 #[derive(Debug, Default, ProtoBuf)]
 pub struct FilterChangesetNotificationPB {
@@ -10,13 +10,55 @@ pub struct FilterChangesetNotificationPB {
 
   #[pb(index = 2)]
   pub filters: RepeatedFilterPB,
+
+  /// The filters that can't be applied, for example a text filter with an invalid regular
+  /// expression. They don't filter out any row.
+  #[pb(index = 3)]
+  pub invalid_filters: Vec<InvalidFilterPB>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct InvalidFilterPB {
+  #[pb(index = 1)]
+  pub filter_id: String,
+
+  #[pb(index = 2)]
+  pub message: String,
 }
 
 impl FilterChangesetNotificationPB {
   pub fn from_filters(view_id: &str, filters: &Vec<Filter>) -> Self {
+    let mut invalid_filters = vec![];
+    collect_invalid_filters(filters, &mut invalid_filters);
     Self {
       view_id: view_id.to_string(),
       filters: filters.into(),
+      invalid_filters,
+    }
+  }
+}
+
+fn collect_invalid_filters(filters: &[Filter], invalid_filters: &mut Vec<InvalidFilterPB>) {
+  for filter in filters {
+    match &filter.inner {
+      FilterInner::And { children } | FilterInner::Or { children } => {
+        collect_invalid_filters(children, invalid_filters)
+      },
+      FilterInner::Data {
+        condition_and_content,
+        ..
+      } => {
+        if let Some(Err(message)) = condition_and_content
+          .downcast_ref::<TextFilterPB>()
+          .map(|text_filter| text_filter.validate())
+        {
+          invalid_filters.push(InvalidFilterPB {
+            filter_id: filter.id.clone(),
+            message,
+          });
+        }
+      },
+      FilterInner::FieldComparison { .. } | FilterInner::Formula { .. } => {},
     }
   }
 }
@@ -0,0 +1,9 @@
+use flowy_derive::ProtoBuf;
+
+/// Keeps the rows for which the expression evaluates to true. The expression uses the syntax of
+/// the formula field, e.g. `{Actual} > {Estimate} && !empty({Owner})`.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct FormulaFilterPB {
+  #[pb(index = 1)]
+  pub expression: String,
+}
@@ -1,7 +1,9 @@
 mod checkbox_filter;
 mod checklist_filter;
 mod date_filter;
+mod field_comparison_filter;
 mod filter_changeset;
+mod formula_filter;
 mod media_filter;
 mod number_filter;
 mod relation_filter;
@@ -13,7 +15,9 @@ mod util;
 pub use checkbox_filter::*;
 pub use checklist_filter::*;
 pub use date_filter::*;
+pub use field_comparison_filter::*;
 pub use filter_changeset::*;
+pub use formula_filter::*;
 pub use media_filter::*;
 pub use number_filter::*;
 pub use relation_filter::*;
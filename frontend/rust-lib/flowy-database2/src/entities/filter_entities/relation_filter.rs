@@ -1,22 +1,224 @@
+use std::str::FromStr;
+
 use collab_database::{fields::Field, rows::Cell};
-use flowy_derive::ProtoBuf;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+use serde::{Deserialize, Serialize};
 
-use crate::services::filter::{ParseFilterData, PreFillCellsWithFilter};
+use crate::entities::{FieldType, FilterDataPB};
+use crate::services::field::RelationCellData;
+use crate::services::filter::{
+  get_raw_condition_and_content, FilterInner, ParseFilterData, PreFillCellsWithFilter,
+};
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct RelationFilterPB {
   #[pb(index = 1)]
+  pub condition: RelationFilterConditionPB,
+
+  /// The related rows of the [RelationFilterConditionPB::RelationContains] and
+  /// [RelationFilterConditionPB::RelationDoesNotContain] conditions. For the
+  /// [RelationFilterConditionPB::RelatedRowMatches] condition, the ids of the related rows that
+  /// match the `related_filter`, filled in by the filter controller.
+  #[pb(index = 2)]
+  pub row_ids: Vec<String>,
+
+  /// The filter of the [RelationFilterConditionPB::RelatedRowMatches] condition. Its field belongs
+  /// to the related database.
+  #[pb(index = 3, one_of)]
+  pub related_filter: Option<FilterDataPB>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum RelationFilterConditionPB {
+  /// The relation filters created before the conditions existed were stored with the condition 0
+  /// and no rows, which doesn't filter out any row.
+  #[default]
+  RelationContains = 0,
+  RelationDoesNotContain = 1,
+  RelationIsEmpty = 2,
+  RelationIsNotEmpty = 3,
+  RelatedRowMatches = 4,
+}
+
+impl From<RelationFilterConditionPB> for u32 {
+  fn from(value: RelationFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl TryFrom<u8> for RelationFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(RelationFilterConditionPB::RelationContains),
+      1 => Ok(RelationFilterConditionPB::RelationDoesNotContain),
+      2 => Ok(RelationFilterConditionPB::RelationIsEmpty),
+      3 => Ok(RelationFilterConditionPB::RelationIsNotEmpty),
+      4 => Ok(RelationFilterConditionPB::RelatedRowMatches),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+/// The content of the relation filter stored in the [FilterMap](collab_database::views::FilterMap)
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+pub struct RelationFilterContent {
+  #[serde(default)]
+  pub row_ids: Vec<String>,
+  #[serde(default)]
+  pub related_filter: Option<RelatedFilterContent>,
+}
+
+#[derive(Deserialize, Serialize, Default, Clone, Debug)]
+pub struct RelatedFilterContent {
+  pub field_id: String,
+  pub field_type: i64,
   pub condition: i64,
+  pub content: String,
+}
+
+impl ToString for RelationFilterContent {
+  fn to_string(&self) -> String {
+    serde_json::to_string(self).unwrap()
+  }
+}
+
+impl FromStr for RelationFilterContent {
+  type Err = serde_json::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    serde_json::from_str(s)
+  }
+}
+
+impl From<&RelationFilterPB> for RelationFilterContent {
+  fn from(filter: &RelationFilterPB) -> Self {
+    if filter.condition == RelationFilterConditionPB::RelatedRowMatches {
+      // the row ids are resolved from the related filter
+      let related_filter = filter.related_filter.clone().and_then(|data| {
+        let field_id = data.field_id.clone();
+        let field_type = data.field_type;
+        let inner = FilterInner::try_from(data).ok()?;
+        let (condition, content) = match &inner {
+          FilterInner::Data {
+            condition_and_content,
+            ..
+          } => get_raw_condition_and_content(&field_type, condition_and_content)?,
+          _ => return None,
+        };
+        Some(RelatedFilterContent {
+          field_id,
+          field_type: i64::from(field_type),
+          condition: condition as i64,
+          content,
+        })
+      });
+      return Self {
+        row_ids: vec![],
+        related_filter,
+      };
+    }
+
+    Self {
+      row_ids: filter.row_ids.clone(),
+      related_filter: None,
+    }
+  }
 }
 
 impl ParseFilterData for RelationFilterPB {
-  fn parse(_condition: u8, _content: String) -> Self {
-    RelationFilterPB { condition: 0 }
+  fn parse(condition: u8, content: String) -> Self {
+    let content = RelationFilterContent::from_str(&content).unwrap_or_default();
+    let related_filter = content.related_filter.and_then(|related_filter| {
+      let inner = FilterInner::new_data(
+        related_filter.field_id,
+        FieldType::from(related_filter.field_type),
+        related_filter.condition,
+        related_filter.content,
+      );
+      match inner {
+        FilterInner::Data {
+          field_id,
+          field_type,
+          condition_and_content,
+        } => FilterDataPB::new(&field_id, field_type, &condition_and_content).ok(),
+        _ => None,
+      }
+    });
+
+    Self {
+      condition: RelationFilterConditionPB::try_from(condition)
+        .unwrap_or(RelationFilterConditionPB::RelationContains),
+      row_ids: content.row_ids,
+      related_filter,
+    }
   }
 }
 
 impl PreFillCellsWithFilter for RelationFilterPB {
   fn get_compliant_cell(&self, _field: &Field) -> Option<Cell> {
-    None
+    match self.condition {
+      RelationFilterConditionPB::RelationContains if !self.row_ids.is_empty() => {
+        let cell_data = RelationCellData {
+          row_ids: self.row_ids.iter().cloned().map(Into::into).collect(),
+        };
+        Some((&cell_data).into())
+      },
+      _ => None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::entities::{
+    FieldType, FilterDataPB, RelationFilterConditionPB, RelationFilterContent, RelationFilterPB,
+    TextFilterConditionPB, TextFilterPB,
+  };
+  use crate::services::filter::ParseFilterData;
+  use lib_infra::box_any::BoxAny;
+
+  #[test]
+  fn relation_filter_content_test() {
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelationDoesNotContain,
+      row_ids: vec!["row_1".to_string(), "row_2".to_string()],
+      related_filter: None,
+    };
+    let content = RelationFilterContent::from(&filter).to_string();
+    let parsed = RelationFilterPB::parse(filter.condition.clone() as u8, content);
+    assert_eq!(parsed, filter);
+
+    let text_filter = BoxAny::new(TextFilterPB {
+      condition: TextFilterConditionPB::TextContains,
+      content: "urgent".to_string(),
+      ..Default::default()
+    });
+    let filter = RelationFilterPB {
+      condition: RelationFilterConditionPB::RelatedRowMatches,
+      row_ids: vec!["resolved_row".to_string()],
+      related_filter: Some(FilterDataPB::new("name", FieldType::RichText, &text_filter).unwrap()),
+    };
+    let content = RelationFilterContent::from(&filter).to_string();
+    let parsed = RelationFilterPB::parse(filter.condition.clone() as u8, content);
+    assert!(parsed.row_ids.is_empty());
+    assert_eq!(parsed.related_filter, filter.related_filter);
+
+    // The content must be the filter of the field type
+    assert!(FilterDataPB::new("name", FieldType::Number, &text_filter).is_err());
+  }
+
+  #[test]
+  fn relation_filter_legacy_content_test() {
+    let filter = RelationFilterPB::parse(0, "".to_string());
+    assert_eq!(
+      filter.condition,
+      RelationFilterConditionPB::RelationContains
+    );
+    assert!(filter.row_ids.is_empty());
+    assert!(filter.related_filter.is_none());
   }
 }
@@ -10,6 +10,15 @@ pub struct TextFilterPB {
 
   #[pb(index = 2)]
   pub content: String,
+
+  /// The text is compared case-insensitively unless it is set
+  #[pb(index = 3)]
+  pub case_sensitive: bool,
+
+  /// Only matches the content if it isn't a part of a longer word. Applies to the contains, starts
+  /// with and ends with conditions.
+  #[pb(index = 4)]
+  pub whole_word: bool,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
@@ -24,6 +33,9 @@ pub enum TextFilterConditionPB {
   TextEndsWith = 5,
   TextIsEmpty = 6,
   TextIsNotEmpty = 7,
+  /// The content is a regular expression
+  TextMatchesRegex = 8,
+  TextDoesNotMatchRegex = 9,
 }
 
 impl std::convert::From<TextFilterConditionPB> for u32 {
@@ -45,6 +57,8 @@ impl std::convert::TryFrom<u8> for TextFilterConditionPB {
       5 => Ok(TextFilterConditionPB::TextEndsWith),
       6 => Ok(TextFilterConditionPB::TextIsEmpty),
       7 => Ok(TextFilterConditionPB::TextIsNotEmpty),
+      8 => Ok(TextFilterConditionPB::TextMatchesRegex),
+      9 => Ok(TextFilterConditionPB::TextDoesNotMatchRegex),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
@@ -56,6 +70,7 @@ impl ParseFilterData for TextFilterPB {
       condition: TextFilterConditionPB::try_from(condition)
         .unwrap_or(TextFilterConditionPB::TextIs),
       content,
+      ..Default::default()
     }
   }
 }
@@ -3,15 +3,18 @@ use std::convert::TryInto;
 use bytes::Bytes;
 
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
-use flowy_error::ErrorCode;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use lib_infra::box_any::BoxAny;
 use protobuf::ProtobufError;
+use tracing::error;
 use validator::Validate;
 
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterPB, FieldType, NumberFilterPB, RelationFilterPB,
-  SelectOptionFilterPB, TextFilterPB, TimeFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterPB, FieldComparisonFilterPB, FieldType,
+  FormulaFilterPB, NumberFilterPB, RelationFilterPB, SelectOptionFilterPB, TextFilterPB,
+  TimeFilterPB,
 };
+use crate::services::field::parse_formula;
 use crate::services::filter::{Filter, FilterChangeset, FilterInner};
 
 use super::MediaFilterPB;
@@ -23,6 +26,8 @@ pub enum FilterType {
   Data = 0,
   And = 1,
   Or = 2,
+  FieldComparison = 3,
+  Formula = 4,
 }
 
 impl From<&FilterInner> for FilterType {
@@ -31,6 +36,8 @@ impl From<&FilterInner> for FilterType {
       FilterInner::And { .. } => Self::And,
       FilterInner::Or { .. } => Self::Or,
       FilterInner::Data { .. } => Self::Data,
+      FilterInner::FieldComparison { .. } => Self::FieldComparison,
+      FilterInner::Formula { .. } => Self::Formula,
     }
   }
 }
@@ -48,6 +55,12 @@ pub struct FilterPB {
 
   #[pb(index = 4, one_of)]
   pub data: Option<FilterDataPB>,
+
+  #[pb(index = 5, one_of)]
+  pub field_comparison: Option<FieldComparisonFilterPB>,
+
+  #[pb(index = 6, one_of)]
+  pub formula: Option<FormulaFilterPB>,
 }
 
 #[derive(Debug, Default, Clone, ProtoBuf, Eq, PartialEq)]
@@ -69,77 +82,98 @@ impl From<&Filter> for FilterPB {
         id: filter.id.clone(),
         filter_type: FilterType::from(&filter.inner),
         children: children.iter().map(FilterPB::from).collect(),
-        data: None,
+        ..Default::default()
       },
       FilterInner::Data {
         field_id,
         field_type,
         condition_and_content,
-      } => {
-        let bytes: Result<Bytes, ProtobufError> = match field_type {
-          FieldType::RichText | FieldType::URL => condition_and_content
-            .cloned::<TextFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Number => condition_and_content
-            .cloned::<NumberFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime => {
-            condition_and_content
-              .cloned::<DateFilterPB>()
-              .unwrap()
-              .try_into()
-          },
-          FieldType::SingleSelect | FieldType::MultiSelect => condition_and_content
-            .cloned::<SelectOptionFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Checklist => condition_and_content
-            .cloned::<ChecklistFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Checkbox => condition_and_content
-            .cloned::<CheckboxFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Relation => condition_and_content
-            .cloned::<RelationFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Summary => condition_and_content
-            .cloned::<TextFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Time => condition_and_content
-            .cloned::<TimeFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Translate => condition_and_content
-            .cloned::<TextFilterPB>()
-            .unwrap()
-            .try_into(),
-          FieldType::Media => condition_and_content
-            .cloned::<MediaFilterPB>()
-            .unwrap()
-            .try_into(),
-        };
-
-        Self {
-          id: filter.id.clone(),
-          filter_type: FilterType::Data,
-          children: vec![],
-          data: Some(FilterDataPB {
-            field_id: field_id.clone(),
-            field_type: *field_type,
-            data: bytes.unwrap().to_vec(),
-          }),
-        }
+      } => Self {
+        id: filter.id.clone(),
+        filter_type: FilterType::Data,
+        children: vec![],
+        data: FilterDataPB::new(field_id, *field_type, condition_and_content)
+          .map_err(|err| error!("Error converting filter data: {:?}", err))
+          .ok(),
+        ..Default::default()
+      },
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        condition,
+      } => Self {
+        id: filter.id.clone(),
+        filter_type: FilterType::FieldComparison,
+        field_comparison: Some(FieldComparisonFilterPB {
+          field_id: field_id.clone(),
+          other_field_id: other_field_id.clone(),
+          condition: condition.clone(),
+        }),
+        ..Default::default()
+      },
+      FilterInner::Formula { expression } => Self {
+        id: filter.id.clone(),
+        filter_type: FilterType::Formula,
+        formula: Some(FormulaFilterPB {
+          expression: expression.clone(),
+        }),
+        ..Default::default()
       },
     }
   }
 }
 
+impl FilterDataPB {
+  /// Serializes the condition and content of a data filter. Fails if the content isn't the filter
+  /// of the field type.
+  pub fn new(
+    field_id: &str,
+    field_type: FieldType,
+    condition_and_content: &BoxAny,
+  ) -> FlowyResult<Self> {
+    let bytes = match field_type {
+      FieldType::RichText | FieldType::URL => {
+        filter_bytes::<TextFilterPB>(field_type, condition_and_content)
+      },
+      FieldType::Number => filter_bytes::<NumberFilterPB>(field_type, condition_and_content),
+      FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime => {
+        filter_bytes::<DateFilterPB>(field_type, condition_and_content)
+      },
+      FieldType::SingleSelect | FieldType::MultiSelect => {
+        filter_bytes::<SelectOptionFilterPB>(field_type, condition_and_content)
+      },
+      FieldType::Checklist => filter_bytes::<ChecklistFilterPB>(field_type, condition_and_content),
+      FieldType::Checkbox => filter_bytes::<CheckboxFilterPB>(field_type, condition_and_content),
+      FieldType::Relation => filter_bytes::<RelationFilterPB>(field_type, condition_and_content),
+      FieldType::Summary => filter_bytes::<TextFilterPB>(field_type, condition_and_content),
+      FieldType::Time => filter_bytes::<TimeFilterPB>(field_type, condition_and_content),
+      FieldType::Translate | FieldType::Formula | FieldType::Rollup => {
+        filter_bytes::<TextFilterPB>(field_type, condition_and_content)
+      },
+      FieldType::Media => filter_bytes::<MediaFilterPB>(field_type, condition_and_content),
+    }?;
+
+    Ok(Self {
+      field_id: field_id.to_string(),
+      field_type,
+      data: bytes.to_vec(),
+    })
+  }
+}
+
+fn filter_bytes<T>(field_type: FieldType, condition_and_content: &BoxAny) -> FlowyResult<Bytes>
+where
+  T: Clone + TryInto<Bytes, Error = ProtobufError> + 'static,
+{
+  let filter = condition_and_content.cloned::<T>().ok_or_else(|| {
+    FlowyError::invalid_data().with_context(format!(
+      "the filter content doesn't match the field type {:?}",
+      field_type
+    ))
+  })?;
+  Ok(filter.try_into()?)
+}
+
 impl TryFrom<FilterDataPB> for FilterInner {
   type Error = ErrorCode;
 
@@ -173,7 +207,7 @@ impl TryFrom<FilterDataPB> for FilterInner {
       FieldType::Time => {
         BoxAny::new(TimeFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
-      FieldType::Translate => {
+      FieldType::Translate | FieldType::Formula | FieldType::Rollup => {
         BoxAny::new(TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
       FieldType::Media => {
@@ -218,6 +252,14 @@ pub struct InsertFilterPB {
 
   #[pb(index = 2)]
   pub data: FilterDataPB,
+
+  /// If set, a field comparison filter is inserted instead of the `data` filter
+  #[pb(index = 3, one_of)]
+  pub field_comparison: Option<FieldComparisonFilterPB>,
+
+  /// If set, a formula filter is inserted instead of the `data` filter
+  #[pb(index = 4, one_of)]
+  pub formula: Option<FormulaFilterPB>,
 }
 
 #[derive(ProtoBuf, Debug, Default, Clone, Validate)]
@@ -238,6 +280,12 @@ pub struct UpdateFilterDataPB {
 
   #[pb(index = 2)]
   pub data: FilterDataPB,
+
+  #[pb(index = 3, one_of)]
+  pub field_comparison: Option<FieldComparisonFilterPB>,
+
+  #[pb(index = 4, one_of)]
+  pub formula: Option<FormulaFilterPB>,
 }
 
 #[derive(ProtoBuf, Debug, Default, Clone, Validate)]
@@ -247,13 +295,41 @@ pub struct DeleteFilterPB {
   pub filter_id: String,
 }
 
+/// Returns the formula filter if there is one, then the field comparison filter, and the data
+/// filter otherwise. The formula is rejected if it can't be parsed.
+fn filter_inner_from(
+  data: FilterDataPB,
+  field_comparison: Option<FieldComparisonFilterPB>,
+  formula: Option<FormulaFilterPB>,
+) -> Result<FilterInner, ErrorCode> {
+  if let Some(formula) = formula {
+    parse_formula(&formula.expression).map_err(|_| ErrorCode::InvalidFormula)?;
+    return Ok(FilterInner::Formula {
+      expression: formula.expression,
+    });
+  }
+
+  if let Some(field_comparison) = field_comparison {
+    if field_comparison.field_id.is_empty() || field_comparison.other_field_id.is_empty() {
+      return Err(ErrorCode::FieldIdIsEmpty);
+    }
+    return Ok(FilterInner::FieldComparison {
+      field_id: field_comparison.field_id,
+      other_field_id: field_comparison.other_field_id,
+      condition: field_comparison.condition,
+    });
+  }
+
+  data.try_into()
+}
+
 impl TryFrom<InsertFilterPB> for FilterChangeset {
   type Error = ErrorCode;
 
   fn try_from(value: InsertFilterPB) -> Result<Self, Self::Error> {
     let changeset = Self::Insert {
       parent_filter_id: value.parent_filter_id,
-      data: value.data.try_into()?,
+      data: filter_inner_from(value.data, value.field_comparison, value.formula)?,
     };
 
     Ok(changeset)
@@ -266,7 +342,7 @@ impl TryFrom<UpdateFilterDataPB> for FilterChangeset {
   fn try_from(value: UpdateFilterDataPB) -> Result<Self, Self::Error> {
     let changeset = Self::UpdateData {
       filter_id: value.filter_id,
-      data: value.data.try_into()?,
+      data: filter_inner_from(value.data, value.field_comparison, value.formula)?,
     };
 
     Ok(changeset)
@@ -277,7 +353,10 @@ impl TryFrom<UpdateFilterTypePB> for FilterChangeset {
   type Error = ErrorCode;
 
   fn try_from(value: UpdateFilterTypePB) -> Result<Self, Self::Error> {
-    if matches!(value.filter_type, FilterType::Data) {
+    if matches!(
+      value.filter_type,
+      FilterType::Data | FilterType::FieldComparison | FilterType::Formula
+    ) {
       return Err(ErrorCode::InvalidParams);
     }
 
@@ -6,7 +6,7 @@ use lib_infra::validator_fn::required_not_empty_str;
 use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
-use crate::entities::{FieldType, RowMetaPB};
+use crate::entities::{CalculationPB, FieldType, RowMetaPB};
 use crate::services::group::{GroupChangeset, GroupData, GroupSetting};
 
 use super::group_config_json_to_pb;
@@ -92,6 +92,15 @@ pub struct GroupPB {
 
   #[pb(index = 6)]
   pub is_visible: bool,
+
+  /// The rows of the group split by the second grouping field of the view. Empty if the view is
+  /// grouped by a single field.
+  #[pb(index = 7)]
+  pub sub_groups: Vec<GroupPB>,
+
+  /// The calculations of the view evaluated over the rows of the group.
+  #[pb(index = 8)]
+  pub calculations: Vec<CalculationPB>,
 }
 
 impl std::convert::From<GroupData> for GroupPB {
@@ -102,6 +111,8 @@ impl std::convert::From<GroupData> for GroupPB {
       rows: group_data.rows.into_iter().map(RowMetaPB::from).collect(),
       is_default: group_data.is_default,
       is_visible: group_data.is_visible,
+      sub_groups: vec![],
+      calculations: vec![],
     }
   }
 }
@@ -143,6 +154,49 @@ pub struct GroupByFieldParams {
   pub setting_content: Vec<u8>,
 }
 
+/// Sets the second grouping field of a board view, or removes it when `field_id` is `None`.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct SubGroupByFieldPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2, one_of)]
+  pub field_id: Option<String>,
+
+  #[pb(index = 3)]
+  pub setting_content: Vec<u8>,
+}
+
+impl TryInto<SubGroupByFieldParams> for SubGroupByFieldPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<SubGroupByFieldParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id)
+      .map_err(|_| ErrorCode::ViewIdIsInvalid)?
+      .0;
+    let field_id = match self.field_id {
+      None => None,
+      Some(field_id) => Some(
+        NotEmptyStr::parse(field_id)
+          .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+          .0,
+      ),
+    };
+
+    Ok(SubGroupByFieldParams {
+      view_id,
+      field_id,
+      setting_content: self.setting_content,
+    })
+  }
+}
+
+pub struct SubGroupByFieldParams {
+  pub view_id: String,
+  pub field_id: Option<String>,
+  pub setting_content: Vec<u8>,
+}
+
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone, Validate)]
 pub struct UpdateGroupPB {
   #[pb(index = 1)]
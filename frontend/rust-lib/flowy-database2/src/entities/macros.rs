@@ -19,6 +19,8 @@ macro_rules! impl_into_field_type {
           12 => FieldType::Translate,
           13 => FieldType::Time,
           14 => FieldType::Media,
+          15 => FieldType::Formula,
+          16 => FieldType::Rollup,
           _ => {
             tracing::error!("🔴Can't parse FieldType from value: {}", ty);
             FieldType::RichText
@@ -62,6 +64,17 @@ macro_rules! impl_into_calculation_type {
           5 => CalculationType::Count,
           6 => CalculationType::CountEmpty,
           7 => CalculationType::CountNonEmpty,
+          8 => CalculationType::CountUnique,
+          9 => CalculationType::PercentEmpty,
+          10 => CalculationType::PercentNotEmpty,
+          11 => CalculationType::CountChecked,
+          12 => CalculationType::PercentChecked,
+          13 => CalculationType::EarliestDate,
+          14 => CalculationType::LatestDate,
+          15 => CalculationType::DateRange,
+          16 => CalculationType::StdDev,
+          17 => CalculationType::Variance,
+          18 => CalculationType::Percentile,
           _ => {
             tracing::error!("🔴 Can't parse CalculationType from value: {}", ty);
             CalculationType::Average
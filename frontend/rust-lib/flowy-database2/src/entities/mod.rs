@@ -13,8 +13,11 @@ mod position_entities;
 mod row_entities;
 pub mod setting_entities;
 mod share_entities;
+mod snapshot_entities;
 mod sort_entities;
+mod timeline_entities;
 mod type_option_entities;
+mod validation_entities;
 mod view_entities;
 
 #[macro_use]
@@ -34,8 +37,11 @@ pub use position_entities::*;
 pub use row_entities::*;
 pub use setting_entities::*;
 pub use share_entities::*;
+pub use snapshot_entities::*;
 pub use sort_entities::*;
+pub use timeline_entities::*;
 pub use type_option_entities::*;
+pub use validation_entities::*;
 pub use view_entities::*;
 
 mod utils {
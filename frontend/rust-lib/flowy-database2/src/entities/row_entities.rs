@@ -506,3 +506,63 @@ pub struct TranslateRowPB {
   #[validate(custom(function = "required_not_empty_str"))]
   pub field_id: String,
 }
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct FindDuplicateRowsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The fields whose cells are compared. The rows are duplicates if all of them are equal.
+  #[pb(index = 2)]
+  pub field_ids: Vec<String>,
+
+  /// Ignores the case of the values and the runs of whitespace in them
+  #[pb(index = 3)]
+  pub fuzzy: bool,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct DuplicateRowGroupPB {
+  /// The ids of the duplicate rows, in the order of the view
+  #[pb(index = 1)]
+  pub row_ids: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct RepeatedDuplicateRowGroupPB {
+  #[pb(index = 1)]
+  pub items: Vec<DuplicateRowGroupPB>,
+}
+
+impl From<Vec<Vec<RowId>>> for RepeatedDuplicateRowGroupPB {
+  fn from(groups: Vec<Vec<RowId>>) -> Self {
+    Self {
+      items: groups
+        .into_iter()
+        .map(|row_ids| DuplicateRowGroupPB {
+          row_ids: row_ids
+            .into_iter()
+            .map(|row_id| row_id.to_string())
+            .collect(),
+        })
+        .collect(),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct MergeDuplicateRowsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The row that is kept
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub row_id: String,
+
+  /// The rows that are merged into the kept row, then deleted
+  #[pb(index = 3)]
+  pub duplicate_row_ids: Vec<String>,
+}
@@ -11,9 +11,10 @@ use crate::entities::parser::NotEmptyStr;
 use crate::entities::{
   CalendarLayoutSettingPB, DeleteFilterPB, DeleteSortPayloadPB, InsertFilterPB,
   RepeatedFieldSettingsPB, RepeatedFilterPB, RepeatedGroupSettingPB, RepeatedSortPB,
-  UpdateFilterDataPB, UpdateFilterTypePB, UpdateGroupPB, UpdateSortPayloadPB,
+  TimelineLayoutSettingPB, UpdateFilterDataPB, UpdateFilterTypePB, UpdateGroupPB,
+  UpdateSortPayloadPB,
 };
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting, TimelineLayoutSetting};
 
 use super::{BoardLayoutSettingPB, ReorderSortPayloadPB};
 
@@ -46,6 +47,8 @@ pub enum DatabaseLayoutPB {
   Grid = 0,
   Board = 1,
   Calendar = 2,
+  /// Stored as a grid view with a [TimelineLayoutSetting]
+  Timeline = 3,
 }
 
 impl std::convert::From<DatabaseLayout> for DatabaseLayoutPB {
@@ -58,10 +61,12 @@ impl std::convert::From<DatabaseLayout> for DatabaseLayoutPB {
   }
 }
 
+/// The timeline layout doesn't exist in the [DatabaseLayout], a timeline view is a grid view
+/// whose grid layout setting is a [TimelineLayoutSetting].
 impl std::convert::From<DatabaseLayoutPB> for DatabaseLayout {
   fn from(layout: DatabaseLayoutPB) -> Self {
     match layout {
-      DatabaseLayoutPB::Grid => DatabaseLayout::Grid,
+      DatabaseLayoutPB::Grid | DatabaseLayoutPB::Timeline => DatabaseLayout::Grid,
       DatabaseLayoutPB::Board => DatabaseLayout::Board,
       DatabaseLayoutPB::Calendar => DatabaseLayout::Calendar,
     }
@@ -120,6 +125,9 @@ pub struct DatabaseLayoutSettingPB {
 
   #[pb(index = 3, one_of)]
   pub calendar: Option<CalendarLayoutSettingPB>,
+
+  #[pb(index = 4, one_of)]
+  pub timeline: Option<TimelineLayoutSettingPB>,
 }
 
 impl DatabaseLayoutSettingPB {
@@ -128,6 +136,7 @@ impl DatabaseLayoutSettingPB {
       layout_type: DatabaseLayoutPB::Board,
       board: Some(layout_setting.into()),
       calendar: None,
+      timeline: None,
     }
   }
 
@@ -136,6 +145,16 @@ impl DatabaseLayoutSettingPB {
       layout_type: DatabaseLayoutPB::Calendar,
       calendar: Some(layout_setting.into()),
       board: None,
+      timeline: None,
+    }
+  }
+
+  pub fn from_timeline(layout_setting: TimelineLayoutSetting) -> Self {
+    Self {
+      layout_type: DatabaseLayoutPB::Timeline,
+      timeline: Some(layout_setting.into()),
+      board: None,
+      calendar: None,
     }
   }
 }
@@ -145,6 +164,7 @@ pub struct LayoutSettingParams {
   pub layout_type: DatabaseLayout,
   pub board: Option<BoardLayoutSetting>,
   pub calendar: Option<CalendarLayoutSetting>,
+  pub timeline: Option<TimelineLayoutSetting>,
 }
 
 impl LayoutSettingParams {
@@ -158,10 +178,16 @@ impl LayoutSettingParams {
 
 impl From<LayoutSettingParams> for DatabaseLayoutSettingPB {
   fn from(data: LayoutSettingParams) -> Self {
+    let layout_type = if data.timeline.is_some() {
+      DatabaseLayoutPB::Timeline
+    } else {
+      data.layout_type.into()
+    };
     Self {
-      layout_type: data.layout_type.into(),
+      layout_type,
       board: data.board.map(|board| board.into()),
       calendar: data.calendar.map(|calendar| calendar.into()),
+      timeline: data.timeline.map(|timeline| timeline.into()),
     }
   }
 }
@@ -179,6 +205,9 @@ pub struct LayoutSettingChangesetPB {
 
   #[pb(index = 4, one_of)]
   pub calendar: Option<CalendarLayoutSettingPB>,
+
+  #[pb(index = 5, one_of)]
+  pub timeline: Option<TimelineLayoutSettingPB>,
 }
 
 #[derive(Debug)]
@@ -187,12 +216,14 @@ pub struct LayoutSettingChangeset {
   pub layout_type: DatabaseLayout,
   pub board: Option<BoardLayoutSetting>,
   pub calendar: Option<CalendarLayoutSetting>,
+  pub timeline: Option<TimelineLayoutSetting>,
 }
 
 impl LayoutSettingChangeset {
   pub fn is_valid(&self) -> bool {
     self.board.is_some() && self.layout_type == DatabaseLayout::Board
       || self.calendar.is_some() && self.layout_type == DatabaseLayout::Calendar
+      || self.timeline.is_some() && self.layout_type == DatabaseLayout::Grid
   }
 }
 
@@ -209,6 +240,7 @@ impl TryInto<LayoutSettingChangeset> for LayoutSettingChangesetPB {
       layout_type: self.layout_type.into(),
       board: self.board.map(Into::into),
       calendar: self.calendar.map(Into::into),
+      timeline: self.timeline.map(Into::into),
     })
   }
 }
@@ -1,5 +1,8 @@
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 
+use crate::entities::{FieldType, NumberFormatPB};
+use crate::services::share::csv::{CSVColumnPreview, CSVImportPreview, CSVImportReport};
+
 #[derive(Debug, ProtoBuf_Enum, Clone, Default)]
 pub enum DatabaseExportDataType {
   #[default]
@@ -7,6 +10,10 @@ pub enum DatabaseExportDataType {
 
   // DatabaseData
   RawDatabaseData = 1,
+
+  XLSX = 2,
+
+  ICS = 3,
 }
 
 #[derive(Debug, ProtoBuf, Default, Clone)]
@@ -16,4 +23,124 @@ pub struct DatabaseExportDataPB {
 
   #[pb(index = 2)]
   pub data: String,
+
+  /// The exported file content for the binary formats, such as XLSX.
+  #[pb(index = 3)]
+  pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportPreviewPayloadPB {
+  #[pb(index = 1)]
+  pub content: String,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportPreviewPB {
+  #[pb(index = 1)]
+  pub columns: Vec<CSVColumnPreviewPB>,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVColumnPreviewPB {
+  #[pb(index = 1)]
+  pub name: String,
+
+  #[pb(index = 2)]
+  pub field_type: FieldType,
+
+  /// Only used when the field type is [FieldType::Number].
+  #[pb(index = 3)]
+  pub number_format: NumberFormatPB,
+
+  #[pb(index = 4)]
+  pub sample_values: Vec<String>,
+}
+
+impl From<CSVImportPreview> for CSVImportPreviewPB {
+  fn from(preview: CSVImportPreview) -> Self {
+    Self {
+      columns: preview
+        .columns
+        .into_iter()
+        .map(|column| CSVColumnPreviewPB {
+          name: column.name,
+          field_type: column.field_type,
+          number_format: column.number_format.into(),
+          sample_values: column.sample_values,
+        })
+        .collect(),
+    }
+  }
+}
+
+impl From<CSVImportPreviewPB> for CSVImportPreview {
+  fn from(pb: CSVImportPreviewPB) -> Self {
+    Self {
+      columns: pb
+        .columns
+        .into_iter()
+        .map(|column| CSVColumnPreview {
+          name: column.name,
+          field_type: column.field_type,
+          number_format: column.number_format.into(),
+          sample_values: column.sample_values,
+        })
+        .collect(),
+    }
+  }
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportProgressPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub num_of_imported_rows: i64,
+
+  #[pb(index = 3)]
+  pub num_of_skipped_lines: i64,
+
+  /// From 0.0 to 1.0, estimated by the number of bytes read from the file.
+  #[pb(index = 4)]
+  pub progress: f64,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVImportReportPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub num_of_imported_rows: i64,
+
+  #[pb(index = 3)]
+  pub skipped_lines: Vec<CSVSkippedLinePB>,
+}
+
+#[derive(Debug, ProtoBuf, Default, Clone)]
+pub struct CSVSkippedLinePB {
+  #[pb(index = 1)]
+  pub line: i64,
+
+  #[pb(index = 2)]
+  pub reason: String,
+}
+
+impl CSVImportReportPB {
+  pub fn new(view_id: &str, report: &CSVImportReport) -> Self {
+    Self {
+      view_id: view_id.to_string(),
+      num_of_imported_rows: report.num_of_rows as i64,
+      skipped_lines: report
+        .skipped_lines
+        .iter()
+        .map(|skipped_line| CSVSkippedLinePB {
+          line: skipped_line.line as i64,
+          reason: skipped_line.reason.clone(),
+        })
+        .collect(),
+    }
+  }
 }
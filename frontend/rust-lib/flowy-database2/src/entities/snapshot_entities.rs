@@ -0,0 +1,159 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+use crate::services::snapshot::{
+  CellSnapshotDiff, DatabaseSnapshotDiff, DatabaseSnapshotMeta, FieldSnapshotDiff, RowSnapshotDiff,
+  SnapshotDiffKind,
+};
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DatabaseSnapshotMetaPB {
+  #[pb(index = 1)]
+  pub snapshot_id: String,
+
+  #[pb(index = 2)]
+  pub database_id: String,
+
+  #[pb(index = 3)]
+  pub created_at: i64,
+}
+
+impl From<DatabaseSnapshotMeta> for DatabaseSnapshotMetaPB {
+  fn from(meta: DatabaseSnapshotMeta) -> Self {
+    Self {
+      snapshot_id: meta.snapshot_id,
+      database_id: meta.object_id,
+      created_at: meta.created_at,
+    }
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RepeatedDatabaseSnapshotMetaPB {
+  #[pb(index = 1)]
+  pub items: Vec<DatabaseSnapshotMetaPB>,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DatabaseSnapshotIdPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub snapshot_id: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RowSnapshotIdPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub snapshot_id: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+pub enum SnapshotDiffKindPB {
+  #[default]
+  Added = 0,
+  Removed = 1,
+  Modified = 2,
+}
+
+impl From<SnapshotDiffKind> for SnapshotDiffKindPB {
+  fn from(kind: SnapshotDiffKind) -> Self {
+    match kind {
+      SnapshotDiffKind::Added => SnapshotDiffKindPB::Added,
+      SnapshotDiffKind::Removed => SnapshotDiffKindPB::Removed,
+      SnapshotDiffKind::Modified => SnapshotDiffKindPB::Modified,
+    }
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct FieldSnapshotDiffPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub kind: SnapshotDiffKindPB,
+}
+
+impl From<FieldSnapshotDiff> for FieldSnapshotDiffPB {
+  fn from(diff: FieldSnapshotDiff) -> Self {
+    Self {
+      field_id: diff.field_id,
+      name: diff.name,
+      kind: diff.kind.into(),
+    }
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct CellSnapshotDiffPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  /// The content of the cell in the snapshot.
+  #[pb(index = 2)]
+  pub old_content: String,
+
+  /// The current content of the cell.
+  #[pb(index = 3)]
+  pub new_content: String,
+}
+
+impl From<CellSnapshotDiff> for CellSnapshotDiffPB {
+  fn from(diff: CellSnapshotDiff) -> Self {
+    Self {
+      field_id: diff.field_id,
+      old_content: diff.old_content,
+      new_content: diff.new_content,
+    }
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RowSnapshotDiffPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub kind: SnapshotDiffKindPB,
+
+  #[pb(index = 3)]
+  pub cells: Vec<CellSnapshotDiffPB>,
+}
+
+impl From<RowSnapshotDiff> for RowSnapshotDiffPB {
+  fn from(diff: RowSnapshotDiff) -> Self {
+    Self {
+      row_id: diff.row_id.to_string(),
+      kind: diff.kind.into(),
+      cells: diff.cells.into_iter().map(Into::into).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DatabaseSnapshotDiffPB {
+  #[pb(index = 1)]
+  pub fields: Vec<FieldSnapshotDiffPB>,
+
+  #[pb(index = 2)]
+  pub rows: Vec<RowSnapshotDiffPB>,
+}
+
+impl From<DatabaseSnapshotDiff> for DatabaseSnapshotDiffPB {
+  fn from(diff: DatabaseSnapshotDiff) -> Self {
+    Self {
+      fields: diff.fields.into_iter().map(Into::into).collect(),
+      rows: diff.rows.into_iter().map(Into::into).collect(),
+    }
+  }
+}
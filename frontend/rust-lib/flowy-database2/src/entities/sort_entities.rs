@@ -2,7 +2,9 @@ use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use lib_infra::validator_fn::required_not_empty_str;
 use validator::Validate;
 
-use crate::services::sort::{Sort, SortCondition};
+use crate::services::sort::{
+  EmptyCellsPosition, RelationSortBy, SelectSortBy, Sort, SortCondition,
+};
 
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct SortPB {
@@ -14,6 +16,21 @@ pub struct SortPB {
 
   #[pb(index = 3)]
   pub condition: SortConditionPB,
+
+  #[pb(index = 4)]
+  pub relation_sort_by: RelationSortByPB,
+
+  #[pb(index = 5)]
+  pub natural_order: bool,
+
+  #[pb(index = 6)]
+  pub locale_collation: bool,
+
+  #[pb(index = 7)]
+  pub select_sort_by: SelectSortByPB,
+
+  #[pb(index = 8)]
+  pub empty_cells: EmptyCellsPositionPB,
 }
 
 impl std::convert::From<&Sort> for SortPB {
@@ -22,6 +39,11 @@ impl std::convert::From<&Sort> for SortPB {
       id: sort.id.clone(),
       field_id: sort.field_id.clone(),
       condition: sort.condition.into(),
+      relation_sort_by: sort.relation_sort_by.into(),
+      natural_order: sort.natural_order,
+      locale_collation: sort.locale_collation,
+      select_sort_by: sort.select_sort_by.into(),
+      empty_cells: sort.empty_cells.into(),
     }
   }
 }
@@ -32,6 +54,11 @@ impl std::convert::From<Sort> for SortPB {
       id: sort.id,
       field_id: sort.field_id,
       condition: sort.condition.into(),
+      relation_sort_by: sort.relation_sort_by.into(),
+      natural_order: sort.natural_order,
+      locale_collation: sort.locale_collation,
+      select_sort_by: sort.select_sort_by.into(),
+      empty_cells: sort.empty_cells.into(),
     }
   }
 }
@@ -94,6 +121,90 @@ impl std::convert::From<SortConditionPB> for SortCondition {
   }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum RelationSortByPB {
+  #[default]
+  Unsorted = 0,
+  PrimaryText = 1,
+  RowCount = 2,
+}
+
+impl std::convert::From<RelationSortBy> for RelationSortByPB {
+  fn from(value: RelationSortBy) -> Self {
+    match value {
+      RelationSortBy::Unsorted => RelationSortByPB::Unsorted,
+      RelationSortBy::PrimaryText => RelationSortByPB::PrimaryText,
+      RelationSortBy::RowCount => RelationSortByPB::RowCount,
+    }
+  }
+}
+
+impl std::convert::From<RelationSortByPB> for RelationSortBy {
+  fn from(value: RelationSortByPB) -> Self {
+    match value {
+      RelationSortByPB::Unsorted => RelationSortBy::Unsorted,
+      RelationSortByPB::PrimaryText => RelationSortBy::PrimaryText,
+      RelationSortByPB::RowCount => RelationSortBy::RowCount,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum SelectSortByPB {
+  #[default]
+  OptionName = 0,
+  OptionOrder = 1,
+}
+
+impl std::convert::From<SelectSortBy> for SelectSortByPB {
+  fn from(value: SelectSortBy) -> Self {
+    match value {
+      SelectSortBy::OptionName => SelectSortByPB::OptionName,
+      SelectSortBy::OptionOrder => SelectSortByPB::OptionOrder,
+    }
+  }
+}
+
+impl std::convert::From<SelectSortByPB> for SelectSortBy {
+  fn from(value: SelectSortByPB) -> Self {
+    match value {
+      SelectSortByPB::OptionName => SelectSortBy::OptionName,
+      SelectSortByPB::OptionOrder => SelectSortBy::OptionOrder,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum EmptyCellsPositionPB {
+  #[default]
+  Default = 0,
+  First = 1,
+  Last = 2,
+}
+
+impl std::convert::From<EmptyCellsPosition> for EmptyCellsPositionPB {
+  fn from(value: EmptyCellsPosition) -> Self {
+    match value {
+      EmptyCellsPosition::Default => EmptyCellsPositionPB::Default,
+      EmptyCellsPosition::First => EmptyCellsPositionPB::First,
+      EmptyCellsPosition::Last => EmptyCellsPositionPB::Last,
+    }
+  }
+}
+
+impl std::convert::From<EmptyCellsPositionPB> for EmptyCellsPosition {
+  fn from(value: EmptyCellsPositionPB) -> Self {
+    match value {
+      EmptyCellsPositionPB::Default => EmptyCellsPosition::Default,
+      EmptyCellsPositionPB::First => EmptyCellsPosition::First,
+      EmptyCellsPositionPB::Last => EmptyCellsPosition::Last,
+    }
+  }
+}
+
 #[derive(ProtoBuf, Debug, Default, Clone, Validate)]
 pub struct UpdateSortPayloadPB {
   #[pb(index = 1)]
@@ -111,6 +222,25 @@ pub struct UpdateSortPayloadPB {
 
   #[pb(index = 4)]
   pub condition: SortConditionPB,
+
+  /// How the relation field is sorted, ignored by the other fields
+  #[pb(index = 5)]
+  pub relation_sort_by: RelationSortByPB,
+
+  /// Compares the numbers in the texts by their value
+  #[pb(index = 6)]
+  pub natural_order: bool,
+
+  /// Compares the texts ignoring their accents and case first
+  #[pb(index = 7)]
+  pub locale_collation: bool,
+
+  /// How the select fields are sorted, ignored by the other fields
+  #[pb(index = 8)]
+  pub select_sort_by: SelectSortByPB,
+
+  #[pb(index = 9)]
+  pub empty_cells: EmptyCellsPositionPB,
 }
 
 #[derive(Debug, Default, Clone, Validate, ProtoBuf)]
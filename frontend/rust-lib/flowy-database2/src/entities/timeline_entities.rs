@@ -0,0 +1,180 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::RowMetaPB;
+use crate::services::setting::{TimelineLayoutSetting, TimelineZoomLevel};
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf)]
+pub struct TimelineLayoutSettingPB {
+  #[pb(index = 1)]
+  pub zoom_level: TimelineZoomLevelPB,
+
+  #[pb(index = 2)]
+  pub start_field_id: String,
+
+  /// The end date of the start field is used if it's None
+  #[pb(index = 3, one_of)]
+  pub end_field_id: Option<String>,
+
+  #[pb(index = 4, one_of)]
+  pub dependency_field_id: Option<String>,
+}
+
+impl std::convert::From<TimelineLayoutSettingPB> for TimelineLayoutSetting {
+  fn from(pb: TimelineLayoutSettingPB) -> Self {
+    TimelineLayoutSetting {
+      zoom_level: pb.zoom_level.into(),
+      start_field_id: pb.start_field_id,
+      end_field_id: pb.end_field_id.filter(|id| !id.is_empty()),
+      dependency_field_id: pb.dependency_field_id.filter(|id| !id.is_empty()),
+    }
+  }
+}
+
+impl std::convert::From<TimelineLayoutSetting> for TimelineLayoutSettingPB {
+  fn from(params: TimelineLayoutSetting) -> Self {
+    TimelineLayoutSettingPB {
+      zoom_level: params.zoom_level.into(),
+      start_field_id: params.start_field_id,
+      end_field_id: params.end_field_id,
+      dependency_field_id: params.dependency_field_id,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum TimelineZoomLevelPB {
+  Day = 0,
+  #[default]
+  Week = 1,
+  Month = 2,
+  Quarter = 3,
+  Year = 4,
+}
+
+impl std::convert::From<TimelineZoomLevelPB> for TimelineZoomLevel {
+  fn from(pb: TimelineZoomLevelPB) -> Self {
+    match pb {
+      TimelineZoomLevelPB::Day => TimelineZoomLevel::Day,
+      TimelineZoomLevelPB::Week => TimelineZoomLevel::Week,
+      TimelineZoomLevelPB::Month => TimelineZoomLevel::Month,
+      TimelineZoomLevelPB::Quarter => TimelineZoomLevel::Quarter,
+      TimelineZoomLevelPB::Year => TimelineZoomLevel::Year,
+    }
+  }
+}
+
+impl std::convert::From<TimelineZoomLevel> for TimelineZoomLevelPB {
+  fn from(level: TimelineZoomLevel) -> Self {
+    match level {
+      TimelineZoomLevel::Day => TimelineZoomLevelPB::Day,
+      TimelineZoomLevel::Week => TimelineZoomLevelPB::Week,
+      TimelineZoomLevel::Month => TimelineZoomLevelPB::Month,
+      TimelineZoomLevel::Quarter => TimelineZoomLevelPB::Quarter,
+      TimelineZoomLevel::Year => TimelineZoomLevelPB::Year,
+    }
+  }
+}
+
+/// Requests the bars that overlap the window between `start` and `end`, both are timestamps in
+/// seconds.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct TimelineBarRequestPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub start: i64,
+
+  #[pb(index = 3)]
+  pub end: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimelineBarRequestParams {
+  pub view_id: String,
+  pub start: i64,
+  pub end: i64,
+}
+
+impl TryInto<TimelineBarRequestParams> for TimelineBarRequestPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<TimelineBarRequestParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    if self.start > self.end {
+      return Err(ErrorCode::InvalidParams);
+    }
+    Ok(TimelineBarRequestParams {
+      view_id: view_id.0,
+      start: self.start,
+      end: self.end,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct TimelineBarPB {
+  #[pb(index = 1)]
+  pub row_meta: RowMetaPB,
+
+  #[pb(index = 2)]
+  pub start: i64,
+
+  #[pb(index = 3)]
+  pub end: i64,
+
+  /// The ids of the rows this row depends on
+  #[pb(index = 4)]
+  pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedTimelineBarPB {
+  #[pb(index = 1)]
+  pub items: Vec<TimelineBarPB>,
+}
+
+/// Moves or resizes the bar of a row by setting its start and end.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct UpdateTimelineBarPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub start: i64,
+
+  #[pb(index = 4)]
+  pub end: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateTimelineBarParams {
+  pub view_id: String,
+  pub row_id: String,
+  pub start: i64,
+  pub end: i64,
+}
+
+impl TryInto<UpdateTimelineBarParams> for UpdateTimelineBarPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<UpdateTimelineBarParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+    if self.start > self.end {
+      return Err(ErrorCode::InvalidParams);
+    }
+    Ok(UpdateTimelineBarParams {
+      view_id: view_id.0,
+      row_id: row_id.0,
+      start: self.start,
+      end: self.end,
+    })
+  }
+}
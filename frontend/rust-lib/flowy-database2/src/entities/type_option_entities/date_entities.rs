@@ -8,6 +8,7 @@ use strum_macros::EnumIter;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 
 use crate::entities::CellIdPB;
+use crate::services::field::{bounded_interval, RecurrenceFrequency, RecurrenceRule};
 
 #[derive(Clone, Debug, Default, ProtoBuf)]
 pub struct DateCellDataPB {
@@ -25,6 +26,9 @@ pub struct DateCellDataPB {
 
   #[pb(index = 5)]
   pub reminder_id: String,
+
+  #[pb(index = 6, one_of)]
+  pub recurrence: Option<RecurrenceRulePB>,
 }
 
 impl From<&DateCellDataPB> for DateCellData {
@@ -61,6 +65,92 @@ pub struct DateCellChangesetPB {
 
   #[pb(index = 7, one_of)]
   pub reminder_id: Option<String>,
+
+  #[pb(index = 8, one_of)]
+  pub recurrence: Option<RecurrenceRulePB>,
+
+  #[pb(index = 9, one_of)]
+  pub clear_recurrence: Option<bool>,
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct RecurrenceRulePB {
+  #[pb(index = 1)]
+  pub frequency: RecurrenceFrequencyPB,
+
+  #[pb(index = 2)]
+  pub interval: u32,
+
+  /// The days of the week of the weekly occurrences, 0 is Monday
+  #[pb(index = 3)]
+  pub by_weekday: Vec<u32>,
+
+  #[pb(index = 4, one_of)]
+  pub until: Option<i64>,
+
+  #[pb(index = 5, one_of)]
+  pub count: Option<u32>,
+
+  /// The start of the occurrences that are removed from the series
+  #[pb(index = 6)]
+  pub exceptions: Vec<i64>,
+}
+
+impl From<RecurrenceRulePB> for RecurrenceRule {
+  fn from(data: RecurrenceRulePB) -> Self {
+    Self {
+      frequency: data.frequency.into(),
+      interval: bounded_interval(data.interval),
+      by_weekday: data.by_weekday,
+      until: data.until,
+      count: data.count,
+      exceptions: data.exceptions,
+    }
+  }
+}
+
+impl From<RecurrenceRule> for RecurrenceRulePB {
+  fn from(data: RecurrenceRule) -> Self {
+    Self {
+      frequency: data.frequency.into(),
+      interval: data.interval,
+      by_weekday: data.by_weekday,
+      until: data.until,
+      count: data.count,
+      exceptions: data.exceptions,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Copy, PartialEq, Eq, ProtoBuf_Enum, Default)]
+pub enum RecurrenceFrequencyPB {
+  #[default]
+  Daily = 0,
+  Weekly = 1,
+  Monthly = 2,
+  Yearly = 3,
+}
+
+impl From<RecurrenceFrequencyPB> for RecurrenceFrequency {
+  fn from(data: RecurrenceFrequencyPB) -> Self {
+    match data {
+      RecurrenceFrequencyPB::Daily => RecurrenceFrequency::Daily,
+      RecurrenceFrequencyPB::Weekly => RecurrenceFrequency::Weekly,
+      RecurrenceFrequencyPB::Monthly => RecurrenceFrequency::Monthly,
+      RecurrenceFrequencyPB::Yearly => RecurrenceFrequency::Yearly,
+    }
+  }
+}
+
+impl From<RecurrenceFrequency> for RecurrenceFrequencyPB {
+  fn from(data: RecurrenceFrequency) -> Self {
+    match data {
+      RecurrenceFrequency::Daily => RecurrenceFrequencyPB::Daily,
+      RecurrenceFrequency::Weekly => RecurrenceFrequencyPB::Weekly,
+      RecurrenceFrequency::Monthly => RecurrenceFrequencyPB::Monthly,
+      RecurrenceFrequency::Yearly => RecurrenceFrequencyPB::Yearly,
+    }
+  }
 }
 
 // Date
@@ -1,9 +1,11 @@
 mod checkbox_entities;
 mod checklist_entities;
 mod date_entities;
+mod formula_entities;
 mod media_entities;
 mod number_entities;
 mod relation_entities;
+mod rollup_entities;
 mod select_option_entities;
 mod summary_entities;
 mod text_entities;
@@ -15,9 +17,11 @@ mod url_entities;
 pub use checkbox_entities::*;
 pub use checklist_entities::*;
 pub use date_entities::*;
+pub use formula_entities::*;
 pub use media_entities::*;
 pub use number_entities::*;
 pub use relation_entities::*;
+pub use rollup_entities::*;
 pub use select_option_entities::*;
 pub use summary_entities::*;
 pub use text_entities::*;
@@ -0,0 +1,80 @@
+use crate::services::field::{RollupCalculationType, RollupTypeOption};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RollupTypeOptionPB {
+  #[pb(index = 1)]
+  pub relation_field_id: String,
+
+  #[pb(index = 2)]
+  pub target_field_id: String,
+
+  #[pb(index = 3)]
+  pub calculation_type: RollupCalculationTypePB,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ProtoBuf_Enum)]
+pub enum RollupCalculationTypePB {
+  #[default]
+  List = 0,
+  Count = 1,
+  CountValues = 2,
+  Sum = 3,
+  Average = 4,
+  Min = 5,
+  Max = 6,
+  Earliest = 7,
+  Latest = 8,
+}
+
+impl From<RollupCalculationType> for RollupCalculationTypePB {
+  fn from(value: RollupCalculationType) -> Self {
+    match value {
+      RollupCalculationType::List => RollupCalculationTypePB::List,
+      RollupCalculationType::Count => RollupCalculationTypePB::Count,
+      RollupCalculationType::CountValues => RollupCalculationTypePB::CountValues,
+      RollupCalculationType::Sum => RollupCalculationTypePB::Sum,
+      RollupCalculationType::Average => RollupCalculationTypePB::Average,
+      RollupCalculationType::Min => RollupCalculationTypePB::Min,
+      RollupCalculationType::Max => RollupCalculationTypePB::Max,
+      RollupCalculationType::Earliest => RollupCalculationTypePB::Earliest,
+      RollupCalculationType::Latest => RollupCalculationTypePB::Latest,
+    }
+  }
+}
+
+impl From<RollupCalculationTypePB> for RollupCalculationType {
+  fn from(value: RollupCalculationTypePB) -> Self {
+    match value {
+      RollupCalculationTypePB::List => RollupCalculationType::List,
+      RollupCalculationTypePB::Count => RollupCalculationType::Count,
+      RollupCalculationTypePB::CountValues => RollupCalculationType::CountValues,
+      RollupCalculationTypePB::Sum => RollupCalculationType::Sum,
+      RollupCalculationTypePB::Average => RollupCalculationType::Average,
+      RollupCalculationTypePB::Min => RollupCalculationType::Min,
+      RollupCalculationTypePB::Max => RollupCalculationType::Max,
+      RollupCalculationTypePB::Earliest => RollupCalculationType::Earliest,
+      RollupCalculationTypePB::Latest => RollupCalculationType::Latest,
+    }
+  }
+}
+
+impl From<RollupTypeOption> for RollupTypeOptionPB {
+  fn from(value: RollupTypeOption) -> Self {
+    RollupTypeOptionPB {
+      relation_field_id: value.relation_field_id,
+      target_field_id: value.target_field_id,
+      calculation_type: value.calculation_type.into(),
+    }
+  }
+}
+
+impl From<RollupTypeOptionPB> for RollupTypeOption {
+  fn from(value: RollupTypeOptionPB) -> Self {
+    RollupTypeOption {
+      relation_field_id: value.relation_field_id,
+      target_field_id: value.target_field_id,
+      calculation_type: value.calculation_type.into(),
+    }
+  }
+}
@@ -0,0 +1,165 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+use crate::services::field::{CellValidationError, FieldValidation, ValidationSeverity};
+
+/// The validation rules of a field, the rules that don't apply to the field type are ignored
+#[derive(Debug, Clone, Default, PartialEq, ProtoBuf)]
+pub struct FieldValidationPB {
+  #[pb(index = 1)]
+  pub required: bool,
+
+  #[pb(index = 2, one_of)]
+  pub min: Option<f64>,
+
+  #[pb(index = 3, one_of)]
+  pub max: Option<f64>,
+
+  #[pb(index = 4, one_of)]
+  pub pattern: Option<String>,
+
+  #[pb(index = 5, one_of)]
+  pub min_date: Option<i64>,
+
+  #[pb(index = 6, one_of)]
+  pub max_date: Option<i64>,
+
+  #[pb(index = 7, one_of)]
+  pub max_selected_options: Option<u32>,
+
+  #[pb(index = 8)]
+  pub severity: ValidationSeverityPB,
+
+  #[pb(index = 9)]
+  pub unique: bool,
+}
+
+impl From<FieldValidation> for FieldValidationPB {
+  fn from(validation: FieldValidation) -> Self {
+    Self {
+      required: validation.required,
+      min: validation.min,
+      max: validation.max,
+      pattern: validation.pattern,
+      min_date: validation.min_date,
+      max_date: validation.max_date,
+      max_selected_options: validation.max_selected_options,
+      severity: validation.severity.into(),
+      unique: validation.unique,
+    }
+  }
+}
+
+impl From<FieldValidationPB> for FieldValidation {
+  fn from(pb: FieldValidationPB) -> Self {
+    Self {
+      required: pb.required,
+      unique: pb.unique,
+      min: pb.min,
+      max: pb.max,
+      pattern: pb.pattern.filter(|pattern| !pattern.is_empty()),
+      min_date: pb.min_date,
+      max_date: pb.max_date,
+      max_selected_options: pb.max_selected_options,
+      severity: pb.severity.into(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum ValidationSeverityPB {
+  /// The invalid cells are saved and reported
+  #[default]
+  Warning = 0,
+  /// The invalid cells are rejected with the [CellValidationFailed](flowy_error::ErrorCode) error
+  Error = 1,
+}
+
+impl From<ValidationSeverity> for ValidationSeverityPB {
+  fn from(severity: ValidationSeverity) -> Self {
+    match severity {
+      ValidationSeverity::Warning => ValidationSeverityPB::Warning,
+      ValidationSeverity::Error => ValidationSeverityPB::Error,
+    }
+  }
+}
+
+impl From<ValidationSeverityPB> for ValidationSeverity {
+  fn from(pb: ValidationSeverityPB) -> Self {
+    match pb {
+      ValidationSeverityPB::Warning => ValidationSeverity::Warning,
+      ValidationSeverityPB::Error => ValidationSeverity::Error,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct FieldValidationIdPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub field_id: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct UpdateFieldValidationPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub field_id: String,
+
+  /// The rules replace the current rules of the field, the empty rules remove them
+  #[pb(index = 3)]
+  pub validation: FieldValidationPB,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, ProtoBuf)]
+pub struct CellValidationErrorPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub message: String,
+
+  #[pb(index = 4)]
+  pub severity: ValidationSeverityPB,
+}
+
+impl From<CellValidationError> for CellValidationErrorPB {
+  fn from(error: CellValidationError) -> Self {
+    Self {
+      row_id: error.row_id.to_string(),
+      field_id: error.field_id,
+      message: error.message,
+      severity: error.severity.into(),
+    }
+  }
+}
+
+/// The validation errors of the cells. It's the payload of the
+/// [CellValidationFailed](flowy_error::ErrorCode) errors, and of the row validation
+/// notifications, whose errors are empty once the row is valid.
+#[derive(Debug, Clone, Default, PartialEq, ProtoBuf)]
+pub struct RepeatedCellValidationErrorPB {
+  #[pb(index = 1)]
+  pub items: Vec<CellValidationErrorPB>,
+}
+
+impl From<Vec<CellValidationError>> for RepeatedCellValidationErrorPB {
+  fn from(errors: Vec<CellValidationError>) -> Self {
+    Self {
+      items: errors.into_iter().map(Into::into).collect(),
+    }
+  }
+}
@@ -162,7 +162,7 @@ pub(crate) async fn update_database_setting_handler(
 
   if let Some(layout_type) = params.layout_type {
     database_editor
-      .update_view_layout(&params.view_id, layout_type.into())
+      .update_view_layout(&params.view_id, layout_type)
       .await?;
   }
   Ok(())
@@ -717,6 +717,8 @@ pub(crate) async fn update_date_cell_handler(
     is_range: data.is_range,
     clear_flag: data.clear_flag,
     reminder_id: data.reminder_id,
+    recurrence: data.recurrence.map(Into::into),
+    clear_recurrence: data.clear_recurrence,
   };
 
   let database_editor = manager
@@ -779,6 +781,26 @@ pub(crate) async fn set_group_by_field_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn set_sub_group_by_field_handler(
+  data: AFPluginData<SubGroupByFieldPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: SubGroupByFieldParams = data.into_inner().try_into()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor
+    .set_sub_group_by_field(
+      &params.view_id,
+      params.field_id.as_deref(),
+      params.setting_content,
+    )
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn update_group_handler(
   data: AFPluginData<UpdateGroupPB>,
@@ -828,6 +850,7 @@ pub(crate) async fn move_group_row_handler(
       &params.to_group_id,
       params.from_row_id,
       params.to_row_id,
+      params.to_sub_group_id.as_deref(),
     )
     .await?;
   Ok(())
@@ -941,7 +964,7 @@ pub(crate) async fn get_calendar_events_handler(
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
   let events = database_editor
-    .get_all_calendar_events(&params.view_id)
+    .get_all_calendar_events(&params.view_id, params.window)
     .await;
   data_result_ok(RepeatedCalendarEventPB { items: events })
 }
@@ -987,21 +1010,46 @@ pub(crate) async fn move_calendar_event_handler(
   manager: AFPluginState<Weak<DatabaseManager>>,
 ) -> FlowyResult<()> {
   let manager = upgrade_manager(manager)?;
-  let data = data.into_inner();
-  let cell_id: CellIdParams = data.cell_path.try_into()?;
-  let cell_changeset = DateCellChangeset {
-    timestamp: Some(data.timestamp),
-    ..Default::default()
-  };
+  let params: MoveCalendarEventParams = data.into_inner().try_into()?;
   let database_editor = manager
-    .get_database_editor_with_view_id(&cell_id.view_id)
+    .get_database_editor_with_view_id(&params.cell_id.view_id)
+    .await?;
+  database_editor.move_calendar_event(params).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn get_timeline_bars_handler(
+  data: AFPluginData<TimelineBarRequestPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedTimelineBarPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: TimelineBarRequestParams = data.into_inner().try_into()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let bars = database_editor
+    .get_timeline_bars(&params.view_id, params.start, params.end)
+    .await?;
+  data_result_ok(RepeatedTimelineBarPB { items: bars })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn update_timeline_bar_handler(
+  data: AFPluginData<UpdateTimelineBarPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: UpdateTimelineBarParams = data.into_inner().try_into()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
     .await?;
   database_editor
-    .update_cell_with_changeset(
-      &cell_id.view_id,
-      &cell_id.row_id,
-      &cell_id.field_id,
-      BoxAny::new(cell_changeset),
+    .update_timeline_bar(
+      &params.view_id,
+      &RowId::from(params.row_id),
+      params.start,
+      params.end,
     )
     .await?;
   Ok(())
@@ -1028,9 +1076,137 @@ pub(crate) async fn export_csv_handler(
   data_result_ok(DatabaseExportDataPB {
     export_type: DatabaseExportDataType::CSV,
     data,
+    ..Default::default()
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_xlsx_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let bytes = manager.export_xlsx(&view_id).await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::XLSX,
+    bytes,
+    ..Default::default()
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_ics_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let data = manager.export_ics(&view_id).await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::ICS,
+    data,
+    ..Default::default()
   })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_field_validation_handler(
+  data: AFPluginData<FieldValidationIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<FieldValidationPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let validation = database_editor
+    .get_field_validation(&params.field_id)
+    .await
+    .unwrap_or_default();
+  data_result_ok(validation.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_field_validation_handler(
+  data: AFPluginData<UpdateFieldValidationPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  database_editor
+    .update_field_validation(&params.field_id, params.validation.into())
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_validation_errors_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedCellValidationErrorPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database_editor = manager.get_database_editor_with_view_id(&view_id).await?;
+  let errors = database_editor.get_validation_errors(&view_id).await?;
+  data_result_ok(errors.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn find_duplicate_rows_handler(
+  data: AFPluginData<FindDuplicateRowsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedDuplicateRowGroupPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let groups = database_editor
+    .find_duplicate_rows(&params.view_id, params.field_ids, params.fuzzy)
+    .await?;
+  data_result_ok(groups.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn merge_duplicate_rows_handler(
+  data: AFPluginData<MergeDuplicateRowsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager
+    .get_database_editor_with_view_id(&params.view_id)
+    .await?;
+  let duplicate_row_ids = params
+    .duplicate_row_ids
+    .into_iter()
+    .map(RowId::from)
+    .collect::<Vec<_>>();
+  database_editor
+    .merge_duplicate_rows(
+      &params.view_id,
+      &RowId::from(params.row_id),
+      &duplicate_row_ids,
+    )
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn preview_csv_import_handler(
+  data: AFPluginData<CSVImportPreviewPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<CSVImportPreviewPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let content = data.into_inner().content;
+  let preview = manager.preview_csv(content).await?;
+  data_result_ok(preview.into())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn export_raw_database_data_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -1042,6 +1218,7 @@ pub(crate) async fn export_raw_database_data_handler(
   data_result_ok(DatabaseExportDataPB {
     export_type: DatabaseExportDataType::RawDatabaseData,
     data,
+    ..Default::default()
   })
 }
 
@@ -1056,6 +1233,106 @@ pub(crate) async fn get_snapshots_handler(
   data_result_ok(RepeatedDatabaseSnapshotPB { items: snapshots })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn create_database_snapshot_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  manager.create_database_snapshot(&view_id).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_database_snapshot_metas_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedDatabaseSnapshotMetaPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let items = manager
+    .get_database_snapshot_metas(&view_id)
+    .await?
+    .into_iter()
+    .map(DatabaseSnapshotMetaPB::from)
+    .collect();
+  data_result_ok(RepeatedDatabaseSnapshotMetaPB { items })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_row_snapshot_metas_handler(
+  data: AFPluginData<DatabaseViewRowIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedDatabaseSnapshotMetaPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: RowIdParams = data.into_inner().try_into()?;
+  let items = manager
+    .get_row_snapshot_metas(&params.view_id, &params.row_id)
+    .await?
+    .into_iter()
+    .map(DatabaseSnapshotMetaPB::from)
+    .collect();
+  data_result_ok(RepeatedDatabaseSnapshotMetaPB { items })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_database_snapshot_diff_handler(
+  data: AFPluginData<DatabaseSnapshotIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseSnapshotDiffPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let diff = manager
+    .get_database_snapshot_diff(&params.view_id, &params.snapshot_id)
+    .await?;
+  data_result_ok(diff.into())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_row_snapshot_diff_handler(
+  data: AFPluginData<RowSnapshotIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseSnapshotDiffPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let row_id = RowId::from(params.row_id);
+  let diff = manager
+    .get_row_snapshot_diff(&params.view_id, &row_id, &params.snapshot_id)
+    .await?;
+  data_result_ok(DatabaseSnapshotDiffPB {
+    fields: vec![],
+    rows: diff.into_iter().map(RowSnapshotDiffPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn restore_database_snapshot_handler(
+  data: AFPluginData<DatabaseSnapshotIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  manager
+    .restore_database_snapshot(&params.view_id, &params.snapshot_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn restore_row_snapshot_handler(
+  data: AFPluginData<RowSnapshotIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let row_id = RowId::from(params.row_id);
+  manager
+    .restore_row_snapshot(&params.view_id, &row_id, &params.snapshot_id)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_field_settings_handler(
   data: AFPluginData<FieldIdsPB>,
@@ -1138,7 +1415,7 @@ pub(crate) async fn update_calculation_handler(
   manager: AFPluginState<Weak<DatabaseManager>>,
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
-  let params: UpdateCalculationChangesetPB = data.into_inner();
+  let params: UpdateCalculationChangesetPB = data.try_into_inner()?;
   let editor = manager
     .get_database_editor_with_view_id(&params.view_id)
     .await?;
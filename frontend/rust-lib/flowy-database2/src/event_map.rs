@@ -56,6 +56,7 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          .event(DatabaseEvent::UpdateDateCell, update_date_cell_handler)
          // Group
          .event(DatabaseEvent::SetGroupByField, set_group_by_field_handler)
+         .event(DatabaseEvent::SetSubGroupByField, set_sub_group_by_field_handler)
          .event(DatabaseEvent::MoveGroup, move_group_handler)
          .event(DatabaseEvent::MoveGroupRow, move_group_row_handler)
          .event(DatabaseEvent::GetGroups, get_groups_handler)
@@ -71,6 +72,9 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          .event(DatabaseEvent::GetNoDateCalendarEvents, get_no_date_calendar_events_handler)
          .event(DatabaseEvent::GetCalendarEvent, get_calendar_event_handler)
          .event(DatabaseEvent::MoveCalendarEvent, move_calendar_event_handler)
+         // Timeline
+         .event(DatabaseEvent::GetTimelineBars, get_timeline_bars_handler)
+         .event(DatabaseEvent::UpdateTimelineBar, update_timeline_bar_handler)
          // Layout setting
          .event(DatabaseEvent::SetLayoutSetting, set_layout_setting_handler)
          .event(DatabaseEvent::GetLayoutSetting, get_layout_setting_handler)
@@ -78,7 +82,23 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
          // Export
          .event(DatabaseEvent::ExportCSV, export_csv_handler)
          .event(DatabaseEvent::ExportRawDatabaseData, export_raw_database_data_handler)
+         .event(DatabaseEvent::ExportXLSX, export_xlsx_handler)
+         .event(DatabaseEvent::ExportICS, export_ics_handler)
+         .event(DatabaseEvent::PreviewCSVImport, preview_csv_import_handler)
+         // Validation
+         .event(DatabaseEvent::GetFieldValidation, get_field_validation_handler)
+         .event(DatabaseEvent::UpdateFieldValidation, update_field_validation_handler)
+         .event(DatabaseEvent::GetValidationErrors, get_validation_errors_handler)
+         .event(DatabaseEvent::FindDuplicateRows, find_duplicate_rows_handler)
+         .event(DatabaseEvent::MergeDuplicateRows, merge_duplicate_rows_handler)
          .event(DatabaseEvent::GetDatabaseSnapshots, get_snapshots_handler)
+         .event(DatabaseEvent::CreateDatabaseSnapshot, create_database_snapshot_handler)
+         .event(DatabaseEvent::GetDatabaseSnapshotMetas, get_database_snapshot_metas_handler)
+         .event(DatabaseEvent::GetRowSnapshotMetas, get_row_snapshot_metas_handler)
+         .event(DatabaseEvent::GetDatabaseSnapshotDiff, get_database_snapshot_diff_handler)
+         .event(DatabaseEvent::GetRowSnapshotDiff, get_row_snapshot_diff_handler)
+         .event(DatabaseEvent::RestoreDatabaseSnapshot, restore_database_snapshot_handler)
+         .event(DatabaseEvent::RestoreRowSnapshot, restore_row_snapshot_handler)
          // Field settings
          .event(DatabaseEvent::GetFieldSettings, get_field_settings_handler)
          .event(DatabaseEvent::GetAllFieldSettings, get_all_field_settings_handler)
@@ -284,6 +304,11 @@ pub enum DatabaseEvent {
   #[event(input = "GroupByFieldPayloadPB")]
   SetGroupByField = 90,
 
+  /// [SetSubGroupByField] event is used to group the rows of each group by a second field, for
+  /// example the swimlanes of a board. The sub groups are removed if the `field_id` is None.
+  #[event(input = "SubGroupByFieldPayloadPB")]
+  SetSubGroupByField = 91,
+
   #[event(input = "DatabaseViewIdPB", output = "RepeatedGroupPB")]
   GetGroups = 100,
 
@@ -333,6 +358,14 @@ pub enum DatabaseEvent {
   #[event(input = "MoveCalendarEventPB")]
   MoveCalendarEvent = 126,
 
+  /// Returns the bars of the timeline view that overlap the requested window
+  #[event(input = "TimelineBarRequestPB", output = "RepeatedTimelineBarPB")]
+  GetTimelineBars = 127,
+
+  /// Moves or resizes the bar of a row in the timeline view
+  #[event(input = "UpdateTimelineBarPB")]
+  UpdateTimelineBar = 128,
+
   #[event(input = "CreateDatabaseViewPayloadPB")]
   CreateDatabaseView = 130,
 
@@ -343,6 +376,37 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotPB")]
   GetDatabaseSnapshots = 150,
 
+  /// Saves the current state of the database as a local snapshot, if it has changed since the
+  /// last snapshot.
+  #[event(input = "DatabaseViewIdPB")]
+  CreateDatabaseSnapshot = 151,
+
+  /// Returns the local snapshots of the database, the latest first.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotMetaPB")]
+  GetDatabaseSnapshotMetas = 152,
+
+  /// Returns the local snapshots in which the row has a different content.
+  #[event(
+    input = "DatabaseViewRowIdPB",
+    output = "RepeatedDatabaseSnapshotMetaPB"
+  )]
+  GetRowSnapshotMetas = 153,
+
+  /// Compares the snapshot with the current fields, rows and cells of the database.
+  #[event(input = "DatabaseSnapshotIdPB", output = "DatabaseSnapshotDiffPB")]
+  GetDatabaseSnapshotDiff = 154,
+
+  /// Compares the row in the snapshot with the current row. The returned diff contains at most
+  /// one row.
+  #[event(input = "RowSnapshotIdPB", output = "DatabaseSnapshotDiffPB")]
+  GetRowSnapshotDiff = 155,
+
+  #[event(input = "DatabaseSnapshotIdPB")]
+  RestoreDatabaseSnapshot = 156,
+
+  #[event(input = "RowSnapshotIdPB")]
+  RestoreRowSnapshot = 157,
+
   /// Returns the field settings for the provided fields in the given view
   #[event(input = "FieldIdsPB", output = "RepeatedFieldSettingsPB")]
   GetFieldSettings = 160,
@@ -396,6 +460,46 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
   ExportRawDatabaseData = 178,
 
+  /// Export the rows of the view as an XLSX workbook. The content is returned in the `bytes` of
+  /// the [DatabaseExportDataPB].
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportXLSX = 179,
+
+  /// Returns the field type inferred for each column of the CSV content before it is imported.
+  /// The edited preview is passed to the import in the `csv_preview` of the
+  /// `ImportValuePayloadPB`.
+  #[event(input = "CSVImportPreviewPayloadPB", output = "CSVImportPreviewPB")]
+  PreviewCSVImport = 180,
+
+  /// Export the events of the calendar view as an iCalendar file. The content is returned in the
+  /// `data` of the [DatabaseExportDataPB].
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportICS = 181,
+
+  /// Returns the validation rules of the field. The rules are empty if the field doesn't have any.
+  #[event(input = "FieldValidationIdPB", output = "FieldValidationPB")]
+  GetFieldValidation = 182,
+
+  /// Replaces the validation rules of the field. The existing cells that break the new rules are
+  /// kept, they are returned by [DatabaseEvent::GetValidationErrors].
+  #[event(input = "UpdateFieldValidationPayloadPB")]
+  UpdateFieldValidation = 183,
+
+  /// Returns the validation errors of the cells of the rows in the view
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedCellValidationErrorPB")]
+  GetValidationErrors = 184,
+
+  /// Returns the groups of rows whose cells of the key fields are equal, or fuzzy-equal
+  #[event(
+    input = "FindDuplicateRowsPayloadPB",
+    output = "RepeatedDuplicateRowGroupPB"
+  )]
+  FindDuplicateRows = 185,
+
+  /// Combines the cells of the duplicate rows into one row and deletes the duplicate rows
+  #[event(input = "MergeDuplicateRowsPayloadPB")]
+  MergeDuplicateRows = 186,
+
   #[event(input = "MediaCellChangesetPB")]
   UpdateMediaCell = 200,
 
@@ -8,8 +8,7 @@ use collab::preclude::Collab;
 use collab_database::database::{Database, DatabaseData};
 use collab_database::entity::{CreateDatabaseParams, CreateViewParams};
 use collab_database::error::DatabaseError;
-use collab_database::rows::RowId;
-use collab_database::template::csv::CSVTemplate;
+use collab_database::rows::{Row, RowId};
 use collab_database::views::DatabaseLayout;
 use collab_database::workspace_database::{
   CollabPersistenceImpl, DatabaseCollabPersistenceService, DatabaseCollabService, DatabaseMeta,
@@ -19,8 +18,13 @@ use collab_entity::{CollabObject, CollabType, EncodedCollab};
 use collab_plugins::local_storage::kv::KVTransactionDB;
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
 use tokio::sync::Mutex;
 use tracing::{error, info, instrument, trace, warn};
 
@@ -30,17 +34,29 @@ use flowy_database_pub::cloud::{
   DatabaseAIService, DatabaseCloudService, SummaryRowContent, TranslateItem, TranslateRowContent,
 };
 use flowy_error::{internal_error, FlowyError, FlowyResult};
+use flowy_search_pub::entities::{ContentIndexManager, IndexableContent};
 use lib_dispatch::prelude::af_spawn;
 use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::TaskDispatcher;
 
-use crate::entities::{DatabaseLayoutPB, DatabaseSnapshotPB, FieldType, RowMetaPB};
+use crate::entities::{
+  CSVImportProgressPB, DatabaseLayoutPB, DatabaseSnapshotPB, FieldType, RowMetaPB,
+};
+use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::cell::stringify_cell;
-use crate::services::database::DatabaseEditor;
+use crate::services::database::{row_index_content, DatabaseCellChanged, DatabaseEditor};
 use crate::services::database_view::DatabaseLayoutDepsResolver;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::share::csv::{CSVFormat, CSVImporter, ImportResult};
+use crate::services::share::csv::{
+  CSVFormat, CSVImportPreview, CSVImportReport, CSVImporter, CSVRecordReader, ImportResult,
+};
+use crate::services::share::ics::ICSImporter;
+use crate::services::share::xlsx::XLSXImporter;
+use crate::services::snapshot::{
+  diff_database_snapshot, diff_row_snapshot, DatabaseSnapshotContent, DatabaseSnapshotData,
+  DatabaseSnapshotDiff, DatabaseSnapshotMeta, RowSnapshotDiff,
+};
 use tokio::sync::RwLock as TokioRwLock;
 
 pub trait DatabaseUser: Send + Sync {
@@ -50,16 +66,26 @@ pub trait DatabaseUser: Send + Sync {
   fn workspace_database_object_id(&self) -> Result<String, FlowyError>;
 }
 
+/// Stores the snapshots of the databases on the local disk, so they are available without
+/// connecting to the server.
+pub trait DatabaseSnapshotService: Send + Sync {
+  fn create_snapshot(&self, database_id: &str, data: Vec<u8>) -> FlowyResult<()>;
+  fn get_snapshot_metas(&self, database_id: &str) -> FlowyResult<Vec<DatabaseSnapshotMeta>>;
+  fn get_snapshot(&self, snapshot_id: &str) -> FlowyResult<DatabaseSnapshotData>;
+}
+
 pub(crate) type DatabaseEditorMap = HashMap<String, Arc<DatabaseEditor>>;
 pub struct DatabaseManager {
   user: Arc<dyn DatabaseUser>,
   workspace_database_manager: ArcSwapOption<RwLock<WorkspaceDatabaseManager>>,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
-  pub(crate) editors: Mutex<DatabaseEditorMap>,
+  pub(crate) editors: Arc<Mutex<DatabaseEditorMap>>,
   removing_editor: Arc<Mutex<HashMap<String, Arc<DatabaseEditor>>>>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
   ai_service: Arc<dyn DatabaseAIService>,
+  snapshot_service: Arc<dyn DatabaseSnapshotService>,
+  database_indexer: Arc<dyn ContentIndexManager>,
 }
 
 impl DatabaseManager {
@@ -69,6 +95,8 @@ impl DatabaseManager {
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DatabaseCloudService>,
     ai_service: Arc<dyn DatabaseAIService>,
+    snapshot_service: Arc<dyn DatabaseSnapshotService>,
+    database_indexer: Arc<dyn ContentIndexManager>,
   ) -> Self {
     Self {
       user: database_user,
@@ -79,6 +107,8 @@ impl DatabaseManager {
       collab_builder,
       cloud_service,
       ai_service,
+      snapshot_service,
+      database_indexer,
     }
   }
 
@@ -251,6 +281,7 @@ impl DatabaseManager {
       database,
       self.task_scheduler.clone(),
       self.collab_builder.clone(),
+      Arc::downgrade(&self.editors),
     )
     .await?;
 
@@ -259,6 +290,11 @@ impl DatabaseManager {
       .lock()
       .await
       .insert(database_id.to_string(), editor.clone());
+    observe_rollup_dependencies(&self.editors, &editor);
+    observe_database_snapshots(self.snapshot_service.clone(), &editor).await;
+    if let Ok(workspace_id) = self.user.workspace_id() {
+      observe_row_index(self.database_indexer.clone(), workspace_id, &editor);
+    }
     Ok(editor)
   }
 
@@ -337,9 +373,53 @@ impl DatabaseManager {
   pub async fn delete_database_view(&self, view_id: &str) -> FlowyResult<()> {
     let database = self.get_database_editor_with_view_id(view_id).await?;
     let _ = database.delete_database_view(view_id).await?;
+    // The rows of the database can't be found anymore once its last view is deleted
+    let is_deleted = database
+      .database
+      .read()
+      .await
+      .get_all_database_views_meta()
+      .is_empty();
+    if is_deleted {
+      self
+        .database_indexer
+        .remove_object(&database.get_database_id())?;
+    }
     Ok(())
   }
 
+  /// Indexes the rows of the database for the local search, unless the database is already
+  /// indexed. The opened databases keep their index current.
+  pub async fn index_database_if_needed(&self, view_id: &str) -> FlowyResult<()> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    if self.database_indexer.is_object_indexed(&database_id) {
+      return Ok(());
+    }
+
+    let workspace_id = self.user.workspace_id()?;
+    let data = self.get_database_data(view_id).await?;
+    let inline_view_id = data
+      .views
+      .iter()
+      .find(|view| view.is_inline)
+      .map(|view| view.id.as_str())
+      .unwrap_or(view_id);
+    let contents = data
+      .rows
+      .iter()
+      .map(|row| {
+        row_index_content(
+          row,
+          &data.fields,
+          &database_id,
+          inline_view_id,
+          &workspace_id,
+        )
+      })
+      .collect();
+    index_database_rows(self.database_indexer.clone(), database_id, contents).await
+  }
+
   pub async fn get_database_data(&self, view_id: &str) -> FlowyResult<DatabaseData> {
     let lock = self.workspace_database()?;
     let wdb = lock.read().await;
@@ -461,25 +541,94 @@ impl DatabaseManager {
     content: String,
     format: CSVFormat,
   ) -> FlowyResult<ImportResult> {
-    let params = match format {
-      CSVFormat::Original => {
-        let mut csv_template = CSVTemplate::try_from_reader(content.as_bytes(), true, None)?;
-        csv_template.reset_view_id(view_id.clone());
+    let cloned_view_id = view_id.clone();
+    let params = tokio::task::spawn_blocking(move || {
+      CSVImporter.import_csv_from_string(cloned_view_id, content, format)
+    })
+    .await
+    .map_err(internal_error)??;
 
-        let database_template = csv_template.try_into_database_template(None).await?;
-        database_template.into_params()
-      },
+    let result = self
+      .import_database_with_encoded_collabs(view_id, params)
+      .await?;
+    info!("import csv result: {}", result);
+    Ok(result)
+  }
 
-      CSVFormat::META => {
-        let cloned_view_id = view_id.clone();
-        tokio::task::spawn_blocking(move || {
-          CSVImporter.import_csv_from_string(cloned_view_id, content, format)
-        })
-        .await
-        .map_err(internal_error)??
-      },
-    };
+  /// Returns the field type that would be used for each column of the CSV content.
+  pub async fn preview_csv(&self, content: String) -> FlowyResult<CSVImportPreview> {
+    tokio::task::spawn_blocking(move || CSVImporter.preview_csv_from_string(content))
+      .await
+      .map_err(internal_error)?
+  }
+
+  /// Imports the CSV content with the field types of the preview, which may have been changed by
+  /// the user.
+  pub async fn import_csv_with_preview(
+    &self,
+    view_id: String,
+    content: String,
+    preview: CSVImportPreview,
+  ) -> FlowyResult<ImportResult> {
+    let cloned_view_id = view_id.clone();
+    let params = tokio::task::spawn_blocking(move || {
+      CSVImporter.import_csv_from_string_with_preview(cloned_view_id, content, preview)
+    })
+    .await
+    .map_err(internal_error)??;
+
+    let result = self
+      .import_database_with_encoded_collabs(view_id, params)
+      .await?;
+    info!("import csv result: {}", result);
+    Ok(result)
+  }
+
+  /// Imports each sheet of the workbook as a separate database. Returns the name of the sheet
+  /// along with the import result of its database.
+  pub async fn import_xlsx(&self, content: Vec<u8>) -> FlowyResult<Vec<(String, ImportResult)>> {
+    let sheets = tokio::task::spawn_blocking(move || XLSXImporter.import_xlsx_from_bytes(content))
+      .await
+      .map_err(internal_error)??;
+
+    let mut results = Vec::with_capacity(sheets.len());
+    for sheet in sheets {
+      let view_id = sheet
+        .params
+        .views
+        .first()
+        .map(|view| view.view_id.clone())
+        .unwrap_or_default();
+      let result = self
+        .import_database_with_encoded_collabs(view_id, sheet.params)
+        .await?;
+      info!("import xlsx sheet {} result: {}", sheet.name, result);
+      results.push((sheet.name, result));
+    }
+    Ok(results)
+  }
+
+  /// Imports the events of the iCalendar content as a calendar database
+  pub async fn import_ics(&self, view_id: String, content: String) -> FlowyResult<ImportResult> {
+    let cloned_view_id = view_id.clone();
+    let params = tokio::task::spawn_blocking(move || {
+      ICSImporter.import_ics_from_string(cloned_view_id, content)
+    })
+    .await
+    .map_err(internal_error)??;
+
+    let result = self
+      .import_database_with_encoded_collabs(view_id, params)
+      .await?;
+    info!("import ics result: {}", result);
+    Ok(result)
+  }
 
+  async fn import_database_with_encoded_collabs(
+    &self,
+    view_id: String,
+    params: CreateDatabaseParams,
+  ) -> FlowyResult<ImportResult> {
     let database_id = params.database_id.clone();
     let database = self.import_database(params).await?;
     let encoded_database = database.read().await.encode_database_collabs().await?;
@@ -487,22 +636,72 @@ impl DatabaseManager {
       .chain(encoded_database.encoded_row_collabs.into_iter())
       .collect::<Vec<_>>();
 
-    let result = ImportResult {
+    Ok(ImportResult {
       database_id,
       view_id,
       encoded_collabs,
-    };
-    info!("import csv result: {}", result);
-    Ok(result)
+    })
   }
 
-  // will implement soon
+  /// Imports the CSV file without loading it into memory. The records are read and inserted in
+  /// batches, and the progress is sent to the `import_id` after each batch. The field types of
+  /// the `preview` are used if it is given, otherwise they are inferred from the first batch.
+  /// The lines that can't be parsed are skipped and returned in the [CSVImportReport].
   pub async fn import_csv_from_file(
     &self,
-    _file_path: String,
-    _format: CSVFormat,
-  ) -> FlowyResult<()> {
-    Ok(())
+    view_id: String,
+    import_id: &str,
+    file_path: String,
+    format: CSVFormat,
+    preview: Option<CSVImportPreview>,
+  ) -> FlowyResult<CSVImportReport> {
+    let total_bytes = tokio::fs::metadata(&file_path)
+      .await
+      .map(|metadata| metadata.len())
+      .unwrap_or_default();
+    let reader = CSVImporter.open_csv_file(&file_path)?;
+    let (mut reader, records) = read_csv_records(reader).await?;
+    let params = CSVImporter.database_from_records(
+      &view_id,
+      reader.fields().to_vec(),
+      records,
+      format,
+      preview,
+    )?;
+    let database_id = params.database_id.clone();
+    let fields = params.fields.clone();
+    let database = self.import_database(params).await?;
+    notify_csv_import_progress(import_id, &view_id, &reader, total_bytes);
+
+    loop {
+      let (next_reader, records) = read_csv_records(reader).await?;
+      reader = next_reader;
+      if records.is_empty() {
+        break;
+      }
+
+      let database_id = database_id.clone();
+      let fields = fields.clone();
+      let rows = tokio::task::spawn_blocking(move || {
+        CSVImporter.rows_from_records(&database_id, &fields, &records, format)
+      })
+      .await
+      .map_err(internal_error)?;
+      let mut database = database.write().await;
+      for row in rows {
+        database.create_row_in_view(&view_id, row).await?;
+      }
+      drop(database);
+      notify_csv_import_progress(import_id, &view_id, &reader, total_bytes);
+    }
+
+    let report = reader.into_report();
+    info!(
+      "import csv file: {} rows, {} skipped lines",
+      report.num_of_rows,
+      report.skipped_lines.len()
+    );
+    Ok(report)
   }
 
   pub async fn export_csv(&self, view_id: &str, style: CSVFormat) -> FlowyResult<String> {
@@ -510,13 +709,28 @@ impl DatabaseManager {
     database.export_csv(style).await
   }
 
+  pub async fn export_xlsx(&self, view_id: &str) -> FlowyResult<Vec<u8>> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    database.export_xlsx(view_id).await
+  }
+
+  pub async fn export_ics(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    database.export_ics(view_id).await
+  }
+
+  pub async fn export_html(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.get_database_editor_with_view_id(view_id).await?;
+    database.export_html(view_id).await
+  }
+
   pub async fn update_database_layout(
     &self,
     view_id: &str,
     layout: DatabaseLayoutPB,
   ) -> FlowyResult<()> {
     let database = self.get_database_editor_with_view_id(view_id).await?;
-    database.update_view_layout(view_id, layout.into()).await
+    database.update_view_layout(view_id, layout).await
   }
 
   pub async fn get_database_snapshots(
@@ -541,6 +755,139 @@ impl DatabaseManager {
     Ok(snapshots)
   }
 
+  /// Saves the current fields and rows of the database as a snapshot. Nothing is saved if the
+  /// database hasn't changed since the last snapshot.
+  pub async fn create_database_snapshot(&self, view_id: &str) -> FlowyResult<()> {
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    save_database_snapshot(self.snapshot_service.as_ref(), &editor).await?;
+    Ok(())
+  }
+
+  /// Returns the local snapshots of the database, the latest first.
+  pub async fn get_database_snapshot_metas(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Vec<DatabaseSnapshotMeta>> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    get_sorted_snapshot_metas(self.snapshot_service.as_ref(), &database_id)
+  }
+
+  /// Returns the snapshots in which the row has a different content than in the older snapshot,
+  /// the latest first.
+  pub async fn get_row_snapshot_metas(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+  ) -> FlowyResult<Vec<DatabaseSnapshotMeta>> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let metas = get_sorted_snapshot_metas(self.snapshot_service.as_ref(), &database_id)?;
+    let mut row_metas = vec![];
+    let mut newer_row: Option<Row> = None;
+    for meta in metas {
+      let row = self
+        .get_database_snapshot_content(&database_id, &meta.snapshot_id)?
+        .rows
+        .into_iter()
+        .find(|row| &row.id == row_id);
+      if let Some(row) = row {
+        if let Some(newer_row) = newer_row.as_ref() {
+          // The row didn't change between the two snapshots, keep the older one only.
+          if newer_row.cells == row.cells {
+            row_metas.pop();
+          }
+        }
+        row_metas.push(meta);
+        newer_row = Some(row);
+      }
+    }
+    Ok(row_metas)
+  }
+
+  pub async fn get_database_snapshot_diff(
+    &self,
+    view_id: &str,
+    snapshot_id: &str,
+  ) -> FlowyResult<DatabaseSnapshotDiff> {
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    let snapshot = self.get_database_snapshot_content(&editor.get_database_id(), snapshot_id)?;
+    let current = editor.get_snapshot_content().await;
+    Ok(diff_database_snapshot(&snapshot, &current))
+  }
+
+  /// Returns None if the row is the same in the snapshot and in the database.
+  pub async fn get_row_snapshot_diff(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    snapshot_id: &str,
+  ) -> FlowyResult<Option<RowSnapshotDiff>> {
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    let snapshot = self.get_database_snapshot_content(&editor.get_database_id(), snapshot_id)?;
+    let current_row = editor.get_row(view_id, row_id).await;
+    let mut fields = editor.get_fields(view_id, None).await;
+    let deleted_fields = snapshot
+      .fields
+      .iter()
+      .filter(|field| fields.iter().all(|current| current.id != field.id))
+      .cloned()
+      .collect::<Vec<_>>();
+    fields.extend(deleted_fields);
+    Ok(diff_row_snapshot(
+      snapshot.get_row(row_id),
+      current_row.as_ref(),
+      &fields,
+    ))
+  }
+
+  /// Restores the fields and the rows of the database. The current state is saved as a snapshot
+  /// first, so the restore can be reverted too.
+  pub async fn restore_database_snapshot(
+    &self,
+    view_id: &str,
+    snapshot_id: &str,
+  ) -> FlowyResult<()> {
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    let snapshot = self.get_database_snapshot_content(&editor.get_database_id(), snapshot_id)?;
+    save_database_snapshot(self.snapshot_service.as_ref(), &editor).await?;
+    editor.restore_snapshot(snapshot).await
+  }
+
+  /// Restores the cells of a single row. The row is created again if it has been deleted.
+  pub async fn restore_row_snapshot(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    snapshot_id: &str,
+  ) -> FlowyResult<()> {
+    let editor = self.get_database_editor_with_view_id(view_id).await?;
+    let snapshot_row = self
+      .get_database_snapshot_content(&editor.get_database_id(), snapshot_id)?
+      .rows
+      .into_iter()
+      .find(|row| &row.id == row_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Row {} not found in the snapshot", row_id))
+      })?;
+    save_database_snapshot(self.snapshot_service.as_ref(), &editor).await?;
+    editor.restore_row(view_id, snapshot_row).await
+  }
+
+  fn get_database_snapshot_content(
+    &self,
+    database_id: &str,
+    snapshot_id: &str,
+  ) -> FlowyResult<DatabaseSnapshotContent> {
+    let snapshot = self.snapshot_service.get_snapshot(snapshot_id)?;
+    if snapshot.object_id != database_id {
+      return Err(FlowyError::record_not_found().with_context(format!(
+        "Snapshot {} doesn't belong to the database {}",
+        snapshot_id, database_id
+      )));
+    }
+    DatabaseSnapshotContent::from_json_bytes(&snapshot.data)
+  }
+
   fn workspace_database(&self) -> FlowyResult<Arc<RwLock<WorkspaceDatabaseManager>>> {
     self
       .workspace_database_manager
@@ -924,6 +1271,254 @@ impl DatabaseCollabService for WorkspaceDatabaseCollabServiceImpl {
   }
 }
 
+/// Keeps the rollups of the opened databases up to date with the given database: refreshes them
+/// once the database is opened and whenever the cells of the database change. The views that
+/// filter or sort by the rows of the given database are refreshed as well.
+fn observe_rollup_dependencies(
+  editors: &Arc<Mutex<DatabaseEditorMap>>,
+  editor: &Arc<DatabaseEditor>,
+) {
+  let weak_editors = Arc::downgrade(editors);
+  let database_id = editor.get_database_id();
+  let mut rx = editor.subscribe_cell_changed();
+  af_spawn(async move {
+    if let Some(editors) = weak_editors.upgrade() {
+      refresh_rollups_on_open(&editors, &database_id).await;
+    }
+
+    loop {
+      match rx.recv().await {
+        Ok(changed) => match weak_editors.upgrade() {
+          Some(editors) => {
+            refresh_rollups_on_cell_changed(&editors, &database_id, changed).await;
+            refresh_relations_on_cell_changed(&editors, &database_id).await;
+          },
+          None => break,
+        },
+        Err(RecvError::Lagged(_)) => continue,
+        Err(RecvError::Closed) => break,
+      }
+    }
+  });
+}
+
+/// The changes of the cells are collected for this duration before the index is updated, so
+/// editing a cell doesn't commit the index after every keystroke.
+const ROW_INDEX_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Indexes the rows of the database for the local search when the database is opened, and keeps
+/// the index current with the changes of the cells and the rows.
+fn observe_row_index(
+  database_indexer: Arc<dyn ContentIndexManager>,
+  workspace_id: String,
+  editor: &Arc<DatabaseEditor>,
+) {
+  let weak_editor = Arc::downgrade(editor);
+  let mut rx = editor.subscribe_cell_changed();
+  af_spawn(async move {
+    if let Some(editor) = weak_editor.upgrade() {
+      index_all_rows(&database_indexer, &workspace_id, &editor).await;
+    }
+
+    loop {
+      let mut changes = match rx.recv().await {
+        Ok(changed) => vec![Some(changed)],
+        // Some changes were missed, so all the rows are indexed again
+        Err(RecvError::Lagged(_)) => vec![None],
+        Err(RecvError::Closed) => break,
+      };
+      tokio::time::sleep(ROW_INDEX_DEBOUNCE).await;
+      loop {
+        match rx.try_recv() {
+          Ok(changed) => changes.push(Some(changed)),
+          Err(TryRecvError::Lagged(_)) => changes.push(None),
+          Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => break,
+        }
+      }
+      let editor = match weak_editor.upgrade() {
+        Some(editor) => editor,
+        None => break,
+      };
+
+      let mut row_ids = vec![];
+      let mut is_all_rows_changed = false;
+      for changed in changes {
+        match changed {
+          Some(DatabaseCellChanged::Cell { row_id, .. }) => row_ids.push(row_id),
+          Some(DatabaseCellChanged::Rows { row_ids: ids }) => row_ids.extend(ids),
+          Some(DatabaseCellChanged::Field { .. }) | None => is_all_rows_changed = true,
+        }
+      }
+      if is_all_rows_changed {
+        index_all_rows(&database_indexer, &workspace_id, &editor).await;
+        continue;
+      }
+
+      row_ids.sort();
+      row_ids.dedup();
+      let (contents, deleted_row_ids) = editor
+        .get_row_index_contents(&workspace_id, Some(&row_ids))
+        .await;
+      let indexer = database_indexer.clone();
+      let result = tokio::task::spawn_blocking(move || {
+        indexer.update_contents(contents)?;
+        indexer.remove_contents(
+          deleted_row_ids
+            .into_iter()
+            .map(|row_id| row_id.to_string())
+            .collect(),
+        )
+      })
+      .await
+      .map_err(internal_error)
+      .and_then(|result| result);
+      if let Err(err) = result {
+        error!(
+          "[Database]: failed to index rows of {}: {}",
+          editor.get_database_id(),
+          err
+        );
+      }
+    }
+  });
+}
+
+async fn index_all_rows(
+  database_indexer: &Arc<dyn ContentIndexManager>,
+  workspace_id: &str,
+  editor: &DatabaseEditor,
+) {
+  let database_id = editor.get_database_id();
+  let (contents, _) = editor.get_row_index_contents(workspace_id, None).await;
+  if let Err(err) =
+    index_database_rows(database_indexer.clone(), database_id.clone(), contents).await
+  {
+    error!(
+      "[Database]: failed to index rows of {}: {}",
+      database_id, err
+    );
+  }
+}
+
+/// Replaces the indexed rows of the database. The index is committed on a blocking thread.
+async fn index_database_rows(
+  database_indexer: Arc<dyn ContentIndexManager>,
+  database_id: String,
+  contents: Vec<IndexableContent>,
+) -> FlowyResult<()> {
+  tokio::task::spawn_blocking(move || database_indexer.index_object(&database_id, contents))
+    .await
+    .map_err(internal_error)?
+}
+
+async fn refresh_rollups_on_open(editors: &Mutex<DatabaseEditorMap>, database_id: &str) {
+  let editors = editors.lock().await.clone();
+  for (id, editor) in editors.iter() {
+    let rollup_field_ids = editor
+      .get_rollup_fields()
+      .await
+      .into_iter()
+      .filter(|rollup| id == database_id || rollup.related_database_id == database_id)
+      .map(|rollup| rollup.field_id)
+      .collect::<Vec<_>>();
+    if rollup_field_ids.is_empty() {
+      continue;
+    }
+
+    if let Err(err) = editor
+      .update_rollup_cells(&rollup_field_ids, None, &editors)
+      .await
+    {
+      error!(
+        "[Database]: failed to refresh rollup cells of {}: {}",
+        id, err
+      );
+    }
+  }
+}
+
+async fn refresh_rollups_on_cell_changed(
+  editors: &Mutex<DatabaseEditorMap>,
+  database_id: &str,
+  changed: DatabaseCellChanged,
+) {
+  let editors = editors.lock().await.clone();
+  for (id, editor) in editors.iter() {
+    for rollup in editor.get_rollup_fields().await {
+      let is_same_database = id == database_id;
+      let is_related_database = rollup.related_database_id == database_id;
+      let row_ids = match &changed {
+        DatabaseCellChanged::Cell { row_id, field_id } => {
+          if is_same_database && field_id == &rollup.type_option.relation_field_id {
+            Some(vec![row_id.clone()])
+          } else if is_related_database && field_id == &rollup.type_option.target_field_id {
+            // Only the rows that the changed row links back to are checked. The rows are only
+            // scanned if the related database has no relation field that links back.
+            let candidate_row_ids = match editors.get(database_id) {
+              Some(related_editor) => related_editor.get_linked_row_ids(row_id, id).await,
+              None => None,
+            };
+            Some(
+              editor
+                .get_rows_related_to(
+                  &rollup.type_option.relation_field_id,
+                  row_id,
+                  candidate_row_ids,
+                )
+                .await,
+            )
+          } else {
+            continue;
+          }
+        },
+        DatabaseCellChanged::Rows { .. } => {
+          // The rollups of the related rows might include the rows that were created or deleted
+          if is_related_database {
+            None
+          } else {
+            continue;
+          }
+        },
+        DatabaseCellChanged::Field { field_id } => {
+          let is_rollup_changed = is_same_database
+            && (field_id == &rollup.field_id || field_id == &rollup.type_option.relation_field_id);
+          let is_target_changed =
+            is_related_database && field_id == &rollup.type_option.target_field_id;
+          if is_rollup_changed || is_target_changed {
+            None
+          } else {
+            continue;
+          }
+        },
+      };
+
+      if let Err(err) = editor
+        .update_rollup_cells(&[rollup.field_id.clone()], row_ids, &editors)
+        .await
+      {
+        error!(
+          "[Database]: failed to update rollup cells of {}: {}",
+          id, err
+        );
+      }
+    }
+  }
+}
+
+/// Only the databases whose relation fields link to the given database are refreshed.
+async fn refresh_relations_on_cell_changed(editors: &Mutex<DatabaseEditorMap>, database_id: &str) {
+  let editors = editors.lock().await.clone();
+  for editor in editors.values() {
+    if editor
+      .get_related_database_ids()
+      .await
+      .contains(database_id)
+    {
+      editor.did_update_related_database(database_id).await;
+    }
+  }
+}
+
 pub struct DatabasePersistenceImpl {
   user: Arc<dyn DatabaseUser>,
 }
@@ -1081,6 +1676,142 @@ impl DatabaseCollabPersistenceService for DatabasePersistenceImpl {
     (vec![], row_ids)
   }
 }
+
+/// A snapshot of an opened database is taken at this interval if the database has changed.
+const DATABASE_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+async fn observe_database_snapshots(
+  snapshot_service: Arc<dyn DatabaseSnapshotService>,
+  editor: &Arc<DatabaseEditor>,
+) {
+  // The fields, the cells and the rows of the database are observed, so the snapshot is only
+  // compared with the last one after a change. It starts as changed, so the state in which the
+  // database was opened is always kept.
+  let is_changed = Arc::new(AtomicBool::new(true));
+  {
+    let database = editor.database.read().await;
+    if let Some(rx) = database.subscribe_row_change() {
+      mark_changed_on_recv(rx, Arc::downgrade(&is_changed));
+    }
+    if let Some(rx) = database.subscribe_field_change() {
+      mark_changed_on_recv(rx, Arc::downgrade(&is_changed));
+    }
+    if let Some(rx) = database.subscribe_view_change() {
+      mark_changed_on_recv(rx, Arc::downgrade(&is_changed));
+    }
+  }
+
+  let weak_editor = Arc::downgrade(editor);
+  af_spawn(async move {
+    let mut interval = tokio::time::interval(DATABASE_SNAPSHOT_INTERVAL);
+    loop {
+      interval.tick().await;
+      let editor = match weak_editor.upgrade() {
+        None => break,
+        Some(editor) => editor,
+      };
+      if !is_changed.swap(false, Ordering::AcqRel) {
+        continue;
+      }
+      if let Err(err) = save_database_snapshot(snapshot_service.as_ref(), &editor).await {
+        error!(
+          "[Database]: failed to save the snapshot of {}: {}",
+          editor.get_database_id(),
+          err
+        );
+      }
+    }
+  });
+}
+
+fn mark_changed_on_recv<T: Clone + Send + 'static>(
+  mut rx: broadcast::Receiver<T>,
+  is_changed: Weak<AtomicBool>,
+) {
+  af_spawn(async move {
+    loop {
+      match rx.recv().await {
+        Ok(_) | Err(RecvError::Lagged(_)) => match is_changed.upgrade() {
+          Some(is_changed) => is_changed.store(true, Ordering::Release),
+          None => break,
+        },
+        Err(RecvError::Closed) => break,
+      }
+    }
+  });
+}
+
+/// Returns false if the database hasn't changed since the last snapshot.
+async fn save_database_snapshot(
+  snapshot_service: &dyn DatabaseSnapshotService,
+  editor: &DatabaseEditor,
+) -> FlowyResult<bool> {
+  let database_id = editor.get_database_id();
+  let content = editor.get_snapshot_content().await;
+  let last_snapshot = get_sorted_snapshot_metas(snapshot_service, &database_id)?
+    .first()
+    .and_then(|meta| snapshot_service.get_snapshot(&meta.snapshot_id).ok())
+    .and_then(|snapshot| DatabaseSnapshotContent::from_json_bytes(&snapshot.data).ok());
+  if let Some(last_snapshot) = last_snapshot {
+    if diff_database_snapshot(&last_snapshot, &content).is_empty() {
+      return Ok(false);
+    }
+  }
+
+  trace!("[Database]: save the snapshot of {}", database_id);
+  snapshot_service.create_snapshot(&database_id, content.to_json_bytes()?)?;
+  Ok(true)
+}
+
+fn get_sorted_snapshot_metas(
+  snapshot_service: &dyn DatabaseSnapshotService,
+  database_id: &str,
+) -> FlowyResult<Vec<DatabaseSnapshotMeta>> {
+  let mut metas = snapshot_service.get_snapshot_metas(database_id)?;
+  metas.sort_by(|a, b| {
+    b.created_at
+      .cmp(&a.created_at)
+      .then_with(|| b.snapshot_id.cmp(&a.snapshot_id))
+  });
+  Ok(metas)
+}
+
+/// The number of records read from the CSV file and inserted into the database at once.
+const CSV_IMPORT_BATCH_SIZE: usize = 1000;
+
+async fn read_csv_records(
+  mut reader: CSVRecordReader<BufReader<File>>,
+) -> FlowyResult<(CSVRecordReader<BufReader<File>>, Vec<Vec<String>>)> {
+  tokio::task::spawn_blocking(move || {
+    let records = reader.read_records(CSV_IMPORT_BATCH_SIZE)?;
+    Ok::<_, FlowyError>((reader, records))
+  })
+  .await
+  .map_err(internal_error)?
+}
+
+fn notify_csv_import_progress(
+  import_id: &str,
+  view_id: &str,
+  reader: &CSVRecordReader<BufReader<File>>,
+  total_bytes: u64,
+) {
+  let progress = if total_bytes == 0 {
+    1.0
+  } else {
+    (reader.bytes_read() as f64 / total_bytes as f64).min(1.0)
+  };
+  let report = reader.report();
+  send_notification(import_id, DatabaseNotification::DidUpdateCSVImportProgress)
+    .payload(CSVImportProgressPB {
+      view_id: view_id.to_string(),
+      num_of_imported_rows: report.num_of_rows as i64,
+      num_of_skipped_lines: report.skipped_lines.len() as i64,
+      progress,
+    })
+    .send();
+}
+
 async fn open_database_with_retry(
   workspace_database_manager: Arc<RwLock<WorkspaceDatabaseManager>>,
   database_id: &str,
@@ -36,6 +36,10 @@ pub enum DatabaseNotification {
   DidReorderSingleRow = 66,
   /// Trigger after updating the row meta
   DidUpdateRowMeta = 67,
+  /// Trigger after the rows of the sub groups are changed
+  DidUpdateSubGroups = 68,
+  /// Trigger after the calculations of a group are changed
+  DidUpdateGroupCalculations = 69,
   /// Trigger when the settings of the database are changed
   DidUpdateSettings = 70,
   // Trigger when the layout setting of the database is updated
@@ -52,6 +56,13 @@ pub enum DatabaseNotification {
   DidUpdateFieldSettings = 86,
   // Trigger when Calculation changed
   DidUpdateCalculation = 87,
+  // Trigger after each batch of rows is imported from a CSV file
+  DidUpdateCSVImportProgress = 88,
+  // Trigger when the CSV file is imported, with the lines that were skipped
+  DidFinishCSVImport = 89,
+  // Trigger when a cell of a row whose fields have validation rules is updated, with the
+  // validation errors of the row
+  DidUpdateRowValidation = 90,
 }
 
 impl std::convert::From<DatabaseNotification> for i32 {
@@ -77,6 +88,8 @@ impl std::convert::From<i32> for DatabaseNotification {
       65 => DatabaseNotification::DidReorderRows,
       66 => DatabaseNotification::DidReorderSingleRow,
       67 => DatabaseNotification::DidUpdateRowMeta,
+      68 => DatabaseNotification::DidUpdateSubGroups,
+      69 => DatabaseNotification::DidUpdateGroupCalculations,
       70 => DatabaseNotification::DidUpdateSettings,
       80 => DatabaseNotification::DidUpdateLayoutSettings,
       82 => DatabaseNotification::DidUpdateDatabaseLayout,
@@ -84,6 +97,9 @@ impl std::convert::From<i32> for DatabaseNotification {
       84 => DatabaseNotification::DidMoveDatabaseViewToTrash,
       86 => DatabaseNotification::DidUpdateFieldSettings,
       87 => DatabaseNotification::DidUpdateCalculation,
+      88 => DatabaseNotification::DidUpdateCSVImportProgress,
+      89 => DatabaseNotification::DidFinishCSVImport,
+      90 => DatabaseNotification::DidUpdateRowValidation,
       _ => DatabaseNotification::Unknown,
     }
   }
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
+use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use collab_database::fields::Field;
-use collab_database::rows::{Cell, Row};
-use dashmap::DashMap;
+use collab_database::rows::{Cell, Row, RowId};
+use dashmap::{DashMap, DashSet};
 use flowy_error::FlowyResult;
 use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
 use serde::{Deserialize, Serialize};
@@ -16,6 +17,7 @@ use crate::entities::{
 };
 use crate::services::calculations::CalculationsByFieldIdCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
+use crate::services::field::TimestampCellData;
 use crate::utils::cache::AnyTypeCache;
 
 use super::{Calculation, CalculationChangeset, CalculationsService};
@@ -23,6 +25,8 @@ use super::{Calculation, CalculationChangeset, CalculationsService};
 #[async_trait]
 pub trait CalculationsDelegate: Send + Sync + 'static {
   async fn get_cells_for_field(&self, view_id: &str, field_id: &str) -> Vec<Arc<Cell>>;
+  /// Returns the rows of the view whose id is in `row_ids`
+  async fn get_rows(&self, view_id: &str, row_ids: &HashSet<RowId>) -> Vec<Arc<Row>>;
   async fn get_field(&self, field_id: &str) -> Option<Field>;
   async fn get_calculation(&self, view_id: &str, field_id: &str) -> Option<Arc<Calculation>>;
   async fn get_all_calculations(&self, view_id: &str) -> Vec<Arc<Calculation>>;
@@ -35,6 +39,9 @@ pub struct CalculationsController {
   handler_id: String,
   delegate: Box<dyn CalculationsDelegate>,
   calculations_by_field_cache: CalculationsByFieldIdCache,
+  /// The timestamp fields that have a calculation. Their cells are built from the rows, so their
+  /// calculations are updated whenever a row changes.
+  timestamp_field_ids: DashSet<String>,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
   calculations_service: CalculationsService,
   notifier: DatabaseViewChangedNotifier,
@@ -52,6 +59,7 @@ impl CalculationsController {
     handler_id: &str,
     delegate: T,
     calculations: Vec<Arc<Calculation>>,
+    timestamp_field_ids: HashSet<String>,
     task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
     notifier: DatabaseViewChangedNotifier,
   ) -> Self
@@ -63,6 +71,7 @@ impl CalculationsController {
       handler_id: handler_id.to_string(),
       delegate: Box::new(delegate),
       calculations_by_field_cache: AnyTypeCache::<String>::new(),
+      timestamp_field_ids: timestamp_field_ids.into_iter().collect(),
       task_scheduler,
       calculations_service: CalculationsService::new(),
       notifier,
@@ -130,6 +139,7 @@ impl CalculationsController {
   }
 
   async fn handle_field_deleted(&self, field_id: String) {
+    self.timestamp_field_ids.remove(&field_id);
     let calculation = self
       .delegate
       .get_calculation(&self.view_id, &field_id)
@@ -171,6 +181,12 @@ impl CalculationsController {
 
     if let Some(calculation) = calculation {
       let calc_type: CalculationType = calculation.calculation_type.into();
+      if calc_type.is_allowed(new_field_type) && new_field_type.is_timestamp() {
+        self.timestamp_field_ids.insert(field_id.clone());
+      } else {
+        self.timestamp_field_ids.remove(&field_id);
+      }
+
       if !calc_type.is_allowed(new_field_type) {
         self
           .delegate
@@ -275,6 +291,28 @@ impl CalculationsController {
       }
     }
 
+    // The created time and the last edited time are not stored in the cells of the row, so their
+    // calculations are updated whenever the row changes.
+    if !cells.is_empty() {
+      let timestamp_field_ids = self
+        .timestamp_field_ids
+        .iter()
+        .map(|field_id| field_id.key().clone())
+        .collect::<Vec<String>>();
+      for field_id in timestamp_field_ids {
+        if let Some(calculation) = self
+          .delegate
+          .get_calculation(&self.view_id, &field_id)
+          .await
+        {
+          let cells = self
+            .get_or_fetch_cells(&calculation.field_id, &mut cells_by_field)
+            .await;
+          updates.extend(self.handle_cells_changed(calculation, cells).await);
+        }
+      }
+    }
+
     if !updates.is_empty() {
       let notification = CalculationChangesetNotificationPB::from_update(&self.view_id, updates);
       let _ = self
@@ -336,7 +374,7 @@ impl CalculationsController {
   ) -> Option<Calculation> {
     let value = self
       .calculations_service
-      .calculate(field, calculation.calculation_type, cells);
+      .calculate(field, &calculation, cells);
 
     if value != calculation.value {
       return Some(calculation.with_value(value));
@@ -358,10 +396,11 @@ impl CalculationsController {
         .await;
 
       let field = self.delegate.get_field(&insert.field_id).await?;
+      if FieldType::from(field.field_type).is_timestamp() {
+        self.timestamp_field_ids.insert(field.id.clone());
+      }
 
-      let value = self
-        .calculations_service
-        .calculate(&field, insert.calculation_type, cells);
+      let value = self.calculations_service.calculate(&field, insert, cells);
 
       notification = Some(CalculationChangesetNotificationPB::from_insert(
         &self.view_id,
@@ -370,11 +409,13 @@ impl CalculationsController {
           field_id: insert.field_id.clone(),
           calculation_type: CalculationType::from(insert.calculation_type),
           value,
+          percentile: insert.percentile,
         }],
       ))
     }
 
     if let Some(delete) = &changeset.delete_calculation {
+      self.timestamp_field_ids.remove(&delete.field_id);
       notification = Some(CalculationChangesetNotificationPB::from_delete(
         &self.view_id,
         vec![CalculationPB {
@@ -382,6 +423,7 @@ impl CalculationsController {
           field_id: delete.field_id.clone(),
           calculation_type: CalculationType::from(delete.calculation_type),
           value: delete.value.clone(),
+          percentile: delete.percentile,
         }],
       ))
     }
@@ -389,6 +431,31 @@ impl CalculationsController {
     notification
   }
 
+  /// Evaluates the calculations of the view over the given rows, which are the rows of a group.
+  /// The rows are loaded once and shared by all the calculations.
+  pub async fn calculate_group(&self, row_ids: &HashSet<RowId>) -> Vec<CalculationPB> {
+    let all_calculations = self.delegate.get_all_calculations(&self.view_id).await;
+    if all_calculations.is_empty() {
+      return vec![];
+    }
+
+    let rows = self.delegate.get_rows(&self.view_id, row_ids).await;
+    let mut calculations = vec![];
+    for calculation in all_calculations {
+      if let Some(field) = self.delegate.get_field(&calculation.field_id).await {
+        let cells = rows
+          .iter()
+          .filter_map(|row| get_cell_in_row(row, &field))
+          .collect();
+        let value = self
+          .calculations_service
+          .calculate(&field, &calculation, cells);
+        calculations.push(CalculationPB::from(&calculation.with_value(value)));
+      }
+    }
+    calculations
+  }
+
   fn update_cache(&self, calculations: Vec<Arc<Calculation>>) {
     for calculation in calculations {
       let field_id = &calculation.field_id;
@@ -399,6 +466,18 @@ impl CalculationsController {
   }
 }
 
+/// Returns the cell of the field in the row. The timestamp fields don't store their cells, so they
+/// are built from the row like [crate::services::database::DatabaseEditor::get_cells_for_field]
+/// does.
+fn get_cell_in_row(row: &Row, field: &Field) -> Option<Arc<Cell>> {
+  let cell = match FieldType::from(field.field_type) {
+    FieldType::CreatedTime => Some(Cell::from(TimestampCellData::new(row.created_at))),
+    FieldType::LastEditedTime => Some(Cell::from(TimestampCellData::new(row.modified_at))),
+    _ => row.cells.get(&field.id).cloned(),
+  };
+  cell.map(Arc::new)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub(crate) enum CalculationEvent {
   InitialRows(Vec<Arc<Row>>),
@@ -3,6 +3,9 @@ use collab::preclude::Any;
 use collab_database::views::{CalculationMap, CalculationMapBuilder};
 use serde::Deserialize;
 
+/// The percentile of a percentile calculation when it's not set, which is the median.
+pub const DEFAULT_PERCENTILE: i64 = 50;
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Calculation {
   pub id: String,
@@ -11,12 +14,19 @@ pub struct Calculation {
   pub calculation_type: i64,
   #[serde(default, rename = "calculation_value")]
   pub value: String,
+  #[serde(default = "default_percentile")]
+  pub percentile: i64,
+}
+
+fn default_percentile() -> i64 {
+  DEFAULT_PERCENTILE
 }
 
 const CALCULATION_ID: &str = "id";
 const FIELD_ID: &str = "field_id";
 const CALCULATION_TYPE: &str = "ty";
 const CALCULATION_VALUE: &str = "calculation_value";
+const CALCULATION_PERCENTILE: &str = "percentile";
 
 impl From<Calculation> for CalculationMap {
   fn from(data: Calculation) -> Self {
@@ -25,6 +35,7 @@ impl From<Calculation> for CalculationMap {
       (FIELD_ID.into(), data.field_id.into()),
       (CALCULATION_TYPE.into(), Any::BigInt(data.calculation_type)),
       (CALCULATION_VALUE.into(), data.value.into()),
+      (CALCULATION_PERCENTILE.into(), Any::BigInt(data.percentile)),
     ])
   }
 }
@@ -60,15 +71,22 @@ impl Calculation {
       field_id,
       calculation_type: calculation_type.unwrap_or(0),
       value: "".to_owned(),
+      percentile: DEFAULT_PERCENTILE,
     }
   }
 
+  pub fn with_percentile(mut self, percentile: i64) -> Self {
+    self.percentile = percentile;
+    self
+  }
+
   pub fn with_value(&self, value: String) -> Self {
     Self {
       id: self.id.clone(),
       field_id: self.field_id.clone(),
       calculation_type: self.calculation_type,
       value,
+      percentile: self.percentile,
     }
   }
 }
@@ -1,20 +1,31 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use collab_database::fields::date_type_option::{DateCellData, DateTypeOption};
+use collab_database::fields::timestamp_type_option::TimestampTypeOption;
 use collab_database::fields::Field;
 use collab_database::rows::Cell;
 
-use crate::entities::CalculationType;
-use crate::services::field::TypeOptionCellExt;
+use crate::entities::{CalculationType, CheckboxCellDataPB, FieldType};
+use crate::services::calculations::Calculation;
+use crate::services::field::{TimestampCellData, TypeOptionCellExt};
 use rayon::prelude::*;
 
+const SECONDS_PER_DAY: i64 = 86_400;
+
 pub struct CalculationsService;
 impl CalculationsService {
   pub fn new() -> Self {
     Self
   }
 
-  pub fn calculate(&self, field: &Field, calculation_type: i64, cells: Vec<Arc<Cell>>) -> String {
-    let ty: CalculationType = calculation_type.into();
+  pub fn calculate(
+    &self,
+    field: &Field,
+    calculation: &Calculation,
+    cells: Vec<Arc<Cell>>,
+  ) -> String {
+    let ty: CalculationType = calculation.calculation_type.into();
 
     match ty {
       CalculationType::Average => self.calculate_average(field, cells),
@@ -25,6 +36,19 @@ impl CalculationsService {
       CalculationType::Count => self.calculate_count(cells),
       CalculationType::CountEmpty => self.calculate_count_empty(field, cells),
       CalculationType::CountNonEmpty => self.calculate_count_non_empty(field, cells),
+      CalculationType::CountUnique => self.calculate_count_unique(field, cells),
+      CalculationType::PercentEmpty => self.calculate_percent_empty(field, cells, true),
+      CalculationType::PercentNotEmpty => self.calculate_percent_empty(field, cells, false),
+      CalculationType::CountChecked => self.calculate_count_checked(cells),
+      CalculationType::PercentChecked => self.calculate_percent_checked(cells),
+      CalculationType::EarliestDate => self.calculate_earliest_date(field, cells),
+      CalculationType::LatestDate => self.calculate_latest_date(field, cells),
+      CalculationType::DateRange => self.calculate_date_range(field, cells),
+      CalculationType::StdDev => self.calculate_std_dev(field, cells),
+      CalculationType::Variance => self.calculate_variance(field, cells),
+      CalculationType::Percentile => {
+        self.calculate_percentile(field, cells, calculation.percentile)
+      },
     }
   }
 
@@ -115,6 +139,109 @@ impl CalculationsService {
     }
   }
 
+  fn calculate_count_unique(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
+      let unique_values = cells
+        .par_iter()
+        .filter(|cell| !handler.handle_is_cell_empty(cell, field))
+        .map(|cell| handler.handle_stringify_cell(cell, field))
+        .collect::<HashSet<_>>();
+      unique_values.len().to_string()
+    } else {
+      "".to_string()
+    }
+  }
+
+  /// The percentage of the empty cells if `is_empty` is true, otherwise the percentage of the non
+  /// empty cells.
+  fn calculate_percent_empty(
+    &self,
+    field: &Field,
+    cells: Vec<Arc<Cell>>,
+    is_empty: bool,
+  ) -> String {
+    if cells.is_empty() {
+      return String::new();
+    }
+
+    if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
+      let count = cells
+        .par_iter()
+        .filter(|cell| handler.handle_is_cell_empty(cell, field) == is_empty)
+        .count();
+      format!("{:.5}", Self::percentage(count, cells.len()))
+    } else {
+      String::new()
+    }
+  }
+
+  fn calculate_count_checked(&self, cells: Vec<Arc<Cell>>) -> String {
+    Self::count_checked(&cells).to_string()
+  }
+
+  fn calculate_percent_checked(&self, cells: Vec<Arc<Cell>>) -> String {
+    if cells.is_empty() {
+      return String::new();
+    }
+
+    format!(
+      "{:.5}",
+      Self::percentage(Self::count_checked(&cells), cells.len())
+    )
+  }
+
+  fn calculate_earliest_date(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let timestamps = self.reduce_timestamps(field, cells);
+    match timestamps.par_iter().min() {
+      Some(earliest) => Self::format_date(field, *earliest),
+      None => String::new(),
+    }
+  }
+
+  fn calculate_latest_date(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let timestamps = self.reduce_timestamps(field, cells);
+    match timestamps.par_iter().max() {
+      Some(latest) => Self::format_date(field, *latest),
+      None => String::new(),
+    }
+  }
+
+  /// The number of whole days between the earliest and the latest date.
+  fn calculate_date_range(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let timestamps = self.reduce_timestamps(field, cells);
+    match (timestamps.par_iter().min(), timestamps.par_iter().max()) {
+      (Some(earliest), Some(latest)) => ((latest - earliest) / SECONDS_PER_DAY).to_string(),
+      _ => String::new(),
+    }
+  }
+
+  fn calculate_std_dev(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let values = self.reduce_values_f64(field, cells);
+    match Self::variance(&values) {
+      Some(variance) => format!("{:.5}", variance.sqrt()),
+      None => String::new(),
+    }
+  }
+
+  fn calculate_variance(&self, field: &Field, cells: Vec<Arc<Cell>>) -> String {
+    let values = self.reduce_values_f64(field, cells);
+    match Self::variance(&values) {
+      Some(variance) => format!("{:.5}", variance),
+      None => String::new(),
+    }
+  }
+
+  fn calculate_percentile(&self, field: &Field, cells: Vec<Arc<Cell>>, percentile: i64) -> String {
+    let mut values = self.reduce_values_f64(field, cells);
+    values.par_sort_by(|a, b| a.total_cmp(b));
+
+    if !values.is_empty() {
+      format!("{:.5}", Self::percentile(&values, percentile))
+    } else {
+      String::new()
+    }
+  }
+
   fn reduce_values_f64(&self, field: &Field, row_cells: Vec<Arc<Cell>>) -> Vec<f64> {
     if let Some(handler) = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler() {
       row_cells
@@ -126,6 +253,68 @@ impl CalculationsService {
     }
   }
 
+  fn reduce_timestamps(&self, field: &Field, cells: Vec<Arc<Cell>>) -> Vec<i64> {
+    let field_type = FieldType::from(field.field_type);
+    cells
+      .par_iter()
+      .filter_map(|cell| match field_type {
+        FieldType::DateTime => DateCellData::from(cell.as_ref()).timestamp,
+        FieldType::CreatedTime | FieldType::LastEditedTime => {
+          TimestampCellData::from(cell.as_ref()).timestamp
+        },
+        _ => None,
+      })
+      .collect::<Vec<_>>()
+  }
+
+  fn format_date(field: &Field, timestamp: i64) -> String {
+    let field_type = FieldType::from(field.field_type);
+    let date = match field_type {
+      FieldType::DateTime => field
+        .get_type_option::<DateTypeOption>(field_type)
+        .map(|type_option| type_option.formatted_date_time_from_timestamp(&Some(timestamp))),
+      _ => field
+        .get_type_option::<TimestampTypeOption>(field_type)
+        .map(|type_option| type_option.formatted_date_time_from_timestamp(&Some(timestamp))),
+    };
+    date.map(|(date, _)| date).unwrap_or_default()
+  }
+
+  fn count_checked(cells: &[Arc<Cell>]) -> usize {
+    cells
+      .par_iter()
+      .filter(|cell| CheckboxCellDataPB::from(cell.as_ref()).is_checked)
+      .count()
+  }
+
+  fn percentage(count: usize, total: usize) -> f64 {
+    count as f64 * 100.0 / total as f64
+  }
+
+  /// The population variance of the values.
+  fn variance(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+      return None;
+    }
+
+    let len = values.len() as f64;
+    let mean = values.par_iter().sum::<f64>() / len;
+    let squared_diff = values
+      .par_iter()
+      .map(|value| (value - mean).powi(2))
+      .sum::<f64>();
+    Some(squared_diff / len)
+  }
+
+  /// Returns the percentile of the sorted values, interpolating linearly between the two closest
+  /// values. The 50th percentile is the median.
+  fn percentile(sorted: &[f64], percentile: i64) -> f64 {
+    let rank = percentile.clamp(0, 100) as f64 / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+  }
+
   fn median(array: &[f64]) -> f64 {
     if array.len() % 2 == 0 {
       let left = array.len() / 2 - 1;
@@ -243,6 +243,11 @@ impl<'a> CellBuilder<'a> {
           FieldType::LastEditedTime | FieldType::CreatedTime => {
             tracing::warn!("Shouldn't insert cell data to cell whose field type is LastEditedTime or CreatedTime");
           },
+          FieldType::Formula | FieldType::Rollup => {
+            tracing::warn!(
+              "Shouldn't insert cell data to cell whose field type is Formula or Rollup"
+            );
+          },
           FieldType::SingleSelect | FieldType::MultiSelect => {
             if let Ok(ids) = SelectOptionIds::from_str(&cell_str) {
               cells.insert(field_id, insert_select_option_cell(ids.into_inner(), field));
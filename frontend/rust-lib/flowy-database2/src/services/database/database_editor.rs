@@ -1,34 +1,55 @@
 use crate::entities::*;
 use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::calculations::Calculation;
-use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, CellCache};
+use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, stringify_cell, CellCache};
 use crate::services::database::database_observe::*;
-use crate::services::database::util::database_view_setting_pb_from_view;
+use crate::services::database::duplicate_rows::{
+  cell_value_key, find_duplicate_rows, merge_duplicate_cells,
+};
+use crate::services::database::util::{
+  database_view_layout_pb, database_view_setting_pb_from_view,
+};
 use crate::services::database_view::{
   DatabaseViewChanged, DatabaseViewEditor, DatabaseViewOperation, DatabaseViews, EditorByViewId,
 };
 use crate::services::field::type_option_transform::transform_type_option;
 use crate::services::field::{
-  default_type_option_data_from_type, select_type_option_from_field, type_option_data_from_pb,
-  ChecklistCellChangeset, RelationTypeOption, SelectOptionCellChangeset, StringCellData,
+  date_cell_data_of_occurrence, default_type_option_data_from_type, formula_value_from_row,
+  moved_date_changeset, rename_field_reference, select_type_option_from_field, time_zone_from_id,
+  type_option_data_from_pb, ChecklistCellChangeset, ComputedCellData, DateCellChangeset,
+  FormulaTypeOption, FormulaValue, ParsedFormula, RecurrenceRule, RelationCellData,
+  RelationTypeOption, RollupTypeOption, SelectOptionCellChangeset, StringCellData,
   TimestampCellData, TimestampCellDataWrapper, TypeOptionCellDataHandler, TypeOptionCellExt,
 };
+use crate::services::field::{
+  keep_field_validation, validate_field_cell, validate_new_row_cells, validate_row_cells,
+  CellValidationError, FieldValidation, ValidationSeverity,
+};
 use crate::services::field_settings::{default_field_settings_by_layout_map, FieldSettings};
-use crate::services::filter::{Filter, FilterChangeset};
+use crate::services::filter::{
+  is_row_visible, parse_formula_filters, Filter, FilterChangeset, FilterInner,
+};
 use crate::services::group::{default_group_setting, GroupChangeset, GroupSetting};
 use crate::services::share::csv::{CSVExport, CSVFormat};
+use crate::services::share::html::HTMLExport;
+use crate::services::share::ics::ICSExport;
+use crate::services::share::xlsx::XLSXExport;
+use crate::services::snapshot::{is_field_changed, DatabaseSnapshotContent};
 use crate::services::sort::Sort;
 use crate::utils::cache::AnyTypeCache;
-use crate::DatabaseUser;
+use crate::{DatabaseEditorMap, DatabaseUser};
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
 use collab::core::collab_plugin::CollabPluginType;
 use collab::lock::RwLock;
-use collab_database::database::Database;
+use collab_database::database::{gen_database_filter_id, Database};
 use collab_database::entity::DatabaseView;
+use collab_database::fields::date_type_option::{DateCellData, DateTypeOption};
 use collab_database::fields::media_type_option::MediaCellData;
 use collab_database::fields::{Field, TypeOptionData};
-use collab_database::rows::{Cell, Cells, DatabaseRow, Row, RowCell, RowDetail, RowId, RowUpdate};
+use collab_database::rows::{
+  Cell, Cells, CreateRowParams, DatabaseRow, Row, RowCell, RowDetail, RowId, RowUpdate,
+};
 use collab_database::views::{
   DatabaseLayout, FilterMap, LayoutSetting, OrderObjectPosition, RowOrder,
 };
@@ -36,18 +57,19 @@ use collab_entity::CollabType;
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_notification::DebounceNotificationSender;
+use flowy_search_pub::entities::IndexableContent;
 use futures::future::join_all;
 use futures::{pin_mut, StreamExt};
 use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::TaskDispatcher;
 use lib_infra::util::timestamp;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::RwLock as TokioRwLock;
-use tokio::sync::{broadcast, oneshot};
+use tokio::sync::{broadcast, oneshot, Mutex};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, event, info, instrument, trace, warn};
 
@@ -67,6 +89,9 @@ pub struct DatabaseEditor {
   database_cancellation: Arc<RwLock<Option<CancellationToken>>>,
   un_finalized_rows_cancellation: Arc<ArcSwapOption<CancellationToken>>,
   finalized_rows: Arc<moka::future::Cache<String, Weak<RwLock<DatabaseRow>>>>,
+  cell_changed_notifier: broadcast::Sender<DatabaseCellChanged>,
+  /// The opened databases, used to update the relations that link to the rows of this database
+  related_databases: Weak<Mutex<DatabaseEditorMap>>,
 }
 
 impl DatabaseEditor {
@@ -75,6 +100,7 @@ impl DatabaseEditor {
     database: Arc<RwLock<Database>>,
     task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
+    related_databases: Weak<Mutex<DatabaseEditorMap>>,
   ) -> FlowyResult<Arc<Self>> {
     let finalized_rows: moka::future::Cache<String, Weak<RwLock<DatabaseRow>>> =
       moka::future::Cache::builder()
@@ -102,6 +128,7 @@ impl DatabaseEditor {
       cell_cache: cell_cache.clone(),
       editor_by_view_id: editor_by_view_id.clone(),
       database_cancellation: database_cancellation.clone(),
+      related_databases: related_databases.clone(),
     });
 
     let database_views = Arc::new(
@@ -138,12 +165,18 @@ impl DatabaseEditor {
       database_cancellation,
       un_finalized_rows_cancellation: Arc::new(Default::default()),
       finalized_rows: Arc::new(finalized_rows),
+      cell_changed_notifier: broadcast::channel(100).0,
+      related_databases,
     });
     observe_block_event(&database_id, &this).await;
     observe_view_change(&database_id, &this).await;
     Ok(this)
   }
 
+  pub fn get_database_id(&self) -> String {
+    self.database_id.clone()
+  }
+
   pub async fn close_view(&self, view_id: &str) {
     self.database_views.remove_view(view_id).await;
   }
@@ -187,7 +220,7 @@ impl DatabaseEditor {
   pub async fn update_view_layout(
     &self,
     view_id: &str,
-    layout_type: DatabaseLayout,
+    layout_type: DatabaseLayoutPB,
   ) -> FlowyResult<()> {
     let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
     view_editor.v_update_layout_type(layout_type).await?;
@@ -203,6 +236,50 @@ impl DatabaseEditor {
     Ok(view_editor.notifier.subscribe())
   }
 
+  pub fn subscribe_cell_changed(&self) -> broadcast::Receiver<DatabaseCellChanged> {
+    self.cell_changed_notifier.subscribe()
+  }
+
+  /// Returns the text of the cells of each row, which is indexed by the local search, and the ids
+  /// of the rows that don't exist anymore. Every row of the database is returned when `row_ids`
+  /// is None.
+  pub async fn get_row_index_contents(
+    &self,
+    workspace_id: &str,
+    row_ids: Option<&[RowId]>,
+  ) -> (Vec<IndexableContent>, Vec<RowId>) {
+    let database = self.database.read().await;
+    let view_id = database.get_inline_view_id();
+    let fields = database.get_fields(None);
+    let mut rows = vec![];
+    let mut deleted_row_ids = vec![];
+    match row_ids {
+      None => {
+        rows = database
+          .get_rows_for_view(&view_id, None)
+          .await
+          .filter_map(|result| async { result.ok() })
+          .collect::<Vec<_>>()
+          .await;
+      },
+      Some(row_ids) => {
+        for row_id in row_ids {
+          if database.contains_row(&view_id, row_id) {
+            rows.push(database.get_row(row_id).await);
+          } else {
+            deleted_row_ids.push(row_id.clone());
+          }
+        }
+      },
+    }
+
+    let contents = rows
+      .iter()
+      .map(|row| row_index_content(row, &fields, &self.database_id, &view_id, workspace_id))
+      .collect();
+    (contents, deleted_row_ids)
+  }
+
   pub async fn get_field(&self, field_id: &str) -> Option<Field> {
     self.database.read().await.get_field(field_id)
   }
@@ -224,8 +301,16 @@ impl DatabaseEditor {
         setting_content = group_config_pb_to_json_str(data, &field_type)?;
         let mut group_setting = default_group_setting(&field);
         group_setting.content = setting_content.clone();
+        // Keep the sub groups unless the rows are now grouped by the same field
+        let mut group_settings = vec![group_setting];
+        if let Some(sub_group_setting) = old_group_settings
+          .get(1)
+          .filter(|setting| setting.field_id != field_id)
+        {
+          group_settings.push(sub_group_setting.clone());
+        }
         database.update_database_view(view_id, |view| {
-          view.set_groups(vec![group_setting.into()]);
+          view.set_groups(group_settings.into_iter().map(Into::into).collect());
         });
       }
     }
@@ -235,12 +320,59 @@ impl DatabaseEditor {
       old_group_setting.is_some() && old_group_setting.unwrap().content == setting_content;
 
     let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    if view_editor.is_sub_grouping_field(field_id).await {
+      view_editor.v_sub_group_by_field().await?;
+    }
     if !view_editor.is_grouping_field(field_id).await || !has_same_content {
       view_editor.v_initialize_new_group(field_id).await?;
     }
     Ok(())
   }
 
+  /// Groups the rows of each group by a second field, or removes the sub groups when `field_id`
+  /// is None. The view must already be grouped by another field.
+  pub async fn set_sub_group_by_field(
+    &self,
+    view_id: &str,
+    field_id: Option<&str>,
+    data: Vec<u8>,
+  ) -> FlowyResult<()> {
+    {
+      let mut database = self.database.write().await;
+      let mut group_settings = database.get_all_group_setting(view_id);
+      if group_settings.is_empty() {
+        return Err(
+          FlowyError::invalid_data()
+            .with_context("The view must be grouped before adding sub groups"),
+        );
+      }
+      group_settings.truncate(1);
+
+      if let Some(field_id) = field_id {
+        if group_settings[0].field_id == field_id {
+          return Err(
+            FlowyError::invalid_data()
+              .with_context("The sub groups can't use the field that groups the view"),
+          );
+        }
+        let field = database
+          .get_field(field_id)
+          .ok_or_else(|| FlowyError::field_record_not_found().with_context(field_id.to_string()))?;
+        let field_type = FieldType::from(field.field_type);
+        let mut sub_group_setting = default_group_setting(&field);
+        sub_group_setting.content = group_config_pb_to_json_str(data, &field_type)?;
+        group_settings.push(sub_group_setting);
+      }
+      database.update_database_view(view_id, |view| {
+        view.set_groups(group_settings.into_iter().map(Into::into).collect());
+      });
+    }
+
+    let view_editor = self.database_views.get_or_init_view_editor(view_id).await?;
+    view_editor.v_sub_group_by_field().await?;
+    Ok(())
+  }
+
   pub async fn delete_group(&self, params: DeleteGroupParams) -> FlowyResult<()> {
     let view_editor = self
       .database_views
@@ -386,12 +518,22 @@ impl DatabaseEditor {
 
   pub async fn update_field(&self, params: FieldChangesetPB) -> FlowyResult<()> {
     let mut database = self.database.write().await;
+    let old_name = database
+      .get_field(&params.field_id)
+      .map(|field| field.name)
+      .unwrap_or_default();
+    let new_name = params.name.clone();
     database.update_field(&params.field_id, |update| {
       update
         .set_name_if_not_none(params.name)
         .set_icon_if_not_none(params.icon);
     });
     notify_did_update_database_field(&database, &params.field_id)?;
+
+    // The formulas reference the fields by name, keep them pointing to the renamed field
+    if let Some(new_name) = new_name.filter(|new_name| *new_name != old_name) {
+      rename_formula_field_references(&mut database, &old_name, &new_name);
+    }
     Ok(())
   }
 
@@ -411,19 +553,33 @@ impl DatabaseEditor {
       ));
     }
 
-    let database_id = {
+    let (database_id, deleted_field_name, has_dependent_formulas) = {
       let mut database = self.database.write().await;
+      let deleted_field = database.get_field(field_id);
+      let has_dependent_formulas = deleted_field
+        .as_ref()
+        .map(|field| has_dependent_formulas(&database.get_fields(None), field))
+        .unwrap_or(false);
       database.delete_field(field_id);
-      database.get_database_id()
+      let deleted_field_name = deleted_field.map(|field| field.name).unwrap_or_default();
+      (
+        database.get_database_id(),
+        deleted_field_name,
+        has_dependent_formulas,
+      )
     };
     let notified_changeset =
       DatabaseFieldChangesetPB::delete(&database_id, vec![FieldIdPB::from(field_id)]);
     self.notify_did_update_database(notified_changeset).await?;
 
     for view in self.database_views.editors().await {
-      view.v_did_delete_field(field_id).await;
+      view.v_did_delete_field(field_id, &deleted_field_name).await;
     }
 
+    // The formulas that read the deleted field can't be evaluated anymore
+    if has_dependent_formulas {
+      self.update_formula_cells_of_all_rows(None).await?;
+    }
     Ok(())
   }
 
@@ -472,6 +628,236 @@ impl DatabaseEditor {
         .v_did_update_field_type_option(&old_field)
         .await?;
     }
+
+    if FieldType::from(old_field.field_type).is_formula() {
+      self.update_formula_cells_of_all_rows(None).await?;
+    }
+
+    let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Field {
+      field_id: old_field.id.clone(),
+    });
+    Ok(())
+  }
+
+  /// Returns the validation rules of the field, or None if it doesn't have any
+  pub async fn get_field_validation(&self, field_id: &str) -> Option<FieldValidation> {
+    let field = self.database.read().await.get_field(field_id)?;
+    FieldValidation::from_field(&field)
+  }
+
+  /// Replaces the validation rules of the field, which are stored in the type option data of its
+  /// current type. The existing cells aren't rejected, their errors are returned by
+  /// [Self::get_validation_errors].
+  pub async fn update_field_validation(
+    &self,
+    field_id: &str,
+    validation: FieldValidation,
+  ) -> FlowyResult<()> {
+    let mut database = self.database.write().await;
+    let field = database.get_field(field_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field with id:{} not found", field_id))
+    })?;
+    let field_type = FieldType::from(field.field_type);
+    let mut type_option_data = field
+      .get_any_type_option(field_type)
+      .unwrap_or_else(|| default_type_option_data_from_type(field_type));
+    validation.write_to(&mut type_option_data);
+    database.update_field(field_id, |update| {
+      update.update_type_options(|type_options_update| {
+        type_options_update.insert(&field_type.to_string(), type_option_data);
+      });
+    });
+    let _ = notify_did_update_database_field(&database, field_id);
+    Ok(())
+  }
+
+  /// Returns the validation errors of the cells of the fields in the view. All the rows of the
+  /// database are validated, including the ones hidden by the filters of the view.
+  pub async fn get_validation_errors(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Vec<CellValidationError>> {
+    let fields = self.get_fields(view_id, None).await;
+    if fields
+      .iter()
+      .all(|field| FieldValidation::from_field(field).is_none())
+    {
+      return Ok(vec![]);
+    }
+
+    let rows = self.get_all_database_rows().await;
+    Ok(
+      rows
+        .iter()
+        .flat_map(|row| validate_row_cells(&row.id, &row.cells, &fields))
+        .collect(),
+    )
+  }
+
+  /// Returns the groups of rows in the view whose cells of the key fields have the same value. With
+  /// `fuzzy`, the whitespace and the case of the values are ignored.
+  pub async fn find_duplicate_rows(
+    &self,
+    view_id: &str,
+    field_ids: Vec<String>,
+    fuzzy: bool,
+  ) -> FlowyResult<Vec<Vec<RowId>>> {
+    let key_fields = self.get_fields(view_id, Some(field_ids)).await;
+    if key_fields.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("No key field to compare the rows"));
+    }
+
+    let rows = self.get_all_rows(view_id).await?;
+    let rows = rows.iter().map(|row| row.as_ref()).collect::<Vec<_>>();
+    Ok(find_duplicate_rows(&rows, &key_fields, fuzzy))
+  }
+
+  /// Combines the cells of the duplicate rows into the row, then deletes the duplicate rows. The
+  /// relations of the opened databases that link to the duplicate rows are moved to the row.
+  pub async fn merge_duplicate_rows(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    duplicate_row_ids: &[RowId],
+  ) -> FlowyResult<()> {
+    let rows = self.get_all_rows(view_id).await?;
+    let row = rows
+      .iter()
+      .find(|row| &row.id == row_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("The merged row doesn't exist"))?;
+    let duplicates = rows
+      .iter()
+      .filter(|row| &row.id != row_id && duplicate_row_ids.contains(&row.id))
+      .map(|row| row.as_ref())
+      .collect::<Vec<_>>();
+    if duplicates.is_empty() {
+      return Ok(());
+    }
+
+    let fields = self.database.read().await.get_fields(None);
+    let merged_cells = merge_duplicate_cells(row, &duplicates, &fields);
+    let duplicate_row_ids = duplicates
+      .iter()
+      .map(|row| row.id.clone())
+      .collect::<Vec<_>>();
+    // The merged cells are written without checking the unique fields, which the duplicate rows
+    // break until they are deleted. Nothing is deleted if writing them fails.
+    for (field_id, cell) in merged_cells {
+      self.write_cell(view_id, row_id, &field_id, cell).await?;
+    }
+
+    let editors = match self.related_databases.upgrade() {
+      Some(editors) => editors.lock().await.values().cloned().collect::<Vec<_>>(),
+      None => vec![],
+    };
+    for editor in editors {
+      editor
+        .replace_related_rows(&self.database_id, &duplicate_row_ids, row_id)
+        .await?;
+    }
+    self.delete_rows(&duplicate_row_ids).await;
+    Ok(())
+  }
+
+  /// Replaces the `old_row_ids` of the relation cells that link to the related database with
+  /// `new_row_id`
+  pub(crate) async fn replace_related_rows(
+    &self,
+    related_database_id: &str,
+    old_row_ids: &[RowId],
+    new_row_id: &RowId,
+  ) -> FlowyResult<()> {
+    let relation_field_ids = self
+      .database
+      .read()
+      .await
+      .get_fields(None)
+      .into_iter()
+      .filter(|field| {
+        field
+          .get_type_option::<RelationTypeOption>(FieldType::Relation)
+          .map_or(false, |type_option| {
+            type_option.database_id == related_database_id
+          })
+      })
+      .map(|field| field.id)
+      .collect::<Vec<_>>();
+    if relation_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    for old_row in self.get_all_database_rows().await {
+      let mut updated_cells = Cells::new();
+      for field_id in relation_field_ids.iter() {
+        let row_ids = match old_row.cells.get(field_id) {
+          Some(cell) => RelationCellData::from(cell).row_ids,
+          None => continue,
+        };
+        if !row_ids.iter().any(|row_id| old_row_ids.contains(row_id)) {
+          continue;
+        }
+
+        let mut new_row_ids = vec![];
+        for row_id in row_ids {
+          let row_id = if old_row_ids.contains(&row_id) {
+            new_row_id.clone()
+          } else {
+            row_id
+          };
+          if !new_row_ids.contains(&row_id) {
+            new_row_ids.push(row_id);
+          }
+        }
+        let cell_data = RelationCellData {
+          row_ids: new_row_ids,
+        };
+        updated_cells.insert(field_id.clone(), Cell::from(&cell_data));
+      }
+      if updated_cells.is_empty() {
+        continue;
+      }
+
+      let updated_field_ids = updated_cells.keys().cloned().collect::<Vec<String>>();
+      self
+        .update_row(old_row.id.clone(), |row_update| {
+          row_update
+            .set_last_modified(timestamp())
+            .set_cells(updated_cells);
+        })
+        .await?;
+      self
+        .did_update_computed_cells(&old_row.id, old_row, &updated_field_ids)
+        .await;
+    }
+    Ok(())
+  }
+
+  /// Rejects the cell if the field is unique and another row of the database has the same value,
+  /// including the rows that are hidden by the filters of the views
+  async fn reject_duplicate_value(
+    &self,
+    row_id: &RowId,
+    field: &Field,
+    cell: &Cell,
+  ) -> FlowyResult<()> {
+    let is_unique =
+      FieldValidation::from_field(field).map_or(false, |validation| validation.unique);
+    let value = match cell_value_key(Some(cell), field, false) {
+      Some(value) if is_unique => value,
+      _ => return Ok(()),
+    };
+
+    let rows = self.get_all_database_rows().await;
+    let is_duplicate = rows.iter().any(|row| {
+      &row.id != row_id
+        && cell_value_key(row.cells.get(&field.id), field, false).as_ref() == Some(&value)
+    });
+    if is_duplicate {
+      return Err(FlowyError::new(
+        ErrorCode::DuplicateCellValue,
+        format!("Another row already has {} in {}", value, field.name),
+      ));
+    }
     Ok(())
   }
 
@@ -521,9 +907,24 @@ impl DatabaseEditor {
         view.v_did_update_field_type(field_id, new_field_type).await;
       }
 
-      let database = self.database.read().await;
+      {
+        let database = self.database.read().await;
+        notify_did_update_database_field(&database, field_id)?;
+      }
 
-      notify_did_update_database_field(&database, field_id)?;
+      // The formulas read the values of the field in its new type. A field that becomes a formula
+      // is evaluated with the others.
+      if old_field_type.is_formula() || new_field_type.is_formula() {
+        self.update_formula_cells_of_all_rows(None).await?;
+      } else {
+        self
+          .update_formula_cells_of_all_rows(Some(field_id))
+          .await?;
+      }
+
+      let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Field {
+        field_id: field_id.to_string(),
+      });
     }
 
     Ok(())
@@ -565,7 +966,7 @@ impl DatabaseEditor {
     Ok(())
   }
 
-  pub async fn duplicate_row(&self, view_id: &str, row_id: &RowId) -> FlowyResult<()> {
+  pub async fn duplicate_row(&self, view_id: &str, row_id: &RowId) -> FlowyResult<RowOrder> {
     let mut database = self.database.write().await;
     let params = database
       .duplicate_row(row_id)
@@ -588,6 +989,9 @@ impl DatabaseEditor {
 
     drop(database);
 
+    let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Rows {
+      row_ids: vec![row_order.id.clone()],
+    });
     trace!(
       "duplicate row: {:?} at index:{}, new row:{:?}",
       row_id,
@@ -595,7 +999,7 @@ impl DatabaseEditor {
       row_order
     );
 
-    Ok(())
+    Ok(row_order)
   }
 
   #[tracing::instrument(level = "trace", skip_all, err)]
@@ -621,6 +1025,7 @@ impl DatabaseEditor {
     to_group: &str,
     from_row: RowId,
     to_row: Option<RowId>,
+    to_sub_group: Option<&str>,
   ) -> FlowyResult<()> {
     let row = self.get_row(view_id, &from_row).await.ok_or_else(|| {
       let msg = format!("Can not find the row:{}", from_row);
@@ -639,7 +1044,7 @@ impl DatabaseEditor {
       .database_views
       .get_or_init_view_editor(view_id)
       .await?
-      .v_move_group_row(&row, to_group, to_row.clone())
+      .v_move_group_row(&row, from_group, to_group, to_row.clone(), to_sub_group)
       .await;
     if !updated_cells.is_empty() {
       self
@@ -670,6 +1075,16 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  /// Evaluates the formula cells of the new row and notifies the observers of the cell changes.
+  async fn did_create_row(&self, row_id: &RowId) {
+    if let Err(err) = self.update_formula_cells(row_id, None).await {
+      error!("Failed to evaluate formula cells of new row: {}", err);
+    }
+    let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Rows {
+      row_ids: vec![row_id.clone()],
+    });
+  }
+
   pub async fn create_row(&self, params: CreateRowPayloadPB) -> FlowyResult<Option<RowDetail>> {
     let view_editor = self
       .database_views
@@ -677,14 +1092,32 @@ impl DatabaseEditor {
       .await?;
 
     let params = view_editor.v_will_create_row(params).await?;
+    let fields = self.database.read().await.get_fields(None);
+    let validation_errors = validate_new_row_cells(&params.id, &params.cells, &fields);
+    reject_validation_errors(&validation_errors)?;
+    for field in fields.iter() {
+      if let Some(cell) = params.cells.get(&field.id) {
+        self.reject_duplicate_value(&params.id, field, cell).await?;
+      }
+    }
 
     let mut database = self.database.write().await;
     let (index, row_order) = database
       .create_row_in_view(&view_editor.view_id, params)
       .await?;
-    let row_detail = database.get_row_detail(&row_order.id).await;
     drop(database);
 
+    self.did_create_row(&row_order.id).await;
+    if !validation_errors.is_empty() {
+      notify_did_update_row_validation(&row_order.id, validation_errors);
+    }
+    let row_detail = self
+      .database
+      .read()
+      .await
+      .get_row_detail(&row_order.id)
+      .await;
+
     trace!("[Database]: did create row: {} at {}", row_order.id, index);
     if let Some(row_detail) = row_detail {
       trace!("created row: {:?} at {}", row_detail, index);
@@ -772,6 +1205,20 @@ impl DatabaseEditor {
     Ok(view_editor.v_get_all_rows().await)
   }
 
+  /// Returns all the rows of the database, regardless of the filters of the views
+  pub async fn get_all_database_rows(&self) -> Vec<Row> {
+    let database = self.database.read().await;
+    let rows_stream = database.get_all_rows(None).await;
+    pin_mut!(rows_stream);
+    let mut rows = vec![];
+    while let Some(result) = rows_stream.next().await {
+      if let Ok(row) = result {
+        rows.push(row);
+      }
+    }
+    rows
+  }
+
   pub async fn get_row(&self, view_id: &str, row_id: &RowId) -> Option<Row> {
     let database = self.database.read().await;
     if database.contains_row(view_id, row_id) {
@@ -851,6 +1298,9 @@ impl DatabaseEditor {
 
   pub async fn delete_rows(&self, row_ids: &[RowId]) {
     let _ = self.database.write().await.remove_rows(row_ids).await;
+    let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Rows {
+      row_ids: row_ids.to_vec(),
+    });
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
@@ -987,6 +1437,24 @@ impl DatabaseEditor {
     row_id: &RowId,
     field_id: &str,
     new_cell: Cell,
+  ) -> FlowyResult<()> {
+    let field = self.database.read().await.get_field(field_id);
+    if let Some(field) = field {
+      reject_validation_errors(&validate_field_cell(row_id, &field, Some(&new_cell)))?;
+      self
+        .reject_duplicate_value(row_id, &field, &new_cell)
+        .await?;
+    }
+    self.write_cell(view_id, row_id, field_id, new_cell).await
+  }
+
+  /// Writes the cell without checking the validation of its field
+  async fn write_cell(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    new_cell: Cell,
   ) -> FlowyResult<()> {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = self.get_row(view_id, row_id).await;
@@ -1008,69 +1476,469 @@ impl DatabaseEditor {
     Ok(())
   }
 
-  pub async fn update_row<F>(&self, row_id: RowId, modify: F) -> FlowyResult<()>
-  where
-    F: FnOnce(RowUpdate),
-  {
-    if self.finalized_rows.get(row_id.as_str()).await.is_none() {
-      info!(
-        "[Database Row]: row:{} is not finalized when editing, init it",
-        row_id
-      );
-      self.init_database_row(&row_id).await?;
+  pub async fn update_row<F>(&self, row_id: RowId, modify: F) -> FlowyResult<()>
+  where
+    F: FnOnce(RowUpdate),
+  {
+    if self.finalized_rows.get(row_id.as_str()).await.is_none() {
+      info!(
+        "[Database Row]: row:{} is not finalized when editing, init it",
+        row_id
+      );
+      self.init_database_row(&row_id).await?;
+    }
+    self.database.write().await.update_row(row_id, modify).await;
+    Ok(())
+  }
+
+  pub async fn clear_cell(&self, view_id: &str, row_id: RowId, field_id: &str) -> FlowyResult<()> {
+    // Get the old row before updating the cell. It would be better to get the old cell
+    let old_row = self.get_row(view_id, &row_id).await;
+    self
+      .update_row(row_id.clone(), |row_update| {
+        row_update.update_cells(|cell_update| {
+          cell_update.clear(field_id);
+        });
+      })
+      .await?;
+
+    self
+      .did_update_row(view_id, &row_id, field_id, old_row)
+      .await;
+
+    Ok(())
+  }
+
+  async fn did_update_row(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    old_row: Option<Row>,
+  ) {
+    let option_row = self.get_row(view_id, row_id).await;
+    let field = self.database.read().await.get_field(field_id);
+    let field_type = field.as_ref().map(|field| field.field_type);
+
+    if let Some(row) = option_row {
+      for view in self.database_views.editors().await {
+        view
+          .v_did_update_row(&old_row, &row, Some(field_id.to_owned()))
+          .await;
+      }
+
+      if let Some(field_type) = field_type {
+        match FieldType::from(field_type) {
+          FieldType::Media => {
+            self
+              .did_update_attachments(view_id, row_id, field_id, old_row.clone())
+              .await;
+          },
+          FieldType::Formula => {},
+          _ => {
+            if let Err(err) = self.update_formula_cells(row_id, Some(field_id)).await {
+              error!("Failed to update formula cells: {}", err);
+            }
+          },
+        }
+      }
+
+      if field.map_or(false, |field| FieldValidation::from_field(&field).is_some()) {
+        let fields = self.database.read().await.get_fields(None);
+        notify_did_update_row_validation(row_id, validate_row_cells(row_id, &row.cells, &fields));
+      }
+
+      let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Cell {
+        row_id: row_id.clone(),
+        field_id: field_id.to_string(),
+      });
+    }
+  }
+
+  /// Re-evaluates the formula cells of all the rows, see [Self::update_formula_cells]
+  async fn update_formula_cells_of_all_rows(
+    &self,
+    source_field_id: Option<&str>,
+  ) -> FlowyResult<()> {
+    let fields = self.database.read().await.get_fields(None);
+    let formulas = parse_formula_fields(&fields);
+    if formulas.is_empty() {
+      return Ok(());
+    }
+
+    for row_id in self.get_row_ids().await {
+      self
+        .update_formula_cells_with(&row_id, source_field_id, &fields, &formulas)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Re-evaluates the formula cells of the row and saves the results. If the `source_field_id`
+  /// is given, only the formulas that depend on it, directly or through other formulas, are
+  /// re-evaluated.
+  async fn update_formula_cells(
+    &self,
+    row_id: &RowId,
+    source_field_id: Option<&str>,
+  ) -> FlowyResult<()> {
+    let fields = self.database.read().await.get_fields(None);
+    let formulas = parse_formula_fields(&fields);
+    if formulas.is_empty() {
+      return Ok(());
+    }
+
+    self
+      .update_formula_cells_with(row_id, source_field_id, &fields, &formulas)
+      .await
+  }
+
+  /// Same as [Self::update_formula_cells], with the formulas already parsed by
+  /// [parse_formula_fields].
+  async fn update_formula_cells_with(
+    &self,
+    row_id: &RowId,
+    source_field_id: Option<&str>,
+    fields: &[Field],
+    formulas: &[FormulaField<'_>],
+  ) -> FlowyResult<()> {
+    let old_row = self.database.read().await.get_row(row_id).await;
+    let mut row = old_row.clone();
+    let mut dirty_field_ids = source_field_id
+      .map(|field_id| vec![field_id.to_string()])
+      .unwrap_or_default();
+    let mut updated_cells = Cells::new();
+    // A formula might read another formula, so keep evaluating until nothing changes. The number
+    // of passes is bounded in case the formulas reference each other.
+    for _ in 0..formulas.len() {
+      let mut did_change = false;
+      for formula in formulas.iter() {
+        let field = formula.field;
+        let is_dirty = source_field_id.is_none()
+          || dirty_field_ids
+            .iter()
+            .any(|field_id| formula.dependencies.contains(field_id));
+        if !is_dirty {
+          continue;
+        }
+
+        let cell_data = formula.parsed.evaluate_row(&row, fields);
+        let old_cell_data = row
+          .cells
+          .get(&field.id)
+          .map(ComputedCellData::from)
+          .unwrap_or_default();
+        if cell_data.0 != old_cell_data.0 {
+          let cell = cell_data.into_cell(FieldType::Formula);
+          row.cells.insert(field.id.clone(), cell.clone());
+          updated_cells.insert(field.id.clone(), cell);
+          if !dirty_field_ids.contains(&field.id) {
+            dirty_field_ids.push(field.id.clone());
+          }
+          did_change = true;
+        }
+      }
+
+      if !did_change {
+        break;
+      }
+    }
+
+    if updated_cells.is_empty() {
+      return Ok(());
+    }
+
+    let updated_field_ids = updated_cells.keys().cloned().collect::<Vec<String>>();
+    trace!(
+      "[Database Row]: update formula cells: {:?} of row: {}",
+      updated_field_ids,
+      row_id
+    );
+    self
+      .update_row(row_id.clone(), |row_update| {
+        row_update.set_cells(updated_cells);
+      })
+      .await?;
+
+    self
+      .did_update_computed_cells(row_id, old_row, &updated_field_ids)
+      .await;
+    Ok(())
+  }
+
+  /// Re-calculates the rollup cells of the given rows, or of all rows if `row_ids` is None. The
+  /// values of the related rows are read from the `related_databases`; the rollups whose related
+  /// database isn't opened are skipped.
+  pub(crate) async fn update_rollup_cells(
+    &self,
+    rollup_field_ids: &[String],
+    row_ids: Option<Vec<RowId>>,
+    related_databases: &DatabaseEditorMap,
+  ) -> FlowyResult<()> {
+    let rollups = self
+      .get_rollup_fields()
+      .await
+      .into_iter()
+      .filter(|rollup| rollup_field_ids.contains(&rollup.field_id))
+      .filter_map(|rollup| {
+        let related_database = related_databases.get(&rollup.related_database_id)?.clone();
+        Some((rollup, related_database))
+      })
+      .collect::<Vec<_>>();
+    if rollups.is_empty() {
+      return Ok(());
+    }
+
+    let row_ids = match row_ids {
+      Some(row_ids) => row_ids,
+      None => self.get_row_ids().await,
+    };
+    for row_id in row_ids {
+      let old_row = self.database.read().await.get_row(&row_id).await;
+      let mut updated_cells = Cells::new();
+      for (rollup, related_database) in rollups.iter() {
+        let related_row_ids = old_row
+          .cells
+          .get(&rollup.type_option.relation_field_id)
+          .map(|cell| RelationCellData::from(cell).row_ids)
+          .unwrap_or_default();
+        let values = related_database
+          .get_field_values(&rollup.type_option.target_field_id, &related_row_ids)
+          .await;
+        let cell_data = rollup.type_option.rollup(values);
+        let old_cell_data = old_row
+          .cells
+          .get(&rollup.field_id)
+          .map(ComputedCellData::from)
+          .unwrap_or_default();
+        if cell_data.0 != old_cell_data.0 {
+          updated_cells.insert(
+            rollup.field_id.clone(),
+            cell_data.into_cell(FieldType::Rollup),
+          );
+        }
+      }
+
+      if updated_cells.is_empty() {
+        continue;
+      }
+
+      let updated_field_ids = updated_cells.keys().cloned().collect::<Vec<String>>();
+      trace!(
+        "[Database Row]: update rollup cells: {:?} of row: {}",
+        updated_field_ids,
+        row_id
+      );
+      self
+        .update_row(row_id.clone(), |row_update| {
+          row_update.set_cells(updated_cells);
+        })
+        .await?;
+      self
+        .did_update_computed_cells(&row_id, old_row, &updated_field_ids)
+        .await;
+      for field_id in updated_field_ids.iter() {
+        if let Err(err) = self.update_formula_cells(&row_id, Some(field_id)).await {
+          error!("Failed to update formula cells: {}", err);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  async fn did_update_computed_cells(&self, row_id: &RowId, old_row: Row, field_ids: &[String]) {
+    let new_row = self.database.read().await.get_row(row_id).await;
+    let old_row = Some(old_row);
+    for view in self.database_views.editors().await {
+      for field_id in field_ids.iter() {
+        view
+          .v_did_update_row(&old_row, &new_row, Some(field_id.clone()))
+          .await;
+      }
+    }
+
+    for field_id in field_ids.iter() {
+      let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Cell {
+        row_id: row_id.clone(),
+        field_id: field_id.clone(),
+      });
+    }
+  }
+
+  pub(crate) async fn get_rollup_fields(&self) -> Vec<RollupField> {
+    let fields = self.database.read().await.get_fields(None);
+    fields
+      .iter()
+      .filter(|field| FieldType::from(field.field_type).is_rollup())
+      .filter_map(|field| {
+        let type_option = field.get_type_option::<RollupTypeOption>(FieldType::Rollup)?;
+        let related_database_id = fields
+          .iter()
+          .find(|relation_field| relation_field.id == type_option.relation_field_id)?
+          .get_type_option::<RelationTypeOption>(FieldType::Relation)?
+          .database_id;
+        Some(RollupField {
+          field_id: field.id.clone(),
+          type_option,
+          related_database_id,
+        })
+      })
+      .collect()
+  }
+
+  /// Returns the ids of the rows whose relation field links to the given row. Only the
+  /// `candidate_row_ids` are checked if given, otherwise all the rows are.
+  pub(crate) async fn get_rows_related_to(
+    &self,
+    relation_field_id: &str,
+    related_row_id: &RowId,
+    candidate_row_ids: Option<Vec<RowId>>,
+  ) -> Vec<RowId> {
+    let is_related = |row: &Row| {
+      row
+        .cells
+        .get(relation_field_id)
+        .map(|cell| {
+          RelationCellData::from(cell)
+            .row_ids
+            .contains(related_row_id)
+        })
+        .unwrap_or(false)
+    };
+
+    let database = self.database.read().await;
+    let mut row_ids = vec![];
+    match candidate_row_ids {
+      Some(candidate_row_ids) => {
+        for row_id in candidate_row_ids {
+          if is_related(&database.get_row(&row_id).await) {
+            row_ids.push(row_id);
+          }
+        }
+      },
+      None => {
+        let rows_stream = database.get_all_rows(None).await;
+        pin_mut!(rows_stream);
+        while let Some(Ok(row)) = rows_stream.next().await {
+          if is_related(&row) {
+            row_ids.push(row.id);
+          }
+        }
+      },
     }
-    self.database.write().await.update_row(row_id, modify).await;
-    Ok(())
+    row_ids
   }
 
-  pub async fn clear_cell(&self, view_id: &str, row_id: RowId, field_id: &str) -> FlowyResult<()> {
-    // Get the old row before updating the cell. It would be better to get the old cell
-    let old_row = self.get_row(view_id, &row_id).await;
-    self
-      .update_row(row_id.clone(), |row_update| {
-        row_update.update_cells(|cell_update| {
-          cell_update.clear(field_id);
-        });
-      })
-      .await?;
+  /// Returns the ids of the rows that match the filter. Used to evaluate the filters of the
+  /// relation fields that link to this database.
+  pub(crate) async fn get_row_ids_matching(&self, filter: FilterDataPB) -> FlowyResult<Vec<RowId>> {
+    let filter = Filter {
+      id: gen_database_filter_id(),
+      inner: FilterInner::try_from(filter)?,
+    };
+    let database = self.database.read().await;
+    let field_by_field_id = database
+      .get_fields(None)
+      .into_iter()
+      .map(|field| (field.id.clone(), field))
+      .collect::<HashMap<String, Field>>();
 
-    self
-      .did_update_row(view_id, &row_id, field_id, old_row)
-      .await;
+    let mut parsed_filters = HashMap::new();
+    parse_formula_filters(&filter, &mut parsed_filters);
 
-    Ok(())
+    let mut row_ids = vec![];
+    let rows_stream = database.get_all_rows(None).await;
+    pin_mut!(rows_stream);
+    while let Some(Ok(row)) = rows_stream.next().await {
+      if is_row_visible(
+        &row,
+        &field_by_field_id,
+        &self.cell_cache,
+        &parsed_filters,
+        &filter,
+      ) {
+        row_ids.push(row.id);
+      }
+    }
+    Ok(row_ids)
   }
 
-  async fn did_update_row(
+  /// Returns the ids of the rows of the given database that the relation cells of the row link to,
+  /// or None if none of the relation fields of this database link to that database.
+  pub(crate) async fn get_linked_row_ids(
     &self,
-    view_id: &str,
     row_id: &RowId,
-    field_id: &str,
-    old_row: Option<Row>,
-  ) {
-    let option_row = self.get_row(view_id, row_id).await;
-    let field_type = self
+    database_id: &str,
+  ) -> Option<Vec<RowId>> {
+    let database = self.database.read().await;
+    let relation_fields = database
+      .get_fields(None)
+      .into_iter()
+      .filter(|field| FieldType::from(field.field_type) == FieldType::Relation)
+      .filter(|field| {
+        field
+          .get_type_option::<RelationTypeOption>(FieldType::Relation)
+          .map(|type_option| type_option.database_id == database_id)
+          .unwrap_or(false)
+      })
+      .collect::<Vec<Field>>();
+    if relation_fields.is_empty() {
+      return None;
+    }
+
+    let row = database.get_row(row_id).await;
+    let mut row_ids = vec![];
+    for field in relation_fields {
+      if let Some(cell) = row.cells.get(&field.id) {
+        for linked_row_id in RelationCellData::from(cell).row_ids {
+          if !row_ids.contains(&linked_row_id) {
+            row_ids.push(linked_row_id);
+          }
+        }
+      }
+    }
+    Some(row_ids)
+  }
+
+  /// Returns the ids of the databases that the relation fields of this database link to.
+  pub(crate) async fn get_related_database_ids(&self) -> HashSet<String> {
+    self
       .database
       .read()
       .await
-      .get_field(field_id)
-      .map(|field| field.field_type);
+      .get_fields(None)
+      .iter()
+      .filter(|field| FieldType::from(field.field_type) == FieldType::Relation)
+      .filter_map(|field| field.get_type_option::<RelationTypeOption>(FieldType::Relation))
+      .map(|type_option| type_option.database_id)
+      .collect()
+  }
 
-    if let Some(row) = option_row {
-      for view in self.database_views.editors().await {
-        view
-          .v_did_update_row(&old_row, &row, Some(field_id.to_owned()))
-          .await;
-      }
+  /// Notifies the views that the rows of the related database changed, so that they can filter
+  /// and sort their rows again if they depend on them.
+  pub(crate) async fn did_update_related_database(&self, database_id: &str) {
+    for view in self.database_views.editors().await {
+      view.v_did_update_related_database(database_id).await;
+    }
+  }
 
-      if let Some(field_type) = field_type {
-        if FieldType::from(field_type) == FieldType::Media {
-          self
-            .did_update_attachments(view_id, row_id, field_id, old_row.clone())
-            .await;
+  /// Returns the value of the field for each of the given rows. Rows that don't exist are skipped.
+  async fn get_field_values(&self, field_id: &str, row_ids: &[RowId]) -> Vec<FormulaValue> {
+    let database = self.database.read().await;
+    let field = match database.get_field(field_id) {
+      Some(field) => field,
+      None => return vec![],
+    };
+
+    let mut values = vec![];
+    for row_id in row_ids {
+      if let Some(database_row) = database.get_or_init_database_row(row_id).await {
+        if let Some(row) = database_row.read().await.get_row() {
+          values.push(formula_value_from_row(&row, &field));
         }
       }
     }
+    values
   }
 
   async fn did_update_attachments(
@@ -1353,9 +2221,16 @@ impl DatabaseEditor {
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn get_all_calendar_events(&self, view_id: &str) -> Vec<CalendarEventPB> {
+  pub async fn get_all_calendar_events(
+    &self,
+    view_id: &str,
+    window: Option<(i64, i64)>,
+  ) -> Vec<CalendarEventPB> {
     match self.database_views.get_or_init_view_editor(view_id).await {
-      Ok(view) => view.v_get_all_calendar_events().await.unwrap_or_default(),
+      Ok(view) => view
+        .v_get_all_calendar_events(window)
+        .await
+        .unwrap_or_default(),
       Err(_) => {
         warn!("Can not find the view: {}", view_id);
         vec![]
@@ -1382,6 +2257,140 @@ impl DatabaseEditor {
     view.v_get_calendar_event(row_id).await
   }
 
+  /// Moves the date of a calendar event. If the date is recurring, the moved occurrence, the
+  /// following occurrences or the whole series are moved depending on the scope. The occurrences
+  /// that are moved apart from the series are moved to a copy of the row.
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn move_calendar_event(&self, params: MoveCalendarEventParams) -> FlowyResult<()> {
+    let MoveCalendarEventParams {
+      cell_id,
+      timestamp,
+      occurrence,
+      scope,
+    } = params;
+    let cell = self.get_cell(&cell_id.field_id, &cell_id.row_id).await;
+    let cell_data = cell.as_ref().map(DateCellData::from).unwrap_or_default();
+    let recurrence = cell.as_ref().and_then(RecurrenceRule::from_cell);
+
+    // The first occurrence is moved if the occurrence is not specified
+    let (recurrence, occurrence, first) = match (recurrence, cell_data.timestamp) {
+      (Some(recurrence), Some(first)) => (recurrence, occurrence.unwrap_or(first), first),
+      _ => {
+        let changeset = moved_date_changeset(&cell_data, timestamp, None);
+        return self
+          .update_cell_with_changeset(
+            &cell_id.view_id,
+            &cell_id.row_id,
+            &cell_id.field_id,
+            BoxAny::new(changeset),
+          )
+          .await;
+      },
+    };
+
+    let delta = timestamp - occurrence;
+    let scope = match scope {
+      RecurrenceEditScopePB::AllFollowing if occurrence <= first => RecurrenceEditScopePB::All,
+      scope => scope,
+    };
+    let (series_changeset, moved_changeset) = match scope {
+      RecurrenceEditScopePB::All => {
+        let changeset =
+          moved_date_changeset(&cell_data, first + delta, Some(recurrence.shifted(delta)));
+        (changeset, None)
+      },
+      RecurrenceEditScopePB::ThisOccurrence => {
+        let mut series = recurrence;
+        series.exceptions.push(occurrence);
+        let mut moved_changeset = moved_date_changeset(
+          &date_cell_data_of_occurrence(&cell_data, occurrence),
+          timestamp,
+          None,
+        );
+        moved_changeset.clear_recurrence = Some(true);
+        (
+          DateCellChangeset {
+            recurrence: Some(series),
+            ..Default::default()
+          },
+          Some(moved_changeset),
+        )
+      },
+      RecurrenceEditScopePB::AllFollowing => {
+        let time_zone = self
+          .get_field(&cell_id.field_id)
+          .await
+          .and_then(|field| field.get_type_option::<DateTypeOption>(FieldType::DateTime))
+          .and_then(|type_option| time_zone_from_id(&type_option.timezone_id));
+        let (before, after) = recurrence.split_at(first, occurrence, time_zone);
+        let moved_changeset = moved_date_changeset(
+          &date_cell_data_of_occurrence(&cell_data, occurrence),
+          timestamp,
+          Some(after.shifted(delta)),
+        );
+        (
+          DateCellChangeset {
+            recurrence: Some(before),
+            ..Default::default()
+          },
+          Some(moved_changeset),
+        )
+      },
+    };
+
+    if let Some(moved_changeset) = moved_changeset {
+      let new_row = self
+        .duplicate_row(&cell_id.view_id, &cell_id.row_id)
+        .await?;
+      self
+        .update_cell_with_changeset(
+          &cell_id.view_id,
+          &new_row.id,
+          &cell_id.field_id,
+          BoxAny::new(moved_changeset),
+        )
+        .await?;
+    }
+    self
+      .update_cell_with_changeset(
+        &cell_id.view_id,
+        &cell_id.row_id,
+        &cell_id.field_id,
+        BoxAny::new(series_changeset),
+      )
+      .await
+  }
+
+  #[tracing::instrument(level = "trace", skip_all)]
+  pub async fn get_timeline_bars(
+    &self,
+    view_id: &str,
+    start: i64,
+    end: i64,
+  ) -> FlowyResult<Vec<TimelineBarPB>> {
+    let view = self.database_views.get_or_init_view_editor(view_id).await?;
+    view.v_get_timeline_bars(start, end).await
+  }
+
+  /// Moves or resizes the bar of the row by updating its date cells
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn update_timeline_bar(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    start: i64,
+    end: i64,
+  ) -> FlowyResult<()> {
+    let view = self.database_views.get_or_init_view_editor(view_id).await?;
+    let changesets = view.v_get_timeline_bar_changesets(start, end).await?;
+    for (field_id, changeset) in changesets {
+      self
+        .update_cell_with_changeset(view_id, row_id, &field_id, BoxAny::new(changeset))
+        .await?;
+    }
+    Ok(())
+  }
+
   #[tracing::instrument(level = "trace", skip_all, err)]
   async fn notify_did_insert_database_field(&self, field: Field, index: usize) -> FlowyResult<()> {
     let database_id = self.database.read().await.get_database_id();
@@ -1477,7 +2486,7 @@ impl DatabaseEditor {
       self
         .is_loading_rows
         .store(Some(Arc::new(broadcast::channel(500).0)));
-      let view_layout = self.database.read().await.get_database_view_layout(view_id);
+      let view_layout = database_view_layout_pb(&*self.database.read().await, view_id);
       let new_token = CancellationToken::new();
       if let Some(old_token) = self
         .database_cancellation
@@ -1542,7 +2551,7 @@ impl DatabaseEditor {
         id: database_id,
         fields,
         rows: order_rows,
-        layout_type: view_layout.into(),
+        layout_type: view_layout,
         is_linked,
       });
       // Mark that the opening process is complete
@@ -1691,6 +2700,178 @@ impl DatabaseEditor {
     Ok(csv)
   }
 
+  pub async fn export_xlsx(&self, view_id: &str) -> FlowyResult<Vec<u8>> {
+    let database = self.database.clone();
+    let database_guard = database.read().await;
+    XLSXExport.export_database(&database_guard, view_id).await
+  }
+
+  pub async fn export_ics(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.database.clone();
+    let database_guard = database.read().await;
+    ICSExport.export_database(&database_guard, view_id).await
+  }
+
+  pub async fn export_html(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.database.clone();
+    let database_guard = database.read().await;
+    HTMLExport.export_database(&database_guard, view_id).await
+  }
+
+  /// Returns the fields and the rows of the database, which are saved as a snapshot.
+  pub async fn get_snapshot_content(&self) -> DatabaseSnapshotContent {
+    let database = self.database.read().await;
+    let inline_view_id = database.get_inline_view_id();
+    let fields = database.get_fields_in_view(&inline_view_id, None);
+    let rows = database
+      .get_rows_for_view(&inline_view_id, None)
+      .await
+      .filter_map(|result| async { result.ok() })
+      .collect::<Vec<_>>()
+      .await;
+    DatabaseSnapshotContent { fields, rows }
+  }
+
+  /// Reverts the fields and the rows of the database to the snapshot. The fields and the rows
+  /// created after the snapshot are deleted, except for the primary field.
+  pub async fn restore_snapshot(&self, snapshot: DatabaseSnapshotContent) -> FlowyResult<()> {
+    let current = self.get_snapshot_content().await;
+    let inline_view_id = self.database.read().await.get_inline_view_id();
+
+    for field in current.fields.iter() {
+      if !field.is_primary && snapshot.fields.iter().all(|old| old.id != field.id) {
+        self.delete_field(&field.id).await?;
+      }
+    }
+    for field in snapshot.fields {
+      match current.fields.iter().find(|current| current.id == field.id) {
+        None => self.restore_deleted_field(&inline_view_id, field).await,
+        Some(current) if is_field_changed(&field, current) => {
+          self.restore_field(current, field).await?
+        },
+        Some(_) => {},
+      }
+    }
+
+    let removed_row_ids = current
+      .rows
+      .iter()
+      .filter(|row| snapshot.get_row(&row.id).is_none())
+      .map(|row| row.id.clone())
+      .collect::<Vec<_>>();
+    if !removed_row_ids.is_empty() {
+      self.delete_rows(&removed_row_ids).await;
+    }
+    for row in snapshot.rows {
+      self.restore_row(&inline_view_id, row).await?;
+    }
+    Ok(())
+  }
+
+  /// Reverts the cells of the row to the snapshot. The row is created again if it has been
+  /// deleted since the snapshot was taken.
+  pub async fn restore_row(&self, view_id: &str, snapshot_row: Row) -> FlowyResult<()> {
+    let row_id = snapshot_row.id.clone();
+    let old_row = match self.get_row(view_id, &row_id).await {
+      Some(old_row) => old_row,
+      None => {
+        let mut params = CreateRowParams::new(row_id.clone(), self.database_id.clone());
+        params.cells = snapshot_row.cells;
+        params.height = snapshot_row.height;
+        params.visibility = snapshot_row.visibility;
+        self
+          .database
+          .write()
+          .await
+          .create_row_in_view(view_id, params)
+          .await?;
+        self.did_create_row(&row_id).await;
+        return Ok(());
+      },
+    };
+
+    let changed_field_ids = old_row
+      .cells
+      .keys()
+      .chain(snapshot_row.cells.keys())
+      .filter(|field_id| old_row.cells.get(*field_id) != snapshot_row.cells.get(*field_id))
+      .cloned()
+      .collect::<HashSet<String>>();
+    if changed_field_ids.is_empty() {
+      return Ok(());
+    }
+
+    self
+      .update_row(row_id.clone(), |row_update| {
+        row_update
+          .set_last_modified(timestamp())
+          .update_cells(|mut cells_update| {
+            for field_id in changed_field_ids.iter() {
+              cells_update = match snapshot_row.cells.get(field_id) {
+                Some(cell) => cells_update.insert(field_id, cell.clone()),
+                None => cells_update.clear(field_id),
+              };
+            }
+          });
+      })
+      .await?;
+
+    if let Some(row) = self.get_row(view_id, &row_id).await {
+      let old_row = Some(old_row);
+      for view in self.database_views.editors().await {
+        view.v_did_update_row(&old_row, &row, None).await;
+      }
+    }
+    for field_id in changed_field_ids {
+      let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Cell {
+        row_id: row_id.clone(),
+        field_id,
+      });
+    }
+    Ok(())
+  }
+
+  /// Inserts the field of the snapshot back into the database with its original id, so the cells
+  /// that still refer to it become visible again.
+  async fn restore_deleted_field(&self, view_id: &str, field: Field) {
+    let name = field.name.clone();
+    let field_type = field.field_type;
+    let (index, field) = self.database.write().await.create_field_with_mut(
+      view_id,
+      name,
+      field_type,
+      &OrderObjectPosition::default(),
+      |new_field| *new_field = field,
+      default_field_settings_by_layout_map(),
+    );
+    let _ = self.notify_did_insert_database_field(field, index).await;
+  }
+
+  async fn restore_field(&self, current: &Field, field: Field) -> FlowyResult<()> {
+    let field_type = FieldType::from(field.field_type);
+    let type_option_data = field.get_any_type_option(field_type);
+    {
+      let mut database = self.database.write().await;
+      database.update_field(&field.id, |update| {
+        update
+          .set_name_if_not_none(Some(field.name.clone()))
+          .set_field_type(field.field_type)
+          .set_type_option(field.field_type, type_option_data);
+      });
+      notify_did_update_database_field(&database, &field.id)?;
+    }
+
+    if current.field_type != field.field_type {
+      for view in self.database_views.editors().await {
+        view.v_did_update_field_type(&field.id, field_type).await;
+      }
+    }
+    let _ = self.cell_changed_notifier.send(DatabaseCellChanged::Field {
+      field_id: field.id.clone(),
+    });
+    Ok(())
+  }
+
   pub async fn get_field_settings(
     &self,
     view_id: &str,
@@ -1859,6 +3040,16 @@ struct DatabaseViewOperationImpl {
   editor_by_view_id: Arc<RwLock<EditorByViewId>>,
   #[allow(dead_code)]
   database_cancellation: Arc<RwLock<Option<CancellationToken>>>,
+  /// The opened databases, used to evaluate the filters and sorts of the relation fields
+  related_databases: Weak<Mutex<DatabaseEditorMap>>,
+}
+
+impl DatabaseViewOperationImpl {
+  async fn get_related_database(&self, database_id: &str) -> Option<Arc<DatabaseEditor>> {
+    let related_databases = self.related_databases.upgrade()?;
+    let editor = related_databases.lock().await.get(database_id).cloned();
+    editor
+  }
 }
 
 #[async_trait]
@@ -2180,6 +3371,32 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
     field_settings
   }
 
+  async fn get_related_row_ids(
+    &self,
+    database_id: &str,
+    filter: FilterDataPB,
+  ) -> Option<Vec<RowId>> {
+    let editor = self.get_related_database(database_id).await?;
+    match editor.get_row_ids_matching(filter).await {
+      Ok(row_ids) => Some(row_ids),
+      Err(err) => {
+        error!("Failed to filter the rows of {}: {}", database_id, err);
+        None
+      },
+    }
+  }
+
+  async fn get_related_row_names(&self, database_id: &str) -> Option<HashMap<RowId, String>> {
+    let editor = self.get_related_database(database_id).await?;
+    let rows = editor.get_related_rows(None).await.ok()?;
+    Some(
+      rows
+        .into_iter()
+        .map(|row| (RowId::from(row.row_id), row.name))
+        .collect(),
+    )
+  }
+
   async fn update_field_settings(&self, params: FieldSettingsChangesetPB) {
     let field_settings_map = self
       .get_field_settings(&params.view_id, &[params.field_id.clone()])
@@ -2247,6 +3464,7 @@ pub async fn update_field_type_option_fn(
     warn!("Update type option with empty data");
     return Ok(());
   }
+  let type_option_data = keep_field_validation(type_option_data, old_field);
   let field_type = FieldType::from(old_field.field_type);
   database.update_field(&old_field.id, |update| {
     if old_field.is_primary {
@@ -2268,6 +3486,133 @@ pub async fn update_field_type_option_fn(
   Ok(())
 }
 
+/// Returns the text of the cells of the row, which is indexed by the local search.
+pub(crate) fn row_index_content(
+  row: &Row,
+  fields: &[Field],
+  database_id: &str,
+  view_id: &str,
+  workspace_id: &str,
+) -> IndexableContent {
+  let content = fields
+    .iter()
+    .filter_map(|field| {
+      row
+        .cells
+        .get(&field.id)
+        .map(|cell| stringify_cell(cell, field))
+    })
+    .filter(|text| !text.trim().is_empty())
+    .collect::<Vec<_>>()
+    .join(" ");
+  IndexableContent {
+    id: row.id.to_string(),
+    object_id: database_id.to_string(),
+    view_id: view_id.to_string(),
+    content,
+    workspace_id: workspace_id.to_string(),
+  }
+}
+
+/// A formula field whose expression is parsed once, see [parse_formula_fields]
+struct FormulaField<'a> {
+  field: &'a Field,
+  parsed: ParsedFormula,
+  /// The ids of the fields read by the formula
+  dependencies: HashSet<String>,
+}
+
+/// Parses the formulas of the fields and resolves the fields each of them reads
+fn parse_formula_fields(fields: &[Field]) -> Vec<FormulaField<'_>> {
+  fields
+    .iter()
+    .filter(|field| FieldType::from(field.field_type).is_formula())
+    .filter_map(|field| {
+      let parsed = field
+        .get_type_option::<FormulaTypeOption>(FieldType::Formula)?
+        .parse();
+      let dependencies = fields
+        .iter()
+        .filter(|other| parsed.depends_on(other))
+        .map(|other| other.id.clone())
+        .collect();
+      Some(FormulaField {
+        field,
+        parsed,
+        dependencies,
+      })
+    })
+    .collect()
+}
+
+/// Returns true if any of the formulas reads the field
+fn has_dependent_formulas(fields: &[Field], field: &Field) -> bool {
+  fields
+    .iter()
+    .filter(|other| FieldType::from(other.field_type).is_formula())
+    .filter_map(|other| other.get_type_option::<FormulaTypeOption>(FieldType::Formula))
+    .any(|type_option| type_option.depends_on(field))
+}
+
+/// Rewrites the formulas that reference the field named `old_name` to use `new_name`
+fn rename_formula_field_references(database: &mut Database, old_name: &str, new_name: &str) {
+  let formula_fields = database
+    .get_fields(None)
+    .into_iter()
+    .filter(|field| FieldType::from(field.field_type).is_formula())
+    .collect::<Vec<_>>();
+  for field in formula_fields {
+    let type_option = match field.get_type_option::<FormulaTypeOption>(FieldType::Formula) {
+      Some(type_option) => type_option,
+      None => continue,
+    };
+    if let Some(expression) = rename_field_reference(&type_option.expression, old_name, new_name) {
+      database.update_field(&field.id, |update| {
+        update.update_type_options(|type_options_update| {
+          let type_option_data = FormulaTypeOption { expression }.into();
+          type_options_update.insert(
+            &FieldType::Formula.to_string(),
+            keep_field_validation(type_option_data, &field),
+          );
+        });
+      });
+      let _ = notify_did_update_database_field(database, &field.id);
+    }
+  }
+}
+
+/// Returns the [ErrorCode::CellValidationFailed] error if any of the errors comes from a
+/// validation whose severity is [ValidationSeverity::Error]
+fn reject_validation_errors(errors: &[CellValidationError]) -> FlowyResult<()> {
+  let errors = errors
+    .iter()
+    .filter(|error| error.severity == ValidationSeverity::Error)
+    .cloned()
+    .collect::<Vec<_>>();
+  if errors.is_empty() {
+    return Ok(());
+  }
+
+  let msg = errors
+    .iter()
+    .map(|error| error.message.as_str())
+    .collect::<Vec<_>>()
+    .join(", ");
+  Err(
+    FlowyError::new(ErrorCode::CellValidationFailed, msg)
+      .with_payload(RepeatedCellValidationErrorPB::from(errors)),
+  )
+}
+
+fn notify_did_update_row_validation(row_id: &RowId, errors: Vec<CellValidationError>) {
+  send_notification(
+    row_id.as_str(),
+    DatabaseNotification::DidUpdateRowValidation,
+  )
+  .payload(RepeatedCellValidationErrorPB::from(errors))
+  .send();
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 fn notify_did_update_database_field(database: &Database, field_id: &str) -> FlowyResult<()> {
   let (database_id, field, views) = {
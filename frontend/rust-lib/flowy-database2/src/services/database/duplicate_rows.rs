@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use collab_database::fields::select_type_option::SelectOptionIds;
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row, RowId};
+
+use crate::entities::FieldType;
+use crate::services::cell::{insert_select_option_cell, stringify_cell};
+use crate::services::field::{RelationCellData, TypeOptionCellExt};
+
+/// Returns the text that identifies the value of the cell, or None if the cell is empty.
+///
+/// With `fuzzy`, the runs of whitespace are collapsed and the case is ignored, so "Jane  Doe" and
+/// "jane doe" have the same key. Otherwise only the leading and trailing whitespace is ignored.
+pub fn cell_value_key(cell: Option<&Cell>, field: &Field, fuzzy: bool) -> Option<String> {
+  let cell = cell?;
+  let is_empty = TypeOptionCellExt::new(field, None)
+    .get_type_option_cell_data_handler()
+    .map_or(false, |handler| handler.handle_is_cell_empty(cell, field));
+  if is_empty {
+    return None;
+  }
+
+  let text = stringify_cell(cell, field);
+  let key = if fuzzy {
+    text
+      .split_whitespace()
+      .collect::<Vec<_>>()
+      .join(" ")
+      .to_lowercase()
+  } else {
+    text.trim().to_string()
+  };
+  Some(key).filter(|key| !key.is_empty())
+}
+
+/// Groups the rows whose cells of the key fields have the same value. The rows whose key cells
+/// are all empty are ignored. The groups and the rows in each group keep the order of `rows`, and
+/// only the groups of two or more rows are returned.
+pub fn find_duplicate_rows(rows: &[&Row], key_fields: &[Field], fuzzy: bool) -> Vec<Vec<RowId>> {
+  let mut group_index_by_key: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+  let mut groups: Vec<Vec<RowId>> = vec![];
+  for row in rows {
+    let key = key_fields
+      .iter()
+      .map(|field| cell_value_key(row.cells.get(&field.id), field, fuzzy))
+      .collect::<Vec<_>>();
+    if key.iter().all(Option::is_none) {
+      continue;
+    }
+
+    match group_index_by_key.get(&key) {
+      Some(index) => groups[*index].push(row.id.clone()),
+      None => {
+        group_index_by_key.insert(key, groups.len());
+        groups.push(vec![row.id.clone()]);
+      },
+    }
+  }
+  groups.retain(|group| group.len() > 1);
+  groups
+}
+
+/// Returns the cells to write into `row` to combine it with its duplicates. The empty cells of
+/// `row` take the first non-empty cell of the duplicates, and the options of the MultiSelect
+/// fields and the rows of the Relation fields are combined. The cells that are computed from the
+/// row are ignored.
+pub fn merge_duplicate_cells(
+  row: &Row,
+  duplicates: &[&Row],
+  fields: &[Field],
+) -> Vec<(String, Cell)> {
+  let mut merged_cells = vec![];
+  for field in fields {
+    let field_type = FieldType::from(field.field_type);
+    if field_type.is_created_time()
+      || field_type.is_last_edited_time()
+      || field_type.is_formula()
+      || field_type.is_rollup()
+    {
+      continue;
+    }
+
+    let cell = row.cells.get(&field.id);
+    let mut duplicate_cells = duplicates
+      .iter()
+      .filter_map(|duplicate| duplicate.cells.get(&field.id))
+      .filter(|cell| cell_value_key(Some(cell), field, false).is_some());
+    match field_type {
+      FieldType::MultiSelect => {
+        let mut option_ids = cell.map(SelectOptionIds::from).unwrap_or_default();
+        let num_of_options = option_ids.len();
+        for option_id in duplicate_cells.flat_map(|cell| SelectOptionIds::from(cell).to_vec()) {
+          if !option_ids.contains(&option_id) {
+            option_ids.push(option_id);
+          }
+        }
+        if option_ids.len() > num_of_options {
+          merged_cells.push((
+            field.id.clone(),
+            insert_select_option_cell(option_ids.to_vec(), field),
+          ));
+        }
+      },
+      FieldType::Relation => {
+        let mut cell_data = cell.map(RelationCellData::from).unwrap_or_default();
+        let num_of_rows = cell_data.row_ids.len();
+        for row_id in duplicate_cells.flat_map(|cell| RelationCellData::from(cell).row_ids) {
+          if !cell_data.row_ids.contains(&row_id) {
+            cell_data.row_ids.push(row_id);
+          }
+        }
+        if cell_data.row_ids.len() > num_of_rows {
+          merged_cells.push((field.id.clone(), Cell::from(&cell_data)));
+        }
+      },
+      _ => {
+        if cell_value_key(cell, field, false).is_none() {
+          if let Some(duplicate_cell) = duplicate_cells.next() {
+            merged_cells.push((field.id.clone(), duplicate_cell.clone()));
+          }
+        }
+      },
+    }
+  }
+  merged_cells
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::Field;
+  use collab_database::rows::{Cells, Row, RowId};
+
+  use crate::entities::FieldType;
+  use crate::services::cell::insert_text_cell;
+  use crate::services::database::{find_duplicate_rows, merge_duplicate_cells};
+  use crate::services::field::{default_type_option_data_from_type, FieldBuilder};
+
+  fn text_field() -> Field {
+    FieldBuilder::new(
+      FieldType::RichText,
+      default_type_option_data_from_type(FieldType::RichText),
+    )
+    .build()
+  }
+
+  fn row_with_texts(id: &str, texts: &[(&Field, &str)]) -> Row {
+    let mut cells = Cells::new();
+    for (field, text) in texts {
+      cells.insert(field.id.clone(), insert_text_cell(text.to_string(), field));
+    }
+    let mut row = Row::new(RowId::from(id.to_string()), "database");
+    row.cells = cells;
+    row
+  }
+
+  #[test]
+  fn find_duplicate_rows_test() {
+    let name = text_field();
+    let rows = [
+      row_with_texts("1", &[(&name, "Jane Doe")]),
+      row_with_texts("2", &[(&name, "John Doe")]),
+      row_with_texts("3", &[(&name, " jane  doe ")]),
+      row_with_texts("4", &[(&name, "")]),
+      row_with_texts("5", &[(&name, "")]),
+      row_with_texts("6", &[(&name, "Jane Doe")]),
+    ];
+    let rows = rows.iter().collect::<Vec<_>>();
+    let ids = |ids: &[&str]| {
+      ids
+        .iter()
+        .map(|id| RowId::from(id.to_string()))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      find_duplicate_rows(&rows, &[name.clone()], false),
+      vec![ids(&["1", "6"])]
+    );
+    assert_eq!(
+      find_duplicate_rows(&rows, &[name], true),
+      vec![ids(&["1", "3", "6"])]
+    );
+  }
+
+  #[test]
+  fn merge_duplicate_cells_test() {
+    let name = text_field();
+    let email = text_field();
+    let row = row_with_texts("1", &[(&name, "Jane Doe")]);
+    let duplicate = row_with_texts("2", &[(&name, "jane doe"), (&email, "jane@example.com")]);
+
+    let merged_cells = merge_duplicate_cells(&row, &[&duplicate], &[name, email.clone()]);
+    assert_eq!(merged_cells.len(), 1);
+    assert_eq!(merged_cells[0].0, email.id);
+    assert_eq!(&merged_cells[0].1, duplicate.cells.get(&email.id).unwrap());
+  }
+}
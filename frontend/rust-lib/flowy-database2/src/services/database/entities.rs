@@ -1,3 +1,4 @@
+use crate::services::field::RollupTypeOption;
 use collab_database::rows::{RowDetail, RowId};
 use collab_database::views::DatabaseLayout;
 
@@ -64,3 +65,24 @@ pub struct CreateDatabaseViewParams {
   pub view_id: String,
   pub layout_type: DatabaseLayout,
 }
+
+/// Emitted by the [crate::services::database::DatabaseEditor] whenever cells of the database
+/// might have changed. The [crate::DatabaseManager] uses it to refresh the cells of other
+/// databases that are calculated from this database, e.g. rollups.
+#[derive(Debug, Clone)]
+pub enum DatabaseCellChanged {
+  /// The cell of the row was updated
+  Cell { row_id: RowId, field_id: String },
+  /// The type option of the field was updated, so every cell of the field might have changed
+  Field { field_id: String },
+  /// The rows were created or deleted
+  Rows { row_ids: Vec<RowId> },
+}
+
+/// A rollup field together with the id of the database that its relation field links to.
+#[derive(Debug, Clone)]
+pub struct RollupField {
+  pub field_id: String,
+  pub type_option: RollupTypeOption,
+  pub related_database_id: String,
+}
@@ -1,8 +1,10 @@
 mod database_editor;
 mod database_observe;
+mod duplicate_rows;
 mod entities;
 mod util;
 
 pub use database_editor::*;
+pub use duplicate_rows::*;
 pub use entities::*;
 pub(crate) use util::database_view_setting_pb_from_view;
@@ -5,13 +5,31 @@ use crate::entities::{
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
 use crate::services::group::GroupSetting;
+use crate::services::setting::TimelineLayoutSetting;
 use crate::services::sort::Sort;
+use collab_database::database::Database;
 use collab_database::entity::DatabaseView;
-use collab_database::views::DatabaseLayout;
+use collab_database::views::{DatabaseLayout, LayoutSetting};
 use tracing::error;
 
+/// Returns the layout of the view as shown to the user. A timeline view is stored as a grid view
+/// with a [TimelineLayoutSetting].
+pub(crate) fn database_view_layout_pb(database: &Database, view_id: &str) -> DatabaseLayoutPB {
+  let layout = database.get_database_view_layout(view_id);
+  let is_timeline = layout == DatabaseLayout::Grid
+    && database
+      .get_layout_setting::<LayoutSetting>(view_id, &layout)
+      .and_then(TimelineLayoutSetting::from_layout_setting)
+      .is_some();
+  if is_timeline {
+    DatabaseLayoutPB::Timeline
+  } else {
+    layout.into()
+  }
+}
+
 pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> DatabaseViewSettingPB {
-  let layout_type: DatabaseLayoutPB = view.layout.into();
+  let mut layout_type: DatabaseLayoutPB = view.layout.into();
   let layout_setting = if let Some(layout_setting) = view.layout_settings.get(&view.layout) {
     match view.layout {
       DatabaseLayout::Board => {
@@ -22,7 +40,15 @@ pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> Database
         let calendar_setting = layout_setting.clone().into();
         DatabaseLayoutSettingPB::from_calendar(calendar_setting)
       },
-      _ => DatabaseLayoutSettingPB::default(),
+      DatabaseLayout::Grid => {
+        match TimelineLayoutSetting::from_layout_setting(layout_setting.clone()) {
+          Some(timeline_setting) => {
+            layout_type = DatabaseLayoutPB::Timeline;
+            DatabaseLayoutSettingPB::from_timeline(timeline_setting)
+          },
+          None => DatabaseLayoutSettingPB::default(),
+        }
+      },
     }
   } else {
     DatabaseLayoutSettingPB::default()
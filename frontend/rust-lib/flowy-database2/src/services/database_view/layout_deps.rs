@@ -1,6 +1,6 @@
 use crate::entities::FieldType;
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting, TimelineLayoutSetting};
 use collab::lock::RwLock;
 use collab_database::database::{gen_field_id, Database};
 use collab_database::fields::date_type_option::DateTypeOption;
@@ -95,7 +95,17 @@ impl DatabaseLayoutDepsResolver {
     let fields = database.get_fields(None);
     // Insert the layout setting if it's not exist
     match &self.database_layout {
-      DatabaseLayout::Grid => {},
+      DatabaseLayout::Grid => {
+        // A timeline view turns back into a grid view when its timeline setting is removed
+        if database
+          .get_layout_setting::<LayoutSetting>(view_id, &self.database_layout)
+          .is_some()
+        {
+          database.update_database_view(view_id, |view| {
+            view.remove_layout_setting(&DatabaseLayout::Grid);
+          });
+        }
+      },
       DatabaseLayout::Board => {
         if database
           .get_layout_setting::<BoardLayoutSetting>(view_id, &self.database_layout)
@@ -135,6 +145,41 @@ impl DatabaseLayoutDepsResolver {
     }
   }
 
+  /// A timeline view is a grid view with a timeline setting. The timeline setting uses the first
+  /// date field of the database, a new date field is created if there is none.
+  pub async fn resolve_deps_when_update_to_timeline(&self, view_id: &str) {
+    let mut database = self.database.write().await;
+    if database
+      .get_layout_setting::<LayoutSetting>(view_id, &DatabaseLayout::Grid)
+      .and_then(TimelineLayoutSetting::from_layout_setting)
+      .is_some()
+    {
+      return;
+    }
+
+    let date_field_id = match database
+      .get_fields(None)
+      .into_iter()
+      .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
+    {
+      None => {
+        tracing::trace!("Create a new date field after layout type change");
+        let field = self.create_date_field();
+        let field_id = field.id.clone();
+        database.create_field(
+          None,
+          field,
+          &OrderObjectPosition::End,
+          default_field_settings_by_layout_map(),
+        );
+        field_id
+      },
+      Some(date_field) => date_field.id,
+    };
+    let layout_setting = TimelineLayoutSetting::new(date_field_id);
+    database.insert_layout_setting(view_id, &DatabaseLayout::Grid, layout_setting);
+  }
+
   fn create_date_field(&self) -> Field {
     let field_type = FieldType::DateTime;
     let default_date_type_option = DateTypeOption::default();
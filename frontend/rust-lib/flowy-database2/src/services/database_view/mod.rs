@@ -12,5 +12,6 @@ mod view_filter;
 mod view_group;
 mod view_operation;
 mod view_sort;
+mod view_timeline;
 mod views;
 // mod trait_impl;
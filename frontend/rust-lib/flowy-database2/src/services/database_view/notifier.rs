@@ -1,8 +1,8 @@
 #![allow(clippy::while_let_loop)]
 use crate::entities::{
   CalculationChangesetNotificationPB, DatabaseViewSettingPB, FilterChangesetNotificationPB,
-  GroupChangesPB, GroupRowsNotificationPB, ReorderAllRowsPB, ReorderSingleRowPB,
-  RowsVisibilityChangePB, SortChangesetNotificationPB,
+  GroupCalculationsPB, GroupChangesPB, GroupRowsNotificationPB, ReorderAllRowsPB,
+  ReorderSingleRowPB, RowsVisibilityChangePB, SortChangesetNotificationPB,
 };
 use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::filter::FilterResultNotification;
@@ -96,6 +96,15 @@ pub async fn notify_did_update_group_rows(payload: GroupRowsNotificationPB) {
     .send();
 }
 
+pub async fn notify_did_update_group_calculations(payload: GroupCalculationsPB) {
+  send_notification(
+    &payload.group_id,
+    DatabaseNotification::DidUpdateGroupCalculations,
+  )
+  .payload(payload)
+  .send();
+}
+
 pub async fn notify_did_update_filter(notification: FilterChangesetNotificationPB) {
   send_notification(&notification.view_id, DatabaseNotification::DidUpdateFilter)
     .payload(notification)
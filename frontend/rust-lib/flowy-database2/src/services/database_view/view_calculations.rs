@@ -1,9 +1,11 @@
 use async_trait::async_trait;
 use collab_database::fields::Field;
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use collab_database::rows::Cell;
+use collab_database::rows::{Cell, Row, RowId};
 
+use crate::entities::FieldType;
 use crate::services::calculations::{
   Calculation, CalculationsController, CalculationsDelegate, CalculationsTaskHandler,
 };
@@ -18,6 +20,17 @@ pub async fn make_calculations_controller(
   notifier: DatabaseViewChangedNotifier,
 ) -> Arc<CalculationsController> {
   let calculations = delegate.get_all_calculations(view_id).await;
+  let field_ids = calculations
+    .iter()
+    .map(|calculation| calculation.field_id.clone())
+    .collect();
+  let timestamp_field_ids = delegate
+    .get_fields(view_id, Some(field_ids))
+    .await
+    .into_iter()
+    .filter(|field| FieldType::from(field.field_type).is_timestamp())
+    .map(|field| field.id)
+    .collect();
   let task_scheduler = delegate.get_task_scheduler();
   let calculations_delegate = DatabaseViewCalculationsDelegateImpl(delegate.clone());
   let handler_id = gen_handler_id();
@@ -27,6 +40,7 @@ pub async fn make_calculations_controller(
     &handler_id,
     calculations_delegate,
     calculations,
+    timestamp_field_ids,
     task_scheduler.clone(),
     notifier,
   );
@@ -56,6 +70,17 @@ impl CalculationsDelegate for DatabaseViewCalculationsDelegateImpl {
       .collect()
   }
 
+  async fn get_rows(&self, view_id: &str, row_ids: &HashSet<RowId>) -> Vec<Arc<Row>> {
+    let row_orders = self
+      .0
+      .get_all_row_orders(view_id)
+      .await
+      .into_iter()
+      .filter(|row_order| row_ids.contains(&row_order.id))
+      .collect();
+    self.0.get_all_rows(view_id, row_orders).await
+  }
+
   async fn get_field(&self, field_id: &str) -> Option<Field> {
     self.0.get_field(field_id).await
   }
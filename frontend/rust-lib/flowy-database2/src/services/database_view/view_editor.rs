@@ -1,14 +1,15 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use super::notify_did_update_calculation;
 use crate::entities::{
-  CalendarEventPB, CreateRowPayloadPB, DatabaseLayoutMetaPB, DatabaseLayoutSettingPB,
-  DeleteSortPayloadPB, FieldSettingsChangesetPB, FieldType, GroupChangesPB, GroupPB, InsertedRowPB,
-  LayoutSettingChangeset, LayoutSettingParams, RemoveCalculationChangesetPB, ReorderSortPayloadPB,
-  RowMetaPB, RowsChangePB, SortChangesetNotificationPB, SortPB, UpdateCalculationChangesetPB,
-  UpdateSortPayloadPB,
+  CalendarEventPB, CreateRowPayloadPB, DatabaseLayoutMetaPB, DatabaseLayoutPB,
+  DatabaseLayoutSettingPB, DeleteSortPayloadPB, FieldSettingsChangesetPB, FieldType,
+  GroupCalculationsPB, GroupChangesPB, GroupPB, InsertedRowPB, LayoutSettingChangeset,
+  LayoutSettingParams, RemoveCalculationChangesetPB, ReorderSortPayloadPB, RepeatedGroupPB,
+  RowMetaPB, RowsChangePB, SortChangesetNotificationPB, SortPB, TimelineBarPB,
+  UpdateCalculationChangesetPB, UpdateSortPayloadPB,
 };
 use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::calculations::{
@@ -18,23 +19,32 @@ use crate::services::cell::{CellBuilder, CellCache};
 use crate::services::database::{database_view_setting_pb_from_view, DatabaseRowEvent, UpdatedRow};
 use crate::services::database_view::view_calculations::make_calculations_controller;
 use crate::services::database_view::view_filter::make_filter_controller;
-use crate::services::database_view::view_group::{get_cell_for_row, new_group_controller};
+use crate::services::database_view::view_group::{
+  get_cell_for_row, make_group_pbs, new_group_controller, new_sub_group_controller,
+};
 use crate::services::database_view::view_operation::DatabaseViewOperation;
 use crate::services::database_view::view_sort::make_sort_controller;
+use crate::services::database_view::view_timeline::{
+  make_timeline_bar, make_timeline_bar_changesets,
+};
 use crate::services::database_view::{
-  notify_did_update_filter, notify_did_update_group_rows, notify_did_update_num_of_groups,
-  notify_did_update_setting, notify_did_update_sort, DatabaseLayoutDepsResolver,
-  DatabaseViewChangedNotifier, DatabaseViewChangedReceiverRunner,
+  notify_did_update_filter, notify_did_update_group_calculations, notify_did_update_group_rows,
+  notify_did_update_num_of_groups, notify_did_update_setting, notify_did_update_sort,
+  DatabaseLayoutDepsResolver, DatabaseViewChangedNotifier, DatabaseViewChangedReceiverRunner,
+};
+use crate::services::field::{
+  occurrence_duration, time_zone_from_id, DateCellChangeset, RecurrenceRule,
 };
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::{Filter, FilterChangeset, FilterController};
 use crate::services::group::{
   DidMoveGroupRowResult, GroupChangeset, GroupController, MoveGroupRowContext, UpdatedCells,
 };
-use crate::services::setting::CalendarLayoutSetting;
+use crate::services::setting::{CalendarLayoutSetting, TimelineLayoutSetting};
 use crate::services::sort::{Sort, SortChangeset, SortController};
 use collab_database::database::{gen_database_calculation_id, gen_database_sort_id, gen_row_id};
 use collab_database::entity::DatabaseView;
+use collab_database::fields::date_type_option::{DateCellData, DateTypeOption};
 use collab_database::fields::Field;
 use collab_database::rows::{Cells, CreateRowParams, Row, RowCell, RowDetail, RowId};
 use collab_database::views::{DatabaseLayout, RowOrder};
@@ -50,6 +60,8 @@ pub struct DatabaseViewEditor {
   pub view_id: String,
   delegate: Arc<dyn DatabaseViewOperation>,
   group_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
+  /// Splits the rows of each group by a second field. None if the view is grouped by one field.
+  sub_group_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
   filter_controller: Arc<FilterController>,
   sort_controller: Arc<RwLock<SortController>>,
   calculations_controller: Arc<CalculationsController>,
@@ -108,6 +120,10 @@ impl DatabaseViewEditor {
       )
       .await?,
     ));
+    let sub_group_controller = Arc::new(RwLock::new(
+      new_sub_group_controller(view_id.clone(), delegate.clone(), filter_controller.clone())
+        .await?,
+    ));
 
     // Calculations
     let calculations_controller =
@@ -118,6 +134,7 @@ impl DatabaseViewEditor {
       view_id,
       delegate,
       group_controller,
+      sub_group_controller,
       filter_controller,
       sort_controller,
       calculations_controller,
@@ -232,16 +249,25 @@ impl DatabaseViewEditor {
     row_changes: &DashMap<String, RowsChangePB>,
   ) {
     // Send the group notification if the current view has groups
+    let mut updated_group_ids = HashSet::new();
     if let Some(controller) = self.group_controller.write().await.as_mut() {
       let rows = vec![Arc::new(row_detail.row.clone())];
       let mut rows = self.v_filter_rows(rows).await;
       if let Some(row) = rows.pop() {
         let changesets = controller.did_create_row(&row, index as usize);
         for changeset in changesets {
+          updated_group_ids.insert(changeset.group_id.clone());
           notify_did_update_group_rows(changeset).await;
         }
+        if let Some(sub_controller) = self.sub_group_controller.write().await.as_mut() {
+          sub_controller.did_create_row(&row, index as usize);
+        }
       }
     }
+    self.notify_did_update_sub_groups().await;
+    self
+      .did_update_group_rows_calculations(updated_group_ids)
+      .await;
 
     let index = self
       .sort_controller
@@ -283,7 +309,17 @@ impl DatabaseViewEditor {
       let result = self
         .mut_group_controller(|group_controller, _| group_controller.did_delete_row(row))
         .await;
+      let updated_group_ids = get_updated_group_ids(&result);
       handle_mut_group_result(&self.view_id, result).await;
+      self
+        .did_update_group_rows_calculations(updated_group_ids)
+        .await;
+      self
+        .mut_sub_group_controller(|sub_group_controller, _| {
+          sub_group_controller.did_delete_row(row)
+        })
+        .await;
+      self.notify_did_update_sub_groups().await;
     }
 
     // Updating calculations for each of the Rows cells is a tedious task
@@ -298,11 +334,27 @@ impl DatabaseViewEditor {
     });
   }
 
+  /// Filters and sorts the rows again if the filters or the sorts depend on the rows of the
+  /// related database
+  pub async fn v_did_update_related_database(&self, database_id: &str) {
+    self
+      .filter_controller
+      .did_update_related_database(database_id)
+      .await;
+    self
+      .sort_controller
+      .read()
+      .await
+      .did_update_related_database(database_id)
+      .await;
+  }
+
   /// Notify the view that the row has been updated. If the view has groups,
   /// send the group notification with [GroupRowsNotificationPB]. Otherwise,
   /// send the view notification with [RowsChangePB]
   #[instrument(level = "trace", skip_all)]
   pub async fn v_did_update_row(&self, old_row: &Option<Row>, row: &Row, field_id: Option<String>) {
+    let mut updated_group_ids = HashSet::new();
     if let Some(controller) = self.group_controller.write().await.as_mut() {
       let field = self
         .delegate
@@ -347,14 +399,27 @@ impl DatabaseViewEditor {
           notify_did_update_num_of_groups(&self.view_id, group_changes).await;
         }
 
+        // The cells of the row may be used by the calculations of the groups that contain it
+        updated_group_ids.extend(
+          controller
+            .get_all_groups()
+            .into_iter()
+            .filter(|group| group.contains_row(&row.id))
+            .map(|group| group.id.clone()),
+        );
         for changeset in row_changesets {
           if !changeset.is_empty() {
             tracing::trace!("Group change after editing the row: {:?}", changeset);
+            updated_group_ids.insert(changeset.group_id.clone());
             notify_did_update_group_rows(changeset).await;
           }
         }
       }
     }
+    self.did_update_sub_group_row(old_row, row).await;
+    self
+      .did_update_group_rows_calculations(updated_group_ids)
+      .await;
 
     // Each row update will trigger a calculations, filter and sort operation. We don't want
     // to block the main thread, so we spawn a new task to do the work.
@@ -363,6 +428,27 @@ impl DatabaseViewEditor {
       .await;
   }
 
+  async fn did_update_sub_group_row(&self, old_row: &Option<Row>, row: &Row) {
+    let is_visible = !self
+      .v_filter_rows(vec![Arc::new(row.clone())])
+      .await
+      .is_empty();
+    let result = self
+      .mut_sub_group_controller(|sub_group_controller, field| {
+        if is_visible {
+          sub_group_controller
+            .did_update_group_row(old_row, row, &field)
+            .map(|_| ())
+        } else {
+          sub_group_controller.did_delete_row(row).map(|_| ())
+        }
+      })
+      .await;
+    if result.is_some() {
+      self.notify_did_update_sub_groups().await;
+    }
+  }
+
   pub async fn v_filter_rows(&self, rows: Vec<Arc<Row>>) -> Vec<Arc<Row>> {
     self.filter_controller.filter_rows(rows).await
   }
@@ -418,11 +504,15 @@ impl DatabaseViewEditor {
     self.delegate.get_row_detail(&self.view_id, row_id).await
   }
 
+  /// Moves the row to the given group, and to the given sub group if the view is grouped by two
+  /// fields. Returns the cells that need to be updated to reflect the new groups of the row.
   pub async fn v_move_group_row(
     &self,
     row: &Row,
+    from_group_id: &str,
     to_group_id: &str,
     to_row_id: Option<RowId>,
+    to_sub_group_id: Option<&str>,
   ) -> UpdatedCells {
     let mut updated_cells = UpdatedCells::new();
     let result = self
@@ -431,29 +521,50 @@ impl DatabaseViewEditor {
           row,
           updated_cells: &mut updated_cells,
           field: &field,
+          from_group_id: Some(from_group_id),
           to_group_id,
-          to_row_id,
+          to_row_id: to_row_id.clone(),
         };
         group_controller.move_group_row(move_row_context)
       })
       .await;
-
+    let updated_group_ids = get_updated_group_ids(&result);
     handle_mut_group_result(&self.view_id, result).await;
+    self
+      .did_update_group_rows_calculations(updated_group_ids)
+      .await;
+
+    if let Some(to_sub_group_id) = to_sub_group_id {
+      // The notifications of the sub groups are sent once the cells of the row are updated.
+      self
+        .mut_sub_group_controller(|sub_group_controller, field| {
+          let move_row_context = MoveGroupRowContext {
+            row,
+            updated_cells: &mut updated_cells,
+            field: &field,
+            from_group_id: None,
+            to_group_id: to_sub_group_id,
+            to_row_id,
+          };
+          sub_group_controller.move_group_row(move_row_context)
+        })
+        .await;
+    }
     updated_cells
   }
 
   /// Only call once after database view editor initialized
   #[tracing::instrument(level = "trace", skip(self))]
   pub async fn v_load_groups(&self) -> Option<Vec<GroupPB>> {
-    let groups = self
-      .group_controller
-      .read()
-      .await
-      .as_ref()?
-      .get_all_groups()
-      .into_iter()
-      .map(|group_data| GroupPB::from(group_data.clone()))
-      .collect::<Vec<_>>();
+    let mut groups = {
+      let group_controller = self.group_controller.read().await;
+      let sub_group_controller = self.sub_group_controller.read().await;
+      make_group_pbs(
+        group_controller.as_ref()?.get_all_groups(),
+        sub_group_controller.as_deref(),
+      )
+    };
+    self.fill_group_calculations(&mut groups).await;
     tracing::trace!("Number of groups: {}", groups.len());
     Some(groups)
   }
@@ -468,7 +579,14 @@ impl DatabaseViewEditor {
       .and_then(|group| group.get_group(group_id))
     {
       None => Err(FlowyError::record_not_found().with_context("Can't find the group")),
-      Some((_, group)) => Ok(GroupPB::from(group)),
+      Some((_, group)) => {
+        let mut groups = {
+          let sub_group_controller = self.sub_group_controller.read().await;
+          make_group_pbs(vec![&group], sub_group_controller.as_deref())
+        };
+        self.fill_group_calculations(&mut groups).await;
+        Ok(groups.remove(0))
+      },
     }
   }
 
@@ -487,6 +605,13 @@ impl DatabaseViewEditor {
     }
   }
 
+  pub async fn is_sub_grouping_field(&self, field_id: &str) -> bool {
+    match self.sub_group_controller.read().await.as_ref() {
+      Some(sub_group_controller) => sub_group_controller.get_grouping_field_id() == field_id,
+      None => false,
+    }
+  }
+
   /// Called when the user changes the grouping field
   pub async fn v_initialize_new_group(&self, field_id: &str) -> FlowyResult<()> {
     if let Some(view) = self.delegate.get_view(&self.view_id).await {
@@ -622,6 +747,11 @@ impl DatabaseViewEditor {
       id: sort_id,
       field_id: params.field_id.clone(),
       condition: params.condition.into(),
+      relation_sort_by: params.relation_sort_by.into(),
+      natural_order: params.natural_order,
+      locale_collation: params.locale_collation,
+      select_sort_by: params.select_sort_by.into(),
+      empty_cells: params.empty_cells.into(),
     };
 
     self.delegate.insert_sort(&self.view_id, sort.clone()).await;
@@ -711,11 +841,14 @@ impl DatabaseViewEditor {
     let calculation_id = params
       .calculation_id
       .unwrap_or_else(gen_database_calculation_id);
-    let calculation = Calculation::none(
+    let mut calculation = Calculation::none(
       calculation_id,
       params.field_id,
       Some(params.calculation_type.value()),
     );
+    if let Some(percentile) = params.percentile {
+      calculation = calculation.with_percentile(percentile);
+    }
 
     let changeset = self
       .calculations_controller
@@ -735,6 +868,7 @@ impl DatabaseViewEditor {
 
       notify_did_update_calculation(changeset).await;
     }
+    self.did_update_calculations_of_all_groups().await;
 
     Ok(())
   }
@@ -758,6 +892,7 @@ impl DatabaseViewEditor {
     if let Some(changeset) = changeset {
       notify_did_update_calculation(changeset).await;
     }
+    self.did_update_calculations_of_all_groups().await;
 
     Ok(())
   }
@@ -782,6 +917,7 @@ impl DatabaseViewEditor {
     drop(group_controller_read_guard);
 
     if let Some(field_id) = grouping_field_id {
+      self.reload_sub_group_controller().await?;
       self.v_group_by_field(&field_id).await?;
     }
 
@@ -796,7 +932,9 @@ impl DatabaseViewEditor {
   pub async fn v_get_layout_settings(&self, layout_ty: &DatabaseLayout) -> LayoutSettingParams {
     let mut layout_setting = LayoutSettingParams::default();
     match layout_ty {
-      DatabaseLayout::Grid => {},
+      DatabaseLayout::Grid => {
+        layout_setting.timeline = self.v_get_timeline_setting().await;
+      },
       DatabaseLayout::Board => {
         if let Some(value) = self
           .delegate
@@ -876,7 +1014,21 @@ impl DatabaseViewEditor {
           None
         }
       },
-      _ => None,
+      DatabaseLayout::Grid => {
+        let layout_setting = params.timeline.unwrap();
+        self.validate_timeline_setting(&layout_setting).await?;
+
+        self
+          .delegate
+          .insert_layout_setting(
+            &self.view_id,
+            &params.layout_type,
+            layout_setting.clone().into(),
+          )
+          .await;
+
+        Some(DatabaseLayoutSettingPB::from_timeline(layout_setting))
+      },
     };
 
     if let Some(payload) = layout_setting_pb {
@@ -888,9 +1040,38 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
-  pub async fn v_did_delete_field(&self, deleted_field_id: &str) {
+  async fn validate_timeline_setting(&self, setting: &TimelineLayoutSetting) -> FlowyResult<()> {
+    let date_field_ids = std::iter::once(&setting.start_field_id).chain(&setting.end_field_id);
+    for field_id in date_field_ids {
+      match self.delegate.get_field(field_id).await {
+        Some(field) if FieldType::from(field.field_type) == FieldType::DateTime => {},
+        _ => {
+          return Err(FlowyError::invalid_data().with_context(format!(
+            "The timeline field:{} is not a date field",
+            field_id
+          )))
+        },
+      }
+    }
+
+    if let Some(field_id) = &setting.dependency_field_id {
+      match self.delegate.get_field(field_id).await {
+        Some(field) if FieldType::from(field.field_type) == FieldType::Relation => {},
+        _ => {
+          return Err(FlowyError::invalid_data().with_context(format!(
+            "The dependency field:{} is not a relation",
+            field_id
+          )))
+        },
+      }
+    }
+    Ok(())
+  }
+
+  pub async fn v_did_delete_field(&self, deleted_field_id: &str, deleted_field_name: &str) {
     let changeset = FilterChangeset::DeleteAllWithFieldId {
       field_id: deleted_field_id.to_string(),
+      field_name: deleted_field_name.to_string(),
     };
     let notification = self.filter_controller.apply_changeset(changeset).await;
     notify_did_update_filter(notification).await;
@@ -914,6 +1095,10 @@ impl DatabaseViewEditor {
       .calculations_controller
       .did_receive_field_deleted(deleted_field_id.to_string())
       .await;
+
+    if self.is_sub_grouping_field(deleted_field_id).await {
+      let _ = self.v_sub_group_by_field().await;
+    }
   }
 
   pub async fn v_did_update_field_type(&self, field_id: &str, new_field_type: FieldType) {
@@ -928,12 +1113,22 @@ impl DatabaseViewEditor {
       .did_receive_field_type_changed(field_id.to_owned(), new_field_type)
       .await;
     if self.filter_controller.has_filters().await {
+      let field_name = self
+        .delegate
+        .get_field(field_id)
+        .await
+        .map(|field| field.name)
+        .unwrap_or_default();
       let changeset = FilterChangeset::DeleteAllWithFieldId {
         field_id: field_id.to_string(),
+        field_name,
       };
       let notification = self.filter_controller.apply_changeset(changeset).await;
       notify_did_update_filter(notification).await;
     }
+    if self.is_sub_grouping_field(field_id).await {
+      let _ = self.v_sub_group_by_field().await;
+    }
     if self.is_grouping_field(field_id).await {
       let _ = self.v_group_by_field(field_id).await;
     }
@@ -956,12 +1151,14 @@ impl DatabaseViewEditor {
 
       // If the id of the grouping field is equal to the updated field's id
       // and something critical changed, then we need to update the group setting
-      if self.is_grouping_field(field_id).await
-        && matches!(
-          FieldType::from(field.field_type),
-          FieldType::SingleSelect | FieldType::MultiSelect
-        )
-      {
+      let is_select_field = matches!(
+        FieldType::from(field.field_type),
+        FieldType::SingleSelect | FieldType::MultiSelect
+      );
+      if is_select_field && self.is_sub_grouping_field(field_id).await {
+        self.v_sub_group_by_field().await?;
+      }
+      if is_select_field && self.is_grouping_field(field_id).await {
         self.v_group_by_field(field_id).await?;
       }
     }
@@ -984,11 +1181,10 @@ impl DatabaseViewEditor {
       .await?;
 
       if let Some(controller) = &new_group_controller {
-        let new_groups = controller
-          .get_all_groups()
-          .into_iter()
-          .map(|group| GroupPB::from(group.clone()))
-          .collect();
+        let new_groups = make_group_pbs(
+          controller.get_all_groups(),
+          self.sub_group_controller.read().await.as_deref(),
+        );
 
         let changeset = GroupChangesPB {
           view_id: self.view_id.clone(),
@@ -1012,6 +1208,34 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// Called when the second grouping field of the view is set, removed or updated. The groups are
+  /// sent again with their new sub groups.
+  #[tracing::instrument(level = "debug", skip_all, err)]
+  pub async fn v_sub_group_by_field(&self) -> FlowyResult<()> {
+    self.reload_sub_group_controller().await?;
+    if let Some(groups) = self.v_load_groups().await {
+      let changeset = GroupChangesPB {
+        view_id: self.view_id.clone(),
+        initial_groups: groups,
+        ..Default::default()
+      };
+      send_notification(&changeset.view_id, DatabaseNotification::DidGroupByField)
+        .payload(changeset)
+        .send();
+    }
+    Ok(())
+  }
+
+  async fn reload_sub_group_controller(&self) -> FlowyResult<()> {
+    *self.sub_group_controller.write().await = new_sub_group_controller(
+      self.view_id.clone(),
+      self.delegate.clone(),
+      self.filter_controller.clone(),
+    )
+    .await?;
+    Ok(())
+  }
+
   pub async fn v_get_calendar_event(&self, row_id: RowId) -> Option<CalendarEventPB> {
     let layout_ty = DatabaseLayout::Calendar;
     let calendar_setting = self.v_get_layout_settings(&layout_ty).await.calendar?;
@@ -1035,16 +1259,28 @@ impl DatabaseViewEditor {
       .timestamp;
 
     let (_, row_detail) = self.delegate.get_row_detail(&self.view_id, &row_id).await?;
+    let is_recurring = row_detail
+      .row
+      .cells
+      .get(&date_field.id)
+      .and_then(RecurrenceRule::from_cell)
+      .is_some();
 
     Some(CalendarEventPB {
       row_meta: RowMetaPB::from(row_detail.as_ref().clone()),
       date_field_id: date_field.id.clone(),
       title,
       timestamp,
+      is_recurring,
     })
   }
 
-  pub async fn v_get_all_calendar_events(&self) -> Option<Vec<CalendarEventPB>> {
+  /// Returns one event for each row. If the window is set, only the events that overlap the
+  /// window are returned and the recurring dates are expanded to their occurrences in the window.
+  pub async fn v_get_all_calendar_events(
+    &self,
+    window: Option<(i64, i64)>,
+  ) -> Option<Vec<CalendarEventPB>> {
     let layout_ty = DatabaseLayout::Calendar;
     let calendar_setting = match self.v_get_layout_settings(&layout_ty).await.calendar {
       None => {
@@ -1059,6 +1295,12 @@ impl DatabaseViewEditor {
     };
 
     let primary_field = self.delegate.get_primary_field().await?;
+    let time_zone = self
+      .delegate
+      .get_field(&calendar_setting.field_id)
+      .await
+      .and_then(|field| field.get_type_option::<DateTypeOption>(FieldType::DateTime))
+      .and_then(|type_option| time_zone_from_id(&type_option.timezone_id));
 
     let mut events: Vec<CalendarEventPB> = vec![];
 
@@ -1066,50 +1308,113 @@ impl DatabaseViewEditor {
 
     for row in rows {
       let primary_cell = get_cell_for_row(self.delegate.clone(), &primary_field.id, &row.id).await;
-      let timestamp_cell =
-        get_cell_for_row(self.delegate.clone(), &calendar_setting.field_id, &row.id).await;
-
-      let timestamp = timestamp_cell
-        .and_then(|cell| cell.into_date_field_cell_data())
-        .and_then(|cell_data| cell_data.timestamp);
+      let date_cell = row.cells.get(&calendar_setting.field_id);
+      let cell_data = date_cell.map(DateCellData::from).unwrap_or_default();
+      let recurrence = date_cell.and_then(RecurrenceRule::from_cell);
+
+      let timestamps = match (window, cell_data.timestamp) {
+        (None, timestamp) => vec![timestamp],
+        (Some(_), None) => vec![],
+        (Some((start, end)), Some(first)) => {
+          let duration = occurrence_duration(&cell_data);
+          let occurrences = match &recurrence {
+            Some(recurrence) => {
+              recurrence.occurrences_between(first, duration, start, end, time_zone)
+            },
+            None if first <= end && first + duration >= start => vec![first],
+            None => vec![],
+          };
+          occurrences.into_iter().map(Some).collect()
+        },
+      };
+      if timestamps.is_empty() {
+        continue;
+      }
 
-      let title = primary_cell
+      let title: String = primary_cell
         .and_then(|cell| cell.into_text_field_cell_data())
         .map(|cell_data| cell_data.into())
         .unwrap_or_default();
 
       let (_, row_detail) = self.delegate.get_row_detail(&self.view_id, &row.id).await?;
-      let event = CalendarEventPB {
-        row_meta: RowMetaPB::from(row_detail.as_ref().clone()),
-        date_field_id: calendar_setting.field_id.clone(),
-        title,
-        timestamp,
-      };
-
-      events.push(event);
+      for timestamp in timestamps {
+        let event = CalendarEventPB {
+          row_meta: RowMetaPB::from(row_detail.as_ref().clone()),
+          date_field_id: calendar_setting.field_id.clone(),
+          title: title.clone(),
+          timestamp,
+          is_recurring: recurrence.is_some(),
+        };
+        events.push(event);
+      }
     }
 
     Some(events)
   }
 
+  /// Returns the timeline setting of the view, None if the view isn't a timeline
+  pub async fn v_get_timeline_setting(&self) -> Option<TimelineLayoutSetting> {
+    self
+      .delegate
+      .get_layout_setting(&self.view_id, &DatabaseLayout::Grid)
+      .await
+      .and_then(TimelineLayoutSetting::from_layout_setting)
+  }
+
+  /// Returns the bars of the visible rows that overlap the window between `start` and `end`
+  pub async fn v_get_timeline_bars(&self, start: i64, end: i64) -> FlowyResult<Vec<TimelineBarPB>> {
+    let setting = self.v_get_timeline_setting().await.ok_or_else(|| {
+      FlowyError::invalid_data()
+        .with_context(format!("The view:{} is not a timeline", self.view_id))
+    })?;
+
+    let bars = self
+      .v_get_all_rows()
+      .await
+      .iter()
+      .filter_map(|row| make_timeline_bar(row, &setting, start, end))
+      .collect();
+    Ok(bars)
+  }
+
+  /// Returns the date cells to update to move or resize the bar of a row
+  pub async fn v_get_timeline_bar_changesets(
+    &self,
+    start: i64,
+    end: i64,
+  ) -> FlowyResult<Vec<(String, DateCellChangeset)>> {
+    let setting = self.v_get_timeline_setting().await.ok_or_else(|| {
+      FlowyError::invalid_data()
+        .with_context(format!("The view:{} is not a timeline", self.view_id))
+    })?;
+    Ok(make_timeline_bar_changesets(&setting, start, end))
+  }
+
   pub async fn v_get_layout_type(&self) -> DatabaseLayout {
     self.delegate.get_layout_for_view(&self.view_id).await
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn v_update_layout_type(&self, new_layout_type: DatabaseLayout) -> FlowyResult<()> {
+  pub async fn v_update_layout_type(&self, new_layout_type: DatabaseLayoutPB) -> FlowyResult<()> {
+    let database_layout = DatabaseLayout::from(new_layout_type.clone());
     self
       .delegate
-      .update_layout_type(&self.view_id, &new_layout_type)
+      .update_layout_type(&self.view_id, &database_layout)
       .await;
 
     // using the {} brackets to denote the lifetime of the resolver. Because the DatabaseLayoutDepsResolver
     // is not sync and send, so we can't pass it to the async block.
     {
-      let resolver = DatabaseLayoutDepsResolver::new(self.delegate.get_database(), new_layout_type);
-      resolver
-        .resolve_deps_when_update_layout_type(&self.view_id)
-        .await;
+      let resolver = DatabaseLayoutDepsResolver::new(self.delegate.get_database(), database_layout);
+      if new_layout_type == DatabaseLayoutPB::Timeline {
+        resolver
+          .resolve_deps_when_update_to_timeline(&self.view_id)
+          .await;
+      } else {
+        resolver
+          .resolve_deps_when_update_layout_type(&self.view_id)
+          .await;
+      }
     }
 
     // initialize the group controller if the current layout support grouping
@@ -1120,10 +1425,11 @@ impl DatabaseViewEditor {
       None,
     )
     .await?;
+    self.reload_sub_group_controller().await?;
 
     let payload = DatabaseLayoutMetaPB {
       view_id: self.view_id.clone(),
-      layout: new_layout_type.into(),
+      layout: new_layout_type,
     };
     send_notification(&self.view_id, DatabaseNotification::DidUpdateDatabaseLayout)
       .payload(payload)
@@ -1164,14 +1470,31 @@ impl DatabaseViewEditor {
   where
     F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
   {
-    let group_field_id = self
-      .group_controller
+    self.mut_controller(&self.group_controller, f).await
+  }
+
+  async fn mut_sub_group_controller<F, T>(&self, f: F) -> Option<T>
+  where
+    F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
+  {
+    self.mut_controller(&self.sub_group_controller, f).await
+  }
+
+  async fn mut_controller<F, T>(
+    &self,
+    controller: &RwLock<Option<Box<dyn GroupController>>>,
+    f: F,
+  ) -> Option<T>
+  where
+    F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
+  {
+    let group_field_id = controller
       .read()
       .await
       .as_ref()
       .map(|controller| controller.get_grouping_field_id().to_owned())?;
     let field = self.delegate.get_field(&group_field_id).await?;
-    let mut write_guard = self.group_controller.write().await;
+    let mut write_guard = controller.write().await;
     if let Some(group_controller) = &mut *write_guard {
       f(group_controller, field).ok()
     } else {
@@ -1179,6 +1502,84 @@ impl DatabaseViewEditor {
     }
   }
 
+  /// Sends all the groups with their sub groups. The changes of the sub groups are not sent one by
+  /// one because a row can be moved to another sub group without leaving its group.
+  async fn notify_did_update_sub_groups(&self) {
+    if self.sub_group_controller.read().await.is_none() {
+      return;
+    }
+    if let Some(groups) = self.v_load_groups().await {
+      send_notification(&self.view_id, DatabaseNotification::DidUpdateSubGroups)
+        .payload(RepeatedGroupPB { items: groups })
+        .send();
+    }
+  }
+
+  async fn fill_group_calculations(&self, groups: &mut [GroupPB]) {
+    for group in groups.iter_mut() {
+      let row_ids = group
+        .rows
+        .iter()
+        .map(|row| RowId::from(row.id.clone()))
+        .collect::<HashSet<_>>();
+      group.calculations = self.calculations_controller.calculate_group(&row_ids).await;
+    }
+  }
+
+  /// Updates the calculations of the groups whose rows were changed. Skipped if the view doesn't
+  /// have any calculation.
+  async fn did_update_group_rows_calculations(&self, group_ids: HashSet<String>) {
+    if !self.v_get_all_calculations().await.is_empty() {
+      self.gen_did_update_group_calculations_task(group_ids).await;
+    }
+  }
+
+  async fn did_update_calculations_of_all_groups(&self) {
+    let group_ids = match self.group_controller.read().await.as_ref() {
+      None => return,
+      Some(controller) => controller
+        .get_all_groups()
+        .into_iter()
+        .map(|group| group.id.clone())
+        .collect::<HashSet<_>>(),
+    };
+    self.gen_did_update_group_calculations_task(group_ids).await;
+  }
+
+  /// The calculations of the groups load the rows of each group, so they are evaluated in a
+  /// separate task.
+  async fn gen_did_update_group_calculations_task(&self, group_ids: HashSet<String>) {
+    let group_row_ids = match self.group_controller.read().await.as_ref() {
+      None => return,
+      Some(controller) => group_ids
+        .iter()
+        .filter_map(|group_id| controller.get_group(group_id))
+        .map(|(_, group)| {
+          let row_ids = group
+            .rows
+            .iter()
+            .map(|row| row.id.clone())
+            .collect::<HashSet<_>>();
+          (group.id, row_ids)
+        })
+        .collect::<Vec<_>>(),
+    };
+
+    let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    tokio::spawn(async move {
+      if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
+        for (group_id, row_ids) in group_row_ids {
+          let calculations = calculations_controller.calculate_group(&row_ids).await;
+          notify_did_update_group_calculations(GroupCalculationsPB {
+            group_id,
+            calculations,
+          })
+          .await;
+        }
+      }
+    });
+  }
+
   async fn gen_did_update_row_view_tasks(&self, row_id: RowId, field_id: Option<String>) {
     let weak_filter_controller = Arc::downgrade(&self.filter_controller);
     let weak_sort_controller = Arc::downgrade(&self.sort_controller);
@@ -1219,6 +1620,14 @@ impl DatabaseViewEditor {
   }
 }
 
+fn get_updated_group_ids(result: &Option<DidMoveGroupRowResult>) -> HashSet<String> {
+  result
+    .iter()
+    .flat_map(|result| result.row_changesets.iter())
+    .map(|changeset| changeset.group_id.clone())
+    .collect()
+}
+
 async fn handle_mut_group_result(view_id: &str, result: Option<DidMoveGroupRowResult>) {
   if let Some(result) = result {
     if let Some(deleted_group) = result.deleted_group {
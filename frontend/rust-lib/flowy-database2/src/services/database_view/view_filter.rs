@@ -1,6 +1,8 @@
 use async_trait::async_trait;
-use std::sync::Arc;
+use chrono::{Duration, Local, TimeZone};
+use std::sync::{Arc, Weak};
 
+use crate::entities::FilterDataPB;
 use crate::services::cell::CellCache;
 use crate::services::database_view::{
   gen_handler_id, DatabaseViewChangedNotifier, DatabaseViewOperation,
@@ -36,9 +38,31 @@ pub async fn make_filter_controller(
       handler_id,
       filter_controller.clone(),
     ));
+  tokio::spawn(observe_day_change(Arc::downgrade(&filter_controller)));
   filter_controller
 }
 
+/// Notifies the filter controller at every local midnight, so that the relative date filters,
+/// such as today or this week, are evaluated again. Stops once the controller is dropped.
+async fn observe_day_change(filter_controller: Weak<FilterController>) {
+  loop {
+    let now = Local::now();
+    let next_midnight = (now.date_naive() + Duration::days(1))
+      .and_hms_opt(0, 0, 0)
+      .and_then(|date_time| Local.from_local_datetime(&date_time).earliest())
+      .unwrap_or_else(|| now + Duration::hours(1));
+    let delay = (next_midnight - now)
+      .to_std()
+      .unwrap_or(std::time::Duration::from_secs(60));
+    tokio::time::sleep(delay).await;
+
+    match filter_controller.upgrade() {
+      None => break,
+      Some(filter_controller) => filter_controller.did_change_day().await,
+    }
+  }
+}
+
 struct DatabaseViewFilterDelegateImpl(Arc<dyn DatabaseViewOperation>);
 
 #[async_trait]
@@ -67,4 +91,12 @@ impl FilterDelegate for DatabaseViewFilterDelegateImpl {
   async fn save_filters(&self, view_id: &str, filters: &[Filter]) {
     self.0.save_filters(view_id, filters).await
   }
+
+  async fn get_related_row_ids(
+    &self,
+    database_id: &str,
+    filter: FilterDataPB,
+  ) -> Option<Vec<RowId>> {
+    self.0.get_related_row_ids(database_id, filter).await
+  }
 }
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use collab_database::fields::Field;
@@ -6,15 +7,20 @@ use collab_database::rows::{Row, RowId};
 
 use flowy_error::FlowyResult;
 
-use crate::entities::FieldType;
+use crate::entities::{FieldType, GroupPB, RowMetaPB};
 use crate::services::database_view::DatabaseViewOperation;
 use crate::services::field::RowSingleCellData;
 use crate::services::filter::FilterController;
 use crate::services::group::{
-  make_group_controller, GroupContextDelegate, GroupController, GroupControllerDelegate,
+  make_group_controller, GroupContextDelegate, GroupController, GroupControllerDelegate, GroupData,
   GroupSetting,
 };
 
+/// The group setting of the first grouping field is stored at index 0 of the view's group
+/// settings, the one of the second grouping field (the sub groups) at index 1.
+const GROUP_LEVEL: usize = 0;
+pub(crate) const SUB_GROUP_LEVEL: usize = 1;
+
 pub async fn new_group_controller(
   view_id: String,
   delegate: Arc<dyn DatabaseViewOperation>,
@@ -28,6 +34,7 @@ pub async fn new_group_controller(
   let controller_delegate = GroupControllerDelegateImpl {
     delegate: delegate.clone(),
     filter_controller: filter_controller.clone(),
+    level: GROUP_LEVEL,
   };
 
   let grouping_field = match grouping_field {
@@ -56,19 +63,98 @@ pub async fn new_group_controller(
   Ok(controller)
 }
 
+/// Returns the controller of the sub groups if the view is grouped by a second field.
+pub async fn new_sub_group_controller(
+  view_id: String,
+  delegate: Arc<dyn DatabaseViewOperation>,
+  filter_controller: Arc<FilterController>,
+) -> FlowyResult<Option<Box<dyn GroupController>>> {
+  if !delegate.get_layout_for_view(&view_id).await.is_board() {
+    return Ok(None);
+  }
+
+  let settings = delegate.get_group_setting(&view_id).await;
+  let sub_grouping_field = match settings.get(SUB_GROUP_LEVEL) {
+    Some(setting) => delegate.get_field(&setting.field_id).await,
+    None => None,
+  };
+  let controller = match sub_grouping_field {
+    Some(field) => {
+      let controller_delegate = GroupControllerDelegateImpl {
+        delegate,
+        filter_controller,
+        level: SUB_GROUP_LEVEL,
+      };
+      Some(make_group_controller(&view_id, field, controller_delegate).await?)
+    },
+    None => None,
+  };
+
+  Ok(controller)
+}
+
+/// Returns the groups of the view. Each group contains its sub groups if the view is grouped by a
+/// second field. The rows of a sub group keep the order they have in the group.
+pub(crate) fn make_group_pbs(
+  groups: Vec<&GroupData>,
+  sub_group_controller: Option<&dyn GroupController>,
+) -> Vec<GroupPB> {
+  let sub_groups = sub_group_controller
+    .map(|controller| controller.get_all_groups())
+    .unwrap_or_default();
+  let sub_group_row_ids = sub_groups
+    .iter()
+    .map(|sub_group| {
+      sub_group
+        .rows
+        .iter()
+        .map(|row| row.id.as_str())
+        .collect::<HashSet<&str>>()
+    })
+    .collect::<Vec<_>>();
+
+  groups
+    .into_iter()
+    .map(|group| {
+      let mut group_pb = GroupPB::from(group.clone());
+      group_pb.sub_groups = sub_groups
+        .iter()
+        .zip(sub_group_row_ids.iter())
+        .map(|(sub_group, row_ids)| GroupPB {
+          field_id: sub_group.field_id.clone(),
+          group_id: sub_group.id.clone(),
+          rows: group
+            .rows
+            .iter()
+            .filter(|row| row_ids.contains(row.id.as_str()))
+            .map(RowMetaPB::from)
+            .collect(),
+          is_default: sub_group.is_default,
+          is_visible: sub_group.is_visible,
+          sub_groups: vec![],
+          calculations: vec![],
+        })
+        .collect();
+      group_pb
+    })
+    .collect()
+}
+
 pub(crate) struct GroupControllerDelegateImpl {
   delegate: Arc<dyn DatabaseViewOperation>,
   filter_controller: Arc<FilterController>,
+  /// The index of the group setting used by the controller.
+  level: usize,
 }
 
 #[async_trait]
 impl GroupContextDelegate for GroupControllerDelegateImpl {
   async fn get_group_setting(&self, view_id: &str) -> Option<Arc<GroupSetting>> {
     let mut settings = self.delegate.get_group_setting(view_id).await;
-    if settings.is_empty() {
+    if settings.len() <= self.level {
       None
     } else {
-      Some(Arc::new(settings.remove(0)))
+      Some(Arc::new(settings.remove(self.level)))
     }
   }
 
@@ -12,7 +12,7 @@ use tokio::sync::RwLock as TokioRwLock;
 use flowy_error::FlowyError;
 use lib_infra::priority_task::TaskDispatcher;
 
-use crate::entities::{FieldSettingsChangesetPB, FieldType};
+use crate::entities::{FieldSettingsChangesetPB, FieldType, FilterDataPB};
 use crate::services::calculations::Calculation;
 use crate::services::field::TypeOptionCellDataHandler;
 use crate::services::field_settings::FieldSettings;
@@ -134,4 +134,16 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
   ) -> HashMap<String, FieldSettings>;
 
   async fn update_field_settings(&self, params: FieldSettingsChangesetPB);
+
+  /// Returns the ids of the rows of the related database that match the filter. Returns None if
+  /// the related database isn't opened.
+  async fn get_related_row_ids(
+    &self,
+    database_id: &str,
+    filter: FilterDataPB,
+  ) -> Option<Vec<RowId>>;
+
+  /// Returns the primary text of each row of the related database. Returns None if the related
+  /// database isn't opened.
+  async fn get_related_row_names(&self, database_id: &str) -> Option<HashMap<RowId, String>>;
 }
@@ -1,8 +1,9 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use collab_database::fields::Field;
-use collab_database::rows::Row;
+use collab_database::rows::{Row, RowId};
 use tokio::sync::RwLock;
 
 use crate::services::cell::CellCache;
@@ -80,4 +81,8 @@ impl SortDelegate for DatabaseViewSortDelegateImpl {
   async fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Vec<Field> {
     self.delegate.get_fields(view_id, field_ids).await
   }
+
+  async fn get_related_row_names(&self, database_id: &str) -> Option<HashMap<RowId, String>> {
+    self.delegate.get_related_row_names(database_id).await
+  }
 }
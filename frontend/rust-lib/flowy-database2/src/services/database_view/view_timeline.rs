@@ -0,0 +1,198 @@
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::rows::Row;
+
+use crate::entities::{RowMetaPB, TimelineBarPB};
+use crate::services::field::{DateCellChangeset, RelationCellData};
+use crate::services::setting::TimelineLayoutSetting;
+
+/// Returns the start and the end of the bar of the row. Returns None if the start date is empty.
+/// The bar lasts a single instant if the end date is empty.
+pub(crate) fn get_timeline_bar_range(
+  row: &Row,
+  setting: &TimelineLayoutSetting,
+) -> Option<(i64, i64)> {
+  let start_cell_data = row
+    .cells
+    .get(&setting.start_field_id)
+    .map(DateCellData::from)?;
+  let start = start_cell_data.timestamp?;
+  let end = match &setting.end_field_id {
+    Some(end_field_id) => row
+      .cells
+      .get(end_field_id)
+      .and_then(|cell| DateCellData::from(cell).timestamp),
+    None if start_cell_data.is_range => start_cell_data.end_timestamp,
+    None => None,
+  };
+  Some((start, end.unwrap_or(start).max(start)))
+}
+
+/// Returns the bar of the row if it overlaps the window between `start` and `end`.
+pub(crate) fn make_timeline_bar(
+  row: &Row,
+  setting: &TimelineLayoutSetting,
+  start: i64,
+  end: i64,
+) -> Option<TimelineBarPB> {
+  let (bar_start, bar_end) = get_timeline_bar_range(row, setting)?;
+  if bar_end < start || bar_start > end {
+    return None;
+  }
+
+  let dependencies = setting
+    .dependency_field_id
+    .as_ref()
+    .and_then(|field_id| row.cells.get(field_id))
+    .map(|cell| {
+      RelationCellData::from(cell)
+        .row_ids
+        .into_iter()
+        .map(|row_id| row_id.into_inner())
+        .collect()
+    })
+    .unwrap_or_default();
+
+  Some(TimelineBarPB {
+    row_meta: RowMetaPB::from(row.clone()),
+    start: bar_start,
+    end: bar_end,
+    dependencies,
+  })
+}
+
+/// Returns the changesets of the date cells that move or resize the bar. The start field holds
+/// the whole range unless the end is stored in its own field.
+pub(crate) fn make_timeline_bar_changesets(
+  setting: &TimelineLayoutSetting,
+  start: i64,
+  end: i64,
+) -> Vec<(String, DateCellChangeset)> {
+  match &setting.end_field_id {
+    Some(end_field_id) => vec![
+      (
+        setting.start_field_id.clone(),
+        DateCellChangeset {
+          timestamp: Some(start),
+          ..Default::default()
+        },
+      ),
+      (
+        end_field_id.clone(),
+        DateCellChangeset {
+          timestamp: Some(end),
+          ..Default::default()
+        },
+      ),
+    ],
+    None => vec![(
+      setting.start_field_id.clone(),
+      DateCellChangeset {
+        timestamp: Some(start),
+        end_timestamp: Some(end),
+        is_range: Some(start != end),
+        ..Default::default()
+      },
+    )],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::date_type_option::DateCellData;
+  use collab_database::rows::{Cell, Row, RowId};
+
+  use crate::services::database_view::view_timeline::{
+    get_timeline_bar_range, make_timeline_bar, make_timeline_bar_changesets,
+  };
+  use crate::services::field::RelationCellData;
+  use crate::services::setting::TimelineLayoutSetting;
+
+  fn date_cell(timestamp: Option<i64>, end_timestamp: Option<i64>) -> Cell {
+    let cell_data = DateCellData {
+      timestamp,
+      end_timestamp,
+      is_range: end_timestamp.is_some(),
+      ..Default::default()
+    };
+    Cell::from(&cell_data)
+  }
+
+  fn row_with_cells(cells: Vec<(&str, Cell)>) -> Row {
+    let mut row = Row::new(RowId::from("row".to_string()), "database");
+    for (field_id, cell) in cells {
+      row.cells.insert(field_id.to_string(), cell);
+    }
+    row
+  }
+
+  #[test]
+  fn timeline_bar_range_test() {
+    let setting = TimelineLayoutSetting::new("start".to_string());
+    let row = row_with_cells(vec![("start", date_cell(Some(100), Some(300)))]);
+    assert_eq!(get_timeline_bar_range(&row, &setting), Some((100, 300)));
+
+    let row = row_with_cells(vec![("start", date_cell(Some(100), None))]);
+    assert_eq!(get_timeline_bar_range(&row, &setting), Some((100, 100)));
+
+    let row = row_with_cells(vec![("start", date_cell(None, None))]);
+    assert_eq!(get_timeline_bar_range(&row, &setting), None);
+
+    // The end is read from the end field, an end before the start is ignored
+    let setting = TimelineLayoutSetting {
+      end_field_id: Some("end".to_string()),
+      ..TimelineLayoutSetting::new("start".to_string())
+    };
+    let row = row_with_cells(vec![
+      ("start", date_cell(Some(100), Some(300))),
+      ("end", date_cell(Some(500), None)),
+    ]);
+    assert_eq!(get_timeline_bar_range(&row, &setting), Some((100, 500)));
+    let row = row_with_cells(vec![
+      ("start", date_cell(Some(100), None)),
+      ("end", date_cell(Some(50), None)),
+    ]);
+    assert_eq!(get_timeline_bar_range(&row, &setting), Some((100, 100)));
+  }
+
+  #[test]
+  fn timeline_bar_window_test() {
+    let setting = TimelineLayoutSetting {
+      dependency_field_id: Some("depends_on".to_string()),
+      ..TimelineLayoutSetting::new("start".to_string())
+    };
+    let relation = RelationCellData {
+      row_ids: vec![RowId::from("other row".to_string())],
+    };
+    let row = row_with_cells(vec![
+      ("start", date_cell(Some(100), Some(300))),
+      ("depends_on", Cell::from(&relation)),
+    ]);
+
+    let bar = make_timeline_bar(&row, &setting, 300, 400).unwrap();
+    assert_eq!((bar.start, bar.end), (100, 300));
+    assert_eq!(bar.dependencies, vec!["other row".to_string()]);
+    assert!(make_timeline_bar(&row, &setting, 0, 100).is_some());
+    assert!(make_timeline_bar(&row, &setting, 301, 400).is_none());
+    assert!(make_timeline_bar(&row, &setting, 0, 99).is_none());
+  }
+
+  #[test]
+  fn timeline_bar_changesets_test() {
+    let setting = TimelineLayoutSetting::new("start".to_string());
+    let changesets = make_timeline_bar_changesets(&setting, 100, 200);
+    assert_eq!(changesets.len(), 1);
+    assert_eq!(changesets[0].1.timestamp, Some(100));
+    assert_eq!(changesets[0].1.end_timestamp, Some(200));
+    assert_eq!(changesets[0].1.is_range, Some(true));
+
+    let setting = TimelineLayoutSetting {
+      end_field_id: Some("end".to_string()),
+      ..setting
+    };
+    let changesets = make_timeline_bar_changesets(&setting, 100, 200);
+    assert_eq!(changesets[0].0, "start");
+    assert_eq!(changesets[0].1.timestamp, Some(100));
+    assert_eq!(changesets[1].0, "end");
+    assert_eq!(changesets[1].1.timestamp, Some(200));
+  }
+}
@@ -0,0 +1,344 @@
+use chrono::{TimeZone, Utc};
+use collab::util::AnyMapExt;
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::fields::select_type_option::SelectOptionIds;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, RowId};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::FieldType;
+use crate::services::cell::stringify_cell;
+use crate::services::field::{compile_regex, TypeOptionCellExt};
+
+/// The key of the [FieldValidation] in the type option data of the field
+const VALIDATION: &str = "validation";
+
+/// The validation rules of a field. They are stored as JSON in the type option data of the
+/// field's current type, so the other clients ignore them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldValidation {
+  #[serde(default)]
+  pub required: bool,
+  /// The cells of the field can't have the same value in two rows. The empty cells are ignored
+  /// and, unlike the other rules, the duplicated values are always rejected.
+  #[serde(default)]
+  pub unique: bool,
+  /// The minimum value of the Number fields
+  #[serde(default)]
+  pub min: Option<f64>,
+  /// The maximum value of the Number fields
+  #[serde(default)]
+  pub max: Option<f64>,
+  /// The regular expression that the text of the RichText and URL fields must match
+  #[serde(default)]
+  pub pattern: Option<String>,
+  /// The earliest timestamp of the DateTime fields
+  #[serde(default)]
+  pub min_date: Option<i64>,
+  /// The latest timestamp of the DateTime fields
+  #[serde(default)]
+  pub max_date: Option<i64>,
+  /// The maximum number of options selected in the MultiSelect fields
+  #[serde(default)]
+  pub max_selected_options: Option<u32>,
+  #[serde(default)]
+  pub severity: ValidationSeverity,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationSeverity {
+  /// The invalid cells are saved and reported
+  #[default]
+  Warning,
+  /// The invalid cells are rejected
+  Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellValidationError {
+  pub row_id: RowId,
+  pub field_id: String,
+  pub message: String,
+  pub severity: ValidationSeverity,
+}
+
+impl FieldValidation {
+  /// Returns the validation of the field, or None if the field doesn't have any rule
+  pub fn from_field(field: &Field) -> Option<Self> {
+    let type_option_data = field.get_any_type_option(FieldType::from(field.field_type))?;
+    Self::from_type_option_data(&type_option_data)
+  }
+
+  pub fn from_type_option_data(type_option_data: &TypeOptionData) -> Option<Self> {
+    let json = type_option_data.get_as::<String>(VALIDATION)?;
+    serde_json::from_str::<Self>(&json)
+      .ok()
+      .filter(|validation| !validation.is_empty())
+  }
+
+  /// Writes the validation into the type option data, or removes it if it's empty
+  pub fn write_to(&self, type_option_data: &mut TypeOptionData) {
+    if self.is_empty() {
+      type_option_data.remove(VALIDATION);
+    } else if let Ok(json) = serde_json::to_string(self) {
+      type_option_data.insert(VALIDATION.to_string(), json.into());
+    }
+  }
+
+  /// Returns true if the validation doesn't have any rule
+  pub fn is_empty(&self) -> bool {
+    !self.required
+      && !self.unique
+      && self.min.is_none()
+      && self.max.is_none()
+      && self.pattern.is_none()
+      && self.min_date.is_none()
+      && self.max_date.is_none()
+      && self.max_selected_options.is_none()
+  }
+
+  /// Returns the messages of the rules that the cell breaks. The rules that don't apply to the
+  /// field type are ignored, and the empty cells only break the `required` rule.
+  pub fn validate_cell(&self, field: &Field, cell: Option<&Cell>) -> Vec<String> {
+    let handler = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler();
+    let cell = match non_empty_cell(field, cell) {
+      None if self.required => return vec![format!("{} is required", field.name)],
+      None => return vec![],
+      Some(cell) => cell,
+    };
+
+    let mut messages = vec![];
+    match FieldType::from(field.field_type) {
+      FieldType::Number => {
+        let value = handler.and_then(|handler| handler.handle_numeric_cell(cell));
+        if let Some(value) = value {
+          if let Some(min) = self.min.filter(|min| value < *min) {
+            messages.push(format!("{} must be at least {}", field.name, min));
+          }
+          if let Some(max) = self.max.filter(|max| value > *max) {
+            messages.push(format!("{} must be at most {}", field.name, max));
+          }
+        }
+      },
+      FieldType::RichText | FieldType::URL => {
+        if let Some(pattern) = self.pattern.as_ref().filter(|pattern| !pattern.is_empty()) {
+          let text = stringify_cell(cell, field);
+          // an invalid pattern doesn't reject any text
+          let is_match = compile_regex(pattern)
+            .map(|regex| regex.is_match(&text).unwrap_or(true))
+            .unwrap_or(true);
+          if !is_match {
+            messages.push(format!("{} doesn't match {}", field.name, pattern));
+          }
+        }
+      },
+      FieldType::DateTime => {
+        let cell_data = DateCellData::from(cell);
+        let timestamps = [cell_data.timestamp, cell_data.end_timestamp];
+        if let Some(min_date) = self.min_date {
+          if timestamps
+            .iter()
+            .flatten()
+            .any(|timestamp| *timestamp < min_date)
+          {
+            messages.push(format!(
+              "{} must be on or after {}",
+              field.name,
+              format_date(min_date)
+            ));
+          }
+        }
+        if let Some(max_date) = self.max_date {
+          if timestamps
+            .iter()
+            .flatten()
+            .any(|timestamp| *timestamp > max_date)
+          {
+            messages.push(format!(
+              "{} must be on or before {}",
+              field.name,
+              format_date(max_date)
+            ));
+          }
+        }
+      },
+      FieldType::MultiSelect => {
+        let num_of_options = SelectOptionIds::from(cell).len();
+        if let Some(max) = self
+          .max_selected_options
+          .filter(|max| num_of_options > *max as usize)
+        {
+          messages.push(format!(
+            "{} can't have more than {} selected options",
+            field.name, max
+          ));
+        }
+      },
+      _ => {},
+    }
+    messages
+  }
+}
+
+/// Returns None if the cell is missing or empty
+fn non_empty_cell<'a>(field: &Field, cell: Option<&'a Cell>) -> Option<&'a Cell> {
+  let handler = TypeOptionCellExt::new(field, None).get_type_option_cell_data_handler();
+  cell.filter(|cell| {
+    handler
+      .as_ref()
+      .map_or(true, |handler| !handler.handle_is_cell_empty(cell, field))
+  })
+}
+
+/// Keeps the validation of the field when its type option is replaced by data that doesn't have
+/// one, for example when the type option is edited by a client that doesn't know the rules.
+pub fn keep_field_validation(
+  mut type_option_data: TypeOptionData,
+  old_field: &Field,
+) -> TypeOptionData {
+  if FieldValidation::from_type_option_data(&type_option_data).is_none() {
+    if let Some(validation) = FieldValidation::from_field(old_field) {
+      validation.write_to(&mut type_option_data);
+    }
+  }
+  type_option_data
+}
+
+/// Validates the cell against the validation of its field
+pub fn validate_field_cell(
+  row_id: &RowId,
+  field: &Field,
+  cell: Option<&Cell>,
+) -> Vec<CellValidationError> {
+  match FieldValidation::from_field(field) {
+    None => vec![],
+    Some(validation) => validation
+      .validate_cell(field, cell)
+      .into_iter()
+      .map(|message| CellValidationError {
+        row_id: row_id.clone(),
+        field_id: field.id.clone(),
+        message,
+        severity: validation.severity,
+      })
+      .collect(),
+  }
+}
+
+/// Validates the cells of a row against the validation of each field
+pub fn validate_row_cells(
+  row_id: &RowId,
+  cells: &Cells,
+  fields: &[Field],
+) -> Vec<CellValidationError> {
+  fields
+    .iter()
+    .flat_map(|field| validate_field_cell(row_id, field, cells.get(&field.id)))
+    .collect()
+}
+
+/// Validates the cells of a new row. The required cells are usually filled in after the row is
+/// created, so the errors of the empty cells are downgraded to warnings.
+pub fn validate_new_row_cells(
+  row_id: &RowId,
+  cells: &Cells,
+  fields: &[Field],
+) -> Vec<CellValidationError> {
+  fields
+    .iter()
+    .flat_map(|field| {
+      let cell = cells.get(&field.id);
+      let mut errors = validate_field_cell(row_id, field, cell);
+      if non_empty_cell(field, cell).is_none() {
+        for error in errors.iter_mut() {
+          error.severity = ValidationSeverity::Warning;
+        }
+      }
+      errors
+    })
+    .collect()
+}
+
+fn format_date(timestamp: i64) -> String {
+  Utc
+    .timestamp_opt(timestamp, 0)
+    .single()
+    .map(|date_time| date_time.format("%Y-%m-%d").to_string())
+    .unwrap_or_else(|| timestamp.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::Field;
+  use collab_database::rows::Cell;
+
+  use crate::entities::FieldType;
+  use crate::services::cell::insert_text_cell;
+  use crate::services::field::{
+    default_type_option_data_from_type, keep_field_validation, FieldBuilder, FieldValidation,
+  };
+
+  fn field_with_validation(field_type: FieldType, validation: &FieldValidation) -> Field {
+    let mut type_option_data = default_type_option_data_from_type(field_type);
+    validation.write_to(&mut type_option_data);
+    FieldBuilder::new(field_type, type_option_data).build()
+  }
+
+  #[test]
+  fn required_validation_test() {
+    let validation = FieldValidation {
+      required: true,
+      ..Default::default()
+    };
+    let field = field_with_validation(FieldType::RichText, &validation);
+    assert_eq!(validation.validate_cell(&field, None).len(), 1);
+    let empty = insert_text_cell("".to_string(), &field);
+    assert_eq!(validation.validate_cell(&field, Some(&empty)).len(), 1);
+    let cell = insert_text_cell("hello".to_string(), &field);
+    assert!(validation.validate_cell(&field, Some(&cell)).is_empty());
+  }
+
+  #[test]
+  fn pattern_validation_test() {
+    let validation = FieldValidation {
+      pattern: Some(r"^\d{3}-\d{4}$".to_string()),
+      ..Default::default()
+    };
+    let field = field_with_validation(FieldType::RichText, &validation);
+    let valid: Cell = insert_text_cell("555-1234".to_string(), &field);
+    let invalid: Cell = insert_text_cell("5551234".to_string(), &field);
+    assert!(validation.validate_cell(&field, Some(&valid)).is_empty());
+    assert_eq!(validation.validate_cell(&field, Some(&invalid)).len(), 1);
+    // the empty cells are valid unless the field is required
+    assert!(validation.validate_cell(&field, None).is_empty());
+  }
+
+  #[test]
+  fn validation_type_option_data_test() {
+    let validation = FieldValidation {
+      min: Some(0.0),
+      max: Some(10.0),
+      ..Default::default()
+    };
+    let field = field_with_validation(FieldType::Number, &validation);
+    assert_eq!(
+      FieldValidation::from_field(&field),
+      Some(validation.clone())
+    );
+
+    // the validation is kept when the type option is replaced
+    let type_option_data = keep_field_validation(
+      default_type_option_data_from_type(FieldType::Number),
+      &field,
+    );
+    assert_eq!(
+      FieldValidation::from_type_option_data(&type_option_data),
+      Some(validation)
+    );
+
+    // the empty validation is removed
+    let mut type_option_data = type_option_data;
+    FieldValidation::default().write_to(&mut type_option_data);
+    assert!(FieldValidation::from_type_option_data(&type_option_data).is_none());
+  }
+}
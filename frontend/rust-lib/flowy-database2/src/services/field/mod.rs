@@ -1,8 +1,10 @@
 mod field_builder;
 mod field_operation;
+mod field_validation;
 pub(crate) mod type_option_transform;
 pub mod type_options;
 
 pub use field_builder::*;
 pub use field_operation::*;
+pub use field_validation::*;
 pub use type_options::*;
@@ -1,7 +1,9 @@
 use crate::entities::FieldType;
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
-use crate::services::field::{ChecklistTypeOption, RelationTypeOption, TypeOptionTransform};
+use crate::services::field::{
+  ChecklistTypeOption, FormulaTypeOption, RelationTypeOption, RollupTypeOption, TypeOptionTransform,
+};
 use async_trait::async_trait;
 use collab_database::database::Database;
 use collab_database::fields::checkbox_type_option::CheckboxTypeOption;
@@ -134,5 +136,11 @@ fn get_type_option_transform_handler(
     FieldType::Media => {
       Box::new(MediaTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
     },
+    FieldType::Formula => {
+      Box::new(FormulaTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Rollup => {
+      Box::new(RollupTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
   }
 }
@@ -13,7 +13,7 @@ use crate::services::field::{
   TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
   TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 
 impl TypeOption for CheckboxTypeOption {
   type CellData = CheckboxCellDataPB;
@@ -97,10 +97,10 @@ impl TypeOptionCellDataCompare for CheckboxTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     let order = cell_data.is_checked.cmp(&other_cell_data.is_checked);
-    sort_condition.evaluate_order(order)
+    sort.condition.evaluate_order(order)
   }
 
   /// Compares two cell data using a specified sort condition and accounts for uninitialized cells.
@@ -113,14 +113,14 @@ impl TypeOptionCellDataCompare for CheckboxTypeOption {
     &self,
     cell_data: Option<&<Self as TypeOption>::CellData>,
     other_cell_data: Option<&<Self as TypeOption>::CellData>,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data, other_cell_data) {
       (None, Some(right_cell_data)) if right_cell_data.is_checked => {
-        sort_condition.evaluate_order(Ordering::Less)
+        sort.condition.evaluate_order(Ordering::Less)
       },
       (Some(left_cell_data), None) if left_cell_data.is_checked => {
-        sort_condition.evaluate_order(Ordering::Greater)
+        sort.condition.evaluate_order(Ordering::Greater)
       },
       _ => Ordering::Equal,
     }
@@ -1,8 +1,11 @@
-use crate::entities::{ChecklistCellDataChangesetPB, FieldType};
+use crate::entities::{ChecklistCellDataChangesetPB, ChecklistCellDataPB, FieldType};
+use crate::services::cell::CellProtobufBlobParser;
 use crate::services::field::{TypeOptionCellData, CELL_DATA};
+use bytes::Bytes;
 use collab::util::AnyMapExt;
 use collab_database::fields::select_type_option::SelectOption;
 use collab_database::rows::{new_cell_builder, Cell};
+use flowy_error::{internal_error, FlowyResult};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
@@ -80,6 +83,15 @@ impl From<ChecklistCellData> for Cell {
   }
 }
 
+pub struct ChecklistCellDataParser();
+impl CellProtobufBlobParser for ChecklistCellDataParser {
+  type Object = ChecklistCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    ChecklistCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ChecklistCellChangeset {
   pub insert_tasks: Vec<ChecklistCellInsertChangeset>,
@@ -5,7 +5,7 @@ use crate::services::field::{
   TypeOption, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use collab_database::fields::select_type_option::{SelectOption, SELECTION_IDS_SEPARATOR};
 use collab_database::fields::{TypeOptionData, TypeOptionDataBuilder};
 use collab_database::rows::Cell;
@@ -192,7 +192,7 @@ impl TypeOptionCellDataCompare for ChecklistTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
       (true, true) => Ordering::Equal,
@@ -203,7 +203,7 @@ impl TypeOptionCellDataCompare for ChecklistTypeOption {
         let right = other_cell_data.percentage_complete();
         // safe to unwrap because the two floats won't be NaN
         let order = left.partial_cmp(&right).unwrap();
-        sort_condition.evaluate_order(order)
+        sort.condition.evaluate_order(order)
       },
     }
   }
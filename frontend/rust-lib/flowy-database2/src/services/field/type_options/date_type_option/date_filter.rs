@@ -3,7 +3,7 @@ use crate::services::cell::insert_date_cell;
 use crate::services::field::TimestampCellData;
 use crate::services::filter::PreFillCellsWithFilter;
 
-use chrono::{Duration, Local, NaiveDate, TimeZone};
+use chrono::{Datelike, Days, Duration, Local, Months, NaiveDate, TimeZone};
 use collab_database::fields::date_type_option::DateCellData;
 use collab_database::fields::Field;
 use collab_database::rows::Cell;
@@ -59,12 +59,63 @@ impl DateFilterPB {
       DateFilterConditionPB::DateStartIsNotEmpty | DateFilterConditionPB::DateEndIsNotEmpty => {
         DateFilterStrategy::NotEmpty
       },
+      _ => {
+        let today = Local::now().date_naive();
+        let (start, end) = relative_date_range(&self.condition, self.num_of_days, today)?;
+        DateFilterStrategy::DateRange { start, end }
+      },
     };
 
     Some(strategy)
   }
 }
 
+/// Returns the first and the last date, both inclusive, of the relative condition. Returns `None`
+/// if the condition isn't relative or if the number of days is missing or negative.
+fn relative_date_range(
+  condition: &DateFilterConditionPB,
+  num_of_days: Option<i64>,
+  today: NaiveDate,
+) -> Option<(NaiveDate, NaiveDate)> {
+  let week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+  let month_start = today.with_day(1)?;
+  let quarter_start = month_start.with_month((today.month0() / 3) * 3 + 1)?;
+  let week = |start: NaiveDate| Some((start, start + Duration::days(6)));
+  let months =
+    |start: NaiveDate, n: u32| Some((start, start.checked_add_months(Months::new(n))?.pred_opt()?));
+  let num_of_days = || num_of_days.filter(|n| *n >= 0).map(|n| Days::new(n as u64));
+
+  match condition {
+    DateFilterConditionPB::DateStartsToday => Some((today, today)),
+    DateFilterConditionPB::DateStartsTomorrow => today.succ_opt().map(|date| (date, date)),
+    DateFilterConditionPB::DateStartsYesterday => today.pred_opt().map(|date| (date, date)),
+    DateFilterConditionPB::DateStartsThisWeek => week(week_start),
+    DateFilterConditionPB::DateStartsNextWeek => week(week_start + Duration::days(7)),
+    DateFilterConditionPB::DateStartsLastWeek => week(week_start - Duration::days(7)),
+    DateFilterConditionPB::DateStartsThisMonth => months(month_start, 1),
+    DateFilterConditionPB::DateStartsNextMonth => {
+      months(month_start.checked_add_months(Months::new(1))?, 1)
+    },
+    DateFilterConditionPB::DateStartsLastMonth => {
+      months(month_start.checked_sub_months(Months::new(1))?, 1)
+    },
+    DateFilterConditionPB::DateStartsThisQuarter => months(quarter_start, 3),
+    DateFilterConditionPB::DateStartsNextQuarter => {
+      months(quarter_start.checked_add_months(Months::new(3))?, 3)
+    },
+    DateFilterConditionPB::DateStartsLastQuarter => {
+      months(quarter_start.checked_sub_months(Months::new(3))?, 3)
+    },
+    DateFilterConditionPB::DateStartsWithinPastDays => {
+      Some((today.checked_sub_days(num_of_days()?)?, today))
+    },
+    DateFilterConditionPB::DateStartsWithinNextDays => {
+      Some((today, today.checked_add_days(num_of_days()?)?))
+    },
+    _ => None,
+  }
+}
+
 #[inline]
 fn naive_date_from_timestamp(timestamp: i64) -> Option<NaiveDate> {
   Local
@@ -80,6 +131,7 @@ enum DateFilterStrategy {
   OnOrBefore(i64),
   OnOrAfter(i64),
   DateBetween { start: i64, end: i64 },
+  DateRange { start: NaiveDate, end: NaiveDate },
   Empty,
   NotEmpty,
 }
@@ -118,6 +170,9 @@ impl DateFilterStrategy {
         let expected_end_date = naive_date_from_timestamp(end);
         cell_date >= expected_start_date && cell_date <= expected_end_date
       }),
+      DateFilterStrategy::DateRange { start, end } => cell_data
+        .and_then(naive_date_from_timestamp)
+        .is_some_and(|cell_date| cell_date >= start && cell_date <= end),
       DateFilterStrategy::Empty => match cell_data {
         None => true,
         Some(timestamp) if naive_date_from_timestamp(timestamp).is_none() => true,
@@ -172,6 +227,13 @@ impl PreFillCellsWithFilter for DateFilterPB {
       DateFilterConditionPB::DateStartsBetween | DateFilterConditionPB::DateEndsBetween => {
         self.start
       },
+      _ if self.condition.is_relative() => {
+        let today = Local::now().date_naive();
+        relative_date_range(&self.condition, self.num_of_days, today)
+          .and_then(|(start, _)| start.and_hms_opt(0, 0, 0))
+          .and_then(|date_time| Local.from_local_datetime(&date_time).earliest())
+          .map(|date_time| date_time.timestamp())
+      },
       _ => None,
     };
 
@@ -181,7 +243,9 @@ impl PreFillCellsWithFilter for DateFilterPB {
 
 #[cfg(test)]
 mod tests {
+  use super::relative_date_range;
   use crate::entities::{DateFilterConditionPB, DateFilterPB};
+  use chrono::{Local, NaiveDate, TimeZone};
   use collab_database::fields::date_type_option::DateCellData;
 
   fn to_cell_data(timestamp: Option<i64>, end_timestamp: Option<i64>) -> DateCellData {
@@ -197,6 +261,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateStartsOn,
       timestamp: Some(1668387885),
+      num_of_days: None,
       end: None,
       start: None,
     };
@@ -215,6 +280,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateStartsOn,
       timestamp: None,
+      num_of_days: None,
       end: None,
       start: None,
     };
@@ -236,6 +302,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateStartsBefore,
       timestamp: Some(1668387885),
+      num_of_days: None,
       start: None,
       end: None,
     };
@@ -256,6 +323,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateStartsOnOrBefore,
       timestamp: Some(1668387885),
+      num_of_days: None,
       start: None,
       end: None,
     };
@@ -275,6 +343,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateStartsAfter,
       timestamp: Some(1668387885),
+      num_of_days: None,
       start: None,
       end: None,
     };
@@ -298,6 +367,7 @@ mod tests {
       start: Some(1668272685), // 11/13
       end: Some(1668618285),   // 11/17
       timestamp: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible, msg) in [
@@ -318,6 +388,7 @@ mod tests {
       start: None,
       end: Some(1668618285), // 11/17
       timestamp: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible, msg) in [
@@ -341,6 +412,7 @@ mod tests {
       start: None,
       end: None,
       timestamp: None,
+      num_of_days: None,
     };
 
     for (start, end, is_visible) in [(None, None, true), (Some(123), None, false)] {
@@ -356,6 +428,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateEndsOnOrBefore,
       timestamp: Some(1668359085), // 11/14
+      num_of_days: None,
       end: None,
       start: None,
     };
@@ -379,6 +452,7 @@ mod tests {
     let filter = DateFilterPB {
       condition: DateFilterConditionPB::DateEndsOnOrBefore,
       timestamp: None,
+      num_of_days: None,
       start: None,
       end: None,
     };
@@ -397,6 +471,132 @@ mod tests {
     }
   }
 
+  #[test]
+  fn date_filter_relative_range_test() {
+    // Wednesday, May 15, 2024
+    let today = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+    let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+
+    for (condition, num_of_days, expected) in [
+      (
+        DateFilterConditionPB::DateStartsToday,
+        None,
+        Some((date(5, 15), date(5, 15))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsTomorrow,
+        None,
+        Some((date(5, 16), date(5, 16))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsYesterday,
+        None,
+        Some((date(5, 14), date(5, 14))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsThisWeek,
+        None,
+        Some((date(5, 13), date(5, 19))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsNextWeek,
+        None,
+        Some((date(5, 20), date(5, 26))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsLastWeek,
+        None,
+        Some((date(5, 6), date(5, 12))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsThisMonth,
+        None,
+        Some((date(5, 1), date(5, 31))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsNextMonth,
+        None,
+        Some((date(6, 1), date(6, 30))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsLastMonth,
+        None,
+        Some((date(4, 1), date(4, 30))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsThisQuarter,
+        None,
+        Some((date(4, 1), date(6, 30))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsNextQuarter,
+        None,
+        Some((date(7, 1), date(9, 30))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsLastQuarter,
+        None,
+        Some((date(1, 1), date(3, 31))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsWithinPastDays,
+        Some(7),
+        Some((date(5, 8), date(5, 15))),
+      ),
+      (
+        DateFilterConditionPB::DateStartsWithinNextDays,
+        Some(20),
+        Some((date(5, 15), date(6, 4))),
+      ),
+      (DateFilterConditionPB::DateStartsWithinNextDays, None, None),
+      (
+        DateFilterConditionPB::DateStartsWithinPastDays,
+        Some(-1),
+        None,
+      ),
+      (DateFilterConditionPB::DateStartsOn, None, None),
+    ] {
+      assert_eq!(
+        relative_date_range(&condition, num_of_days, today),
+        expected,
+        "{:?}",
+        condition
+      );
+    }
+  }
+
+  #[test]
+  fn date_filter_relative_test() {
+    let timestamp_of_day = |days: i64| {
+      let date = Local::now().date_naive() + chrono::Duration::days(days);
+      Local
+        .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+        .earliest()
+        .unwrap()
+        .timestamp()
+    };
+    let filter = DateFilterPB {
+      condition: DateFilterConditionPB::DateStartsWithinPastDays,
+      num_of_days: Some(3),
+      ..Default::default()
+    };
+
+    for (start, is_visible) in [
+      (Some(timestamp_of_day(0)), true),
+      (Some(timestamp_of_day(-3)), true),
+      (Some(timestamp_of_day(-4)), false),
+      (Some(timestamp_of_day(1)), false),
+      (None, false),
+    ] {
+      assert_eq!(
+        filter
+          .is_visible(&to_cell_data(start, None))
+          .unwrap_or(true),
+        is_visible
+      );
+    }
+  }
+
   // #[test]
   // fn timezoned_filter_test() {
   //   let filter = DateFilterPB {
@@ -0,0 +1,564 @@
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Tz;
+use collab::util::AnyMapExt;
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::rows::Cell;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_repr::*;
+
+use crate::services::field::DateCellChangeset;
+
+/// The key of the recurrence rule in the date cell. The rule is stored as a json string next to
+/// the [DateCellData].
+pub const RECURRENCE: &str = "recurrence";
+
+/// Stops the expansion of rules whose occurrences can't be converted to local timestamps
+const MAX_PERIODS: i64 = 100_000;
+
+/// The maximum number of periods between two occurrences
+pub const MAX_INTERVAL: u32 = 1000;
+
+/// A recurrence rule similar to the RRULE of iCalendar. The timestamp of the date cell is the
+/// start of the first occurrence, the occurrences keep the time and the duration of the first one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecurrenceRule {
+  pub frequency: RecurrenceFrequency,
+  /// The number of periods between two occurrences, e.g. 2 with a weekly frequency is every other
+  /// week.
+  #[serde(
+    default = "default_interval",
+    deserialize_with = "deserialize_interval"
+  )]
+  pub interval: u32,
+  /// The days of the week of the weekly occurrences, 0 is Monday. The day of the first occurrence
+  /// is used if it's empty. Ignored by the other frequencies.
+  #[serde(default)]
+  pub by_weekday: Vec<u32>,
+  /// The timestamp after which there is no occurrence, inclusive
+  #[serde(default)]
+  pub until: Option<i64>,
+  /// The maximum number of occurrences, the exceptions included
+  #[serde(default)]
+  pub count: Option<u32>,
+  /// The start of the occurrences that are removed from the series
+  #[serde(default)]
+  pub exceptions: Vec<i64>,
+}
+
+fn default_interval() -> u32 {
+  1
+}
+
+/// Returns the interval between 1 and [MAX_INTERVAL]
+pub fn bounded_interval(interval: u32) -> u32 {
+  interval.clamp(1, MAX_INTERVAL)
+}
+
+fn deserialize_interval<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+  u32::deserialize(deserializer).map(bounded_interval)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum RecurrenceFrequency {
+  #[default]
+  Daily = 0,
+  Weekly = 1,
+  Monthly = 2,
+  Yearly = 3,
+}
+
+impl RecurrenceRule {
+  pub fn new(frequency: RecurrenceFrequency) -> Self {
+    Self {
+      frequency,
+      interval: default_interval(),
+      by_weekday: vec![],
+      until: None,
+      count: None,
+      exceptions: vec![],
+    }
+  }
+
+  /// Returns None if the cell doesn't have a recurrence rule
+  pub fn from_cell(cell: &Cell) -> Option<Self> {
+    let s = cell.get_as::<String>(RECURRENCE)?;
+    serde_json::from_str(&s).ok()
+  }
+
+  pub fn insert_into_cell(&self, cell: &mut Cell) {
+    if let Ok(s) = serde_json::to_string(self) {
+      cell.insert(RECURRENCE.into(), s.into());
+    }
+  }
+
+  /// Returns the start of the occurrences in chronological order. The series starts at `first`,
+  /// the start of the first occurrence. The occurrences keep the wall clock time of the first one
+  /// in the time zone, or in the local time zone if there is none.
+  pub fn occurrences(&self, first: i64, time_zone: Option<Tz>) -> impl Iterator<Item = i64> + '_ {
+    let first_date_time = date_time_of_timestamp(first, time_zone);
+    let interval = bounded_interval(self.interval) as i64;
+    let mut weekdays = self
+      .by_weekday
+      .iter()
+      .copied()
+      .filter(|weekday| *weekday < 7)
+      .collect::<Vec<_>>();
+    weekdays.sort_unstable();
+    weekdays.dedup();
+
+    let frequency = self.frequency;
+    first_date_time
+      .into_iter()
+      .flat_map(move |first_date_time| {
+        let weekdays = weekdays.clone();
+        // The series stops at the first period that is out of the range of the dates
+        (0..MAX_PERIODS)
+          .map_while(move |period| {
+            let num_of_periods = period.checked_mul(interval)?;
+            match frequency {
+              RecurrenceFrequency::Daily => Duration::try_days(num_of_periods)
+                .and_then(|duration| first_date_time.checked_add_signed(duration))
+                .map(|date_time| vec![date_time]),
+              RecurrenceFrequency::Weekly => {
+                weekly_occurrences(first_date_time, &weekdays, num_of_periods)
+              },
+              RecurrenceFrequency::Monthly => Some(
+                add_months(first_date_time, num_of_periods)
+                  .into_iter()
+                  .collect(),
+              ),
+              RecurrenceFrequency::Yearly => {
+                let num_of_months = num_of_periods.checked_mul(12)?;
+                Some(
+                  add_months(first_date_time, num_of_months)
+                    .into_iter()
+                    .collect(),
+                )
+              },
+            }
+          })
+          .flatten()
+      })
+      .filter_map(move |date_time| timestamp_of_date_time(date_time, time_zone))
+      .filter(move |timestamp| *timestamp >= first)
+      .take_while(move |timestamp| self.until.map_or(true, |until| *timestamp <= until))
+      .take(self.count.map_or(usize::MAX, |count| count as usize))
+      .filter(move |timestamp| !self.exceptions.contains(timestamp))
+  }
+
+  /// Returns the start of the occurrences that overlap the window between `start` and `end`. Each
+  /// occurrence lasts `duration` seconds.
+  pub fn occurrences_between(
+    &self,
+    first: i64,
+    duration: i64,
+    start: i64,
+    end: i64,
+    time_zone: Option<Tz>,
+  ) -> Vec<i64> {
+    self
+      .occurrences(first, time_zone)
+      .take_while(|timestamp| *timestamp <= end)
+      .filter(|timestamp| timestamp + duration >= start)
+      .collect()
+  }
+
+  /// Returns the occurrence that is in progress at `now` or the next one. Returns None if the
+  /// series is over.
+  pub fn next_occurrence(
+    &self,
+    first: i64,
+    duration: i64,
+    now: i64,
+    time_zone: Option<Tz>,
+  ) -> Option<i64> {
+    self
+      .occurrences(first, time_zone)
+      .find(|timestamp| timestamp + duration >= now)
+  }
+
+  /// Returns the number of occurrences, the exceptions included, that start before `timestamp`
+  pub fn num_of_occurrences_before(
+    &self,
+    first: i64,
+    timestamp: i64,
+    time_zone: Option<Tz>,
+  ) -> u32 {
+    let rule = RecurrenceRule {
+      exceptions: vec![],
+      ..self.clone()
+    };
+    rule
+      .occurrences(first, time_zone)
+      .take_while(|occurrence| *occurrence < timestamp)
+      .count() as u32
+  }
+
+  /// Returns the rule whose dates are moved by `delta` seconds
+  pub fn shifted(&self, delta: i64) -> Self {
+    Self {
+      until: self.until.map(|until| until + delta),
+      exceptions: self
+        .exceptions
+        .iter()
+        .map(|exception| exception + delta)
+        .collect(),
+      ..self.clone()
+    }
+  }
+
+  /// Splits the series at `occurrence`. Returns the rule of the occurrences before it and the rule
+  /// of the series that starts at `occurrence`.
+  pub fn split_at(&self, first: i64, occurrence: i64, time_zone: Option<Tz>) -> (Self, Self) {
+    let num_of_occurrences_before = self.num_of_occurrences_before(first, occurrence, time_zone);
+    let (exceptions_before, exceptions_after): (Vec<i64>, Vec<i64>) = self
+      .exceptions
+      .iter()
+      .partition(|exception| **exception < occurrence);
+    let before = Self {
+      until: Some(occurrence - 1),
+      count: None,
+      exceptions: exceptions_before,
+      ..self.clone()
+    };
+    let after = Self {
+      count: self
+        .count
+        .map(|count| count.saturating_sub(num_of_occurrences_before)),
+      exceptions: exceptions_after,
+      ..self.clone()
+    };
+    (before, after)
+  }
+}
+
+/// Returns the cell data of the occurrence that starts at `timestamp`. The end is moved by the same
+/// amount as the start.
+pub fn date_cell_data_of_occurrence(cell_data: &DateCellData, timestamp: i64) -> DateCellData {
+  let end_timestamp = match (cell_data.timestamp, cell_data.end_timestamp) {
+    (Some(first), Some(end)) => Some(end - first + timestamp),
+    _ => None,
+  };
+  DateCellData {
+    timestamp: Some(timestamp),
+    end_timestamp,
+    ..cell_data.clone()
+  }
+}
+
+/// Returns the changeset that moves the date to `timestamp`. The end of a range is moved by the
+/// same amount as the start.
+pub fn moved_date_changeset(
+  cell_data: &DateCellData,
+  timestamp: i64,
+  recurrence: Option<RecurrenceRule>,
+) -> DateCellChangeset {
+  let end_timestamp = match (cell_data.timestamp, cell_data.end_timestamp) {
+    (Some(start), Some(end)) if cell_data.is_range => Some(end - start + timestamp),
+    _ => None,
+  };
+  DateCellChangeset {
+    timestamp: Some(timestamp),
+    end_timestamp,
+    recurrence,
+    ..Default::default()
+  }
+}
+
+/// Returns the duration of the occurrences in seconds
+pub fn occurrence_duration(cell_data: &DateCellData) -> i64 {
+  match (cell_data.timestamp, cell_data.end_timestamp) {
+    (Some(start), Some(end)) if cell_data.is_range => (end - start).max(0),
+    _ => 0,
+  }
+}
+
+/// Returns None if the week is out of the range of the dates
+fn weekly_occurrences(
+  first_date_time: NaiveDateTime,
+  weekdays: &[u32],
+  num_of_weeks: i64,
+) -> Option<Vec<NaiveDateTime>> {
+  let add_days = |date_time: NaiveDateTime, num_of_days: i64| {
+    date_time.checked_add_signed(Duration::try_days(num_of_days)?)
+  };
+  let first_weekday = first_date_time.weekday().num_days_from_monday() as i64;
+  let week_start = add_days(first_date_time, -first_weekday)?;
+  let week_start = week_start.checked_add_signed(Duration::try_weeks(num_of_weeks)?)?;
+  if weekdays.is_empty() {
+    return add_days(week_start, first_weekday).map(|date_time| vec![date_time]);
+  }
+  weekdays
+    .iter()
+    .map(|weekday| add_days(week_start, *weekday as i64))
+    .collect()
+}
+
+/// Returns the time zone of the IANA id, such as `Europe/Paris`. The ids that aren't IANA names,
+/// such as UTC offsets, return None.
+pub(crate) fn time_zone_from_id(id: &str) -> Option<Tz> {
+  id.trim().trim_start_matches('/').parse::<Tz>().ok()
+}
+
+/// Returns the wall clock time of the timestamp in the time zone, or in the local time zone if
+/// there is none.
+pub(crate) fn date_time_of_timestamp(
+  timestamp: i64,
+  time_zone: Option<Tz>,
+) -> Option<NaiveDateTime> {
+  match time_zone {
+    Some(time_zone) => time_zone
+      .timestamp_opt(timestamp, 0)
+      .single()
+      .map(|date_time| date_time.naive_local()),
+    None => Local
+      .timestamp_opt(timestamp, 0)
+      .single()
+      .map(|date_time| date_time.naive_local()),
+  }
+}
+
+/// Returns the timestamp of the wall clock time in the time zone, or in the local time zone if
+/// there is none. The earliest time is used when the wall clock time is ambiguous.
+pub(crate) fn timestamp_of_date_time(
+  date_time: NaiveDateTime,
+  time_zone: Option<Tz>,
+) -> Option<i64> {
+  match time_zone {
+    Some(time_zone) => time_zone
+      .from_local_datetime(&date_time)
+      .earliest()
+      .map(|date_time| date_time.timestamp()),
+    None => Local
+      .from_local_datetime(&date_time)
+      .earliest()
+      .map(|date_time| date_time.timestamp()),
+  }
+}
+
+/// Returns None if the day of the month doesn't exist in the new month, e.g. the 31st of April.
+/// Those months are skipped like in iCalendar.
+fn add_months(date_time: NaiveDateTime, num_of_months: i64) -> Option<NaiveDateTime> {
+  let months = date_time.year() as i64 * 12 + date_time.month0() as i64 + num_of_months;
+  let year = i32::try_from(months.div_euclid(12)).ok()?;
+  let month = months.rem_euclid(12) as u32 + 1;
+  NaiveDate::from_ymd_opt(year, month, date_time.day()).map(|date| date.and_time(date_time.time()))
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{Local, NaiveDate, TimeZone};
+  use chrono_tz::Tz;
+
+  use crate::services::field::{RecurrenceFrequency, RecurrenceRule, MAX_INTERVAL};
+
+  fn local_timestamp(year: i32, month: u32, day: u32) -> i64 {
+    let date_time = NaiveDate::from_ymd_opt(year, month, day)
+      .unwrap()
+      .and_hms_opt(10, 0, 0)
+      .unwrap();
+    Local
+      .from_local_datetime(&date_time)
+      .earliest()
+      .unwrap()
+      .timestamp()
+  }
+
+  #[test]
+  fn daily_recurrence_test() {
+    let first = local_timestamp(2024, 1, 30);
+    let rule = RecurrenceRule {
+      interval: 2,
+      count: Some(3),
+      ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+    };
+    assert_eq!(
+      rule.occurrences(first, None).collect::<Vec<_>>(),
+      vec![
+        first,
+        local_timestamp(2024, 2, 1),
+        local_timestamp(2024, 2, 3)
+      ]
+    );
+  }
+
+  #[test]
+  fn weekly_recurrence_test() {
+    // 2024-01-03 is a Wednesday
+    let first = local_timestamp(2024, 1, 3);
+    let rule = RecurrenceRule {
+      by_weekday: vec![0, 2],
+      until: Some(local_timestamp(2024, 1, 15)),
+      ..RecurrenceRule::new(RecurrenceFrequency::Weekly)
+    };
+    assert_eq!(
+      rule.occurrences(first, None).collect::<Vec<_>>(),
+      vec![
+        first,
+        local_timestamp(2024, 1, 8),
+        local_timestamp(2024, 1, 10),
+        local_timestamp(2024, 1, 15)
+      ]
+    );
+
+    // The day of the first occurrence is used without weekdays
+    let rule = RecurrenceRule::new(RecurrenceFrequency::Weekly);
+    assert_eq!(
+      rule.occurrences(first, None).nth(1),
+      Some(local_timestamp(2024, 1, 10))
+    );
+  }
+
+  #[test]
+  fn monthly_and_yearly_recurrence_test() {
+    let first = local_timestamp(2024, 1, 31);
+    let rule = RecurrenceRule::new(RecurrenceFrequency::Monthly);
+    assert_eq!(
+      rule.occurrences(first, None).take(3).collect::<Vec<_>>(),
+      vec![
+        first,
+        local_timestamp(2024, 3, 31),
+        local_timestamp(2024, 5, 31)
+      ]
+    );
+
+    let first = local_timestamp(2024, 2, 29);
+    let rule = RecurrenceRule::new(RecurrenceFrequency::Yearly);
+    assert_eq!(
+      rule.occurrences(first, None).nth(1),
+      Some(local_timestamp(2028, 2, 29))
+    );
+  }
+
+  #[test]
+  fn recurrence_exceptions_test() {
+    let first = local_timestamp(2024, 1, 1);
+    let rule = RecurrenceRule {
+      count: Some(3),
+      exceptions: vec![local_timestamp(2024, 1, 2)],
+      ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+    };
+    assert_eq!(
+      rule.occurrences(first, None).collect::<Vec<_>>(),
+      vec![first, local_timestamp(2024, 1, 3)]
+    );
+    assert_eq!(
+      rule.num_of_occurrences_before(first, local_timestamp(2024, 1, 3), None),
+      2
+    );
+  }
+
+  #[test]
+  fn split_recurrence_test() {
+    let first = local_timestamp(2024, 1, 1);
+    let rule = RecurrenceRule {
+      count: Some(5),
+      exceptions: vec![local_timestamp(2024, 1, 2), local_timestamp(2024, 1, 4)],
+      ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+    };
+    let occurrence = local_timestamp(2024, 1, 3);
+    let (before, after) = rule.split_at(first, occurrence, None);
+    assert_eq!(
+      before.occurrences(first, None).collect::<Vec<_>>(),
+      vec![first]
+    );
+    assert_eq!(
+      after.occurrences(occurrence, None).collect::<Vec<_>>(),
+      vec![occurrence, local_timestamp(2024, 1, 5)]
+    );
+
+    // Moving the series keeps the exceptions
+    let delta = 3600;
+    let moved = after.shifted(delta);
+    assert_eq!(
+      moved
+        .occurrences(occurrence + delta, None)
+        .collect::<Vec<_>>(),
+      vec![occurrence + delta, local_timestamp(2024, 1, 5) + delta]
+    );
+  }
+
+  #[test]
+  fn occurrences_in_window_test() {
+    let first = local_timestamp(2024, 1, 1);
+    let rule = RecurrenceRule::new(RecurrenceFrequency::Weekly);
+    let occurrences = rule.occurrences_between(
+      first,
+      3600,
+      local_timestamp(2024, 1, 10),
+      local_timestamp(2024, 1, 31),
+      None,
+    );
+    assert_eq!(
+      occurrences,
+      vec![
+        local_timestamp(2024, 1, 15),
+        local_timestamp(2024, 1, 22),
+        local_timestamp(2024, 1, 29)
+      ]
+    );
+
+    // The occurrence in progress is the next one
+    assert_eq!(
+      rule.next_occurrence(first, 3600, local_timestamp(2024, 1, 8) + 60, None),
+      Some(local_timestamp(2024, 1, 8))
+    );
+    let rule = RecurrenceRule {
+      count: Some(2),
+      ..rule
+    };
+    assert_eq!(
+      rule.next_occurrence(first, 3600, local_timestamp(2024, 2, 1), None),
+      None
+    );
+  }
+
+  #[test]
+  fn recurrence_time_zone_test() {
+    // The occurrences keep the wall clock time in Paris across the daylight saving time change
+    let paris = "Europe/Paris".parse::<Tz>().unwrap();
+    let paris_timestamp = |day: u32| {
+      paris
+        .with_ymd_and_hms(2024, 3, day, 10, 0, 0)
+        .unwrap()
+        .timestamp()
+    };
+    let rule = RecurrenceRule {
+      count: Some(2),
+      ..RecurrenceRule::new(RecurrenceFrequency::Weekly)
+    };
+    assert_eq!(
+      rule
+        .occurrences(paris_timestamp(25), Some(paris))
+        .collect::<Vec<_>>(),
+      vec![
+        paris_timestamp(25),
+        paris_timestamp(25) + 7 * 24 * 3600 - 3600
+      ]
+    );
+  }
+
+  #[test]
+  fn recurrence_out_of_range_test() {
+    // The series stops instead of overflowing the dates
+    let first = local_timestamp(2024, 1, 1);
+    for frequency in [
+      RecurrenceFrequency::Daily,
+      RecurrenceFrequency::Weekly,
+      RecurrenceFrequency::Monthly,
+      RecurrenceFrequency::Yearly,
+    ] {
+      let rule = RecurrenceRule {
+        interval: u32::MAX,
+        ..RecurrenceRule::new(frequency)
+      };
+      assert!(rule.occurrences(first, None).count() > 1);
+    }
+
+    // The interval is bounded when the rule is parsed
+    let rule: RecurrenceRule =
+      serde_json::from_str(r#"{"frequency":0,"interval":4294967295}"#).unwrap();
+    assert_eq!(rule.interval, MAX_INTERVAL);
+  }
+}
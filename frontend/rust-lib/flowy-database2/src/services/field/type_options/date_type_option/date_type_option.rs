@@ -8,15 +8,17 @@ use collab_database::fields::{Field, TypeOptionData};
 use collab_database::rows::Cell;
 use collab_database::template::date_parse::cast_string_to_timestamp;
 use flowy_error::FlowyResult;
+use lib_infra::util::timestamp;
 use tracing::info;
 
 use crate::entities::{DateCellDataPB, DateFilterPB, FieldType};
 use crate::services::cell::{CellDataChangeset, CellDataDecoder};
 use crate::services::field::{
-  default_order, DateCellChangeset, TypeOption, TypeOptionCellDataCompare,
+  date_cell_data_of_occurrence, default_order, occurrence_duration, time_zone_from_id,
+  DateCellChangeset, RecurrenceRule, TypeOption, TypeOptionCellDataCompare,
   TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform, CELL_DATA,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 
 impl TypeOption for DateTypeOption {
   type CellData = DateCellData;
@@ -48,9 +50,20 @@ impl TypeOptionCellDataSerde for DateTypeOption {
       include_time,
       is_range,
       reminder_id,
+      recurrence: None,
     }
   }
 
+  fn protobuf_encode_cell(
+    &self,
+    cell: &Cell,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    let mut cell_data_pb = self.protobuf_encode(cell_data);
+    cell_data_pb.recurrence = RecurrenceRule::from_cell(cell).map(Into::into);
+    cell_data_pb
+  }
+
   fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
     Ok(DateCellData::from(cell))
   }
@@ -164,9 +177,14 @@ impl CellDataChangeset for DateTypeOption {
     }
 
     // old date cell data
-    let cell_data = match cell {
-      Some(cell) => DateCellData::from(&cell),
-      None => DateCellData::default(),
+    let (cell_data, recurrence) = match cell {
+      Some(cell) => (DateCellData::from(&cell), RecurrenceRule::from_cell(&cell)),
+      None => (DateCellData::default(), None),
+    };
+    let recurrence = if let Some(true) = changeset.clear_recurrence {
+      None
+    } else {
+      changeset.recurrence.or(recurrence)
     };
 
     let is_range = changeset.is_range.unwrap_or(cell_data.is_range);
@@ -177,7 +195,7 @@ impl CellDataChangeset for DateTypeOption {
     let missing_timestamp = is_range && has_timestamp != has_end_timestamp;
 
     if unexpected_end_changeset || missing_timestamp {
-      return Ok((Cell::from(&cell_data), cell_data));
+      return Ok((make_date_cell(&cell_data, recurrence.as_ref()), cell_data));
     }
 
     let DateCellData {
@@ -207,10 +225,19 @@ impl CellDataChangeset for DateTypeOption {
       reminder_id,
     };
 
-    Ok((Cell::from(&cell_data), cell_data))
+    Ok((make_date_cell(&cell_data, recurrence.as_ref()), cell_data))
   }
 }
 
+/// The recurrence rule is only kept if the cell has a date
+fn make_date_cell(cell_data: &DateCellData, recurrence: Option<&RecurrenceRule>) -> Cell {
+  let mut cell = Cell::from(cell_data);
+  if let (Some(recurrence), Some(_)) = (recurrence, cell_data.timestamp) {
+    recurrence.insert_into_cell(&mut cell);
+  }
+  cell
+}
+
 impl TypeOptionCellDataFilter for DateTypeOption {
   fn apply_filter(
     &self,
@@ -219,6 +246,32 @@ impl TypeOptionCellDataFilter for DateTypeOption {
   ) -> bool {
     filter.is_visible(cell_data).unwrap_or(true)
   }
+
+  /// The recurring dates are filtered by their occurrence in progress or their next occurrence.
+  /// The first occurrence is used when the series is over.
+  fn apply_filter_to_cell(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell: &Cell,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    let next_occurrence = match (RecurrenceRule::from_cell(cell), cell_data.timestamp) {
+      (Some(recurrence), Some(first)) => recurrence.next_occurrence(
+        first,
+        occurrence_duration(cell_data),
+        timestamp(),
+        time_zone_from_id(&self.timezone_id),
+      ),
+      _ => None,
+    };
+    match next_occurrence {
+      Some(next_occurrence) => {
+        let cell_data = date_cell_data_of_occurrence(cell_data, next_occurrence);
+        self.apply_filter(filter, &cell_data)
+      },
+      None => self.apply_filter(filter, cell_data),
+    }
+  }
 }
 
 impl TypeOptionCellDataCompare for DateTypeOption {
@@ -226,12 +279,12 @@ impl TypeOptionCellDataCompare for DateTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data.timestamp, other_cell_data.timestamp) {
       (Some(left), Some(right)) => {
         let order = left.cmp(&right);
-        sort_condition.evaluate_order(order)
+        sort.condition.evaluate_order(order)
       },
       (Some(_), None) => Ordering::Less,
       (None, Some(_)) => Ordering::Greater,
@@ -6,7 +6,7 @@ use flowy_error::{internal_error, FlowyResult};
 
 use crate::entities::DateCellDataPB;
 use crate::services::cell::CellProtobufBlobParser;
-use crate::services::field::TypeOptionCellData;
+use crate::services::field::{RecurrenceRule, TypeOptionCellData};
 
 #[derive(Clone, Debug, Default)]
 pub struct DateCellChangeset {
@@ -16,6 +16,8 @@ pub struct DateCellChangeset {
   pub is_range: Option<bool>,
   pub clear_flag: Option<bool>,
   pub reminder_id: Option<String>,
+  pub recurrence: Option<RecurrenceRule>,
+  pub clear_recurrence: Option<bool>,
 }
 
 impl TypeOptionCellData for DateCellData {
@@ -0,0 +1,211 @@
+use crate::entities::{CheckboxCellDataPB, FieldType, TextFilterPB};
+use crate::services::cell::{stringify_cell, CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  parse_formula, ComputedCellData, FormulaContext, FormulaExpr, FormulaValue, TypeOption,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionCellExt,
+  TypeOptionTransform,
+};
+use crate::services::sort::Sort;
+use collab::util::AnyMapExt;
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{Cell, Row};
+use flowy_error::FlowyResult;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// A computed field. The `expression` is evaluated against the other fields of the same row,
+/// which are referenced by wrapping their name or id in braces, e.g. `{Price} * {Quantity}`.
+#[derive(Default, Debug, Clone)]
+pub struct FormulaTypeOption {
+  pub expression: String,
+}
+
+impl From<TypeOptionData> for FormulaTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let expression: String = value.get_as("expression").unwrap_or_default();
+    Self { expression }
+  }
+}
+
+impl From<FormulaTypeOption> for TypeOptionData {
+  fn from(value: FormulaTypeOption) -> Self {
+    TypeOptionDataBuilder::from([("expression".into(), value.expression.into())])
+  }
+}
+
+impl FormulaTypeOption {
+  /// Parses the expression, so that it can be evaluated against many rows
+  pub fn parse(&self) -> ParsedFormula {
+    let expr = parse_formula(&self.expression);
+    let references = expr
+      .as_ref()
+      .map(|expr| expr.field_references().into_iter().collect())
+      .unwrap_or_default();
+    ParsedFormula {
+      expression: self.expression.clone(),
+      expr,
+      references,
+    }
+  }
+
+  /// Returns true if the formula reads the given field. Invalid formulas never depend on any field.
+  pub fn depends_on(&self, field: &Field) -> bool {
+    self.parse().depends_on(field)
+  }
+}
+
+/// A formula whose expression is parsed once
+pub struct ParsedFormula {
+  expression: String,
+  expr: FlowyResult<FormulaExpr>,
+  /// The names or ids of the fields referenced by the expression
+  references: HashSet<String>,
+}
+
+impl ParsedFormula {
+  /// Returns true if the formula reads the given field. Invalid formulas never depend on any field.
+  pub fn depends_on(&self, field: &Field) -> bool {
+    self.references.contains(&field.id) || self.references.contains(&field.name)
+  }
+
+  /// Evaluates the formula against the given row. An empty cell is returned if the formula
+  /// can't be evaluated.
+  pub fn evaluate_row(&self, row: &Row, fields: &[Field]) -> ComputedCellData {
+    let ctx = RowFormulaContext { row, fields };
+    match self.expr.as_ref().map(|expr| expr.evaluate(&ctx)) {
+      Ok(Ok(value)) => ComputedCellData(value.to_string()),
+      Ok(Err(err)) | Err(err) => {
+        tracing::trace!("[Formula]: failed to evaluate {}: {}", self.expression, err);
+        ComputedCellData::default()
+      },
+    }
+  }
+}
+
+struct RowFormulaContext<'a> {
+  row: &'a Row,
+  fields: &'a [Field],
+}
+
+impl FormulaContext for RowFormulaContext<'_> {
+  fn field_value(&self, field_name_or_id: &str) -> Option<FormulaValue> {
+    let field = self
+      .fields
+      .iter()
+      .find(|field| field.id == field_name_or_id)
+      .or_else(|| {
+        self
+          .fields
+          .iter()
+          .find(|field| field.name == field_name_or_id)
+      })?;
+    Some(formula_value_from_row(self.row, field))
+  }
+}
+
+/// Reads the value of the field from the row in the form that formulas and rollups work with.
+pub(crate) fn formula_value_from_row(row: &Row, field: &Field) -> FormulaValue {
+  let field_type = FieldType::from(field.field_type);
+  match field_type {
+    FieldType::CreatedTime => FormulaValue::Date(row.created_at),
+    FieldType::LastEditedTime => FormulaValue::Date(row.modified_at),
+    _ => match row.cells.get(&field.id) {
+      None => FormulaValue::Empty,
+      Some(cell) => formula_value_from_cell(cell, field, field_type),
+    },
+  }
+}
+
+fn formula_value_from_cell(cell: &Cell, field: &Field, field_type: FieldType) -> FormulaValue {
+  match field_type {
+    FieldType::Number | FieldType::Time => TypeOptionCellExt::new(field, None)
+      .get_type_option_cell_data_handler()
+      .and_then(|handler| handler.handle_numeric_cell(cell))
+      .map(FormulaValue::Number)
+      .unwrap_or(FormulaValue::Empty),
+    FieldType::Checkbox => FormulaValue::Bool(CheckboxCellDataPB::from(cell).is_checked),
+    FieldType::DateTime => DateCellData::from(cell)
+      .timestamp
+      .map(FormulaValue::Date)
+      .unwrap_or(FormulaValue::Empty),
+    FieldType::Formula | FieldType::Rollup => ComputedCellData::from(cell).formula_value(),
+    _ => {
+      let s = stringify_cell(cell, field);
+      if s.is_empty() {
+        FormulaValue::Empty
+      } else {
+        FormulaValue::Text(s)
+      }
+    },
+  }
+}
+
+impl TypeOption for FormulaTypeOption {
+  type CellData = ComputedCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+impl CellDataChangeset for FormulaTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: String,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, ComputedCellData)> {
+    let cell_data = ComputedCellData(changeset);
+    Ok((cell_data.clone().into_cell(FieldType::Formula), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for FormulaTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for FormulaTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort: &Sort,
+  ) -> Ordering {
+    cell_data.cmp_cell_data(other_cell_data, sort)
+  }
+}
+
+impl CellDataDecoder for FormulaTypeOption {
+  fn decode_cell(&self, cell: &Cell) -> FlowyResult<ComputedCellData> {
+    Ok(ComputedCellData::from(cell))
+  }
+
+  fn stringify_cell_data(&self, cell_data: ComputedCellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    ComputedCellData::from(cell).numeric_value()
+  }
+}
+
+impl TypeOptionTransform for FormulaTypeOption {}
+
+impl TypeOptionCellDataSerde for FormulaTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.0)
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(ComputedCellData::from(cell))
+  }
+}
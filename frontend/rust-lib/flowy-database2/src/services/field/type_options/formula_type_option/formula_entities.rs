@@ -0,0 +1,87 @@
+use crate::entities::FieldType;
+use crate::services::field::{FormulaValue, TypeOptionCellData, CELL_DATA};
+use crate::services::sort::Sort;
+use collab::util::AnyMapExt;
+use collab_database::rows::{new_cell_builder, Cell};
+use std::cmp::Ordering;
+
+/// The value of a computed cell, i.e. the evaluated result of a formula or the aggregated value
+/// of a rollup. The result is stored in the cell, so that it only needs to be re-calculated when
+/// one of its source cells changes.
+#[derive(Default, Debug, Clone)]
+pub struct ComputedCellData(pub String);
+impl std::ops::Deref for ComputedCellData {
+  type Target = String;
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
+
+impl ComputedCellData {
+  pub fn numeric_value(&self) -> Option<f64> {
+    self.0.parse::<f64>().ok()
+  }
+
+  /// Returns the value in the form that formulas and rollups read from other cells. The dates,
+  /// e.g. the result of an `Earliest` rollup, are stored formatted, so they are parsed back.
+  pub fn formula_value(&self) -> FormulaValue {
+    if self.is_cell_empty() {
+      return FormulaValue::Empty;
+    }
+    match self.numeric_value() {
+      Some(value) => FormulaValue::Number(value),
+      None => match FormulaValue::parse_date(&self.0) {
+        Some(timestamp) => FormulaValue::Date(timestamp),
+        None => FormulaValue::Text(self.0.clone()),
+      },
+    }
+  }
+
+  /// Builds the cell of the computed field whose type is `field_type`
+  pub fn into_cell(self, field_type: FieldType) -> Cell {
+    let mut cell = new_cell_builder(field_type);
+    cell.insert(CELL_DATA.into(), self.0.into());
+    cell
+  }
+
+  /// Empty cells are sorted last. Numbers are compared by value, anything else as text.
+  pub fn cmp_cell_data(&self, other: &Self, sort: &Sort) -> Ordering {
+    match (self.is_cell_empty(), other.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = match (self.numeric_value(), other.numeric_value()) {
+          (Some(left), Some(right)) => left.total_cmp(&right),
+          _ => sort.cmp_text(&self.0, &other.0),
+        };
+        sort.condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+impl TypeOptionCellData for ComputedCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl From<&Cell> for ComputedCellData {
+  fn from(cell: &Cell) -> Self {
+    Self(cell.get_as::<String>(CELL_DATA).unwrap_or_default())
+  }
+}
+
+impl ToString for ComputedCellData {
+  fn to_string(&self) -> String {
+    self.0.clone()
+  }
+}
+
+impl AsRef<str> for ComputedCellData {
+  fn as_ref(&self) -> &str {
+    &self.0
+  }
+}
@@ -0,0 +1,756 @@
+use chrono::{DateTime, Duration, Months, NaiveDate, NaiveDateTime, Utc};
+use flowy_error::{FlowyError, FlowyResult};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The value produced by evaluating a formula, or by reading a referenced field of the row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaValue {
+  Empty,
+  Number(f64),
+  Text(String),
+  Bool(bool),
+  /// Unix timestamp in seconds
+  Date(i64),
+}
+
+impl FormulaValue {
+  pub fn is_empty(&self) -> bool {
+    match self {
+      FormulaValue::Empty => true,
+      FormulaValue::Text(s) => s.is_empty(),
+      _ => false,
+    }
+  }
+
+  fn as_number(&self) -> FlowyResult<f64> {
+    match self {
+      FormulaValue::Empty => Ok(0.0),
+      FormulaValue::Number(n) => Ok(*n),
+      FormulaValue::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+      FormulaValue::Date(ts) => Ok(*ts as f64),
+      FormulaValue::Text(s) => s
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| FlowyError::invalid_formula().with_context(format!("{} is not a number", s))),
+    }
+  }
+
+  pub(crate) fn as_bool(&self) -> bool {
+    match self {
+      FormulaValue::Empty => false,
+      FormulaValue::Number(n) => *n != 0.0,
+      FormulaValue::Text(s) => !s.is_empty() && s != "false",
+      FormulaValue::Bool(b) => *b,
+      FormulaValue::Date(_) => true,
+    }
+  }
+
+  fn as_date(&self) -> FlowyResult<i64> {
+    match self {
+      FormulaValue::Date(ts) => Ok(*ts),
+      FormulaValue::Number(n) => Ok(*n as i64),
+      _ => Err(FlowyError::invalid_formula().with_context(format!("{} is not a date", self))),
+    }
+  }
+
+  /// Parses a date formatted by [FormulaValue::Date]'s `Display`, e.g. `2024-01-01` or
+  /// `2024-01-01 09:30`. Returns the timestamp in seconds.
+  pub fn parse_date(s: &str) -> Option<i64> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+      .ok()
+      .or_else(|| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+          .ok()
+          .and_then(|date| date.and_hms_opt(0, 0, 0))
+      })
+      .map(|date| date.and_utc().timestamp())
+  }
+}
+
+impl Display for FormulaValue {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FormulaValue::Empty => Ok(()),
+      FormulaValue::Number(n) => {
+        if n.fract() == 0.0 && n.abs() < 1e15 {
+          write!(f, "{}", *n as i64)
+        } else {
+          write!(f, "{}", n)
+        }
+      },
+      FormulaValue::Text(s) => write!(f, "{}", s),
+      FormulaValue::Bool(b) => write!(f, "{}", b),
+      FormulaValue::Date(ts) => match DateTime::<Utc>::from_timestamp(*ts, 0) {
+        Some(date) if *ts % SECONDS_PER_DAY == 0 => write!(f, "{}", date.format("%Y-%m-%d")),
+        Some(date) => write!(f, "{}", date.format("%Y-%m-%d %H:%M")),
+        None => Ok(()),
+      },
+    }
+  }
+}
+
+/// Resolves the values of the fields referenced by a formula, e.g. `{Estimate}`.
+pub trait FormulaContext {
+  /// Returns the value of the field with the given name or id. Returns `None` if the field
+  /// doesn't exist.
+  fn field_value(&self, field_name_or_id: &str) -> Option<FormulaValue>;
+
+  /// The current time in seconds, used by `now()` and `today()`.
+  fn now(&self) -> i64 {
+    Utc::now().timestamp()
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Number(f64),
+  Text(String),
+  Field(String),
+  Ident(String),
+  Op(&'static str),
+  LParen,
+  RParen,
+  Comma,
+}
+
+fn tokenize(expression: &str) -> FlowyResult<Vec<Token>> {
+  let chars = expression.chars().collect::<Vec<char>>();
+  let mut tokens = vec![];
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    match c {
+      c if c.is_whitespace() => i += 1,
+      '0'..='9' | '.' => {
+        let start = i;
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+          i += 1;
+        }
+        let s = chars[start..i].iter().collect::<String>();
+        let n = s.parse::<f64>().map_err(|_| {
+          FlowyError::invalid_formula().with_context(format!("invalid number {}", s))
+        })?;
+        tokens.push(Token::Number(n));
+      },
+      '"' | '\'' => {
+        let quote = c;
+        let mut s = String::new();
+        i += 1;
+        loop {
+          match chars.get(i) {
+            None => return Err(FlowyError::invalid_formula().with_context("unterminated string")),
+            Some('\\') if i + 1 < chars.len() => {
+              s.push(chars[i + 1]);
+              i += 2;
+            },
+            Some(ch) if *ch == quote => {
+              i += 1;
+              break;
+            },
+            Some(ch) => {
+              s.push(*ch);
+              i += 1;
+            },
+          }
+        }
+        tokens.push(Token::Text(s));
+      },
+      '{' => {
+        let start = i + 1;
+        let end = chars[start..]
+          .iter()
+          .position(|ch| *ch == '}')
+          .map(|offset| start + offset)
+          .ok_or_else(|| {
+            FlowyError::invalid_formula().with_context("unterminated field reference")
+          })?;
+        let name = chars[start..end].iter().collect::<String>();
+        tokens.push(Token::Field(name.trim().to_string()));
+        i = end + 1;
+      },
+      c if c.is_alphabetic() || c == '_' => {
+        let start = i;
+        while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+          i += 1;
+        }
+        tokens.push(Token::Ident(
+          chars[start..i].iter().collect::<String>().to_lowercase(),
+        ));
+      },
+      '(' => {
+        tokens.push(Token::LParen);
+        i += 1;
+      },
+      ')' => {
+        tokens.push(Token::RParen);
+        i += 1;
+      },
+      ',' => {
+        tokens.push(Token::Comma);
+        i += 1;
+      },
+      _ => {
+        let next = chars.get(i + 1).copied();
+        let op = match (c, next) {
+          ('=', Some('=')) => "==",
+          ('!', Some('=')) => "!=",
+          ('<', Some('=')) => "<=",
+          ('>', Some('=')) => ">=",
+          ('&', Some('&')) => "&&",
+          ('|', Some('|')) => "||",
+          ('=', _) => "==",
+          ('<', _) => "<",
+          ('>', _) => ">",
+          ('!', _) => "!",
+          ('&', _) => "&",
+          ('+', _) => "+",
+          ('-', _) => "-",
+          ('*', _) => "*",
+          ('/', _) => "/",
+          ('%', _) => "%",
+          _ => {
+            return Err(
+              FlowyError::invalid_formula().with_context(format!("unexpected character {}", c)),
+            )
+          },
+        };
+        i += op.len();
+        tokens.push(Token::Op(op));
+      },
+    }
+  }
+  Ok(tokens)
+}
+
+/// The parsed representation of a formula expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaExpr {
+  Literal(FormulaValue),
+  Field(String),
+  Unary(&'static str, Box<FormulaExpr>),
+  Binary(&'static str, Box<FormulaExpr>, Box<FormulaExpr>),
+  Call(String, Vec<FormulaExpr>),
+}
+
+impl FormulaExpr {
+  /// Returns the names or ids of the fields referenced by the expression.
+  pub fn field_references(&self) -> Vec<String> {
+    let mut references = vec![];
+    self.collect_field_references(&mut references);
+    references
+  }
+
+  /// Evaluates the parsed expression against the given context.
+  pub fn evaluate(&self, ctx: &dyn FormulaContext) -> FlowyResult<FormulaValue> {
+    eval(self, ctx)
+  }
+
+  /// Returns true if the expression calls the function with the given name.
+  pub fn calls_function(&self, function_name: &str) -> bool {
+    match self {
+      FormulaExpr::Literal(_) | FormulaExpr::Field(_) => false,
+      FormulaExpr::Unary(_, expr) => expr.calls_function(function_name),
+      FormulaExpr::Binary(_, left, right) => {
+        left.calls_function(function_name) || right.calls_function(function_name)
+      },
+      FormulaExpr::Call(name, args) => {
+        name == function_name || args.iter().any(|arg| arg.calls_function(function_name))
+      },
+    }
+  }
+
+  fn collect_field_references(&self, references: &mut Vec<String>) {
+    match self {
+      FormulaExpr::Literal(_) => {},
+      FormulaExpr::Field(name) => {
+        if !references.contains(name) {
+          references.push(name.clone());
+        }
+      },
+      FormulaExpr::Unary(_, expr) => expr.collect_field_references(references),
+      FormulaExpr::Binary(_, left, right) => {
+        left.collect_field_references(references);
+        right.collect_field_references(references);
+      },
+      FormulaExpr::Call(_, args) => {
+        for arg in args {
+          arg.collect_field_references(references);
+        }
+      },
+    }
+  }
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    self.pos += 1;
+    token
+  }
+
+  fn eat_op(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+    match self.peek() {
+      Some(Token::Op(op)) if ops.contains(op) => {
+        let op = *op;
+        self.pos += 1;
+        Some(op)
+      },
+      _ => None,
+    }
+  }
+
+  fn expect(&mut self, token: Token) -> FlowyResult<()> {
+    match self.next() {
+      Some(next) if next == token => Ok(()),
+      other => Err(
+        FlowyError::invalid_formula()
+          .with_context(format!("expect {:?}, but got {:?}", token, other)),
+      ),
+    }
+  }
+
+  fn parse_binary(
+    &mut self,
+    ops: &[&'static str],
+    next: fn(&mut Self) -> FlowyResult<FormulaExpr>,
+  ) -> FlowyResult<FormulaExpr> {
+    let mut left = next(self)?;
+    while let Some(op) = self.eat_op(ops) {
+      let right = next(self)?;
+      left = FormulaExpr::Binary(op, Box::new(left), Box::new(right));
+    }
+    Ok(left)
+  }
+
+  fn parse_or(&mut self) -> FlowyResult<FormulaExpr> {
+    self.parse_binary(&["||"], Self::parse_and)
+  }
+
+  fn parse_and(&mut self) -> FlowyResult<FormulaExpr> {
+    self.parse_binary(&["&&"], Self::parse_comparison)
+  }
+
+  fn parse_comparison(&mut self) -> FlowyResult<FormulaExpr> {
+    self.parse_binary(&["==", "!=", "<", "<=", ">", ">="], Self::parse_additive)
+  }
+
+  fn parse_additive(&mut self) -> FlowyResult<FormulaExpr> {
+    self.parse_binary(&["+", "-", "&"], Self::parse_multiplicative)
+  }
+
+  fn parse_multiplicative(&mut self) -> FlowyResult<FormulaExpr> {
+    self.parse_binary(&["*", "/", "%"], Self::parse_unary)
+  }
+
+  fn parse_unary(&mut self) -> FlowyResult<FormulaExpr> {
+    match self.eat_op(&["-", "!"]) {
+      Some(op) => Ok(FormulaExpr::Unary(op, Box::new(self.parse_unary()?))),
+      None => self.parse_primary(),
+    }
+  }
+
+  fn parse_primary(&mut self) -> FlowyResult<FormulaExpr> {
+    match self.next() {
+      Some(Token::Number(n)) => Ok(FormulaExpr::Literal(FormulaValue::Number(n))),
+      Some(Token::Text(s)) => Ok(FormulaExpr::Literal(FormulaValue::Text(s))),
+      Some(Token::Field(name)) => Ok(FormulaExpr::Field(name)),
+      Some(Token::Ident(ident)) => match ident.as_str() {
+        "true" => Ok(FormulaExpr::Literal(FormulaValue::Bool(true))),
+        "false" => Ok(FormulaExpr::Literal(FormulaValue::Bool(false))),
+        _ => {
+          self.expect(Token::LParen)?;
+          let mut args = vec![];
+          if self.peek() != Some(&Token::RParen) {
+            loop {
+              args.push(self.parse_or()?);
+              if self.peek() == Some(&Token::Comma) {
+                self.pos += 1;
+              } else {
+                break;
+              }
+            }
+          }
+          self.expect(Token::RParen)?;
+          Ok(FormulaExpr::Call(ident, args))
+        },
+      },
+      Some(Token::LParen) => {
+        let expr = self.parse_or()?;
+        self.expect(Token::RParen)?;
+        Ok(expr)
+      },
+      other => {
+        Err(FlowyError::invalid_formula().with_context(format!("unexpected token {:?}", other)))
+      },
+    }
+  }
+}
+
+/// Parses the formula expression. Fields are referenced by wrapping their name or id in braces,
+/// for example: `if({Done}, "✅", {Estimate} * 2)`.
+pub fn parse_formula(expression: &str) -> FlowyResult<FormulaExpr> {
+  let mut parser = Parser {
+    tokens: tokenize(expression)?,
+    pos: 0,
+  };
+  let expr = parser.parse_or()?;
+  if parser.pos < parser.tokens.len() {
+    return Err(
+      FlowyError::invalid_formula()
+        .with_context(format!("unexpected token {:?}", parser.tokens[parser.pos])),
+    );
+  }
+  Ok(expr)
+}
+
+/// Replaces the references to the field named `old_name` with `new_name`. Returns None if the
+/// expression doesn't reference the field.
+pub fn rename_field_reference(expression: &str, old_name: &str, new_name: &str) -> Option<String> {
+  let chars = expression.chars().collect::<Vec<char>>();
+  let mut renamed = String::with_capacity(expression.len());
+  let mut did_rename = false;
+  let mut quote = None;
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    match (quote, c) {
+      // The characters of the strings are kept as is, including the escaped ones
+      (Some(_), '\\') if i + 1 < chars.len() => {
+        renamed.push(c);
+        renamed.push(chars[i + 1]);
+        i += 2;
+        continue;
+      },
+      (Some(q), c) if c == q => quote = None,
+      (None, '"' | '\'') => quote = Some(c),
+      (None, '{') => {
+        let end = chars[i + 1..].iter().position(|ch| *ch == '}');
+        if let Some(end) = end.map(|offset| i + 1 + offset) {
+          let name = chars[i + 1..end].iter().collect::<String>();
+          if name.trim() == old_name {
+            renamed.push('{');
+            renamed.push_str(new_name);
+            renamed.push('}');
+            did_rename = true;
+          } else {
+            renamed.extend(&chars[i..=end]);
+          }
+          i = end + 1;
+          continue;
+        }
+      },
+      _ => {},
+    }
+    renamed.push(c);
+    i += 1;
+  }
+  did_rename.then_some(renamed)
+}
+
+/// Parses and evaluates the formula expression against the given context.
+pub fn evaluate_formula(expression: &str, ctx: &dyn FormulaContext) -> FlowyResult<FormulaValue> {
+  if expression.trim().is_empty() {
+    return Ok(FormulaValue::Empty);
+  }
+  eval(&parse_formula(expression)?, ctx)
+}
+
+fn eval(expr: &FormulaExpr, ctx: &dyn FormulaContext) -> FlowyResult<FormulaValue> {
+  match expr {
+    FormulaExpr::Literal(value) => Ok(value.clone()),
+    FormulaExpr::Field(name) => ctx.field_value(name).ok_or_else(|| {
+      FlowyError::invalid_formula().with_context(format!("field {} not found", name))
+    }),
+    FormulaExpr::Unary(op, expr) => {
+      let value = eval(expr, ctx)?;
+      match *op {
+        "-" => Ok(FormulaValue::Number(-value.as_number()?)),
+        _ => Ok(FormulaValue::Bool(!value.as_bool())),
+      }
+    },
+    FormulaExpr::Binary(op, left, right) => {
+      let left = eval(left, ctx)?;
+      match *op {
+        "&&" => Ok(FormulaValue::Bool(
+          left.as_bool() && eval(right, ctx)?.as_bool(),
+        )),
+        "||" => Ok(FormulaValue::Bool(
+          left.as_bool() || eval(right, ctx)?.as_bool(),
+        )),
+        _ => eval_binary(op, left, eval(right, ctx)?),
+      }
+    },
+    FormulaExpr::Call(name, args) => eval_call(name, args, ctx),
+  }
+}
+
+fn eval_binary(op: &str, left: FormulaValue, right: FormulaValue) -> FlowyResult<FormulaValue> {
+  use FormulaValue::*;
+  match op {
+    "&" => Ok(Text(format!("{}{}", left, right))),
+    "+" => match (&left, &right) {
+      (Text(_), _) | (_, Text(_)) => Ok(Text(format!("{}{}", left, right))),
+      (Date(ts), Number(days)) | (Number(days), Date(ts)) => Ok(Date(add_days(*ts, *days)?)),
+      _ => Ok(Number(left.as_number()? + right.as_number()?)),
+    },
+    "-" => match (&left, &right) {
+      (Date(ts), Number(days)) => Ok(Date(add_days(*ts, -*days)?)),
+      (Date(a), Date(b)) => {
+        let seconds = a
+          .checked_sub(*b)
+          .ok_or_else(|| FlowyError::invalid_formula().with_context("date out of range"))?;
+        Ok(Number(seconds as f64 / SECONDS_PER_DAY as f64))
+      },
+      _ => Ok(Number(left.as_number()? - right.as_number()?)),
+    },
+    "*" => Ok(Number(left.as_number()? * right.as_number()?)),
+    "/" | "%" => {
+      let divisor = right.as_number()?;
+      if divisor == 0.0 {
+        return Err(FlowyError::invalid_formula().with_context("division by zero"));
+      }
+      if op == "/" {
+        Ok(Number(left.as_number()? / divisor))
+      } else {
+        Ok(Number(left.as_number()? % divisor))
+      }
+    },
+    _ => {
+      let ordering = compare_values(&left, &right);
+      let result = match op {
+        "==" => ordering == Ordering::Equal,
+        "!=" => ordering != Ordering::Equal,
+        "<" => ordering == Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        _ => ordering != Ordering::Less,
+      };
+      Ok(Bool(result))
+    },
+  }
+}
+
+pub(crate) fn compare_values(left: &FormulaValue, right: &FormulaValue) -> Ordering {
+  if let (FormulaValue::Text(a), FormulaValue::Text(b)) = (left, right) {
+    return a.cmp(b);
+  }
+  match (left.as_number(), right.as_number()) {
+    (Ok(a), Ok(b)) => a.total_cmp(&b),
+    _ => left.to_string().cmp(&right.to_string()),
+  }
+}
+
+fn add_days(timestamp: i64, days: f64) -> FlowyResult<i64> {
+  let seconds = days * SECONDS_PER_DAY as f64;
+  if !seconds.is_finite() || seconds.abs() >= i64::MAX as f64 {
+    return Err(FlowyError::invalid_formula().with_context("date out of range"));
+  }
+  timestamp
+    .checked_add(seconds as i64)
+    .ok_or_else(|| FlowyError::invalid_formula().with_context("date out of range"))
+}
+
+fn date_add(timestamp: i64, amount: i64, unit: &str) -> FlowyResult<i64> {
+  let date = DateTime::<Utc>::from_timestamp(timestamp, 0)
+    .ok_or_else(|| FlowyError::invalid_formula().with_context("invalid date"))?
+    .naive_utc();
+  let add_duration = |duration: Option<Duration>| duration.and_then(|d| date.checked_add_signed(d));
+  let result = match unit {
+    "minutes" | "minute" => add_duration(Duration::try_minutes(amount)),
+    "hours" | "hour" => add_duration(Duration::try_hours(amount)),
+    "days" | "day" => add_duration(Duration::try_days(amount)),
+    "weeks" | "week" => add_duration(Duration::try_weeks(amount)),
+    "months" | "month" => add_months(date, amount),
+    "years" | "year" => amount
+      .checked_mul(12)
+      .and_then(|months| add_months(date, months)),
+    _ => return Err(FlowyError::invalid_formula().with_context(format!("unknown unit {}", unit))),
+  };
+  result
+    .map(|date| date.and_utc().timestamp())
+    .ok_or_else(|| FlowyError::invalid_formula().with_context("date out of range"))
+}
+
+fn add_months(date: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+  let num_of_months = u32::try_from(months.unsigned_abs()).ok()?;
+  if months >= 0 {
+    date.checked_add_months(Months::new(num_of_months))
+  } else {
+    date.checked_sub_months(Months::new(num_of_months))
+  }
+}
+
+fn date_diff(start: i64, end: i64, unit: &str) -> FlowyResult<f64> {
+  let seconds = end
+    .checked_sub(start)
+    .ok_or_else(|| FlowyError::invalid_formula().with_context("date out of range"))?
+    as f64;
+  let value = match unit {
+    "minutes" | "minute" => seconds / 60.0,
+    "hours" | "hour" => seconds / 3600.0,
+    "days" | "day" => seconds / SECONDS_PER_DAY as f64,
+    "weeks" | "week" => seconds / (SECONDS_PER_DAY * 7) as f64,
+    "months" | "month" => seconds / (SECONDS_PER_DAY as f64 * 30.4375),
+    "years" | "year" => seconds / (SECONDS_PER_DAY as f64 * 365.25),
+    _ => return Err(FlowyError::invalid_formula().with_context(format!("unknown unit {}", unit))),
+  };
+  Ok(value.trunc())
+}
+
+fn eval_call(
+  name: &str,
+  args: &[FormulaExpr],
+  ctx: &dyn FormulaContext,
+) -> FlowyResult<FormulaValue> {
+  let arity = |min: usize, max: usize| -> FlowyResult<()> {
+    if args.len() < min || args.len() > max {
+      return Err(FlowyError::invalid_formula().with_context(format!(
+        "{} expects {} to {} arguments, but got {}",
+        name,
+        min,
+        max,
+        args.len()
+      )));
+    }
+    Ok(())
+  };
+
+  // `if` only evaluates the branch that is taken.
+  if name == "if" {
+    arity(2, 3)?;
+    return if eval(&args[0], ctx)?.as_bool() {
+      eval(&args[1], ctx)
+    } else {
+      args
+        .get(2)
+        .map(|arg| eval(arg, ctx))
+        .unwrap_or(Ok(FormulaValue::Empty))
+    };
+  }
+
+  let values = args
+    .iter()
+    .map(|arg| eval(arg, ctx))
+    .collect::<FlowyResult<Vec<FormulaValue>>>()?;
+
+  match name {
+    "concat" => Ok(FormulaValue::Text(
+      values.iter().map(|value| value.to_string()).collect(),
+    )),
+    "len" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Number(
+        values[0].to_string().chars().count() as f64
+      ))
+    },
+    "lower" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Text(values[0].to_string().to_lowercase()))
+    },
+    "upper" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Text(values[0].to_string().to_uppercase()))
+    },
+    "empty" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Bool(values[0].is_empty()))
+    },
+    "abs" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Number(values[0].as_number()?.abs()))
+    },
+    "floor" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Number(values[0].as_number()?.floor()))
+    },
+    "ceil" => {
+      arity(1, 1)?;
+      Ok(FormulaValue::Number(values[0].as_number()?.ceil()))
+    },
+    "round" => {
+      arity(1, 2)?;
+      let digits = values
+        .get(1)
+        .map(|v| v.as_number())
+        .transpose()?
+        .unwrap_or(0.0);
+      let factor = 10f64.powi(digits as i32);
+      Ok(FormulaValue::Number(
+        (values[0].as_number()? * factor).round() / factor,
+      ))
+    },
+    "min" | "max" => {
+      arity(1, usize::MAX)?;
+      let numbers = values
+        .iter()
+        .filter(|value| !value.is_empty())
+        .map(|value| value.as_number())
+        .collect::<FlowyResult<Vec<f64>>>()?;
+      let result = if name == "min" {
+        numbers.into_iter().reduce(f64::min)
+      } else {
+        numbers.into_iter().reduce(f64::max)
+      };
+      Ok(
+        result
+          .map(FormulaValue::Number)
+          .unwrap_or(FormulaValue::Empty),
+      )
+    },
+    "now" => {
+      arity(0, 0)?;
+      Ok(FormulaValue::Date(ctx.now()))
+    },
+    "today" => {
+      arity(0, 0)?;
+      let now = ctx.now();
+      Ok(FormulaValue::Date(now - now.rem_euclid(SECONDS_PER_DAY)))
+    },
+    "date_add" => {
+      arity(3, 3)?;
+      let date = values[0].as_date()?;
+      let amount = values[1].as_number()? as i64;
+      Ok(FormulaValue::Date(date_add(
+        date,
+        amount,
+        &values[2].to_string().to_lowercase(),
+      )?))
+    },
+    "date_diff" => {
+      arity(3, 3)?;
+      let start = values[0].as_date()?;
+      let end = values[1].as_date()?;
+      Ok(FormulaValue::Number(date_diff(
+        start,
+        end,
+        &values[2].to_string().to_lowercase(),
+      )?))
+    },
+    "year" | "month" | "day" => {
+      arity(1, 1)?;
+      let date = DateTime::<Utc>::from_timestamp(values[0].as_date()?, 0)
+        .ok_or_else(|| FlowyError::invalid_formula().with_context("invalid date"))?;
+      let value = match name {
+        "year" => date.format("%Y"),
+        "month" => date.format("%m"),
+        _ => date.format("%d"),
+      };
+      Ok(FormulaValue::Number(
+        value.to_string().parse::<f64>().unwrap_or_default(),
+      ))
+    },
+    _ => Err(FlowyError::invalid_formula().with_context(format!("unknown function {}", name))),
+  }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use crate::services::field::{
+    evaluate_formula, parse_formula, rename_field_reference, FormulaContext, FormulaValue,
+  };
+
+  struct MockContext(HashMap<&'static str, FormulaValue>);
+
+  impl FormulaContext for MockContext {
+    fn field_value(&self, field_name_or_id: &str) -> Option<FormulaValue> {
+      self.0.get(field_name_or_id).cloned()
+    }
+
+    fn now(&self) -> i64 {
+      // 2024-01-01 09:46:40 UTC
+      1704102400
+    }
+  }
+
+  fn mock_context() -> MockContext {
+    MockContext(HashMap::from([
+      ("Estimate", FormulaValue::Number(3.0)),
+      ("Actual", FormulaValue::Number(5.5)),
+      ("Name", FormulaValue::Text("Task".to_string())),
+      ("Done", FormulaValue::Bool(true)),
+      // 2024-01-01
+      ("Due", FormulaValue::Date(1704067200)),
+      // 2023-12-25
+      ("Start", FormulaValue::Date(1703462400)),
+      ("Notes", FormulaValue::Empty),
+    ]))
+  }
+
+  fn assert_formula(expression: &str, expected: &str) {
+    let value = evaluate_formula(expression, &mock_context()).unwrap();
+    assert_eq!(value.to_string(), expected, "expression: {}", expression);
+  }
+
+  #[test]
+  fn formula_arithmetic_test() {
+    assert_formula("1 + 2 * 3", "7");
+    assert_formula("(1 + 2) * 3", "9");
+    assert_formula("({Estimate} + {Actual}) / 2", "4.25");
+    assert_formula("-{Estimate} % 2", "-1");
+    assert_formula("round(10 / 3, 2)", "3.33");
+    assert_formula("min({Estimate}, {Actual}, {Notes})", "3");
+    assert_formula("max({Estimate}, {Actual})", "5.5");
+  }
+
+  #[test]
+  fn formula_string_concat_test() {
+    assert_formula("{Name} & \" #\" & 3", "Task #3");
+    assert_formula("concat({Name}, \"-\", {Estimate})", "Task-3");
+    assert_formula("upper({Name})", "TASK");
+    assert_formula("len({Name})", "4");
+  }
+
+  #[test]
+  fn formula_if_test() {
+    assert_formula("if({Done}, \"yes\", \"no\")", "yes");
+    assert_formula("if({Actual} > {Estimate}, \"over\", \"ok\")", "over");
+    assert_formula("if(empty({Notes}), \"-\", {Notes})", "-");
+    assert_formula("if(!{Done} || {Estimate} >= 3, 1, 0)", "1");
+  }
+
+  #[test]
+  fn formula_date_math_test() {
+    assert_formula("{Due} - {Start}", "7");
+    assert_formula("{Due} + 7", "2024-01-08");
+    assert_formula("date_add({Due}, 1, \"months\")", "2024-02-01");
+    assert_formula("date_diff({Start}, {Due}, \"days\")", "7");
+    assert_formula("today()", "2024-01-01");
+    assert_formula("year({Due})", "2024");
+  }
+
+  #[test]
+  fn formula_invalid_expression_test() {
+    let ctx = mock_context();
+    assert!(evaluate_formula("1 +", &ctx).is_err());
+    assert!(evaluate_formula("1 / 0", &ctx).is_err());
+    assert!(evaluate_formula("unknown(1)", &ctx).is_err());
+    assert!(evaluate_formula("{Missing} + 1", &ctx).is_err());
+    assert_eq!(evaluate_formula("", &ctx).unwrap(), FormulaValue::Empty);
+
+    // The dates out of range are invalid instead of overflowing
+    assert!(evaluate_formula("date_add({Due}, 9223372036854775807, \"days\")", &ctx).is_err());
+    assert!(evaluate_formula("date_add({Due}, 9223372036854775807, \"years\")", &ctx).is_err());
+    assert!(evaluate_formula("date_add({Due}, 5000000000, \"months\")", &ctx).is_err());
+    assert!(evaluate_formula("{Due} + 100000000000000000000", &ctx).is_err());
+    let (latest, earliest) = ("({Due} + 100000000000000)", "({Due} - 100000000000000)");
+    assert!(evaluate_formula(&format!("{} - {}", latest, earliest), &ctx).is_err());
+    assert!(evaluate_formula(
+      &format!("date_diff({}, {}, \"days\")", earliest, latest),
+      &ctx
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn formula_field_references_test() {
+    let expr = parse_formula("if({Done}, {Estimate} * 2, {Estimate} + {Actual})").unwrap();
+    assert_eq!(
+      expr.field_references(),
+      vec![
+        "Done".to_string(),
+        "Estimate".to_string(),
+        "Actual".to_string()
+      ]
+    );
+  }
+
+  #[test]
+  fn formula_rename_field_reference_test() {
+    assert_eq!(
+      rename_field_reference("{Estimate} * 2 + { Estimate }", "Estimate", "Points").unwrap(),
+      "{Points} * 2 + {Points}"
+    );
+    // The strings aren't field references
+    assert_eq!(
+      rename_field_reference("\"{Estimate}\" & {Estimate}", "Estimate", "Points").unwrap(),
+      "\"{Estimate}\" & {Points}"
+    );
+    assert_eq!(
+      rename_field_reference("{Actual} + 1", "Estimate", "Points"),
+      None
+    );
+  }
+}
@@ -0,0 +1,9 @@
+#![allow(clippy::module_inception)]
+mod formula;
+mod formula_entities;
+mod formula_evaluator;
+mod formula_tests;
+
+pub use formula::*;
+pub use formula_entities::*;
+pub use formula_evaluator::*;
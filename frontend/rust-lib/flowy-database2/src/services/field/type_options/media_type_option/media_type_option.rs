@@ -11,7 +11,7 @@ use crate::{
       default_order, StringCellData, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
       TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform,
     },
-    sort::SortCondition,
+    sort::Sort,
   },
 };
 
@@ -69,6 +69,8 @@ impl CellDataDecoder for MediaTypeOption {
       | FieldType::LastEditedTime
       | FieldType::CreatedTime
       | FieldType::Relation
+      | FieldType::Formula
+      | FieldType::Rollup
       | FieldType::Media => None,
     }
   }
@@ -130,7 +132,7 @@ impl TypeOptionCellDataCompare for MediaTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    _sort_condition: SortCondition,
+    _sort: &Sort,
   ) -> Ordering {
     match (cell_data.files.is_empty(), other_cell_data.is_cell_empty()) {
       (true, true) => Ordering::Equal,
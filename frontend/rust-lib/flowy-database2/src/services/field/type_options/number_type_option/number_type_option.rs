@@ -23,7 +23,7 @@ use crate::services::field::{
   TypeOption, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform, CELL_DATA,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 
 #[derive(Clone, Debug, Default)]
 pub struct NumberCellData(pub String);
@@ -217,7 +217,7 @@ impl TypeOptionCellDataCompare for NumberTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
       (true, true) => Ordering::Equal,
@@ -229,7 +229,7 @@ impl TypeOptionCellDataCompare for NumberTypeOption {
         match (left, right) {
           (Ok(left), Ok(right)) => {
             let order = left.decimal().cmp(right.decimal());
-            sort_condition.evaluate_order(order)
+            sort.condition.evaluate_order(order)
           },
           (Ok(_), Err(_)) => Ordering::Less,
           (Err(_), Ok(_)) => Ordering::Greater,
@@ -1,4 +1,5 @@
 use crate::services::cell::{CellBytesCustomParser, CellProtobufBlobParser};
+use crate::services::field::type_options::util::ProtobufStr;
 use bytes::Bytes;
 use collab_database::fields::number_type_option::{NumberCellFormat, NumberFormat};
 use flowy_error::FlowyResult;
@@ -14,6 +15,17 @@ impl CellProtobufBlobParser for NumberCellDataParser {
   }
 }
 
+/// Parses the bytes into the formatted number string, the protobuf type of the number cell.
+pub struct NumberCellStrParser();
+impl CellProtobufBlobParser for NumberCellStrParser {
+  type Object = ProtobufStr;
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    Ok(ProtobufStr::from(
+      String::from_utf8(bytes.to_vec()).unwrap_or_default(),
+    ))
+  }
+}
+
 pub struct NumberCellCustomDataParser(pub NumberFormat);
 impl CellBytesCustomParser for NumberCellCustomDataParser {
   type Object = NumberCellFormat;
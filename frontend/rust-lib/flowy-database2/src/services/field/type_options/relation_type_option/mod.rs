@@ -1,5 +1,6 @@
 mod relation;
 mod relation_entities;
+mod relation_tests;
 
 pub use relation::*;
 pub use relation_entities::*;
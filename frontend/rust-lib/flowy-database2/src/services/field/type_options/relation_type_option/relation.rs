@@ -6,13 +6,13 @@ use collab_database::rows::Cell;
 use flowy_error::FlowyResult;
 use serde::{Deserialize, Serialize};
 
-use crate::entities::{RelationCellDataPB, RelationFilterPB};
+use crate::entities::{RelationCellDataPB, RelationFilterConditionPB, RelationFilterPB};
 use crate::services::cell::{CellDataChangeset, CellDataDecoder};
 use crate::services::field::{
-  default_order, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  TypeOption, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 
 use super::{RelationCellChangeset, RelationCellData};
 
@@ -88,20 +88,48 @@ impl CellDataDecoder for RelationTypeOption {
   }
 }
 
+/// Sorts the cells by their number of related rows. Sorting by the primary text of the related
+/// rows needs the related database, see [SortController](crate::services::sort::SortController).
 impl TypeOptionCellDataCompare for RelationTypeOption {
   fn apply_cmp(
     &self,
-    _cell_data: &RelationCellData,
-    _other_cell_data: &RelationCellData,
-    _sort_condition: SortCondition,
+    cell_data: &RelationCellData,
+    other_cell_data: &RelationCellData,
+    sort: &Sort,
   ) -> Ordering {
-    default_order()
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = cell_data.row_ids.len().cmp(&other_cell_data.row_ids.len());
+        sort.condition.evaluate_order(order)
+      },
+    }
   }
 }
 
 impl TypeOptionCellDataFilter for RelationTypeOption {
-  fn apply_filter(&self, _filter: &RelationFilterPB, _cell_data: &RelationCellData) -> bool {
-    true
+  fn apply_filter(&self, filter: &RelationFilterPB, cell_data: &RelationCellData) -> bool {
+    let contains_any = || {
+      cell_data
+        .row_ids
+        .iter()
+        .any(|row_id| filter.row_ids.iter().any(|id| id == row_id.as_str()))
+    };
+    match filter.condition {
+      RelationFilterConditionPB::RelationContains => filter.row_ids.is_empty() || contains_any(),
+      RelationFilterConditionPB::RelationDoesNotContain => {
+        filter.row_ids.is_empty() || !contains_any()
+      },
+      RelationFilterConditionPB::RelationIsEmpty => cell_data.is_cell_empty(),
+      RelationFilterConditionPB::RelationIsNotEmpty => !cell_data.is_cell_empty(),
+      // The related filter is replaced by the matching rows once it is resolved against the
+      // related database. It can't be evaluated if the related database isn't opened.
+      RelationFilterConditionPB::RelatedRowMatches => {
+        filter.related_filter.is_some() || contains_any()
+      },
+    }
   }
 }
 
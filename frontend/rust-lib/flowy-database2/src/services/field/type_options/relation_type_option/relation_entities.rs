@@ -1,9 +1,12 @@
 use std::sync::Arc;
 
+use bytes::Bytes;
 use collab::preclude::Any;
 use collab_database::rows::{new_cell_builder, Cell, RowId};
+use flowy_error::{internal_error, FlowyResult};
 
-use crate::entities::FieldType;
+use crate::entities::{FieldType, RelationCellDataPB};
+use crate::services::cell::CellProtobufBlobParser;
 use crate::services::field::{TypeOptionCellData, CELL_DATA};
 
 #[derive(Debug, Clone, Default)]
@@ -78,6 +81,15 @@ impl ToString for RelationCellData {
   }
 }
 
+pub struct RelationCellDataParser();
+impl CellProtobufBlobParser for RelationCellDataParser {
+  type Object = RelationCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    RelationCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RelationCellChangeset {
   pub inserted_row_ids: Vec<RowId>,
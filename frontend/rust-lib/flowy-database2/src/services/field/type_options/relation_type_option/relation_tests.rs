@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+  use std::cmp::Ordering;
+
+  use crate::entities::{FieldType, FilterDataPB, RelationFilterConditionPB, RelationFilterPB};
+  use crate::services::field::{
+    RelationCellData, RelationTypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  };
+  use crate::services::sort::{Sort, SortCondition};
+
+  fn cell_data(row_ids: &[&str]) -> RelationCellData {
+    RelationCellData {
+      row_ids: row_ids.iter().map(|id| id.to_string().into()).collect(),
+    }
+  }
+
+  fn filter(condition: RelationFilterConditionPB, row_ids: &[&str]) -> RelationFilterPB {
+    RelationFilterPB {
+      condition,
+      row_ids: row_ids.iter().map(|id| id.to_string()).collect(),
+      related_filter: None,
+    }
+  }
+
+  #[test]
+  fn relation_filter_test() {
+    let type_option = RelationTypeOption::default();
+    let empty = cell_data(&[]);
+    let related = cell_data(&["a", "b"]);
+
+    for (filter, cell_data, expected) in [
+      (
+        filter(RelationFilterConditionPB::RelationContains, &["b"]),
+        &related,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationContains, &["c"]),
+        &related,
+        false,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationContains, &[]),
+        &empty,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationDoesNotContain, &["a"]),
+        &related,
+        false,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationDoesNotContain, &["c"]),
+        &related,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationDoesNotContain, &["c"]),
+        &empty,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationIsEmpty, &[]),
+        &empty,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationIsEmpty, &[]),
+        &related,
+        false,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelationIsNotEmpty, &[]),
+        &related,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelatedRowMatches, &["a"]),
+        &related,
+        true,
+      ),
+      (
+        filter(RelationFilterConditionPB::RelatedRowMatches, &[]),
+        &related,
+        false,
+      ),
+    ] {
+      assert_eq!(
+        type_option.apply_filter(&filter, cell_data),
+        expected,
+        "{:?}",
+        filter
+      );
+    }
+
+    // the related filter that isn't resolved doesn't filter out any row
+    let unresolved = RelationFilterPB {
+      related_filter: Some(FilterDataPB {
+        field_id: "name".to_string(),
+        field_type: FieldType::RichText,
+        data: vec![],
+      }),
+      ..filter(RelationFilterConditionPB::RelatedRowMatches, &[])
+    };
+    assert!(type_option.apply_filter(&unresolved, &empty));
+  }
+
+  #[test]
+  fn relation_sort_by_row_count_test() {
+    let type_option = RelationTypeOption::default();
+    let one = cell_data(&["a"]);
+    let two = cell_data(&["a", "b"]);
+    let empty = cell_data(&[]);
+    let ascending = Sort::default();
+    let descending = Sort {
+      condition: SortCondition::Descending,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      type_option.apply_cmp(&one, &two, &ascending),
+      Ordering::Less
+    );
+    assert_eq!(
+      type_option.apply_cmp(&one, &two, &descending),
+      Ordering::Greater
+    );
+    // the empty cells are always last
+    assert_eq!(
+      type_option.apply_cmp(&empty, &one, &descending),
+      Ordering::Greater
+    );
+  }
+}
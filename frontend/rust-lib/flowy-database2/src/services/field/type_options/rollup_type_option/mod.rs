@@ -0,0 +1,5 @@
+#![allow(clippy::module_inception)]
+mod rollup;
+mod rollup_tests;
+
+pub use rollup::*;
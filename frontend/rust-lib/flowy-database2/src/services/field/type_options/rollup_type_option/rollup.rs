@@ -0,0 +1,211 @@
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  ComputedCellData, FormulaValue, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  TypeOptionCellDataSerde, TypeOptionTransform,
+};
+use crate::services::sort::Sort;
+use collab::preclude::Any;
+use collab::util::AnyMapExt;
+use collab_database::fields::{TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+use flowy_error::FlowyResult;
+use std::cmp::Ordering;
+
+/// Aggregates the `target_field_id` field of the rows that are linked by the relation field
+/// `relation_field_id`. The target field belongs to the related database.
+#[derive(Default, Debug, Clone)]
+pub struct RollupTypeOption {
+  pub relation_field_id: String,
+  pub target_field_id: String,
+  pub calculation_type: RollupCalculationType,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RollupCalculationType {
+  /// Comma-joined list of the related values
+  #[default]
+  List = 0,
+  /// Number of related rows
+  Count = 1,
+  /// Number of related rows whose value is not empty
+  CountValues = 2,
+  Sum = 3,
+  Average = 4,
+  Min = 5,
+  Max = 6,
+  Earliest = 7,
+  Latest = 8,
+}
+
+impl From<i64> for RollupCalculationType {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => RollupCalculationType::Count,
+      2 => RollupCalculationType::CountValues,
+      3 => RollupCalculationType::Sum,
+      4 => RollupCalculationType::Average,
+      5 => RollupCalculationType::Min,
+      6 => RollupCalculationType::Max,
+      7 => RollupCalculationType::Earliest,
+      8 => RollupCalculationType::Latest,
+      _ => RollupCalculationType::List,
+    }
+  }
+}
+
+impl From<TypeOptionData> for RollupTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let relation_field_id: String = value.get_as("relation_field_id").unwrap_or_default();
+    let target_field_id: String = value.get_as("target_field_id").unwrap_or_default();
+    let calculation_type: i64 = value.get_as("calculation_type").unwrap_or_default();
+    Self {
+      relation_field_id,
+      target_field_id,
+      calculation_type: RollupCalculationType::from(calculation_type),
+    }
+  }
+}
+
+impl From<RollupTypeOption> for TypeOptionData {
+  fn from(value: RollupTypeOption) -> Self {
+    TypeOptionDataBuilder::from([
+      ("relation_field_id".into(), value.relation_field_id.into()),
+      ("target_field_id".into(), value.target_field_id.into()),
+      (
+        "calculation_type".into(),
+        Any::BigInt(value.calculation_type as i64),
+      ),
+    ])
+  }
+}
+
+impl RollupTypeOption {
+  /// Aggregates the values of the target field. The `values` contains one value per related row.
+  pub fn rollup(&self, values: Vec<FormulaValue>) -> ComputedCellData {
+    let numbers = || {
+      values.iter().filter_map(|value| match value {
+        FormulaValue::Number(n) => Some(*n),
+        _ => None,
+      })
+    };
+    let dates = || {
+      values.iter().filter_map(|value| match value {
+        FormulaValue::Date(ts) => Some(*ts),
+        _ => None,
+      })
+    };
+
+    let value = match self.calculation_type {
+      RollupCalculationType::List => {
+        let list = values
+          .iter()
+          .filter(|value| !value.is_empty())
+          .map(|value| value.to_string())
+          .collect::<Vec<String>>()
+          .join(", ");
+        FormulaValue::Text(list)
+      },
+      RollupCalculationType::Count => FormulaValue::Number(values.len() as f64),
+      RollupCalculationType::CountValues => {
+        FormulaValue::Number(values.iter().filter(|value| !value.is_empty()).count() as f64)
+      },
+      RollupCalculationType::Sum => FormulaValue::Number(numbers().sum()),
+      RollupCalculationType::Average => {
+        let count = numbers().count();
+        if count == 0 {
+          FormulaValue::Empty
+        } else {
+          FormulaValue::Number(numbers().sum::<f64>() / count as f64)
+        }
+      },
+      RollupCalculationType::Min => numbers()
+        .reduce(f64::min)
+        .map(FormulaValue::Number)
+        .unwrap_or(FormulaValue::Empty),
+      RollupCalculationType::Max => numbers()
+        .reduce(f64::max)
+        .map(FormulaValue::Number)
+        .unwrap_or(FormulaValue::Empty),
+      RollupCalculationType::Earliest => dates()
+        .min()
+        .map(FormulaValue::Date)
+        .unwrap_or(FormulaValue::Empty),
+      RollupCalculationType::Latest => dates()
+        .max()
+        .map(FormulaValue::Date)
+        .unwrap_or(FormulaValue::Empty),
+    };
+    ComputedCellData(value.to_string())
+  }
+}
+
+impl TypeOption for RollupTypeOption {
+  type CellData = ComputedCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+impl CellDataChangeset for RollupTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: String,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, ComputedCellData)> {
+    let cell_data = ComputedCellData(changeset);
+    Ok((cell_data.clone().into_cell(FieldType::Rollup), cell_data))
+  }
+}
+
+impl TypeOptionCellDataFilter for RollupTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for RollupTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort: &Sort,
+  ) -> Ordering {
+    cell_data.cmp_cell_data(other_cell_data, sort)
+  }
+}
+
+impl CellDataDecoder for RollupTypeOption {
+  fn decode_cell(&self, cell: &Cell) -> FlowyResult<ComputedCellData> {
+    Ok(ComputedCellData::from(cell))
+  }
+
+  fn stringify_cell_data(&self, cell_data: ComputedCellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    ComputedCellData::from(cell).numeric_value()
+  }
+}
+
+impl TypeOptionTransform for RollupTypeOption {}
+
+impl TypeOptionCellDataSerde for RollupTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(cell_data.0)
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(ComputedCellData::from(cell))
+  }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+  use crate::services::field::{
+    evaluate_formula, FormulaContext, FormulaValue, RollupCalculationType, RollupTypeOption,
+  };
+  use std::collections::HashMap;
+
+  fn assert_rollup(
+    calculation_type: RollupCalculationType,
+    values: Vec<FormulaValue>,
+    expected: &str,
+  ) {
+    let type_option = RollupTypeOption {
+      calculation_type,
+      ..Default::default()
+    };
+    assert_eq!(
+      type_option.rollup(values).0,
+      expected,
+      "calculation: {:?}",
+      calculation_type
+    );
+  }
+
+  fn numbers() -> Vec<FormulaValue> {
+    vec![
+      FormulaValue::Number(3.0),
+      FormulaValue::Empty,
+      FormulaValue::Number(1.5),
+      FormulaValue::Number(7.5),
+    ]
+  }
+
+  #[test]
+  fn rollup_number_test() {
+    assert_rollup(RollupCalculationType::Sum, numbers(), "12");
+    assert_rollup(RollupCalculationType::Average, numbers(), "4");
+    assert_rollup(RollupCalculationType::Min, numbers(), "1.5");
+    assert_rollup(RollupCalculationType::Max, numbers(), "7.5");
+    assert_rollup(RollupCalculationType::Count, numbers(), "4");
+    assert_rollup(RollupCalculationType::CountValues, numbers(), "3");
+    assert_rollup(RollupCalculationType::List, numbers(), "3, 1.5, 7.5");
+  }
+
+  #[test]
+  fn rollup_date_test() {
+    let dates = vec![
+      // 2024-01-01
+      FormulaValue::Date(1704067200),
+      // 2023-12-25
+      FormulaValue::Date(1703462400),
+      FormulaValue::Empty,
+    ];
+    assert_rollup(RollupCalculationType::Earliest, dates.clone(), "2023-12-25");
+    assert_rollup(RollupCalculationType::Latest, dates, "2024-01-01");
+  }
+
+  #[test]
+  fn formula_reads_date_rollup_test() {
+    struct RollupContext(HashMap<&'static str, FormulaValue>);
+    impl FormulaContext for RollupContext {
+      fn field_value(&self, field_name_or_id: &str) -> Option<FormulaValue> {
+        self.0.get(field_name_or_id).cloned()
+      }
+    }
+
+    let rollup = |calculation_type| RollupTypeOption {
+      calculation_type,
+      ..Default::default()
+    };
+    let dates = vec![
+      // 2024-01-01 09:30
+      FormulaValue::Date(1704101400),
+      // 2023-12-25
+      FormulaValue::Date(1703462400),
+    ];
+    let earliest = rollup(RollupCalculationType::Earliest).rollup(dates.clone());
+    let latest = rollup(RollupCalculationType::Latest).rollup(dates);
+    assert_eq!(earliest.formula_value(), FormulaValue::Date(1703462400));
+    assert_eq!(latest.formula_value(), FormulaValue::Date(1704101400));
+
+    let ctx = RollupContext(HashMap::from([
+      ("Start", earliest.formula_value()),
+      ("End", latest.formula_value()),
+    ]));
+    let days = evaluate_formula("date_diff({Start}, {End}, \"days\")", &ctx).unwrap();
+    assert_eq!(days, FormulaValue::Number(7.0));
+    let next = evaluate_formula("date_add({Start}, 1, \"days\")", &ctx).unwrap();
+    assert_eq!(next.to_string(), "2023-12-26");
+  }
+
+  #[test]
+  fn rollup_without_related_rows_test() {
+    assert_rollup(RollupCalculationType::Sum, vec![], "0");
+    assert_rollup(RollupCalculationType::Count, vec![], "0");
+    assert_rollup(RollupCalculationType::Average, vec![], "");
+    assert_rollup(RollupCalculationType::Latest, vec![], "");
+    assert_rollup(RollupCalculationType::List, vec![], "");
+  }
+}
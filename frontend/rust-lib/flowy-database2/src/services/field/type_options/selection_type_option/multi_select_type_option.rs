@@ -4,7 +4,7 @@ use crate::services::field::{
   default_order, SelectOptionCellChangeset, SelectTypeOptionSharedAction, TypeOption,
   TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::{SelectSortBy, Sort};
 
 use collab_database::fields::select_type_option::{
   MultiSelectTypeOption, SelectOption, SelectOptionIds,
@@ -115,27 +115,36 @@ impl TypeOptionCellDataCompare for MultiSelectTypeOption {
   /// - If both cells are empty (`None`), they are considered equal.
   /// - If the left cell is empty and the right is not, the left cell is ordered to come after the right.
   /// - If the right cell is empty and the left is not, the left cell is ordered to come before the right.
-  /// - If both cells are non-empty, they are ordered based on their names. If there is an additional sort condition,
+  /// - If both cells are non-empty, they are ordered based on their names, or on their order in the field
+  ///   according to the [SelectSortBy] of the sort. If there is an additional sort condition,
   ///   this condition will further evaluate their order.
   ///
   fn apply_cmp(
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match cell_data.len().cmp(&other_cell_data.len()) {
       Ordering::Equal => {
         for (left_id, right_id) in cell_data.iter().zip(other_cell_data.iter()) {
-          let left = self.options.iter().find(|option| &option.id == left_id);
-          let right = self.options.iter().find(|option| &option.id == right_id);
-          let order = match (left, right) {
+          let find_option = |id: &String| {
+            self
+              .options
+              .iter()
+              .enumerate()
+              .find(|(_, option)| &option.id == id)
+          };
+          let order = match (find_option(left_id), find_option(right_id)) {
             (None, None) => Ordering::Equal,
             (None, Some(_)) => Ordering::Greater,
             (Some(_), None) => Ordering::Less,
-            (Some(left_option), Some(right_option)) => {
-              let name_order = left_option.name.cmp(&right_option.name);
-              sort_condition.evaluate_order(name_order)
+            (Some((left_index, left_option)), Some((right_index, right_option))) => {
+              let option_order = match sort.select_sort_by {
+                SelectSortBy::OptionName => sort.cmp_text(&left_option.name, &right_option.name),
+                SelectSortBy::OptionOrder => left_index.cmp(&right_index),
+              };
+              sort.condition.evaluate_order(option_order)
             },
           };
 
@@ -145,7 +154,7 @@ impl TypeOptionCellDataCompare for MultiSelectTypeOption {
         }
         default_order()
       },
-      order => sort_condition.evaluate_order(order),
+      order => sort.condition.evaluate_order(order),
     }
   }
 }
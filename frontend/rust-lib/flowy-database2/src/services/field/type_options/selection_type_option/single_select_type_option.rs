@@ -5,7 +5,7 @@ use crate::services::field::{
   TypeOptionCellDataSerde,
 };
 use crate::services::field::{SelectOptionCellChangeset, SelectTypeOptionSharedAction};
-use crate::services::sort::SortCondition;
+use crate::services::sort::{SelectSortBy, Sort};
 
 use collab_database::fields::select_type_option::{
   SelectOption, SelectOptionIds, SingleSelectTypeOption,
@@ -106,19 +106,25 @@ impl TypeOptionCellDataCompare for SingleSelectTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
+    let find_option = |id: &String| {
+      self
+        .options
+        .iter()
+        .enumerate()
+        .find(|(_, option)| &option.id == id)
+    };
     match (
-      cell_data
-        .first()
-        .and_then(|id| self.options.iter().find(|option| &option.id == id)),
-      other_cell_data
-        .first()
-        .and_then(|id| self.options.iter().find(|option| &option.id == id)),
+      cell_data.first().and_then(find_option),
+      other_cell_data.first().and_then(find_option),
     ) {
-      (Some(left), Some(right)) => {
-        let order = left.name.cmp(&right.name);
-        sort_condition.evaluate_order(order)
+      (Some((left_index, left)), Some((right_index, right))) => {
+        let order = match sort.select_sort_by {
+          SelectSortBy::OptionName => sort.cmp_text(&left.name, &right.name),
+          SelectSortBy::OptionOrder => left_index.cmp(&right_index),
+        };
+        sort.condition.evaluate_order(order)
       },
       (Some(_), None) => Ordering::Less,
       (None, Some(_)) => Ordering::Greater,
@@ -6,7 +6,7 @@ use crate::services::field::{
   TypeOption, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use collab::util::AnyMapExt;
 use collab_database::fields::{TypeOptionData, TypeOptionDataBuilder};
 use collab_database::rows::Cell;
@@ -64,15 +64,15 @@ impl TypeOptionCellDataCompare for SummarizationTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
       (true, true) => Ordering::Equal,
       (true, false) => Ordering::Greater,
       (false, true) => Ordering::Less,
       (false, false) => {
-        let order = cell_data.0.cmp(&other_cell_data.0);
-        sort_condition.evaluate_order(order)
+        let order = sort.cmp_text(&cell_data.0, &other_cell_data.0);
+        sort.condition.evaluate_order(order)
       },
     }
   }
@@ -1,37 +1,115 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
 use collab_database::{fields::Field, rows::Cell};
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
 
 use crate::entities::{TextFilterConditionPB, TextFilterPB};
 use crate::services::cell::insert_text_cell;
 use crate::services::filter::PreFillCellsWithFilter;
 
+/// The maximum number of compiled patterns kept in the [REGEX_CACHE]
+const REGEX_CACHE_CAPACITY: usize = 64;
+
+lazy_static! {
+  /// The filters are applied to every row, so the patterns are only compiled once
+  static ref REGEX_CACHE: Mutex<HashMap<String, Arc<Regex>>> = Mutex::new(HashMap::new());
+}
+
 impl TextFilterPB {
   pub fn is_visible<T: AsRef<str>>(&self, cell_data: T) -> bool {
-    let cell_data = cell_data.as_ref().to_lowercase();
-    let content = &self.content.to_lowercase();
+    let cell_data = cell_data.as_ref();
+    match self.condition {
+      TextFilterConditionPB::TextIsEmpty => return cell_data.is_empty(),
+      TextFilterConditionPB::TextIsNotEmpty => return !cell_data.is_empty(),
+      _ if self.content.is_empty() => return true,
+      _ => {},
+    }
+
+    if let Some(pattern) = self.pattern() {
+      // The rows aren't filtered out by an invalid pattern, it is reported by
+      // [TextFilterPB::validate] instead
+      let is_match = match compile_regex(&pattern) {
+        Ok(regex) => regex.is_match(cell_data).unwrap_or(false),
+        Err(_) => return true,
+      };
+      return match self.condition {
+        TextFilterConditionPB::TextDoesNotContain
+        | TextFilterConditionPB::TextDoesNotMatchRegex => !is_match,
+        _ => is_match,
+      };
+    }
 
+    let (cell_data, content) = if self.case_sensitive {
+      (cell_data.to_string(), self.content.clone())
+    } else {
+      (cell_data.to_lowercase(), self.content.to_lowercase())
+    };
     match self.condition {
-      TextFilterConditionPB::TextIs
-      | TextFilterConditionPB::TextIsNot
-      | TextFilterConditionPB::TextContains
-      | TextFilterConditionPB::TextDoesNotContain
-      | TextFilterConditionPB::TextStartsWith
-      | TextFilterConditionPB::TextEndsWith
-        if content.is_empty() =>
+      TextFilterConditionPB::TextIs => cell_data == content,
+      TextFilterConditionPB::TextIsNot => cell_data != content,
+      TextFilterConditionPB::TextContains => cell_data.contains(&content),
+      TextFilterConditionPB::TextDoesNotContain => !cell_data.contains(&content),
+      TextFilterConditionPB::TextStartsWith => cell_data.starts_with(&content),
+      TextFilterConditionPB::TextEndsWith => cell_data.ends_with(&content),
+      _ => true,
+    }
+  }
+
+  /// Returns an error message if the filter's regular expression is invalid
+  pub fn validate(&self) -> Result<(), String> {
+    match self.pattern() {
+      Some(pattern) if !self.content.is_empty() => compile_regex(&pattern)
+        .map(|_| ())
+        .map_err(|err| format!("Invalid regular expression {}: {}", self.content, err)),
+      _ => Ok(()),
+    }
+  }
+
+  /// Returns the regular expression of the regex conditions, and of the whole word conditions.
+  /// The other conditions are compared as plain text.
+  fn pattern(&self) -> Option<String> {
+    let pattern = match self.condition {
+      TextFilterConditionPB::TextMatchesRegex | TextFilterConditionPB::TextDoesNotMatchRegex => {
+        self.content.clone()
+      },
+      TextFilterConditionPB::TextContains | TextFilterConditionPB::TextDoesNotContain
+        if self.whole_word =>
       {
-        true
+        format!(r"(?<!\w){}(?!\w)", fancy_regex::escape(&self.content))
+      },
+      TextFilterConditionPB::TextStartsWith if self.whole_word => {
+        format!(r"^{}(?!\w)", fancy_regex::escape(&self.content))
+      },
+      TextFilterConditionPB::TextEndsWith if self.whole_word => {
+        format!(r"(?<!\w){}$", fancy_regex::escape(&self.content))
       },
-      TextFilterConditionPB::TextIs => &cell_data == content,
-      TextFilterConditionPB::TextIsNot => &cell_data != content,
-      TextFilterConditionPB::TextContains => cell_data.contains(content),
-      TextFilterConditionPB::TextDoesNotContain => !cell_data.contains(content),
-      TextFilterConditionPB::TextStartsWith => cell_data.starts_with(content),
-      TextFilterConditionPB::TextEndsWith => cell_data.ends_with(content),
-      TextFilterConditionPB::TextIsEmpty => cell_data.is_empty(),
-      TextFilterConditionPB::TextIsNotEmpty => !cell_data.is_empty(),
+      _ => return None,
+    };
+
+    if self.case_sensitive {
+      Some(pattern)
+    } else {
+      Some(format!("(?i){}", pattern))
     }
   }
 }
 
+pub(crate) fn compile_regex(pattern: &str) -> Result<Arc<Regex>, fancy_regex::Error> {
+  let mut cache = REGEX_CACHE.lock().unwrap();
+  if let Some(regex) = cache.get(pattern) {
+    return Ok(regex.clone());
+  }
+
+  let regex = Arc::new(Regex::new(pattern)?);
+  if cache.len() >= REGEX_CACHE_CAPACITY {
+    cache.clear();
+  }
+  cache.insert(pattern.to_string(), regex.clone());
+  Ok(regex)
+}
+
 impl PreFillCellsWithFilter for TextFilterPB {
   fn get_compliant_cell(&self, field: &Field) -> Option<Cell> {
     let text = match self.condition {
@@ -60,6 +138,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIs,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy"), true);
@@ -71,6 +150,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIs,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy"), true);
@@ -84,6 +164,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextStartsWith,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy.io"), true);
@@ -94,6 +175,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextStartsWith,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("AppFlowy.io"), true);
@@ -107,6 +189,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextEndsWith,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -117,6 +200,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextEndsWith,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -129,6 +213,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIsEmpty,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible(""), true);
@@ -137,6 +222,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextIsEmpty,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible(""), true);
@@ -147,6 +233,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextContains,
       content: "appflowy".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -158,6 +245,7 @@ mod tests {
     let text_filter = TextFilterPB {
       condition: TextFilterConditionPB::TextContains,
       content: "".to_owned(),
+      ..Default::default()
     };
 
     assert_eq!(text_filter.is_visible("https://github.com/appflowy"), true);
@@ -166,4 +254,100 @@ mod tests {
     assert_eq!(text_filter.is_visible(""), true);
     assert_eq!(text_filter.is_visible("github"), true);
   }
+
+  #[test]
+  fn text_filter_case_sensitive_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextContains,
+      content: "AppFlowy".to_owned(),
+      case_sensitive: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("Welcome to AppFlowy"), true);
+    assert_eq!(text_filter.is_visible("Welcome to appflowy"), false);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextIs,
+      content: "AppFlowy".to_owned(),
+      case_sensitive: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("AppFlowy"), true);
+    assert_eq!(text_filter.is_visible("Appflowy"), false);
+  }
+
+  #[test]
+  fn text_filter_whole_word_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextContains,
+      content: "app".to_owned(),
+      whole_word: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("An App for notes"), true);
+    assert_eq!(text_filter.is_visible("app"), true);
+    assert_eq!(text_filter.is_visible("AppFlowy"), false);
+    assert_eq!(text_filter.is_visible("web-app."), true);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextDoesNotContain,
+      content: "app".to_owned(),
+      whole_word: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("AppFlowy"), true);
+    assert_eq!(text_filter.is_visible("An app"), false);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextStartsWith,
+      content: "c++".to_owned(),
+      whole_word: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("C++ compiler"), true);
+    assert_eq!(text_filter.is_visible("c++20"), false);
+  }
+
+  #[test]
+  fn text_filter_regex_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextMatchesRegex,
+      content: r"INC-\d+".to_owned(),
+      case_sensitive: true,
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.validate(), Ok(()));
+    assert_eq!(text_filter.is_visible("Follow up on INC-1024"), true);
+    assert_eq!(text_filter.is_visible("Follow up on inc-1024"), false);
+    assert_eq!(text_filter.is_visible("INC-"), false);
+
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextDoesNotMatchRegex,
+      content: r"^inc-\d+$".to_owned(),
+      ..Default::default()
+    };
+
+    assert_eq!(text_filter.is_visible("INC-1024"), false);
+    assert_eq!(text_filter.is_visible("INC-1024 duplicate"), true);
+  }
+
+  #[test]
+  fn text_filter_invalid_regex_test() {
+    let text_filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextMatchesRegex,
+      content: "INC-(".to_owned(),
+      ..Default::default()
+    };
+
+    assert!(text_filter.validate().is_err());
+    // the invalid pattern doesn't filter out any row
+    assert_eq!(text_filter.is_visible("INC-1024"), true);
+    assert_eq!(text_filter.is_visible(""), true);
+  }
 }
@@ -14,7 +14,7 @@ use crate::services::field::{
   TypeOption, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform, CELL_DATA,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 
 impl TypeOption for RichTextTypeOption {
   type CellData = StringCellData;
@@ -60,6 +60,8 @@ impl CellDataDecoder for RichTextTypeOption {
       | FieldType::Summary
       | FieldType::Translate
       | FieldType::Media
+      | FieldType::Formula
+      | FieldType::Rollup
       | FieldType::Time => Some(StringCellData::from(stringify_cell(cell, field))),
       FieldType::Checklist
       | FieldType::LastEditedTime
@@ -110,15 +112,15 @@ impl TypeOptionCellDataCompare for RichTextTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
       (true, true) => Ordering::Equal,
       (true, false) => Ordering::Greater,
       (false, true) => Ordering::Less,
       (false, false) => {
-        let order = cell_data.0.cmp(&other_cell_data.0);
-        sort_condition.evaluate_order(order)
+        let order = sort.cmp_text(&cell_data.0, &other_cell_data.0);
+        sort.condition.evaluate_order(order)
       },
     }
   }
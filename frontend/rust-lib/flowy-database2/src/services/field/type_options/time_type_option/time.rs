@@ -4,7 +4,7 @@ use crate::services::field::{
   TimeCellData, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
   TypeOptionCellDataSerde, TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use collab_database::fields::date_type_option::TimeTypeOption;
 
 use collab_database::rows::Cell;
@@ -87,9 +87,9 @@ impl TypeOptionCellDataCompare for TimeTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     let order = cell_data.0.cmp(&other_cell_data.0);
-    sort_condition.evaluate_order(order)
+    sort.condition.evaluate_order(order)
   }
 }
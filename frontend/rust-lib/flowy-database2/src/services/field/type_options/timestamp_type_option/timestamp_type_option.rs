@@ -4,7 +4,7 @@ use crate::services::field::{
   default_order, TimestampCellData, TypeOption, TypeOptionCellDataCompare,
   TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use collab_database::fields::timestamp_type_option::TimestampTypeOption;
 use collab_database::rows::Cell;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
@@ -88,12 +88,12 @@ impl TypeOptionCellDataCompare for TimestampTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (cell_data.timestamp, other_cell_data.timestamp) {
       (Some(left), Some(right)) => {
         let order = left.cmp(&right);
-        sort_condition.evaluate_order(order)
+        sort.condition.evaluate_order(order)
       },
       (Some(_), None) => Ordering::Less,
       (None, Some(_)) => Ordering::Greater,
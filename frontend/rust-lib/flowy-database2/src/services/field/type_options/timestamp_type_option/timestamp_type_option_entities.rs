@@ -1,10 +1,15 @@
+use bytes::Bytes;
 use collab::util::AnyMapExt;
 use collab_database::rows::{new_cell_builder, Cell};
+use flowy_error::{internal_error, FlowyResult};
 use serde::Serialize;
 
 use crate::{
-  entities::FieldType,
-  services::field::{TypeOptionCellData, CELL_DATA},
+  entities::{FieldType, TimestampCellDataPB},
+  services::{
+    cell::CellProtobufBlobParser,
+    field::{TypeOptionCellData, CELL_DATA},
+  },
 };
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -62,6 +67,15 @@ impl From<TimestampCellData> for Cell {
 
 impl TypeOptionCellData for TimestampCellData {}
 
+pub struct TimestampCellDataParser();
+impl CellProtobufBlobParser for TimestampCellDataParser {
+  type Object = TimestampCellDataPB;
+
+  fn parser(bytes: &Bytes) -> FlowyResult<Self::Object> {
+    TimestampCellDataPB::try_from(bytes.as_ref()).map_err(internal_error)
+  }
+}
+
 impl ToString for TimestampCellData {
   fn to_string(&self) -> String {
     serde_json::to_string(self).unwrap()
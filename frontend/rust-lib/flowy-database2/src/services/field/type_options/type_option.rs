@@ -1,16 +1,16 @@
 use crate::entities::{
-  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType, MediaTypeOptionPB,
-  MultiSelectTypeOptionPB, NumberTypeOptionPB, RelationTypeOptionPB, RichTextTypeOptionPB,
-  SingleSelectTypeOptionPB, SummarizationTypeOptionPB, TimeTypeOptionPB, TimestampTypeOptionPB,
-  TranslateTypeOptionPB, URLTypeOptionPB,
+  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType, FormulaTypeOptionPB,
+  MediaTypeOptionPB, MultiSelectTypeOptionPB, NumberTypeOptionPB, RelationTypeOptionPB,
+  RichTextTypeOptionPB, RollupTypeOptionPB, SingleSelectTypeOptionPB, SummarizationTypeOptionPB,
+  TimeTypeOptionPB, TimestampTypeOptionPB, TranslateTypeOptionPB, URLTypeOptionPB,
 };
 use crate::services::cell::CellDataDecoder;
 use crate::services::field::checklist_type_option::ChecklistTypeOption;
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
-use crate::services::field::RelationTypeOption;
+use crate::services::field::{FormulaTypeOption, RelationTypeOption, RollupTypeOption};
 use crate::services::filter::{ParseFilterData, PreFillCellsWithFilter};
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use async_trait::async_trait;
 use bytes::Bytes;
 use collab_database::database::Database;
@@ -81,6 +81,16 @@ pub trait TypeOptionCellDataSerde: TypeOption {
     cell_data: <Self as TypeOption>::CellData,
   ) -> <Self as TypeOption>::CellProtobufType;
 
+  /// Encode the cell data of the [Cell] into corresponding `Protobuf struct`. Override it when the
+  /// [Cell] stores data that isn't part of the [TypeOption::CellData].
+  fn protobuf_encode_cell(
+    &self,
+    _cell: &Cell,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    self.protobuf_encode(cell_data)
+  }
+
   /// Parse the opaque [Cell] to corresponding data struct.
   /// The [Cell] is a map that stores list of key/value data. Each [TypeOption::CellData]
   /// should implement the From<&Cell> trait to parse the [Cell] to corresponding data struct.
@@ -127,6 +137,17 @@ pub trait TypeOptionCellDataFilter: TypeOption + CellDataDecoder {
     filter: &<Self as TypeOption>::CellFilter,
     cell_data: &<Self as TypeOption>::CellData,
   ) -> bool;
+
+  /// Applies the filter to the cell data of the [Cell]. Override it when the filter depends on data
+  /// of the [Cell] that isn't part of the [TypeOption::CellData].
+  fn apply_filter_to_cell(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    _cell: &Cell,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    self.apply_filter(filter, cell_data)
+  }
 }
 
 #[inline(always)]
@@ -141,7 +162,7 @@ pub trait TypeOptionCellDataCompare: TypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering;
 
   /// Compares the two cells where one of the cells is None
@@ -149,7 +170,7 @@ pub trait TypeOptionCellDataCompare: TypeOption {
     &self,
     cell_data: Option<&<Self as TypeOption>::CellData>,
     other_cell_data: Option<&<Self as TypeOption>::CellData>,
-    _sort_condition: SortCondition,
+    _sort: &Sort,
   ) -> Ordering {
     match (cell_data, other_cell_data) {
       (None, Some(cell_data)) if !cell_data.is_cell_empty() => Ordering::Greater,
@@ -203,6 +224,12 @@ pub fn type_option_data_from_pb<T: Into<Bytes>>(
     FieldType::Media => {
       MediaTypeOptionPB::try_from(bytes).map(|pb| MediaTypeOption::from(pb).into())
     },
+    FieldType::Formula => {
+      FormulaTypeOptionPB::try_from(bytes).map(|pb| FormulaTypeOption::from(pb).into())
+    },
+    FieldType::Rollup => {
+      RollupTypeOptionPB::try_from(bytes).map(|pb| RollupTypeOption::from(pb).into())
+    },
   }
 }
 
@@ -286,6 +313,18 @@ pub fn type_option_to_pb(type_option: TypeOptionData, field_type: &FieldType) ->
         .try_into()
         .unwrap()
     },
+    FieldType::Formula => {
+      let formula_type_option: FormulaTypeOption = type_option.into();
+      FormulaTypeOptionPB::from(formula_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Rollup => {
+      let rollup_type_option: RollupTypeOption = type_option.into();
+      RollupTypeOptionPB::from(rollup_type_option)
+        .try_into()
+        .unwrap()
+    },
   }
 }
 
@@ -309,5 +348,7 @@ pub fn default_type_option_data_from_type(field_type: FieldType) -> TypeOptionDa
     FieldType::Translate => TranslateTypeOption::default().into(),
     FieldType::Time => TimeTypeOption.into(),
     FieldType::Media => MediaTypeOption::default().into(),
+    FieldType::Formula => FormulaTypeOption::default().into(),
+    FieldType::Rollup => RollupTypeOption::default().into(),
   }
 }
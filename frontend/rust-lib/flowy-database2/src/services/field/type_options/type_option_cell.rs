@@ -3,11 +3,11 @@ use crate::services::cell::{CellCache, CellDataChangeset, CellDataDecoder, CellP
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
 use crate::services::field::{
-  ChecklistTypeOption, RelationTypeOption, TypeOption, TypeOptionCellData,
-  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  ChecklistTypeOption, FormulaTypeOption, RelationTypeOption, RollupTypeOption, TypeOption,
+  TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
   TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use collab::preclude::Any;
 use collab_database::fields::checkbox_type_option::CheckboxTypeOption;
 use collab_database::fields::date_type_option::{DateTypeOption, TimeTypeOption};
@@ -64,13 +64,13 @@ pub trait TypeOptionCellDataHandler: Send + Sync + 'static {
   /// - `left_cell`: An optional reference to the left cell's data.
   /// - `right_cell`: An optional reference to the right cell's data.
   /// - `field`: A reference to the field information, which includes details about the field type.
-  /// - `sort_condition`: The condition that dictates the sort order based on the results of the comparison.
+  /// - `sort`: The sort whose condition dictates the sort order based on the results of the comparison.
   ///
   /// # Returns
   /// An `Ordering` indicating:
   /// - `Ordering::Equal` if both cells are `None` or if their decoded values are equal.
   /// - `Ordering::Less` or `Ordering::Greater` based on the `apply_cmp_with_uninitialized` or `apply_cmp`
-  ///   method results and the specified `sort_condition`.
+  ///   method results and the condition of the `sort`.
   ///
   /// # Note
   /// - If only one of the cells is `None`, the other cell is decoded, and the comparison is made using
@@ -81,7 +81,7 @@ pub trait TypeOptionCellDataHandler: Send + Sync + 'static {
     left_cell: Option<&Cell>,
     right_cell: Option<&Cell>,
     field: &Field,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering;
 
   fn handle_cell_filter(&self, field: &Field, cell: &Cell, filter: &BoxAny) -> bool;
@@ -270,7 +270,7 @@ where
   ) -> FlowyResult<CellProtobufBlob> {
     let cell_data = self.get_cell_data(cell, field_rev).unwrap_or_default();
 
-    CellProtobufBlob::from(self.protobuf_encode(cell_data))
+    CellProtobufBlob::from(self.protobuf_encode_cell(cell, cell_data))
   }
 
   fn handle_cell_changeset(
@@ -290,25 +290,25 @@ where
     left_cell: Option<&Cell>,
     right_cell: Option<&Cell>,
     field: &Field,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     match (left_cell, right_cell) {
       (None, None) => Ordering::Equal,
       (None, Some(right_cell)) => {
         let right_cell_data = self.get_cell_data(right_cell, field).unwrap_or_default();
 
-        self.apply_cmp_with_uninitialized(None, Some(right_cell_data).as_ref(), sort_condition)
+        self.apply_cmp_with_uninitialized(None, Some(right_cell_data).as_ref(), sort)
       },
       (Some(left_cell), None) => {
         let left_cell_data = self.get_cell_data(left_cell, field).unwrap_or_default();
 
-        self.apply_cmp_with_uninitialized(Some(left_cell_data).as_ref(), None, sort_condition)
+        self.apply_cmp_with_uninitialized(Some(left_cell_data).as_ref(), None, sort)
       },
       (Some(left_cell), Some(right_cell)) => {
         let left_cell_data = self.get_cell_data(left_cell, field).unwrap_or_default();
         let right_cell_data = self.get_cell_data(right_cell, field).unwrap_or_default();
 
-        self.apply_cmp(&left_cell_data, &right_cell_data, sort_condition)
+        self.apply_cmp(&left_cell_data, &right_cell_data, sort)
       },
     }
   }
@@ -317,7 +317,7 @@ where
     let perform_filter = || {
       let cell_filter = filter.downcast_ref::<T::CellFilter>()?;
       let cell_data = self.get_cell_data(cell, field).unwrap_or_default();
-      Some(self.apply_filter(cell_filter, &cell_data))
+      Some(self.apply_filter_to_cell(cell_filter, cell, &cell_data))
     };
 
     perform_filter().unwrap_or(true)
@@ -511,6 +511,26 @@ impl<'a> TypeOptionCellExt<'a> {
             self.cell_data_cache.clone(),
           )
         }),
+      FieldType::Formula => self
+        .field
+        .get_type_option::<FormulaTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Rollup => self
+        .field
+        .get_type_option::<RollupTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
     }
   }
 
@@ -594,4 +614,8 @@ impl RowSingleCellData {
     into_check_list_field_cell_data,
     <CheckboxTypeOption as TypeOption>::CellData
   );
+  into_cell_data!(
+    into_relation_field_cell_data,
+    <RelationTypeOption as TypeOption>::CellData
+  );
 }
@@ -4,7 +4,7 @@ use crate::services::field::{
   TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
   TypeOptionTransform,
 };
-use crate::services::sort::SortCondition;
+use crate::services::sort::Sort;
 use async_trait::async_trait;
 use collab_database::database::Database;
 use collab_database::fields::url_type_option::{URLCellData, URLTypeOption};
@@ -129,7 +129,7 @@ impl TypeOptionCellDataCompare for URLTypeOption {
     &self,
     cell_data: &<Self as TypeOption>::CellData,
     other_cell_data: &<Self as TypeOption>::CellData,
-    sort_condition: SortCondition,
+    sort: &Sort,
   ) -> Ordering {
     let is_left_empty = cell_data.data.is_empty();
     let is_right_empty = other_cell_data.data.is_empty();
@@ -138,8 +138,8 @@ impl TypeOptionCellDataCompare for URLTypeOption {
       (true, false) => Ordering::Greater,
       (false, true) => Ordering::Less,
       (false, false) => {
-        let order = cell_data.data.cmp(&other_cell_data.data);
-        sort_condition.evaluate_order(order)
+        let order = sort.cmp_text(&cell_data.data, &other_cell_data.data);
+        sort.condition.evaluate_order(order)
       },
     }
   }
@@ -9,6 +9,7 @@ use collab_database::fields::Field;
 use collab_database::rows::{Cell, Cells, Row, RowDetail, RowId};
 use dashmap::DashMap;
 use flowy_error::FlowyResult;
+use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
 use rayon::prelude::*;
 
@@ -20,7 +21,10 @@ use crate::entities::filter_entities::*;
 use crate::entities::{FieldType, InsertedRowPB, RowMetaPB};
 use crate::services::cell::CellCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
-use crate::services::field::{TimestampCellData, TimestampCellDataWrapper, TypeOptionCellExt};
+use crate::services::field::{
+  evaluate_formula, formula_value_from_row, parse_formula, FormulaContext, FormulaExpr,
+  FormulaValue, RelationTypeOption, TimestampCellData, TimestampCellDataWrapper, TypeOptionCellExt,
+};
 use crate::services::filter::{Filter, FilterChangeset, FilterInner, FilterResultNotification};
 
 #[async_trait]
@@ -31,6 +35,13 @@ pub trait FilterDelegate: Send + Sync + 'static {
   async fn get_row(&self, view_id: &str, rows_id: &RowId) -> Option<(usize, Arc<RowDetail>)>;
   async fn get_all_filters(&self, view_id: &str) -> Vec<Filter>;
   async fn save_filters(&self, view_id: &str, filters: &[Filter]);
+  /// Returns the ids of the rows of the related database that match the filter, or None if the
+  /// related database isn't opened
+  async fn get_related_row_ids(
+    &self,
+    database_id: &str,
+    filter: FilterDataPB,
+  ) -> Option<Vec<RowId>>;
 }
 
 pub trait PreFillCellsWithFilter {
@@ -42,6 +53,10 @@ pub struct FilterController {
   handler_id: String,
   delegate: Box<dyn FilterDelegate>,
   result_by_row_id: DashMap<RowId, bool>,
+  /// The ids of the related rows that match the filters of the related rows, keyed by the id of
+  /// the filter and the id of the related database. Cleared when the filters or the related
+  /// databases change.
+  related_row_ids_by_filter: DashMap<(String, String), Vec<RowId>>,
   cell_cache: CellCache,
   filters: RwLock<Vec<Filter>>,
   task_scheduler: Arc<TokioRwLock<TaskDispatcher>>,
@@ -104,6 +119,7 @@ impl FilterController {
       handler_id: handler_id.to_string(),
       delegate: Box::new(delegate),
       result_by_row_id: DashMap::default(),
+      related_row_ids_by_filter: DashMap::default(),
       cell_cache,
       filters: RwLock::new(filters),
       task_scheduler,
@@ -136,6 +152,21 @@ impl FilterController {
     self.task_scheduler.write().await.add_task(task);
   }
 
+  /// Filters the rows again if any of the filters is relative to the current date
+  pub async fn did_change_day(&self) {
+    let has_relative_filters = self
+      .filters
+      .read()
+      .await
+      .iter()
+      .any(has_relative_date_filter);
+    if has_relative_filters {
+      self
+        .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
+        .await
+    }
+  }
+
   pub async fn did_receive_row_changed(&self, row_id: RowId) {
     if !self.filters.read().await.is_empty() {
       self
@@ -202,10 +233,13 @@ impl FilterController {
         }
       },
       FilterChangeset::Delete { filter_id } => Self::delete_filter(&mut filters, &filter_id),
-      FilterChangeset::DeleteAllWithFieldId { field_id } => {
+      FilterChangeset::DeleteAllWithFieldId {
+        field_id,
+        field_name,
+      } => {
         let mut filter_ids = vec![];
         for filter in filters.iter() {
-          filter.find_all_filters_with_field_id(&field_id, &mut filter_ids);
+          filter.find_all_filters_with_field_id(&field_id, &field_name, &mut filter_ids);
         }
         for filter_id in filter_ids {
           Self::delete_filter(&mut filters, &filter_id)
@@ -214,6 +248,7 @@ impl FilterController {
     }
 
     self.delegate.save_filters(&self.view_id, &filters).await;
+    self.related_row_ids_by_filter.clear();
 
     self
       .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
@@ -286,6 +321,10 @@ impl FilterController {
               let filter = condition_and_content.cloned::<TimeFilterPB>().unwrap();
               filter.get_compliant_cell(field)
             },
+            FieldType::Relation => {
+              let filter = condition_and_content.cloned::<RelationFilterPB>().unwrap();
+              filter.get_compliant_cell(field)
+            },
             _ => None,
           };
 
@@ -321,12 +360,14 @@ impl FilterController {
 
     if let Some((_, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
       let field_by_field_id = self.get_field_map().await;
+      let resolved_filters = self.resolve_filters(&filters, &field_by_field_id).await;
       let mut notification = FilterResultNotification::new(self.view_id.clone());
       if filter_row(
         &row_detail.row,
         &self.result_by_row_id,
         &field_by_field_id,
         &self.cell_cache,
+        &resolved_filters,
         &filters,
       ) {
         if let Some((index, _row)) = self.delegate.get_row(&self.view_id, &row_id).await {
@@ -349,6 +390,7 @@ impl FilterController {
   pub async fn filter_rows_and_notify(&self, rows: &mut Vec<Arc<Row>>) -> FlowyResult<()> {
     let filters = self.filters.read().await;
     let field_by_field_id = self.get_field_map().await;
+    let resolved_filters = self.resolve_filters(&filters, &field_by_field_id).await;
     let (visible_rows, invisible_rows): (Vec<_>, Vec<_>) =
       rows.par_iter().enumerate().partition_map(|(index, row)| {
         if filter_row(
@@ -356,6 +398,7 @@ impl FilterController {
           &self.result_by_row_id,
           &field_by_field_id,
           &self.cell_cache,
+          &resolved_filters,
           &filters,
         ) {
           let row_meta = RowMetaPB::from(row.as_ref());
@@ -385,12 +428,14 @@ impl FilterController {
   pub async fn filter_rows(&self, mut rows: Vec<Arc<Row>>) -> Vec<Arc<Row>> {
     let filters = self.filters.read().await;
     let field_by_field_id = self.get_field_map().await;
+    let resolved_filters = self.resolve_filters(&filters, &field_by_field_id).await;
     rows.par_iter().for_each(|row| {
       let _ = filter_row(
         row,
         &self.result_by_row_id,
         &field_by_field_id,
         &self.cell_cache,
+        &resolved_filters,
         &filters,
       );
     });
@@ -407,6 +452,89 @@ impl FilterController {
     rows
   }
 
+  /// Prepares the filters for a filter pass. The filters of the related rows are evaluated against
+  /// the related databases, each of them is replaced by a filter on the ids of the related rows
+  /// that match it. The expressions of the formula filters are parsed once instead of per row.
+  async fn resolve_filters(
+    &self,
+    filters: &[Filter],
+    field_by_field_id: &HashMap<String, Field>,
+  ) -> HashMap<String, BoxAny> {
+    let mut related_row_filters = vec![];
+    for filter in filters.iter() {
+      get_related_row_filters(filter, &mut related_row_filters);
+    }
+
+    let mut resolved_filters = HashMap::new();
+    for (filter_id, field_id, relation_filter) in related_row_filters {
+      let database_id = match get_related_database_id(field_by_field_id.get(field_id)) {
+        Some(database_id) => database_id,
+        None => continue,
+      };
+      let cache_key = (filter_id.to_string(), database_id);
+      let row_ids = match self.related_row_ids_by_filter.get(&cache_key) {
+        Some(row_ids) => Some(row_ids.clone()),
+        None => match relation_filter.related_filter {
+          Some(related_filter) => {
+            let row_ids = self
+              .delegate
+              .get_related_row_ids(&cache_key.1, related_filter)
+              .await;
+            if let Some(row_ids) = row_ids.as_ref() {
+              self
+                .related_row_ids_by_filter
+                .insert(cache_key.clone(), row_ids.clone());
+            }
+            row_ids
+          },
+          None => None,
+        },
+      };
+      if let Some(row_ids) = row_ids {
+        let resolved_filter = RelationFilterPB {
+          condition: RelationFilterConditionPB::RelatedRowMatches,
+          row_ids: row_ids
+            .into_iter()
+            .map(|row_id| row_id.to_string())
+            .collect(),
+          related_filter: None,
+        };
+        resolved_filters.insert(filter_id.to_string(), BoxAny::new(resolved_filter));
+      }
+    }
+    for filter in filters.iter() {
+      parse_formula_filters(filter, &mut resolved_filters);
+    }
+    resolved_filters
+  }
+
+  /// Filters the rows again if any of the filters depends on the rows of the given database
+  pub async fn did_update_related_database(&self, database_id: &str) {
+    let is_related = {
+      let filters = self.filters.read().await;
+      let mut related_row_filters = vec![];
+      for filter in filters.iter() {
+        get_related_row_filters(filter, &mut related_row_filters);
+      }
+      if related_row_filters.is_empty() {
+        return;
+      }
+
+      let field_by_field_id = self.get_field_map().await;
+      related_row_filters.iter().any(|(_, field_id, _)| {
+        get_related_database_id(field_by_field_id.get(*field_id)).as_deref() == Some(database_id)
+      })
+    };
+    if is_related {
+      self
+        .related_row_ids_by_filter
+        .retain(|(_, related_database_id), _| related_database_id != database_id);
+      self
+        .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
+        .await
+    }
+  }
+
   async fn get_field_map(&self) -> HashMap<String, Field> {
     self
       .delegate
@@ -450,6 +578,7 @@ fn filter_row(
   result_by_row_id: &DashMap<RowId, bool>,
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
+  resolved_filters: &HashMap<String, BoxAny>,
   filters: &Vec<Filter>,
 ) -> bool {
   // Create a filter result cache if it doesn't exist
@@ -457,7 +586,13 @@ fn filter_row(
   let mut new_is_visible = true;
 
   for filter in filters {
-    if let Some(is_visible) = apply_filter(row, field_by_field_id, cell_data_cache, filter) {
+    if let Some(is_visible) = apply_filter(
+      row,
+      field_by_field_id,
+      cell_data_cache,
+      resolved_filters,
+      filter,
+    ) {
       new_is_visible = new_is_visible && is_visible;
       // short-circuit as soon as one filter tree returns false
       if !new_is_visible {
@@ -470,11 +605,110 @@ fn filter_row(
   new_is_visible
 }
 
-/// Recursively applies a `Filter` to a `Row`'s cells.
+/// Returns true if the filter depends on the current date, like the relative date conditions and
+/// the formulas that call `today()` or `now()`
+fn has_relative_date_filter(filter: &Filter) -> bool {
+  match &filter.inner {
+    FilterInner::And { children } | FilterInner::Or { children } => {
+      children.iter().any(has_relative_date_filter)
+    },
+    FilterInner::Data {
+      field_type,
+      condition_and_content,
+      ..
+    } => {
+      matches!(
+        field_type,
+        FieldType::DateTime | FieldType::CreatedTime | FieldType::LastEditedTime
+      ) && condition_and_content
+        .cloned::<DateFilterPB>()
+        .is_some_and(|filter| filter.condition.is_relative())
+    },
+    FilterInner::FieldComparison { .. } => false,
+    FilterInner::Formula { expression } => parse_formula(expression)
+      .map(|expr| expr.calls_function("today") || expr.calls_function("now"))
+      .unwrap_or(false),
+  }
+}
+
+fn get_related_database_id(field: Option<&Field>) -> Option<String> {
+  field
+    .and_then(|field| field.get_type_option::<RelationTypeOption>(FieldType::Relation))
+    .map(|type_option| type_option.database_id)
+}
+
+/// Returns the relation filters whose related rows must match a filter of the related database
+fn get_related_row_filters<'a>(
+  filter: &'a Filter,
+  related_row_filters: &mut Vec<(&'a str, &'a str, RelationFilterPB)>,
+) {
+  match &filter.inner {
+    FilterInner::And { children } | FilterInner::Or { children } => {
+      for child in children.iter() {
+        get_related_row_filters(child, related_row_filters);
+      }
+    },
+    FilterInner::Data {
+      field_id,
+      field_type: FieldType::Relation,
+      condition_and_content,
+    } => {
+      if let Some(relation_filter) = condition_and_content
+        .cloned::<RelationFilterPB>()
+        .filter(|filter| filter.condition == RelationFilterConditionPB::RelatedRowMatches)
+      {
+        related_row_filters.push((filter.id.as_str(), field_id.as_str(), relation_filter));
+      }
+    },
+    FilterInner::Data { .. }
+    | FilterInner::FieldComparison { .. }
+    | FilterInner::Formula { .. } => {},
+  }
+}
+
+/// Parses the expressions of the formula filters. The parsed expressions are keyed by the id of
+/// their filter, see [apply_filter].
+pub(crate) fn parse_formula_filters(filter: &Filter, parsed_filters: &mut HashMap<String, BoxAny>) {
+  match &filter.inner {
+    FilterInner::And { children } | FilterInner::Or { children } => {
+      for child in children.iter() {
+        parse_formula_filters(child, parsed_filters);
+      }
+    },
+    FilterInner::Formula { expression } => {
+      parsed_filters.insert(filter.id.clone(), BoxAny::new(parse_formula(expression)));
+    },
+    FilterInner::Data { .. } | FilterInner::FieldComparison { .. } => {},
+  }
+}
+
+/// Returns true if the row isn't filtered out by the filter. The filters that depend on other
+/// databases aren't applied. The `parsed_filters` are the formula filters parsed by
+/// [parse_formula_filters].
+pub(crate) fn is_row_visible(
+  row: &Row,
+  field_by_field_id: &HashMap<String, Field>,
+  cell_data_cache: &CellCache,
+  parsed_filters: &HashMap<String, BoxAny>,
+  filter: &Filter,
+) -> bool {
+  apply_filter(
+    row,
+    field_by_field_id,
+    cell_data_cache,
+    parsed_filters,
+    filter,
+  )
+  .unwrap_or(true)
+}
+
+/// Recursively applies a `Filter` to a `Row`'s cells. The `resolved_filters` replace the content
+/// of the filters with the same id, see [FilterController::resolve_filters].
 fn apply_filter(
   row: &Row,
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
+  resolved_filters: &HashMap<String, BoxAny>,
   filter: &Filter,
 ) -> Option<bool> {
   match &filter.inner {
@@ -483,7 +717,13 @@ fn apply_filter(
         return None;
       }
       for child_filter in children.iter() {
-        if let Some(false) = apply_filter(row, field_by_field_id, cell_data_cache, child_filter) {
+        if let Some(false) = apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          resolved_filters,
+          child_filter,
+        ) {
           return Some(false);
         }
       }
@@ -494,7 +734,13 @@ fn apply_filter(
         return None;
       }
       for child_filter in children.iter() {
-        if let Some(true) = apply_filter(row, field_by_field_id, cell_data_cache, child_filter) {
+        if let Some(true) = apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          resolved_filters,
+          child_filter,
+        ) {
           return Some(true);
         }
       }
@@ -530,6 +776,9 @@ fn apply_filter(
         _ => None,
       };
       let cell = timestamp_cell.or_else(|| row.cells.get(field_id).cloned());
+      let condition_and_content = resolved_filters
+        .get(&filter.id)
+        .unwrap_or(condition_and_content);
       if let Some(handler) = TypeOptionCellExt::new(field, Some(cell_data_cache.clone()))
         .get_type_option_cell_data_handler()
       {
@@ -538,6 +787,67 @@ fn apply_filter(
         Some(true)
       }
     },
+    FilterInner::FieldComparison {
+      field_id,
+      other_field_id,
+      condition,
+    } => {
+      let (field, other_field) = match (
+        field_by_field_id.get(field_id),
+        field_by_field_id.get(other_field_id),
+      ) {
+        (Some(field), Some(other_field)) => (field, other_field),
+        _ => {
+          tracing::error!("cannot find the compared fields");
+          return Some(false);
+        },
+      };
+      Some(condition.is_visible(
+        &formula_value_from_row(row, field),
+        &formula_value_from_row(row, other_field),
+      ))
+    },
+    FilterInner::Formula { expression } => {
+      let ctx = RowFilterFormulaContext {
+        row,
+        field_by_field_id,
+      };
+      // The formulas that weren't parsed beforehand are parsed here
+      let parsed_expr = resolved_filters
+        .get(&filter.id)
+        .and_then(|parsed| parsed.downcast_ref::<FlowyResult<FormulaExpr>>());
+      let result = match parsed_expr {
+        Some(Ok(expr)) => expr.evaluate(&ctx),
+        Some(Err(err)) => Err(err.clone()),
+        None => evaluate_formula(expression, &ctx),
+      };
+      // The rows for which the formula can't be evaluated are hidden
+      match result {
+        Ok(value) => Some(value.as_bool()),
+        Err(err) => {
+          trace!("[Filter]: failed to evaluate {}: {}", expression, err);
+          Some(false)
+        },
+      }
+    },
+  }
+}
+
+/// Resolves the fields referenced by a formula filter, by id or by name
+struct RowFilterFormulaContext<'a> {
+  row: &'a Row,
+  field_by_field_id: &'a HashMap<String, Field>,
+}
+
+impl FormulaContext for RowFilterFormulaContext<'_> {
+  fn field_value(&self, field_name_or_id: &str) -> Option<FormulaValue> {
+    let field = self.field_by_field_id.get(field_name_or_id).or_else(|| {
+      self
+        .field_by_field_id
+        .values()
+        .find(|field| field.name == field_name_or_id)
+    })?;
+    Some(formula_value_from_row(self.row, field))
   }
 }
 
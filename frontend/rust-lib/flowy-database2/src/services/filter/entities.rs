@@ -14,10 +14,11 @@ use lib_infra::box_any::BoxAny;
 use tracing::error;
 
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterContent, DateFilterPB, FieldType, FilterType,
-  InsertedRowPB, MediaFilterPB, NumberFilterPB, RelationFilterPB, SelectOptionFilterPB,
-  TextFilterPB, TimeFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterContent, DateFilterPB, FieldComparisonConditionPB,
+  FieldType, FilterType, InsertedRowPB, MediaFilterPB, NumberFilterPB, RelationFilterContent,
+  RelationFilterPB, SelectOptionFilterPB, TextFilterPB, TimeFilterPB,
 };
+use crate::services::field::parse_formula;
 
 pub trait ParseFilterData {
   fn parse(condition: u8, content: String) -> Self;
@@ -38,7 +39,9 @@ impl Filter {
         .iter()
         .map(|filter| filter.is_empty())
         .all(|is_empty| is_empty),
-      FilterInner::Data { .. } => false,
+      FilterInner::Data { .. }
+      | FilterInner::FieldComparison { .. }
+      | FilterInner::Formula { .. } => false,
     }
   }
 
@@ -57,7 +60,9 @@ impl Filter {
         }
         None
       },
-      FilterInner::Data { .. } => None,
+      FilterInner::Data { .. }
+      | FilterInner::FieldComparison { .. }
+      | FilterInner::Formula { .. } => None,
     }
   }
 
@@ -80,7 +85,9 @@ impl Filter {
         }
         None
       },
-      FilterInner::Data { .. } => None,
+      FilterInner::Data { .. }
+      | FilterInner::FieldComparison { .. }
+      | FilterInner::Formula { .. } => None,
     }
   }
 
@@ -103,21 +110,32 @@ impl Filter {
         };
         Ok(None)
       },
-      (FilterInner::Data { .. }, FilterType::And) => {
+      (
+        FilterInner::Data { .. }
+        | FilterInner::FieldComparison { .. }
+        | FilterInner::Formula { .. },
+        FilterType::And,
+      ) => {
         let mut inner = FilterInner::And { children: vec![] };
         mem::swap(&mut self.inner, &mut inner);
         Ok(Some(inner))
       },
-      (FilterInner::Data { .. }, FilterType::Or) => {
+      (
+        FilterInner::Data { .. }
+        | FilterInner::FieldComparison { .. }
+        | FilterInner::Formula { .. },
+        FilterType::Or,
+      ) => {
         let mut inner = FilterInner::Or { children: vec![] };
         mem::swap(&mut self.inner, &mut inner);
         Ok(Some(inner))
       },
-      (_, FilterType::Data) => {
-        // from And/Or to Data
+      (_, FilterType::Data | FilterType::FieldComparison | FilterType::Formula) => {
+        // from And/Or to a filter that can't have children
         Err(FlowyError::internal().with_context(format!(
-          "conversion from {:?} to FilterType::Data not supported",
-          FilterType::from(&self.inner)
+          "conversion from {:?} to {:?} not supported",
+          FilterType::from(&self.inner),
+          filter_type
         )))
       },
       _ => {
@@ -136,7 +154,9 @@ impl Filter {
       FilterInner::And { children } | FilterInner::Or { children } => {
         children.push(filter);
       },
-      FilterInner::Data { .. } => {
+      FilterInner::Data { .. }
+      | FilterInner::FieldComparison { .. }
+      | FilterInner::Formula { .. } => {
         // convert to FilterInner::And by default
         let old_filter = self
           .convert_to_and_or_filter_type(FilterType::And)
@@ -183,19 +203,29 @@ impl Filter {
           FlowyError::internal()
             .with_context(format!("filter with filter_id {:?} not found", filter_id))
         }),
-      FilterInner::Data { .. } => Err(
-        FlowyError::internal().with_context("unexpected parent filter type of FilterInner::Data"),
-      ),
+      FilterInner::Data { .. }
+      | FilterInner::FieldComparison { .. }
+      | FilterInner::Formula { .. } => Err(FlowyError::internal().with_context(format!(
+        "unexpected parent filter type {:?}",
+        FilterType::from(&self.inner)
+      ))),
     }
   }
 
-  /// Recursively finds any Data filter whose `field_id` is equal to `matching_field_id`. Any found
-  /// filters' id is appended to the `ids` vector.
-  pub fn find_all_filters_with_field_id(&self, matching_field_id: &str, ids: &mut Vec<String>) {
+  /// Recursively finds any Data filter whose `field_id` is equal to `matching_field_id`, and any
+  /// field comparison or formula filter that reads the field. Formulas reference the field by its
+  /// id or by its name, `matching_field_name`. Any found filters' id is appended to the `ids`
+  /// vector.
+  pub fn find_all_filters_with_field_id(
+    &self,
+    matching_field_id: &str,
+    matching_field_name: &str,
+    ids: &mut Vec<String>,
+  ) {
     match &self.inner {
       FilterInner::And { children } | FilterInner::Or { children } => {
         for child_filter in children.iter() {
-          child_filter.find_all_filters_with_field_id(matching_field_id, ids);
+          child_filter.find_all_filters_with_field_id(matching_field_id, matching_field_name, ids);
         }
       },
       FilterInner::Data { field_id, .. } => {
@@ -203,6 +233,28 @@ impl Filter {
           ids.push(self.id.clone());
         }
       },
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        ..
+      } => {
+        if field_id == matching_field_id || other_field_id == matching_field_id {
+          ids.push(self.id.clone());
+        }
+      },
+      FilterInner::Formula { expression } => {
+        let is_referenced = parse_formula(expression)
+          .map(|expr| {
+            expr
+              .field_references()
+              .iter()
+              .any(|reference| reference == matching_field_id || reference == matching_field_name)
+          })
+          .unwrap_or(false);
+        if is_referenced {
+          ids.push(self.id.clone());
+        }
+      },
     }
   }
 
@@ -210,7 +262,7 @@ impl Filter {
   /// filters are appended to the `min_effective_filters` vector. The following rules are followed
   /// when determining if a filter should get included. If the current filter is:
   ///
-  /// 1. a Data filter, then it should be included.
+  /// 1. a Data, field comparison or formula filter, then it should be included.
   /// 2. an AND filter, then all of its effective children should be
   /// included.
   /// 3. an OR filter, then only the first child should be included.
@@ -226,11 +278,14 @@ impl Filter {
           filter.get_min_effective_filters(min_effective_filters);
         }
       },
-      FilterInner::Data { .. } => min_effective_filters.push(&self.inner),
+      FilterInner::Data { .. }
+      | FilterInner::FieldComparison { .. }
+      | FilterInner::Formula { .. } => min_effective_filters.push(&self.inner),
     }
   }
 
-  /// Recursively get all of the filtering field ids and the associated filter_ids
+  /// Recursively get all of the filtering field ids and the associated filter_ids. The fields of
+  /// the formula filters aren't included because they may be referenced by their names.
   pub fn get_all_filtering_field_ids(&self, field_ids: &mut HashMap<String, Vec<String>>) {
     match &self.inner {
       FilterInner::And { children } | FilterInner::Or { children } => {
@@ -244,6 +299,19 @@ impl Filter {
           .and_modify(|filter_ids| filter_ids.push(self.id.clone()))
           .or_insert_with(|| vec![self.id.clone()]);
       },
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        ..
+      } => {
+        for field_id in [field_id, other_field_id] {
+          field_ids
+            .entry(field_id.clone())
+            .and_modify(|filter_ids| filter_ids.push(self.id.clone()))
+            .or_insert_with(|| vec![self.id.clone()]);
+        }
+      },
+      FilterInner::Formula { .. } => {},
     }
   }
 }
@@ -261,6 +329,16 @@ pub enum FilterInner {
     field_type: FieldType,
     condition_and_content: BoxAny,
   },
+  /// Compares the cells of two fields of the same row
+  FieldComparison {
+    field_id: String,
+    other_field_id: String,
+    condition: FieldComparisonConditionPB,
+  },
+  /// Keeps the rows for which the expression evaluates to true, see [parse_formula]
+  Formula {
+    expression: String,
+  },
 }
 
 impl FilterInner {
@@ -296,6 +374,8 @@ impl FilterInner {
       FieldType::Translate => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
       FieldType::Time => BoxAny::new(TimeFilterPB::parse(condition as u8, content)),
       FieldType::Media => BoxAny::new(MediaFilterPB::parse(condition as u8, content)),
+      FieldType::Formula => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
+      FieldType::Rollup => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
     };
 
     FilterInner::Data {
@@ -310,6 +390,8 @@ impl FilterInner {
       FilterInner::And { .. } => FILTER_AND_INDEX,
       FilterInner::Or { .. } => FILTER_OR_INDEX,
       FilterInner::Data { .. } => FILTER_DATA_INDEX,
+      FilterInner::FieldComparison { .. } => FILTER_FIELD_COMPARISON_INDEX,
+      FilterInner::Formula { .. } => FILTER_FORMULA_INDEX,
     }
   }
 }
@@ -318,13 +400,19 @@ const FILTER_ID: &str = "id";
 const FILTER_TYPE: &str = "filter_type";
 const FIELD_ID: &str = "field_id";
 const FIELD_TYPE: &str = "ty";
+const OTHER_FIELD_ID: &str = "other_field_id";
 const FILTER_CONDITION: &str = "condition";
 const FILTER_CONTENT: &str = "content";
 const FILTER_CHILDREN: &str = "children";
+const FILTER_EXPRESSION: &str = "expression";
+const FILTER_CASE_SENSITIVE: &str = "case_sensitive";
+const FILTER_WHOLE_WORD: &str = "whole_word";
 
 const FILTER_AND_INDEX: i64 = 0;
 const FILTER_OR_INDEX: i64 = 1;
 const FILTER_DATA_INDEX: i64 = 2;
+const FILTER_FIELD_COMPARISON_INDEX: i64 = 3;
+const FILTER_FORMULA_INDEX: i64 = 4;
 
 impl<'a> From<&'a Filter> for FilterMap {
   fn from(filter: &'a Filter) -> Self {
@@ -348,72 +436,42 @@ impl<'a> From<&'a Filter> for FilterMap {
         field_type,
         condition_and_content,
       } => {
-        let get_raw_condition_and_content = || -> Option<(u8, String)> {
-          let (condition, content) = match field_type {
-            FieldType::RichText | FieldType::URL => {
-              let filter = condition_and_content.cloned::<TextFilterPB>()?;
-              (filter.condition as u8, filter.content)
-            },
-            FieldType::Number => {
-              let filter = condition_and_content.cloned::<NumberFilterPB>()?;
-              (filter.condition as u8, filter.content)
-            },
-            FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
-              let filter = condition_and_content.cloned::<DateFilterPB>()?;
-              let content = DateFilterContent {
-                start: filter.start,
-                end: filter.end,
-                timestamp: filter.timestamp,
-              }
-              .to_string();
-              (filter.condition as u8, content)
-            },
-            FieldType::SingleSelect | FieldType::MultiSelect => {
-              let filter = condition_and_content.cloned::<SelectOptionFilterPB>()?;
-              let content = SelectOptionIds::from(filter.option_ids).to_string();
-              (filter.condition as u8, content)
-            },
-            FieldType::Checkbox => {
-              let filter = condition_and_content.cloned::<CheckboxFilterPB>()?;
-              (filter.condition as u8, "".to_string())
-            },
-            FieldType::Checklist => {
-              let filter = condition_and_content.cloned::<ChecklistFilterPB>()?;
-              (filter.condition as u8, "".to_string())
-            },
-            FieldType::Relation => {
-              let filter = condition_and_content.cloned::<RelationFilterPB>()?;
-              (filter.condition as u8, "".to_string())
-            },
-            FieldType::Summary => {
-              let filter = condition_and_content.cloned::<TextFilterPB>()?;
-              (filter.condition as u8, filter.content)
-            },
-            FieldType::Time => {
-              let filter = condition_and_content.cloned::<TimeFilterPB>()?;
-              (filter.condition as u8, filter.content)
-            },
-            FieldType::Translate => {
-              let filter = condition_and_content.cloned::<TextFilterPB>()?;
-              (filter.condition as u8, filter.content)
-            },
-            FieldType::Media => {
-              let filter = condition_and_content.cloned::<MediaFilterPB>()?;
-              (filter.condition as u8, filter.content)
-            },
-          };
-          Some((condition, content))
-        };
-
-        let (condition, content) = get_raw_condition_and_content().unwrap_or_else(|| {
-          tracing::error!("cannot deserialize filter condition and content filter properly");
-          Default::default()
-        });
+        let (condition, content) = get_raw_condition_and_content(field_type, condition_and_content)
+          .unwrap_or_else(|| {
+            tracing::error!("cannot deserialize filter condition and content filter properly");
+            Default::default()
+          });
 
         builder.insert(FIELD_ID.into(), field_id.as_str().into());
         builder.insert(FIELD_TYPE.into(), Any::BigInt(i64::from(field_type)));
         builder.insert(FILTER_CONDITION.into(), Any::BigInt(condition as i64));
         builder.insert(FILTER_CONTENT.into(), content.into());
+        // The text filter options are stored next to the content to keep the content readable by
+        // the versions without them
+        if let Some(text_filter) = condition_and_content.downcast_ref::<TextFilterPB>() {
+          builder.insert(
+            FILTER_CASE_SENSITIVE.into(),
+            Any::Bool(text_filter.case_sensitive),
+          );
+          builder.insert(FILTER_WHOLE_WORD.into(), Any::Bool(text_filter.whole_word));
+        }
+        builder
+      },
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        condition,
+      } => {
+        builder.insert(FIELD_ID.into(), field_id.as_str().into());
+        builder.insert(OTHER_FIELD_ID.into(), other_field_id.as_str().into());
+        builder.insert(
+          FILTER_CONDITION.into(),
+          Any::BigInt(condition.clone() as i64),
+        );
+        builder
+      },
+      FilterInner::Formula { expression } => {
+        builder.insert(FILTER_EXPRESSION.into(), expression.as_str().into());
         builder
       },
     };
@@ -422,6 +480,69 @@ impl<'a> From<&'a Filter> for FilterMap {
   }
 }
 
+/// Returns the condition and the content of the data filter as they are stored in the [FilterMap]
+pub(crate) fn get_raw_condition_and_content(
+  field_type: &FieldType,
+  condition_and_content: &BoxAny,
+) -> Option<(u8, String)> {
+  let (condition, content) = match field_type {
+    FieldType::RichText | FieldType::URL => {
+      let filter = condition_and_content.cloned::<TextFilterPB>()?;
+      (filter.condition as u8, filter.content)
+    },
+    FieldType::Number => {
+      let filter = condition_and_content.cloned::<NumberFilterPB>()?;
+      (filter.condition as u8, filter.content)
+    },
+    FieldType::DateTime | FieldType::LastEditedTime | FieldType::CreatedTime => {
+      let filter = condition_and_content.cloned::<DateFilterPB>()?;
+      let content = DateFilterContent {
+        start: filter.start,
+        end: filter.end,
+        timestamp: filter.timestamp,
+        num_of_days: filter.num_of_days,
+      }
+      .to_string();
+      (filter.condition as u8, content)
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let filter = condition_and_content.cloned::<SelectOptionFilterPB>()?;
+      let content = SelectOptionIds::from(filter.option_ids).to_string();
+      (filter.condition as u8, content)
+    },
+    FieldType::Checkbox => {
+      let filter = condition_and_content.cloned::<CheckboxFilterPB>()?;
+      (filter.condition as u8, "".to_string())
+    },
+    FieldType::Checklist => {
+      let filter = condition_and_content.cloned::<ChecklistFilterPB>()?;
+      (filter.condition as u8, "".to_string())
+    },
+    FieldType::Relation => {
+      let filter = condition_and_content.cloned::<RelationFilterPB>()?;
+      let content = RelationFilterContent::from(&filter).to_string();
+      (filter.condition as u8, content)
+    },
+    FieldType::Summary => {
+      let filter = condition_and_content.cloned::<TextFilterPB>()?;
+      (filter.condition as u8, filter.content)
+    },
+    FieldType::Time => {
+      let filter = condition_and_content.cloned::<TimeFilterPB>()?;
+      (filter.condition as u8, filter.content)
+    },
+    FieldType::Translate | FieldType::Formula | FieldType::Rollup => {
+      let filter = condition_and_content.cloned::<TextFilterPB>()?;
+      (filter.condition as u8, filter.content)
+    },
+    FieldType::Media => {
+      let filter = condition_and_content.cloned::<MediaFilterPB>()?;
+      (filter.condition as u8, filter.content)
+    },
+  };
+  Some((condition, content))
+}
+
 impl TryFrom<FilterMap> for Filter {
   type Error = anyhow::Error;
 
@@ -451,7 +572,29 @@ impl TryFrom<FilterMap> for Filter {
           let condition: i64 = filter_map.get_as(FILTER_CONDITION).unwrap_or_default();
           let content: String = filter_map.get_as(FILTER_CONTENT).unwrap_or_default();
 
-          FilterInner::new_data(field_id, field_type, condition, content)
+          let inner = FilterInner::new_data(field_id, field_type, condition, content);
+          with_text_filter_options(inner, &filter_map)
+        },
+        FILTER_FIELD_COMPARISON_INDEX => {
+          let field_id: String = filter_map
+            .get_as(FIELD_ID)
+            .ok_or_else(|| anyhow::anyhow!("invalid filter data"))?;
+          let other_field_id: String = filter_map
+            .get_as(OTHER_FIELD_ID)
+            .ok_or_else(|| anyhow::anyhow!("invalid filter data"))?;
+          let condition: i64 = filter_map.get_as(FILTER_CONDITION).unwrap_or_default();
+
+          FilterInner::FieldComparison {
+            field_id,
+            other_field_id,
+            condition: FieldComparisonConditionPB::try_from(condition as u8).unwrap_or_default(),
+          }
+        },
+        FILTER_FORMULA_INDEX => {
+          let expression: String = filter_map
+            .get_as(FILTER_EXPRESSION)
+            .ok_or_else(|| anyhow::anyhow!("invalid filter data"))?;
+          FilterInner::Formula { expression }
         },
         _ => bail!("Unsupported filter type"),
       },
@@ -461,6 +604,32 @@ impl TryFrom<FilterMap> for Filter {
   }
 }
 
+/// Reads the options of the text filters that are stored next to their content
+fn with_text_filter_options(inner: FilterInner, filter_map: &FilterMap) -> FilterInner {
+  match inner {
+    FilterInner::Data {
+      field_id,
+      field_type,
+      condition_and_content,
+    } => {
+      let condition_and_content = match condition_and_content.cloned::<TextFilterPB>() {
+        Some(text_filter) => BoxAny::new(TextFilterPB {
+          case_sensitive: filter_map.get_as(FILTER_CASE_SENSITIVE).unwrap_or_default(),
+          whole_word: filter_map.get_as(FILTER_WHOLE_WORD).unwrap_or_default(),
+          ..text_filter
+        }),
+        None => condition_and_content,
+      };
+      FilterInner::Data {
+        field_id,
+        field_type,
+        condition_and_content,
+      }
+    },
+    inner => inner,
+  }
+}
+
 fn get_children(filter_map: FilterMap) -> Vec<Filter> {
   //TODO: this method wouldn't be necessary if we could make Filters serializable in backward
   // compatible way
@@ -501,6 +670,7 @@ pub enum FilterChangeset {
   },
   DeleteAllWithFieldId {
     field_id: String,
+    field_name: String,
   },
 }
 
@@ -524,3 +694,70 @@ impl FilterResultNotification {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use collab_database::views::FilterMap;
+
+  use crate::entities::FieldComparisonConditionPB;
+  use crate::services::filter::{Filter, FilterInner};
+
+  #[test]
+  fn field_comparison_and_formula_filter_map_test() {
+    let filter = Filter {
+      id: "root".to_string(),
+      inner: FilterInner::Or {
+        children: vec![
+          Filter {
+            id: "comparison".to_string(),
+            inner: FilterInner::FieldComparison {
+              field_id: "actual".to_string(),
+              other_field_id: "estimate".to_string(),
+              condition: FieldComparisonConditionPB::GreaterThan,
+            },
+          },
+          Filter {
+            id: "formula".to_string(),
+            inner: FilterInner::Formula {
+              expression: "{due} < {start} && !empty({start})".to_string(),
+            },
+          },
+        ],
+      },
+    };
+
+    let filter = Filter::try_from(FilterMap::from(&filter)).unwrap();
+    let children = match filter.inner {
+      FilterInner::Or { children } => children,
+      _ => panic!("expected an OR filter"),
+    };
+    assert_eq!(children.len(), 2);
+    match &children[0].inner {
+      FilterInner::FieldComparison {
+        field_id,
+        other_field_id,
+        condition,
+      } => {
+        assert_eq!(field_id, "actual");
+        assert_eq!(other_field_id, "estimate");
+        assert_eq!(condition, &FieldComparisonConditionPB::GreaterThan);
+      },
+      _ => panic!("expected a field comparison filter"),
+    }
+    match &children[1].inner {
+      FilterInner::Formula { expression } => {
+        assert_eq!(expression, "{due} < {start} && !empty({start})")
+      },
+      _ => panic!("expected a formula filter"),
+    }
+
+    let mut filter_ids = vec![];
+    children[1].find_all_filters_with_field_id("start_id", "start", &mut filter_ids);
+    children[0].find_all_filters_with_field_id("estimate", "Estimate", &mut filter_ids);
+    children[1].find_all_filters_with_field_id("priority", "Priority", &mut filter_ids);
+    assert_eq!(
+      filter_ids,
+      vec!["formula".to_string(), "comparison".to_string()]
+    );
+  }
+}
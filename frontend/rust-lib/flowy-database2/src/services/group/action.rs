@@ -24,6 +24,12 @@ pub trait GroupCustomize: Send + Sync {
     None
   }
 
+  /// Returns the cell of the row that is used to group the row. Override it when the cell data
+  /// isn't stored in the row's cells, e.g. the created time or the last edited time.
+  fn get_row_cell(&self, row: &Row, field_id: &str) -> Option<Cell> {
+    row.cells.get(field_id).cloned()
+  }
+
   /// Returns a bool value to determine whether the group should contain this cell or not.
   fn can_group(
     &self,
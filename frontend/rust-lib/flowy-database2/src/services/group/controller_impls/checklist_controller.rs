@@ -0,0 +1,264 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, Row};
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{GroupPB, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB};
+use crate::services::field::{
+  ChecklistCellData, ChecklistCellDataParser, ChecklistTypeOption, TypeOption,
+};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+/// The groups of the checklist, each group contains the rows whose completion percentage is
+/// within the inclusive range. The checklists without any task are put in the no status group.
+const CHECKLIST_GROUPS: [(&str, usize, usize); 4] = [
+  ("0", 0, 0),
+  ("1-49", 1, 49),
+  ("50-99", 50, 99),
+  ("100", 100, 100),
+];
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ChecklistGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+pub type ChecklistGroupController =
+  BaseGroupController<ChecklistGroupConfiguration, ChecklistGroupBuilder, ChecklistCellDataParser>;
+
+pub type ChecklistGroupControllerContext = GroupControllerContext<ChecklistGroupConfiguration>;
+
+#[async_trait]
+impl GroupCustomize for ChecklistGroupController {
+  type GroupTypeOption = ChecklistTypeOption;
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    let num_of_completed = cell_data.selected_options().len();
+    get_checklist_group_id(cell_data.options.len(), num_of_completed) == Some(content)
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    let group_id =
+      get_checklist_group_id(cell_data.options.len(), cell_data.selected_options.len());
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if Some(group.id.as_str()) == group_id {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row.clone())));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+        group.remove_row(&row.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    (None, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let cell_data = self
+      .get_row_cell(context.row, &self.grouping_field_id)
+      .map(|cell| ChecklistCellData::from(&cell))
+      .unwrap_or_default();
+    let num_of_completed = cell_data.selected_options().len();
+    let from_group_id = get_checklist_group_id(cell_data.options.len(), num_of_completed)
+      .unwrap_or(self.grouping_field_id.as_str());
+
+    // Completes or uncompletes the tasks to move the checklist into the new group
+    let mut updated_cell = None;
+    if from_group_id != context.to_group_id {
+      match complete_tasks_for_group(&cell_data, context.to_group_id) {
+        None => {
+          tracing::warn!(
+            "[Database Group]: Can't move the row:{} to the group:{}",
+            context.row.id,
+            context.to_group_id
+          );
+          return vec![];
+        },
+        Some(cell_data) => updated_cell = Some(Cell::from(cell_data)),
+      }
+    }
+
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    if let Some(cell) = updated_cell {
+      context.updated_cells.insert(context.field.id.clone(), cell);
+    }
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, _group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    Ok(None)
+  }
+
+  fn will_create_row(&self, _cells: &mut Cells, _field: &Field, _group_id: &str) {
+    // A new row doesn't have any task, so it's always put in the no status group.
+  }
+}
+
+pub struct ChecklistGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for ChecklistGroupBuilder {
+  type Context = ChecklistGroupControllerContext;
+  type GroupTypeOption = ChecklistTypeOption;
+
+  async fn build(
+    field: &Field,
+    _context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    let groups = CHECKLIST_GROUPS
+      .iter()
+      .map(|(group_id, _, _)| Group::new(group_id.to_string()))
+      .collect();
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
+
+/// Returns the id of the group that contains a checklist with the given number of tasks. Returns
+/// None if the checklist doesn't have any task.
+fn get_checklist_group_id(num_of_tasks: usize, num_of_completed: usize) -> Option<&'static str> {
+  if num_of_tasks == 0 {
+    return None;
+  }
+  let percentage = num_of_completed.min(num_of_tasks) * 100 / num_of_tasks;
+  CHECKLIST_GROUPS
+    .iter()
+    .find(|(_, start, end)| (*start..=*end).contains(&percentage))
+    .map(|(group_id, _, _)| *group_id)
+}
+
+/// Returns the checklist with the fewest changes to the completed tasks that puts it in the
+/// group. Returns None if the checklist can't be in the group, e.g. a checklist with one task
+/// can't be half done.
+fn complete_tasks_for_group(
+  cell_data: &ChecklistCellData,
+  group_id: &str,
+) -> Option<ChecklistCellData> {
+  let num_of_tasks = cell_data.options.len();
+  let completed_options = cell_data.selected_options();
+  let num_of_completed = (0..=num_of_tasks)
+    .filter(|num| get_checklist_group_id(num_of_tasks, *num) == Some(group_id))
+    .min_by_key(|num| num.abs_diff(completed_options.len()))?;
+
+  // Keep the tasks that are already completed, then complete the tasks in order
+  let selected_option_ids = completed_options
+    .iter()
+    .chain(
+      cell_data
+        .options
+        .iter()
+        .filter(|option| !cell_data.selected_option_ids.contains(&option.id)),
+    )
+    .take(num_of_completed)
+    .map(|option| option.id.clone())
+    .collect();
+
+  Some(ChecklistCellData {
+    options: cell_data.options.clone(),
+    selected_option_ids,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::select_type_option::SelectOption;
+
+  use crate::services::field::ChecklistCellData;
+  use crate::services::group::controller_impls::checklist_controller::{
+    complete_tasks_for_group, get_checklist_group_id,
+  };
+
+  #[test]
+  fn checklist_group_id_test() {
+    assert_eq!(get_checklist_group_id(0, 0), None);
+    assert_eq!(get_checklist_group_id(3, 0), Some("0"));
+    assert_eq!(get_checklist_group_id(3, 1), Some("1-49"));
+    assert_eq!(get_checklist_group_id(3, 2), Some("50-99"));
+    assert_eq!(get_checklist_group_id(200, 199), Some("50-99"));
+    assert_eq!(get_checklist_group_id(3, 3), Some("100"));
+  }
+
+  #[test]
+  fn complete_tasks_for_group_test() {
+    let options = vec![
+      SelectOption::new("task 1"),
+      SelectOption::new("task 2"),
+      SelectOption::new("task 3"),
+      SelectOption::new("task 4"),
+    ];
+    let cell_data = ChecklistCellData {
+      options: options.clone(),
+      selected_option_ids: vec![options[2].id.clone()],
+    };
+
+    let completed = complete_tasks_for_group(&cell_data, "100").unwrap();
+    assert_eq!(completed.selected_option_ids.len(), 4);
+
+    // The completed task is kept
+    let half_done = complete_tasks_for_group(&cell_data, "50-99").unwrap();
+    assert_eq!(
+      half_done.selected_option_ids,
+      vec![options[2].id.clone(), options[0].id.clone()]
+    );
+
+    let not_started = complete_tasks_for_group(&cell_data, "0").unwrap();
+    assert!(not_started.selected_option_ids.is_empty());
+
+    let single_task = ChecklistCellData {
+      options: vec![options[0].clone()],
+      selected_option_ids: vec![],
+    };
+    assert!(complete_tasks_for_group(&single_task, "1-49").is_none());
+  }
+}
@@ -268,8 +268,14 @@ fn make_group_from_date_cell(cell_data: &DateCellData, setting_content: &str) ->
 const GROUP_ID_DATE_FORMAT: &str = "%Y/%m/%d";
 
 fn get_date_group_id(cell_data: &DateCellData, setting_content: &str) -> String {
+  get_timestamp_group_id(cell_data.timestamp, setting_content)
+}
+
+/// Returns the id of the group that contains the timestamp according to the [DateCondition] of
+/// the setting. It's shared by the date and the timestamp group controllers.
+pub(crate) fn get_timestamp_group_id(timestamp: Option<i64>, setting_content: &str) -> String {
   let config = DateGroupConfiguration::from_json(setting_content).unwrap_or_default();
-  let date_time = date_time_from_timestamp(cell_data.timestamp);
+  let date_time = date_time_from_timestamp(timestamp);
 
   let date_format = GROUP_ID_DATE_FORMAT;
   let month_format = &date_format.replace("%d", "01");
@@ -1,11 +1,19 @@
 mod checkbox_controller;
+mod checklist_controller;
 mod date_controller;
 mod default_controller;
+mod number_controller;
+mod relation_controller;
 mod select_option_controller;
+mod timestamp_controller;
 mod url_controller;
 
 pub use checkbox_controller::*;
+pub use checklist_controller::*;
 pub use date_controller::*;
 pub use default_controller::*;
+pub use number_controller::*;
+pub use relation_controller::*;
 pub use select_option_controller::*;
+pub use timestamp_controller::*;
 pub use url_controller::*;
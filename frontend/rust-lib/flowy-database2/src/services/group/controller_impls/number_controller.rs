@@ -0,0 +1,336 @@
+use std::cmp::Ordering;
+
+use async_trait::async_trait;
+use collab_database::fields::number_type_option::{
+  NumberCellFormat, NumberFormat, NumberTypeOption,
+};
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cells, Row};
+use flowy_error::{internal_error, FlowyResult};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{GroupPB, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB};
+use crate::services::cell::insert_text_cell;
+use crate::services::field::{NumberCellData, NumberCellStrParser, TypeOption};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+/// The number of ranges generated from the values of the cells when the boundaries of the ranges
+/// are not set.
+const AUTO_RANGE_COUNT: f64 = 5.0;
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct NumberGroupConfiguration {
+  pub hide_empty: bool,
+  /// The numbers that separate the ranges. The first range contains the numbers lower than the
+  /// first boundary and the last range the numbers greater than or equal to the last boundary.
+  /// The boundaries are generated from the values of the cells if it's empty.
+  #[serde(default)]
+  pub boundaries: Vec<f64>,
+}
+
+impl NumberGroupConfiguration {
+  pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+    serde_json::from_str(s)
+  }
+
+  pub fn to_json(&self) -> FlowyResult<String> {
+    serde_json::to_string(self).map_err(internal_error)
+  }
+}
+
+/// The range of numbers of a group, the start is inclusive and the end is exclusive. The id of the
+/// group is the range written as `start..end`, with the unbounded side left empty, e.g. `..10`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumberGroupRange {
+  pub start: Option<f64>,
+  pub end: Option<f64>,
+}
+
+impl NumberGroupRange {
+  pub fn from_group_id(group_id: &str) -> Option<Self> {
+    let (start, end) = group_id.split_once("..")?;
+    let parse_bound = |s: &str| match s {
+      "" => Some(None),
+      s => s.parse::<f64>().ok().map(Some),
+    };
+    Some(Self {
+      start: parse_bound(start)?,
+      end: parse_bound(end)?,
+    })
+  }
+
+  pub fn group_id(&self) -> String {
+    let format_bound = |bound: Option<f64>| bound.map(|n| n.to_string()).unwrap_or_default();
+    format!("{}..{}", format_bound(self.start), format_bound(self.end))
+  }
+
+  pub fn contains(&self, num: f64) -> bool {
+    self.start.map_or(true, |start| num >= start) && self.end.map_or(true, |end| num < end)
+  }
+
+  /// Returns the number written to the cell when a row is moved to the group.
+  pub fn default_number(&self) -> f64 {
+    match (self.start, self.end) {
+      (Some(start), _) => start,
+      (None, Some(end)) => end - 1.0,
+      (None, None) => 0.0,
+    }
+  }
+}
+
+pub type NumberGroupController =
+  BaseGroupController<NumberGroupConfiguration, NumberGroupBuilder, NumberCellStrParser>;
+
+pub type NumberGroupControllerContext = GroupControllerContext<NumberGroupConfiguration>;
+
+#[async_trait]
+impl GroupCustomize for NumberGroupController {
+  type GroupTypeOption = NumberTypeOption;
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    match (
+      NumberGroupRange::from_group_id(content),
+      parse_number(&cell_data.0, &NumberFormat::Num),
+    ) {
+      (Some(range), Some(num)) => range.contains(num),
+      _ => false,
+    }
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    // The protobuf type of the cell is the formatted number, read the number from the row instead.
+    let num = self
+      .get_row_cell(row, &self.grouping_field_id)
+      .and_then(|cell| parse_number(&NumberCellData::from(&cell).0, &NumberFormat::Num));
+
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      let is_in_range = match (NumberGroupRange::from_group_id(&group.id), num) {
+        (Some(range), Some(num)) => range.contains(num),
+        _ => false,
+      };
+      if is_in_range {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row.clone())));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    _cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+        group.remove_row(&row.id);
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    (None, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, _group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    Ok(None)
+  }
+
+  fn will_create_row(&self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match NumberGroupRange::from_group_id(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some(range) => {
+        // use `insert_text_cell` because the number might not be an integer
+        let cell = insert_text_cell(range.default_number().to_string(), field);
+        cells.insert(field.id.clone(), cell);
+      },
+    }
+  }
+}
+
+pub struct NumberGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for NumberGroupBuilder {
+  type Context = NumberGroupControllerContext;
+  type GroupTypeOption = NumberTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    let config =
+      NumberGroupConfiguration::from_json(&context.get_setting_content()).unwrap_or_default();
+    let mut boundaries = config
+      .boundaries
+      .into_iter()
+      .filter(|boundary| boundary.is_finite())
+      .collect::<Vec<_>>();
+    if boundaries.is_empty() {
+      // The cell data of the cells are formatted with the number format of the field
+      let nums = context
+        .get_all_cells()
+        .await
+        .into_iter()
+        .flat_map(|value| value.into_number_field_cell_data())
+        .flat_map(|cell_data| parse_number(&cell_data.0, &type_option.format))
+        .collect::<Vec<_>>();
+      boundaries = make_auto_boundaries(&nums);
+    } else {
+      boundaries.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+      boundaries.dedup();
+    }
+
+    let groups = make_number_group_ranges(&boundaries)
+      .into_iter()
+      .map(|range| Group::new(range.group_id()))
+      .collect();
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
+
+pub(crate) fn parse_number(s: &str, format: &NumberFormat) -> Option<f64> {
+  NumberCellFormat::from_format_str(s, format)
+    .ok()?
+    .decimal()
+    .as_ref()?
+    .to_f64()
+}
+
+fn make_number_group_ranges(boundaries: &[f64]) -> Vec<NumberGroupRange> {
+  let mut bounds = vec![None];
+  bounds.extend(boundaries.iter().map(|boundary| Some(*boundary)));
+  bounds.push(None);
+  bounds
+    .windows(2)
+    .map(|window| NumberGroupRange {
+      start: window[0],
+      end: window[1],
+    })
+    .collect()
+}
+
+/// Splits the numbers into about [AUTO_RANGE_COUNT] ranges. The width of the ranges is rounded to
+/// 1, 2 or 5 times a power of ten to keep the boundaries readable.
+fn make_auto_boundaries(nums: &[f64]) -> Vec<f64> {
+  let min = nums.iter().copied().fold(f64::INFINITY, f64::min);
+  let max = nums.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+  if !min.is_finite() || !max.is_finite() {
+    return vec![];
+  }
+  if min == max {
+    return vec![min];
+  }
+
+  let exact_width = (max - min) / AUTO_RANGE_COUNT;
+  let exponent = exact_width.log10().floor() as i32;
+  // Dividing by the power of ten avoids the rounding errors of multiplying by a negative power
+  let to_number = |units: f64| {
+    if exponent >= 0 {
+      units * 10f64.powi(exponent)
+    } else {
+      units / 10f64.powi(-exponent)
+    }
+  };
+  let width_units = [1.0, 2.0, 5.0]
+    .into_iter()
+    .find(|units| to_number(*units) >= exact_width)
+    .unwrap_or(10.0);
+
+  let first = (min / to_number(width_units)).floor() as i64 + 1;
+  (first..)
+    .map(|index| to_number(index as f64 * width_units))
+    .take_while(|boundary| *boundary < max)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::group::controller_impls::number_controller::{
+    make_auto_boundaries, make_number_group_ranges, NumberGroupRange,
+  };
+
+  #[test]
+  fn number_group_range_test() {
+    let ranges = make_number_group_ranges(&[0.5, 10.0]);
+    let group_ids = ranges
+      .iter()
+      .map(|range| range.group_id())
+      .collect::<Vec<_>>();
+    assert_eq!(group_ids, vec!["..0.5", "0.5..10", "10.."]);
+
+    for (i, range) in ranges.iter().enumerate() {
+      assert_eq!(
+        NumberGroupRange::from_group_id(&group_ids[i]).as_ref(),
+        Some(range)
+      );
+    }
+    assert!(ranges[0].contains(-100.0));
+    assert!(!ranges[0].contains(0.5));
+    assert!(ranges[1].contains(0.5));
+    assert!(!ranges[1].contains(10.0));
+    assert!(ranges[2].contains(10.0));
+    assert_eq!(ranges[0].default_number(), -0.5);
+    assert_eq!(ranges[2].default_number(), 10.0);
+
+    assert_eq!(make_number_group_ranges(&[])[0].group_id(), "..");
+    assert!(NumberGroupRange::from_group_id("not a range").is_none());
+  }
+
+  #[test]
+  fn auto_boundaries_test() {
+    assert!(make_auto_boundaries(&[]).is_empty());
+    assert_eq!(make_auto_boundaries(&[3.0, 3.0]), vec![3.0]);
+    assert_eq!(
+      make_auto_boundaries(&[0.0, 37.0, 100.0]),
+      vec![20.0, 40.0, 60.0, 80.0]
+    );
+    assert_eq!(make_auto_boundaries(&[0.0, 1.0]), vec![0.2, 0.4, 0.6, 0.8]);
+    assert_eq!(make_auto_boundaries(&[1.0, 2.0, 3.0, 4.0]), vec![2.0, 3.0]);
+  }
+}
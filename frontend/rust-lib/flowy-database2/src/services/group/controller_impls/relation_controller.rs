@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+use collab_database::fields::{Field, TypeOptionData};
+use collab_database::rows::{Cell, Cells, Row, RowId};
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{
+  GroupPB, GroupRowsNotificationPB, InsertedGroupPB, InsertedRowPB, RowMetaPB,
+};
+use crate::services::field::{
+  RelationCellData, RelationCellDataParser, RelationTypeOption, TypeOption,
+};
+use crate::services::group::action::GroupCustomize;
+use crate::services::group::configuration::GroupControllerContext;
+use crate::services::group::controller::BaseGroupController;
+use crate::services::group::{
+  make_no_status_group, move_group_row, GeneratedGroups, Group, GroupsBuilder, MoveGroupRowContext,
+};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct RelationGroupConfiguration {
+  pub hide_empty: bool,
+}
+
+/// Groups the rows by their related rows. The id of each group is the id of the related row, a
+/// row that relates to multiple rows is put in each of their groups.
+pub type RelationGroupController =
+  BaseGroupController<RelationGroupConfiguration, RelationGroupBuilder, RelationCellDataParser>;
+
+pub type RelationGroupControllerContext = GroupControllerContext<RelationGroupConfiguration>;
+
+#[async_trait]
+impl GroupCustomize for RelationGroupController {
+  type GroupTypeOption = RelationTypeOption;
+
+  fn can_group(
+    &self,
+    content: &str,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> bool {
+    cell_data
+      .row_ids
+      .iter()
+      .any(|row_id| row_id.as_str() == content)
+  }
+
+  fn create_or_delete_group_when_cell_changed(
+    &mut self,
+    row: &Row,
+    old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
+    // Creates the groups of the newly related rows. The cell is usually updated one related row at
+    // a time, so only the first inserted group is returned.
+    let mut inserted_group = None;
+    for related_row_id in cell_data.row_ids.iter() {
+      if self.context.get_group(related_row_id).is_none() {
+        let mut new_group = self
+          .context
+          .add_new_group(Group::new(related_row_id.clone()))?;
+        new_group.group.rows.push(RowMetaPB::from(row.clone()));
+        if inserted_group.is_none() {
+          inserted_group = Some(new_group);
+        }
+      }
+    }
+
+    // Deletes the groups of the unrelated rows if there are no other rows in them
+    let unrelated_row_ids = old_cell_data
+      .map(|old_cell_data| {
+        old_cell_data
+          .row_ids
+          .iter()
+          .filter(|row_id| !cell_data.row_ids.contains(row_id))
+          .cloned()
+          .collect::<Vec<_>>()
+      })
+      .unwrap_or_default();
+    let mut deleted_group = None;
+    for unrelated_row_id in unrelated_row_ids {
+      let group = match self.context.get_group(&unrelated_row_id) {
+        Some((_, group)) if group.rows.len() == 1 && group.contains_row(&row.id) => group.clone(),
+        _ => continue,
+      };
+      self.context.delete_group(&group.id)?;
+      if deleted_group.is_none() {
+        deleted_group = Some(GroupPB::from(group));
+      }
+    }
+
+    Ok((inserted_group, deleted_group))
+  }
+
+  fn add_or_remove_row_when_cell_changed(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
+  ) -> Vec<GroupRowsNotificationPB> {
+    let mut changesets = vec![];
+    self.context.iter_mut_status_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if cell_data.row_ids.contains(&group.id) {
+        if !group.contains_row(&row.id) {
+          changeset
+            .inserted_rows
+            .push(InsertedRowPB::new(RowMetaPB::from(row.clone())));
+          group.add_row(row.clone());
+        }
+      } else if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+    changesets
+  }
+
+  fn delete_row(
+    &mut self,
+    row: &Row,
+    cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
+  ) -> (Option<GroupPB>, Vec<GroupRowsNotificationPB>) {
+    let mut changesets = vec![];
+    self.context.iter_mut_groups(|group| {
+      let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
+      if group.contains_row(&row.id) {
+        group.remove_row(&row.id);
+        changeset.deleted_rows.push(row.id.clone().into_inner());
+      }
+
+      if !changeset.is_empty() {
+        changesets.push(changeset);
+      }
+    });
+
+    let empty_groups = cell_data
+      .row_ids
+      .iter()
+      .filter_map(
+        |related_row_id| match self.context.get_group(related_row_id) {
+          Some((_, group)) if group.rows.is_empty() => Some(group.clone()),
+          _ => None,
+        },
+      )
+      .collect::<Vec<_>>();
+    let mut deleted_group = None;
+    for group in empty_groups {
+      let _ = self.context.delete_group(&group.id);
+      if deleted_group.is_none() {
+        deleted_group = Some(GroupPB::from(group));
+      }
+    }
+
+    (deleted_group, changesets)
+  }
+
+  fn move_row(&mut self, mut context: MoveGroupRowContext) -> Vec<GroupRowsNotificationPB> {
+    let mut group_changeset = vec![];
+    self.context.iter_mut_groups(|group| {
+      if let Some(changeset) = move_group_row(group, &mut context) {
+        group_changeset.push(changeset);
+      }
+    });
+    group_changeset
+  }
+
+  async fn delete_group(&mut self, group_id: &str) -> FlowyResult<Option<TypeOptionData>> {
+    self.context.delete_group(group_id)?;
+    Ok(None)
+  }
+
+  fn will_create_row(&self, cells: &mut Cells, field: &Field, group_id: &str) {
+    match self.context.get_group(group_id) {
+      None => tracing::warn!("Can not find the group: {}", group_id),
+      Some((_, group)) => {
+        let cell_data = RelationCellData {
+          row_ids: vec![RowId::from(group.id.clone())],
+        };
+        cells.insert(field.id.clone(), Cell::from(&cell_data));
+      },
+    }
+  }
+}
+
+pub struct RelationGroupBuilder();
+#[async_trait]
+impl GroupsBuilder for RelationGroupBuilder {
+  type Context = RelationGroupControllerContext;
+  type GroupTypeOption = RelationTypeOption;
+
+  async fn build(
+    field: &Field,
+    context: &Self::Context,
+    _type_option: &Self::GroupTypeOption,
+  ) -> GeneratedGroups {
+    // Read all the cells for the grouping field
+    let cells = context.get_all_cells().await;
+
+    // Generate one group for each related row
+    let mut groups: Vec<Group> = vec![];
+    for cell_data in cells
+      .into_iter()
+      .flat_map(|value| value.into_relation_field_cell_data())
+    {
+      for related_row_id in cell_data.row_ids {
+        if groups
+          .iter()
+          .all(|group| group.id != related_row_id.as_str())
+        {
+          groups.push(Group::new(related_row_id.into_inner()));
+        }
+      }
+    }
+
+    GeneratedGroups {
+      no_status_group: Some(make_no_status_group(field)),
+      groups,
+    }
+  }
+}
@@ -2,14 +2,18 @@ use crate::entities::{
   FieldType, GroupRowsNotificationPB, InsertedRowPB, RowMetaPB, SelectOptionCellDataPB,
 };
 use crate::services::cell::{
-  insert_checkbox_cell, insert_date_cell, insert_select_option_cell, insert_url_cell,
+  insert_checkbox_cell, insert_date_cell, insert_select_option_cell, insert_text_cell,
+  insert_url_cell,
+};
+use crate::services::field::{NumberCellData, RelationCellData, CHECK};
+use crate::services::group::{
+  parse_number, Group, GroupData, MoveGroupRowContext, NumberGroupRange,
 };
-use crate::services::field::CHECK;
-use crate::services::group::{Group, GroupData, MoveGroupRowContext};
 use chrono::NaiveDateTime;
+use collab_database::fields::number_type_option::NumberFormat;
 use collab_database::fields::select_type_option::{SelectOption, SelectOptionIds};
 use collab_database::fields::Field;
-use collab_database::rows::{Cell, Row};
+use collab_database::rows::{Cell, Row, RowId};
 use tracing::debug;
 
 pub fn add_or_remove_select_option_row(
@@ -75,9 +79,13 @@ pub fn move_group_row(
     row,
     updated_cells,
     field,
+    from_group_id,
     to_group_id,
     to_row_id,
   } = context;
+  // A row can be in multiple relation groups, moving it only takes it out of the group it's
+  // dragged from.
+  let is_relation = FieldType::from(field.field_type) == FieldType::Relation;
 
   let from_index = group.index_of_row(&row.id);
   let to_index = match to_row_id {
@@ -86,7 +94,10 @@ pub fn move_group_row(
   };
 
   // Remove the row in which group contains it
-  if from_index.is_some() {
+  let is_moved_out = !is_relation
+    || group.id == *to_group_id
+    || from_group_id.map_or(false, |from_group_id| group.id == from_group_id);
+  if from_index.is_some() && is_moved_out {
     changeset.deleted_rows.push(row.id.clone().into_inner());
     group.remove_row(&row.id);
   }
@@ -115,9 +126,12 @@ pub fn move_group_row(
 
     // Update the corresponding row's cell content.
     // If the from_index is none which means the row is not belong to this group before and
-    // it is moved from other groups.
-    if from_index.is_none() {
-      let cell = make_inserted_cell(&group.id, field);
+    // it is moved from other groups. A related row might already be in the target group, but it
+    // still needs to leave the group it's moved from.
+    let is_moved_in = from_index.is_none()
+      || (is_relation && from_group_id.map_or(false, |from_group_id| from_group_id != group.id));
+    if is_moved_in {
+      let cell = make_inserted_cell(row, *from_group_id, &group.id, field);
       if let Some(cell) = cell {
         debug!(
           "[Database Group]: Update content of the cell in the row:{} to group:{}",
@@ -134,7 +148,14 @@ pub fn move_group_row(
   }
 }
 
-pub fn make_inserted_cell(group_id: &str, field: &Field) -> Option<Cell> {
+/// Returns the cell of the row after it's moved from `from_group_id` to `group_id`, or `None` if
+/// the cell doesn't need to change.
+pub fn make_inserted_cell(
+  row: &Row,
+  from_group_id: Option<&str>,
+  group_id: &str,
+  field: &Field,
+) -> Option<Cell> {
   let field_type = FieldType::from(field.field_type);
   match field_type {
     FieldType::SingleSelect => {
@@ -160,6 +181,47 @@ pub fn make_inserted_cell(group_id: &str, field: &Field) -> Option<Cell> {
       let cell = insert_date_cell(date.and_utc().timestamp(), None, Some(false), field);
       Some(cell)
     },
+    FieldType::Number => {
+      // Keep the number if it's already in the range. Moving the row to the no status group
+      // clears the number. Use `insert_text_cell` because the number might not be an integer.
+      let num = match NumberGroupRange::from_group_id(group_id) {
+        None => String::new(),
+        Some(range) => {
+          let current_num = row
+            .cells
+            .get(&field.id)
+            .and_then(|cell| parse_number(&NumberCellData::from(cell).0, &NumberFormat::Num));
+          if current_num.map_or(false, |num| range.contains(num)) {
+            return None;
+          }
+          range.default_number().to_string()
+        },
+      };
+      Some(insert_text_cell(num, field))
+    },
+    FieldType::Relation => {
+      // Replace the related row of the group the row is moved from and keep the others. Moving
+      // the row to the no status group clears the relations.
+      let row_ids = if group_id == field.id {
+        vec![]
+      } else {
+        let mut row_ids = row
+          .cells
+          .get(&field.id)
+          .map(|cell| RelationCellData::from(cell).row_ids)
+          .unwrap_or_default();
+        if let Some(from_group_id) = from_group_id {
+          row_ids.retain(|row_id| row_id.as_str() != from_group_id);
+        }
+        if !row_ids.iter().any(|row_id| row_id.as_str() == group_id) {
+          row_ids.push(RowId::from(group_id.to_owned()));
+        }
+        row_ids
+      };
+      Some(Cell::from(&RelationCellData { row_ids }))
+    },
+    // The checklist controller completes the tasks itself and the timestamps can't be edited
+    FieldType::Checklist | FieldType::CreatedTime | FieldType::LastEditedTime => None,
     _ => {
       tracing::warn!("Unknown field type: {:?}", field_type);
       None
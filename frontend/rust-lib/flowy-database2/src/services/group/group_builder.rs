@@ -9,11 +9,13 @@ use flowy_error::FlowyResult;
 use crate::entities::FieldType;
 use crate::services::field::TypeOption;
 use crate::services::group::{
-  CheckboxGroupController, CheckboxGroupControllerContext, DateGroupController,
-  DateGroupControllerContext, DefaultGroupController, Group, GroupContextDelegate, GroupController,
-  GroupControllerDelegate, GroupSetting, MultiSelectGroupController,
-  MultiSelectGroupControllerContext, SingleSelectGroupController,
-  SingleSelectGroupControllerContext, URLGroupController, URLGroupControllerContext,
+  CheckboxGroupController, CheckboxGroupControllerContext, ChecklistGroupController,
+  ChecklistGroupControllerContext, DateGroupController, DateGroupControllerContext,
+  DefaultGroupController, Group, GroupContextDelegate, GroupController, GroupControllerDelegate,
+  GroupSetting, MultiSelectGroupController, MultiSelectGroupControllerContext,
+  NumberGroupController, NumberGroupControllerContext, RelationGroupController,
+  RelationGroupControllerContext, SingleSelectGroupController, SingleSelectGroupControllerContext,
+  TimestampGroupController, URLGroupController, URLGroupControllerContext,
 };
 
 /// The [GroupsBuilder] trait is used to generate the groups for different [FieldType]
@@ -38,6 +40,8 @@ pub struct MoveGroupRowContext<'a> {
   pub row: &'a Row,
   pub updated_cells: &'a mut UpdatedCells,
   pub field: &'a Field,
+  /// The group the row is moved from. `None` if it's unknown, e.g. when moving between sub groups.
+  pub from_group_id: Option<&'a str>,
   pub to_group_id: &'a str,
   pub to_row_id: Option<RowId>,
 }
@@ -133,6 +137,50 @@ where
         DateGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
       group_controller = Box::new(controller);
     },
+    FieldType::CreatedTime | FieldType::LastEditedTime => {
+      let configuration = DateGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        TimestampGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Number => {
+      let configuration = NumberGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        NumberGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Relation => {
+      let configuration = RelationGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        RelationGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
+    FieldType::Checklist => {
+      let configuration = ChecklistGroupControllerContext::new(
+        view_id.to_string(),
+        grouping_field.clone(),
+        delegate.clone(),
+      )
+      .await?;
+      let controller =
+        ChecklistGroupController::new(&grouping_field, configuration, delegate.clone()).await?;
+      group_controller = Box::new(controller);
+    },
     _ => {
       group_controller = Box::new(DefaultGroupController::new(
         &grouping_field,
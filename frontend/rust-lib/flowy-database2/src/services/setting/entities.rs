@@ -118,3 +118,75 @@ impl From<BoardLayoutSetting> for LayoutSetting {
     ])
   }
 }
+
+/// The setting of a timeline view. A timeline view is stored as a grid view with this setting,
+/// the view is shown as a timeline as long as the setting has a start field.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimelineLayoutSetting {
+  #[serde(default)]
+  pub zoom_level: TimelineZoomLevel,
+  #[serde(default)]
+  pub start_field_id: String,
+  /// The date field that contains the end of the bars. The end date of the start field is used
+  /// if it's None.
+  #[serde(default)]
+  pub end_field_id: Option<String>,
+  /// The relation field that contains the rows a row depends on.
+  #[serde(default)]
+  pub dependency_field_id: Option<String>,
+}
+
+impl TimelineLayoutSetting {
+  pub fn new(start_field_id: String) -> Self {
+    Self {
+      start_field_id,
+      ..Default::default()
+    }
+  }
+
+  /// Returns None if the layout setting isn't the setting of a timeline view.
+  pub fn from_layout_setting(setting: LayoutSetting) -> Option<Self> {
+    let mut setting = from_any::<Self>(&Any::from(setting)).ok()?;
+    if setting.start_field_id.is_empty() {
+      return None;
+    }
+    // The optional fields are stored as empty strings
+    setting.end_field_id = setting.end_field_id.filter(|id| !id.is_empty());
+    setting.dependency_field_id = setting.dependency_field_id.filter(|id| !id.is_empty());
+    Some(setting)
+  }
+}
+
+impl From<TimelineLayoutSetting> for LayoutSetting {
+  fn from(setting: TimelineLayoutSetting) -> Self {
+    LayoutSettingBuilder::from([
+      ("zoom_level".into(), Any::BigInt(setting.zoom_level.value())),
+      ("start_field_id".into(), setting.start_field_id.into()),
+      (
+        "end_field_id".into(),
+        setting.end_field_id.unwrap_or_default().into(),
+      ),
+      (
+        "dependency_field_id".into(),
+        setting.dependency_field_id.unwrap_or_default().into(),
+      ),
+    ])
+  }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum TimelineZoomLevel {
+  Day = 0,
+  #[default]
+  Week = 1,
+  Month = 2,
+  Quarter = 3,
+  Year = 4,
+}
+
+impl TimelineZoomLevel {
+  pub fn value(&self) -> i64 {
+    *self as i64
+  }
+}
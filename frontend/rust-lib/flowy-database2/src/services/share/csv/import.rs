@@ -1,33 +1,69 @@
 use collab_database::database::{gen_database_id, gen_field_id, gen_row_id, timestamp};
 use collab_database::entity::{CreateDatabaseParams, CreateViewParams, EncodedCollabInfo};
+use collab_database::fields::number_type_option::NumberTypeOption;
+use collab_database::fields::select_type_option::{SelectOption, SelectTypeOption};
 use collab_database::fields::Field;
 use collab_database::rows::{new_cell_builder, Cell, CreateRowParams};
 use collab_database::views::DatabaseLayout;
 use flowy_error::{FlowyError, FlowyResult};
 use std::fmt::Display;
-use std::{fs::File, io::prelude::*};
+use std::fs::File;
+use std::io::BufReader;
 
 use crate::entities::FieldType;
-use crate::services::field::{default_type_option_data_from_type, CELL_DATA};
+use crate::services::field::{
+  default_type_option_data_from_type, new_select_option_color, FieldBuilder, CELL_DATA,
+};
 use crate::services::field_settings::default_field_settings_for_fields;
-use crate::services::share::csv::CSVFormat;
+use crate::services::share::csv::preview::infer_columns;
+use crate::services::share::csv::{CSVColumnPreview, CSVFormat, CSVImportPreview, CSVRecordReader};
+use crate::services::share::infer::{cell_from_content, split_select_options};
 
 #[derive(Default)]
 pub struct CSVImporter;
 
 impl CSVImporter {
-  pub fn import_csv_from_file(
+  /// Opens the CSV file without reading it into memory. The records are read in batches with
+  /// [CSVRecordReader::read_records].
+  pub fn open_csv_file(&self, path: &str) -> FlowyResult<CSVRecordReader<BufReader<File>>> {
+    let file = File::open(path)?;
+    CSVRecordReader::new(BufReader::new(file))
+  }
+
+  /// Creates the database from the header and the first records of the CSV file. The field
+  /// types of the preview are used if it is given, otherwise they are inferred from these
+  /// records. The remaining records are converted with [CSVImporter::rows_from_records].
+  pub fn database_from_records(
     &self,
     view_id: &str,
-    path: &str,
-    style: CSVFormat,
+    fields: Vec<String>,
+    records: Vec<Vec<String>>,
+    format: CSVFormat,
+    preview: Option<CSVImportPreview>,
   ) -> FlowyResult<CreateDatabaseParams> {
-    let mut file = File::open(path)?;
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
-    let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style);
-    Ok(database_data)
+    let fields_with_rows = FieldsRows {
+      fields,
+      rows: records,
+    };
+    if let Some(preview) = &preview {
+      check_preview_columns(preview, &fields_with_rows)?;
+    }
+    Ok(database_from_fields_and_rows(
+      view_id,
+      fields_with_rows,
+      &format,
+      preview,
+    ))
+  }
+
+  pub fn rows_from_records(
+    &self,
+    database_id: &str,
+    fields: &[Field],
+    records: &[Vec<String>],
+    format: CSVFormat,
+  ) -> Vec<CreateRowParams> {
+    rows_from_records(database_id, fields, records, &format)
   }
 
   pub fn import_csv_from_string(
@@ -37,54 +73,86 @@ impl CSVImporter {
     format: CSVFormat,
   ) -> FlowyResult<CreateDatabaseParams> {
     let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format);
+    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format, None);
+    Ok(database_data)
+  }
+
+  /// Returns the field type inferred for each column of the CSV content. The caller can change
+  /// the field types and pass the preview to [CSVImporter::import_csv_from_string_with_preview].
+  pub fn preview_csv_from_string(&self, content: String) -> FlowyResult<CSVImportPreview> {
+    let fields_with_rows = self.get_fields_and_rows(content)?;
+    Ok(infer_columns(
+      &fields_with_rows.fields,
+      &fields_with_rows.rows,
+    ))
+  }
+
+  pub fn import_csv_from_string_with_preview(
+    &self,
+    view_id: String,
+    content: String,
+    preview: CSVImportPreview,
+  ) -> FlowyResult<CreateDatabaseParams> {
+    let fields_with_rows = self.get_fields_and_rows(content)?;
+    check_preview_columns(&preview, &fields_with_rows)?;
+    let database_data = database_from_fields_and_rows(
+      &view_id,
+      fields_with_rows,
+      &CSVFormat::Original,
+      Some(preview),
+    );
     Ok(database_data)
   }
 
   fn get_fields_and_rows(&self, content: String) -> Result<FieldsRows, FlowyError> {
-    let mut fields: Vec<String> = vec![];
     if content.is_empty() {
       return Err(FlowyError::invalid_data().with_context("Import content is empty"));
     }
 
-    let mut reader = csv::Reader::from_reader(content.as_bytes());
-    if let Ok(headers) = reader.headers() {
-      for header in headers {
-        fields.push(header.to_string());
-      }
-    } else {
-      return Err(FlowyError::invalid_data().with_context("Header not found"));
-    }
-
-    let rows = reader
-      .records()
-      .flat_map(|r| r.ok())
-      .map(|record| {
-        record
-          .into_iter()
-          .map(|s| s.to_string())
-          .collect::<Vec<String>>()
-      })
-      .collect();
-
+    let mut reader = CSVRecordReader::new(content.as_bytes())?;
+    let rows = reader.read_records(usize::MAX)?;
+    let fields = reader.fields().to_vec();
     Ok(FieldsRows { fields, rows })
   }
 }
 
+fn check_preview_columns(
+  preview: &CSVImportPreview,
+  fields_and_rows: &FieldsRows,
+) -> FlowyResult<()> {
+  if preview.columns.len() != fields_and_rows.fields.len() {
+    return Err(
+      FlowyError::invalid_data()
+        .with_context("The preview doesn't match the columns of the CSV content"),
+    );
+  }
+  Ok(())
+}
+
 fn database_from_fields_and_rows(
   view_id: &str,
   fields_and_rows: FieldsRows,
   format: &CSVFormat,
+  preview: Option<CSVImportPreview>,
 ) -> CreateDatabaseParams {
   let (fields, rows) = fields_and_rows.split();
   let database_id = gen_database_id();
 
-  let fields = fields
-    .into_iter()
-    .enumerate()
-    .map(|(index, field_meta)| match format {
-      CSVFormat::Original => default_field(field_meta, index == 0),
-      CSVFormat::META => {
+  let fields = match format {
+    CSVFormat::Original => preview
+      .unwrap_or_else(|| infer_columns(&fields, &rows))
+      .columns
+      .into_iter()
+      .enumerate()
+      .map(|(index, column)| {
+        let values = rows.iter().filter_map(|row| row.get(index));
+        field_from_column(column, values, index == 0)
+      })
+      .collect::<Vec<Field>>(),
+    CSVFormat::META => fields
+      .into_iter()
+      .enumerate()
+      .map(|(index, field_meta)| {
         //
         match serde_json::from_str(&field_meta) {
           Ok(field) => field,
@@ -93,16 +161,41 @@ fn database_from_fields_and_rows(
             default_field(field_meta, index == 0)
           },
         }
-      },
-    })
-    .collect::<Vec<Field>>();
+      })
+      .collect::<Vec<Field>>(),
+  };
 
   let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
+  let rows = rows_from_records(&database_id, &fields, &rows, format);
+  let timestamp = timestamp();
+
+  CreateDatabaseParams {
+    database_id: database_id.clone(),
+    rows,
+    fields,
+    views: vec![CreateViewParams {
+      database_id,
+      view_id: view_id.to_string(),
+      name: "".to_string(),
+      layout: DatabaseLayout::Grid,
+      field_settings,
+      created_at: timestamp,
+      modified_at: timestamp,
+      ..Default::default()
+    }],
+  }
+}
 
-  let rows = rows
+fn rows_from_records(
+  database_id: &str,
+  fields: &[Field],
+  records: &[Vec<String>],
+  format: &CSVFormat,
+) -> Vec<CreateRowParams> {
+  records
     .iter()
     .map(|cells| {
-      let mut params = CreateRowParams::new(gen_row_id(), database_id.clone());
+      let mut params = CreateRowParams::new(gen_row_id(), database_id.to_string());
       for (index, cell_content) in cells.iter().enumerate() {
         if let Some(field) = fields.get(index) {
           let field_type = FieldType::from(field.field_type);
@@ -111,7 +204,7 @@ fn database_from_fields_and_rows(
           let mut cell = new_cell_builder(field_type);
           match format {
             CSVFormat::Original => {
-              cell.insert(CELL_DATA.into(), cell_content.as_str().into());
+              cell = cell_from_content(field, cell_content);
             },
             CSVFormat::META => match serde_json::from_str::<Cell>(cell_content) {
               Ok(cell_json) => cell = cell_json,
@@ -125,25 +218,38 @@ fn database_from_fields_and_rows(
       }
       params
     })
-    .collect::<Vec<CreateRowParams>>();
-
-  let timestamp = timestamp();
+    .collect::<Vec<CreateRowParams>>()
+}
 
-  CreateDatabaseParams {
-    database_id: database_id.clone(),
-    rows,
-    fields,
-    views: vec![CreateViewParams {
-      database_id,
-      view_id: view_id.to_string(),
-      name: "".to_string(),
-      layout: DatabaseLayout::Grid,
-      field_settings,
-      created_at: timestamp,
-      modified_at: timestamp,
-      ..Default::default()
-    }],
-  }
+fn field_from_column<'a>(
+  column: CSVColumnPreview,
+  values: impl Iterator<Item = &'a String>,
+  is_primary: bool,
+) -> Field {
+  let field_type = column.field_type;
+  let builder = match field_type {
+    FieldType::Number => {
+      let mut type_option = NumberTypeOption::default();
+      type_option.set_format(column.number_format);
+      FieldBuilder::new(field_type, type_option)
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let mut options: Vec<SelectOption> = vec![];
+      for name in values.flat_map(|value| split_select_options(value, field_type)) {
+        if !name.is_empty() && options.iter().all(|option| option.name != name) {
+          let color = new_select_option_color(&options);
+          options.push(SelectOption::with_color(&name, color));
+        }
+      }
+      let type_option = SelectTypeOption {
+        options,
+        disable_color: false,
+      };
+      FieldBuilder::new(field_type, type_option)
+    },
+    _ => FieldBuilder::from_field_type(field_type),
+  };
+  builder.name(&column.name).primary(is_primary).build()
 }
 
 fn default_field(field_str: String, is_primary: bool) -> Field {
@@ -189,8 +295,11 @@ impl Display for ImportResult {
 #[cfg(test)]
 mod tests {
   use collab_database::database::gen_database_view_id;
+  use collab_database::fields::number_type_option::NumberFormat;
 
-  use crate::services::share::csv::{CSVFormat, CSVImporter};
+  use crate::entities::FieldType;
+  use crate::services::cell::stringify_cell;
+  use crate::services::share::csv::{CSVFormat, CSVImporter, CSVRecordReader};
 
   #[test]
   fn test_import_csv_from_str() {
@@ -219,6 +328,89 @@ mod tests {
     println!("{:?}", result);
   }
 
+  const TASKS_CSV: &str = r#"Name,Price,Due,Done,Website,Status,Tags,Tasks
+Write,"$1,200.50",2024-01-01,true,https://appflowy.io,Todo,"docs, code","[x] outline, [ ] draft"
+Review,$3,2024-01-02 10:00,false,https://github.com,Done,docs,[ ] read
+Ship,$7.25,2024-01-03,false,https://appflowy.com,Todo,code,[x] release"#;
+
+  #[test]
+  fn import_csv_infer_field_type_test() {
+    let importer = CSVImporter;
+    let preview = importer
+      .preview_csv_from_string(TASKS_CSV.to_string())
+      .unwrap();
+    let field_types = preview
+      .columns
+      .iter()
+      .map(|column| column.field_type)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::Number,
+        FieldType::DateTime,
+        FieldType::Checkbox,
+        FieldType::URL,
+        FieldType::SingleSelect,
+        FieldType::MultiSelect,
+        FieldType::Checklist,
+      ]
+    );
+    assert_eq!(preview.columns[1].number_format, NumberFormat::USD);
+    assert_eq!(preview.columns[1].sample_values[0], "$1,200.50");
+
+    let result = importer
+      .import_csv_from_string(
+        gen_database_view_id(),
+        TASKS_CSV.to_string(),
+        CSVFormat::Original,
+      )
+      .unwrap();
+    let cell_string = |row: usize, field: usize| {
+      let field = &result.fields[field];
+      stringify_cell(result.rows[row].cells.get(&field.id).unwrap(), field)
+    };
+    assert_eq!(cell_string(0, 5), "Todo");
+    assert_eq!(cell_string(0, 6), "docs,code");
+    assert_eq!(cell_string(0, 7), "outline,draft");
+    assert_eq!(cell_string(1, 3), "No");
+  }
+
+  #[test]
+  fn import_csv_with_overridden_field_type_test() {
+    let importer = CSVImporter;
+    let mut preview = importer
+      .preview_csv_from_string(TASKS_CSV.to_string())
+      .unwrap();
+    preview.columns[1].field_type = FieldType::RichText;
+    preview.columns[5].field_type = FieldType::RichText;
+
+    let result = importer
+      .import_csv_from_string_with_preview(gen_database_view_id(), TASKS_CSV.to_string(), preview)
+      .unwrap();
+    assert_eq!(
+      FieldType::from(result.fields[1].field_type),
+      FieldType::RichText
+    );
+    let price = &result.fields[1];
+    assert_eq!(
+      stringify_cell(result.rows[0].cells.get(&price.id).unwrap(), price),
+      "$1,200.50"
+    );
+
+    let mismatched_preview = importer
+      .preview_csv_from_string("Name,Price\nWrite,1".to_string())
+      .unwrap();
+    assert!(importer
+      .import_csv_from_string_with_preview(
+        gen_database_view_id(),
+        TASKS_CSV.to_string(),
+        mismatched_preview
+      )
+      .is_err());
+  }
+
   #[test]
   fn import_empty_csv_data_test() {
     let s = r#""#;
@@ -227,4 +419,22 @@ mod tests {
       importer.import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original);
     assert!(result.is_err());
   }
+
+  #[test]
+  fn read_csv_records_in_batches_test() {
+    let s = b"Name,Price\nWrite,1\nReview,2,extra\nShip\nTest,\xff\nDone,5\n";
+    let mut reader = CSVRecordReader::new(&s[..]).unwrap();
+    assert_eq!(reader.fields(), ["Name", "Price"]);
+
+    let records = reader.read_records(2).unwrap();
+    assert_eq!(records, vec![vec!["Write", "1"], vec!["Review", "2"]]);
+    let records = reader.read_records(2).unwrap();
+    assert_eq!(records, vec![vec!["Ship"], vec!["Done", "5"]]);
+    assert!(reader.read_records(2).unwrap().is_empty());
+
+    let report = reader.into_report();
+    assert_eq!(report.num_of_rows, 4);
+    assert_eq!(report.skipped_lines.len(), 1);
+    assert_eq!(report.skipped_lines[0].line, 5);
+  }
 }
@@ -1,5 +1,9 @@
 mod export;
 mod import;
+mod preview;
+mod reader;
 
 pub use export::*;
 pub use import::*;
+pub use preview::{CSVColumnPreview, CSVImportPreview};
+pub use reader::*;
@@ -0,0 +1,58 @@
+use collab_database::fields::number_type_option::NumberFormat;
+
+use crate::entities::FieldType;
+use crate::services::share::infer::infer_field_type;
+
+/// Only the first rows of the file are used to infer the type of the columns.
+const MAX_NUM_OF_SAMPLE_ROWS: usize = 1000;
+/// The number of values that are kept in the preview of each column.
+const MAX_NUM_OF_PREVIEW_VALUES: usize = 5;
+
+/// The field type chosen for a column of the CSV file. The caller can change the `field_type` and
+/// the `number_format` before the database is created.
+#[derive(Debug, Clone)]
+pub struct CSVColumnPreview {
+  pub name: String,
+  pub field_type: FieldType,
+  /// Only used when the `field_type` is [FieldType::Number].
+  pub number_format: NumberFormat,
+  pub sample_values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CSVImportPreview {
+  pub columns: Vec<CSVColumnPreview>,
+}
+
+pub(crate) fn infer_columns(fields: &[String], rows: &[Vec<String>]) -> CSVImportPreview {
+  let columns = fields
+    .iter()
+    .enumerate()
+    .map(|(index, name)| {
+      let values = rows
+        .iter()
+        .take(MAX_NUM_OF_SAMPLE_ROWS)
+        .filter_map(|row| row.get(index))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .collect::<Vec<_>>();
+      // The first column is used as the primary field, which is always a text field.
+      let (field_type, number_format) = if index == 0 {
+        (FieldType::RichText, NumberFormat::Num)
+      } else {
+        infer_field_type(&values)
+      };
+      CSVColumnPreview {
+        name: name.clone(),
+        field_type,
+        number_format,
+        sample_values: values
+          .iter()
+          .take(MAX_NUM_OF_PREVIEW_VALUES)
+          .map(|value| value.to_string())
+          .collect(),
+      }
+    })
+    .collect();
+  CSVImportPreview { columns }
+}
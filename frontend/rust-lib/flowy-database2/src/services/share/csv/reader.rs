@@ -0,0 +1,105 @@
+use std::io::Read;
+
+use csv::{ErrorKind, StringRecord};
+use flowy_error::{FlowyError, FlowyResult};
+
+/// A line of the CSV file that was not imported.
+#[derive(Debug, Clone)]
+pub struct CSVSkippedLine {
+  /// The line number starts from 1 and includes the header.
+  pub line: u64,
+  pub reason: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CSVImportReport {
+  pub num_of_rows: usize,
+  pub skipped_lines: Vec<CSVSkippedLine>,
+}
+
+/// Reads the records of a CSV file one batch at a time, so the whole file never needs to be kept
+/// in memory. The records that can't be parsed are collected into the [CSVImportReport] instead
+/// of being dropped silently.
+pub struct CSVRecordReader<R> {
+  reader: csv::Reader<R>,
+  fields: Vec<String>,
+  record: StringRecord,
+  report: CSVImportReport,
+}
+
+impl<R: Read> CSVRecordReader<R> {
+  pub fn new(reader: R) -> FlowyResult<Self> {
+    // Rows with a different number of values than the header are still imported, the missing
+    // values are empty and the extra values are ignored.
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let fields = reader
+      .headers()
+      .map_err(|_| FlowyError::invalid_data().with_context("Header not found"))?
+      .iter()
+      .map(|header| header.to_string())
+      .collect::<Vec<String>>();
+    if fields.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("Import content is empty"));
+    }
+
+    Ok(Self {
+      reader,
+      fields,
+      record: StringRecord::new(),
+      report: CSVImportReport::default(),
+    })
+  }
+
+  pub fn fields(&self) -> &[String] {
+    &self.fields
+  }
+
+  /// The number of bytes that have been read so far.
+  pub fn bytes_read(&self) -> u64 {
+    self.reader.position().byte()
+  }
+
+  pub fn report(&self) -> &CSVImportReport {
+    &self.report
+  }
+
+  pub fn into_report(self) -> CSVImportReport {
+    self.report
+  }
+
+  /// Reads up to `limit` records. Returns an empty vector when the end of the file is reached.
+  pub fn read_records(&mut self, limit: usize) -> FlowyResult<Vec<Vec<String>>> {
+    let mut records = vec![];
+    while records.len() < limit {
+      match self.reader.read_record(&mut self.record) {
+        Ok(false) => break,
+        Ok(true) => {
+          let values = self.record.iter().take(self.fields.len());
+          records.push(values.map(|s| s.to_string()).collect());
+        },
+        Err(err) => {
+          let line = err
+            .position()
+            .map(|position| position.line())
+            .unwrap_or_default();
+          match err.kind() {
+            ErrorKind::Utf8 { .. } | ErrorKind::UnequalLengths { .. } => {
+              self.skip_line(line, err.to_string());
+            },
+            _ => return Err(FlowyError::invalid_data().with_context(err)),
+          }
+        },
+      }
+    }
+    self.report.num_of_rows += records.len();
+    Ok(records)
+  }
+
+  fn skip_line(&mut self, line: u64, reason: String) {
+    tracing::warn!("[CSV import]: skip line {}: {}", line, reason);
+    self
+      .report
+      .skipped_lines
+      .push(CSVSkippedLine { line, reason });
+  }
+}
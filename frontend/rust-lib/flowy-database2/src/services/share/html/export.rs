@@ -0,0 +1,89 @@
+use collab_database::database::Database;
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row};
+use futures::StreamExt;
+
+use flowy_error::FlowyResult;
+use lib_infra::util::escape_html;
+
+use crate::entities::{CheckboxCellDataPB, FieldType};
+use crate::services::cell::stringify_cell;
+use crate::services::field::{TimestampCellData, TimestampCellDataWrapper};
+
+pub struct HTMLExport;
+impl HTMLExport {
+  /// Exports the rows of the view as an HTML table whose header contains the names of the fields.
+  /// The cells are written as text, except the checkboxes and the URLs that are written as the
+  /// corresponding HTML elements.
+  pub async fn export_database(&self, database: &Database, view_id: &str) -> FlowyResult<String> {
+    let fields = database.get_fields_in_view(view_id, None);
+    let rows = database
+      .get_rows_for_view(view_id, None)
+      .await
+      .filter_map(|result| async { result.ok() })
+      .collect::<Vec<_>>()
+      .await;
+
+    let mut html = String::from("<table>");
+    html.push_str("<thead><tr>");
+    for field in &fields {
+      html.push_str(&format!("<th>{}</th>", escape_html(&field.name)));
+    }
+    html.push_str("</tr></thead>");
+
+    html.push_str("<tbody>");
+    for row in &rows {
+      html.push_str("<tr>");
+      for field in &fields {
+        html.push_str(&format!("<td>{}</td>", cell_to_html(row, field)));
+      }
+      html.push_str("</tr>");
+    }
+    html.push_str("</tbody></table>");
+    Ok(html)
+  }
+}
+
+fn cell_to_html(row: &Row, field: &Field) -> String {
+  let field_type = FieldType::from(field.field_type);
+  match field_type {
+    FieldType::CreatedTime | FieldType::LastEditedTime => {
+      let cell_data = if field_type.is_created_time() {
+        TimestampCellData::new(row.created_at)
+      } else {
+        TimestampCellData::new(row.modified_at)
+      };
+      let cell = Cell::from(TimestampCellDataWrapper::from((field_type, cell_data)));
+      return text_to_html(&stringify_cell(&cell, field));
+    },
+    _ => {},
+  }
+
+  let cell = match row.cells.get(&field.id) {
+    None => return "".to_string(),
+    Some(cell) => cell,
+  };
+  match field_type {
+    FieldType::Checkbox => {
+      let checked = if CheckboxCellDataPB::from(cell).is_checked {
+        " checked"
+      } else {
+        ""
+      };
+      format!("<input type=\"checkbox\" disabled{} />", checked)
+    },
+    FieldType::URL => {
+      let url = stringify_cell(cell, field);
+      if url.is_empty() {
+        return url;
+      }
+      let url = escape_html(&url);
+      format!("<a href=\"{}\">{}</a>", url, url)
+    },
+    _ => text_to_html(&stringify_cell(cell, field)),
+  }
+}
+
+fn text_to_html(text: &str) -> String {
+  escape_html(text).replace('\n', "<br />")
+}
@@ -0,0 +1,3 @@
+mod export;
+
+pub use export::*;
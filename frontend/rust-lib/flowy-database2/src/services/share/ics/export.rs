@@ -0,0 +1,304 @@
+use chrono::{Days, TimeZone, Utc};
+use chrono_tz::Tz;
+use collab_database::database::Database;
+use collab_database::fields::date_type_option::{DateCellData, DateTypeOption};
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+use collab_database::views::DatabaseLayout;
+use futures::StreamExt;
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::entities::FieldType;
+use crate::services::cell::stringify_cell;
+use crate::services::field::{time_zone_from_id, RecurrenceFrequency, RecurrenceRule};
+use crate::services::setting::CalendarLayoutSetting;
+use crate::services::share::ics::format::{
+  date_of_timestamp, DATE_FORMAT, DATE_TIME_FORMAT, WEEKDAYS,
+};
+
+const PRODUCT_ID: &str = "-//AppFlowy//Calendar//EN";
+
+/// The maximum length of a content line in octets, the longer lines are folded
+const MAX_LINE_LENGTH: usize = 75;
+
+pub struct ICSExport;
+impl ICSExport {
+  /// Exports the rows of the calendar view as the events of an iCalendar file. The primary field
+  /// is written as the SUMMARY, the date field of the calendar as the DTSTART and the DTEND, and
+  /// the other text fields as the DESCRIPTION. The rows without a date are skipped.
+  pub async fn export_database(&self, database: &Database, view_id: &str) -> FlowyResult<String> {
+    let fields = database.get_fields_in_view(view_id, None);
+    let date_field = get_calendar_date_field(database, view_id, &fields).ok_or_else(|| {
+      FlowyError::invalid_data().with_context("The view doesn't have a date field")
+    })?;
+    let time_zone = date_field
+      .get_type_option::<DateTypeOption>(FieldType::DateTime)
+      .and_then(|type_option| time_zone_from_id(&type_option.timezone_id));
+    let primary_field = fields.iter().find(|field| field.is_primary);
+    let text_fields = fields
+      .iter()
+      .filter(|field| !field.is_primary && FieldType::from(field.field_type).is_text())
+      .collect::<Vec<_>>();
+    let rows = database
+      .get_rows_for_view(view_id, None)
+      .await
+      .filter_map(|result| async { result.ok() })
+      .collect::<Vec<_>>()
+      .await;
+
+    let mut writer = ICSWriter::default();
+    writer.property("BEGIN", "VCALENDAR");
+    writer.property("VERSION", "2.0");
+    writer.property("PRODID", PRODUCT_ID);
+    writer.property("CALSCALE", "GREGORIAN");
+    if let Some(view) = database.get_view(view_id) {
+      writer.property("X-WR-CALNAME", &escape_text(&view.name));
+    }
+    if let Some(time_zone) = time_zone {
+      writer.property("X-WR-TIMEZONE", time_zone.name());
+    }
+
+    for row in rows.iter() {
+      let cell = match row.cells.get(&date_field.id) {
+        None => continue,
+        Some(cell) => cell,
+      };
+      let cell_data = DateCellData::from(cell);
+      let start = match cell_data.timestamp {
+        None => continue,
+        Some(start) => start,
+      };
+      let all_day = !cell_data.include_time;
+
+      writer.property("BEGIN", "VEVENT");
+      writer.property("UID", row.id.as_str());
+      writer.property("DTSTAMP", &format_utc(row.modified_at));
+      writer.date_property("DTSTART", start, all_day, time_zone);
+      match cell_data.end_timestamp {
+        Some(end) if cell_data.is_range && all_day => {
+          // The end date of an all-day event is exclusive
+          if let Some(end_date) =
+            date_of_timestamp(end, time_zone).and_then(|date| date.checked_add_days(Days::new(1)))
+          {
+            writer.property_with_params(
+              "DTEND",
+              "VALUE=DATE",
+              &end_date.format(DATE_FORMAT).to_string(),
+            );
+          }
+        },
+        Some(end) if cell_data.is_range => writer.date_property("DTEND", end, all_day, time_zone),
+        _ => {},
+      }
+      if let Some(recurrence) = RecurrenceRule::from_cell(cell) {
+        writer.property("RRULE", &format_recurrence(&recurrence, all_day, time_zone));
+        for exception in recurrence.exceptions.iter() {
+          writer.date_property("EXDATE", *exception, all_day, time_zone);
+        }
+      }
+      if let Some(primary_field) = primary_field {
+        let summary = row_cell_string(row, primary_field);
+        if !summary.is_empty() {
+          writer.property("SUMMARY", &escape_text(&summary));
+        }
+      }
+      let description = row_description(row, &text_fields);
+      if !description.is_empty() {
+        writer.property("DESCRIPTION", &escape_text(&description));
+      }
+      writer.property("END", "VEVENT");
+    }
+
+    writer.property("END", "VCALENDAR");
+    Ok(writer.content)
+  }
+}
+
+/// Returns the date field of the calendar layout setting, or the first date field of the view if
+/// the view isn't a calendar.
+fn get_calendar_date_field<'a>(
+  database: &Database,
+  view_id: &str,
+  fields: &'a [Field],
+) -> Option<&'a Field> {
+  let is_date_field = |field: &&Field| FieldType::from(field.field_type).is_date();
+  database
+    .get_layout_setting::<CalendarLayoutSetting>(view_id, &DatabaseLayout::Calendar)
+    .and_then(|setting| {
+      fields
+        .iter()
+        .filter(is_date_field)
+        .find(|field| field.id == setting.field_id)
+    })
+    .or_else(|| fields.iter().find(is_date_field))
+}
+
+fn row_cell_string(row: &Row, field: &Field) -> String {
+  row
+    .cells
+    .get(&field.id)
+    .map(|cell| stringify_cell(cell, field))
+    .unwrap_or_default()
+}
+
+/// A single text field is written as is, the name of each field is written before its value
+/// otherwise.
+fn row_description(row: &Row, text_fields: &[&Field]) -> String {
+  if let [field] = text_fields {
+    return row_cell_string(row, field);
+  }
+  text_fields
+    .iter()
+    .map(|field| (field, row_cell_string(row, field)))
+    .filter(|(_, s)| !s.is_empty())
+    .map(|(field, s)| format!("{}: {}", field.name, s))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn format_utc(timestamp: i64) -> String {
+  Utc
+    .timestamp_opt(timestamp, 0)
+    .single()
+    .map(|date_time| format!("{}Z", date_time.format(DATE_TIME_FORMAT)))
+    .unwrap_or_default()
+}
+
+fn format_recurrence(recurrence: &RecurrenceRule, all_day: bool, time_zone: Option<Tz>) -> String {
+  let frequency = match recurrence.frequency {
+    RecurrenceFrequency::Daily => "DAILY",
+    RecurrenceFrequency::Weekly => "WEEKLY",
+    RecurrenceFrequency::Monthly => "MONTHLY",
+    RecurrenceFrequency::Yearly => "YEARLY",
+  };
+  let mut parts = vec![format!("FREQ={}", frequency)];
+  if recurrence.interval > 1 {
+    parts.push(format!("INTERVAL={}", recurrence.interval));
+  }
+  if recurrence.frequency == RecurrenceFrequency::Weekly && !recurrence.by_weekday.is_empty() {
+    let weekdays = recurrence
+      .by_weekday
+      .iter()
+      .filter_map(|weekday| WEEKDAYS.get(*weekday as usize))
+      .copied()
+      .collect::<Vec<_>>();
+    parts.push(format!("BYDAY={}", weekdays.join(",")));
+  }
+  // The UNTIL has the same value type as the DTSTART
+  let until = recurrence.until.and_then(|until| {
+    if all_day {
+      date_of_timestamp(until, time_zone).map(|date| date.format(DATE_FORMAT).to_string())
+    } else {
+      Some(format_utc(until))
+    }
+  });
+  if let Some(until) = until {
+    parts.push(format!("UNTIL={}", until));
+  }
+  if let Some(count) = recurrence.count {
+    parts.push(format!("COUNT={}", count));
+  }
+  parts.join(";")
+}
+
+/// Escapes the characters that have a meaning in the TEXT values
+fn escape_text(s: &str) -> String {
+  s.replace('\\', "\\\\")
+    .replace(';', "\\;")
+    .replace(',', "\\,")
+    .replace("\r\n", "\\n")
+    .replace('\n', "\\n")
+}
+
+#[derive(Default)]
+struct ICSWriter {
+  content: String,
+}
+
+impl ICSWriter {
+  fn property(&mut self, name: &str, value: &str) {
+    self.line(format!("{}:{}", name, value));
+  }
+
+  fn property_with_params(&mut self, name: &str, params: &str, value: &str) {
+    self.line(format!("{};{}:{}", name, params, value));
+  }
+
+  /// Writes a date as a DATE value for the all-day events. The date-times are written in the time
+  /// zone of the date field if it has one, in UTC otherwise.
+  fn date_property(&mut self, name: &str, timestamp: i64, all_day: bool, time_zone: Option<Tz>) {
+    if all_day {
+      if let Some(date) = date_of_timestamp(timestamp, time_zone) {
+        self.property_with_params(name, "VALUE=DATE", &date.format(DATE_FORMAT).to_string());
+      }
+      return;
+    }
+
+    let date_time = time_zone.and_then(|time_zone| time_zone.timestamp_opt(timestamp, 0).single());
+    match date_time {
+      Some(date_time) => self.property_with_params(
+        name,
+        &format!("TZID={}", date_time.timezone().name()),
+        &date_time.format(DATE_TIME_FORMAT).to_string(),
+      ),
+      None => self.property(name, &format_utc(timestamp)),
+    }
+  }
+
+  /// Folds the line into lines of at most 75 octets, the continuation lines start with a space
+  fn line(&mut self, line: String) {
+    let mut length = 0;
+    for c in line.chars() {
+      if length + c.len_utf8() > MAX_LINE_LENGTH {
+        self.content.push_str("\r\n ");
+        length = 1;
+      }
+      self.content.push(c);
+      length += c.len_utf8();
+    }
+    self.content.push_str("\r\n");
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::services::field::{RecurrenceFrequency, RecurrenceRule};
+  use crate::services::share::ics::export::{escape_text, format_recurrence, ICSWriter};
+
+  #[test]
+  fn ics_fold_and_escape_test() {
+    let mut writer = ICSWriter::default();
+    let description = escape_text(&format!("{}, second line\n{}", "a".repeat(80), "b"));
+    writer.property("DESCRIPTION", &description);
+    let lines = writer.content.split("\r\n").collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].len(), 75);
+    assert!(lines[1].starts_with(' '));
+    assert!(lines[1].ends_with("\\, second line\\nb"));
+    assert_eq!(lines[2], "");
+  }
+
+  #[test]
+  fn ics_format_recurrence_test() {
+    let recurrence = RecurrenceRule {
+      interval: 2,
+      by_weekday: vec![0, 2],
+      until: Some(1704067200),
+      ..RecurrenceRule::new(RecurrenceFrequency::Weekly)
+    };
+    assert_eq!(
+      format_recurrence(&recurrence, false, None),
+      "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20240101T000000Z"
+    );
+
+    let recurrence = RecurrenceRule {
+      count: Some(3),
+      ..RecurrenceRule::new(RecurrenceFrequency::Monthly)
+    };
+    assert_eq!(
+      format_recurrence(&recurrence, true, None),
+      "FREQ=MONTHLY;COUNT=3"
+    );
+  }
+}
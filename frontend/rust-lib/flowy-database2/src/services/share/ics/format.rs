@@ -0,0 +1,16 @@
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+
+use crate::services::field::date_time_of_timestamp;
+
+pub(crate) const DATE_FORMAT: &str = "%Y%m%d";
+pub(crate) const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// The weekdays of the `BYDAY` part of a `RRULE`, starting from Monday like
+/// [RecurrenceRule::by_weekday](crate::services::field::RecurrenceRule).
+pub(crate) const WEEKDAYS: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+/// Returns the date of the timestamp in the time zone, or in the local time zone if there is none.
+pub(crate) fn date_of_timestamp(timestamp: i64, time_zone: Option<Tz>) -> Option<NaiveDate> {
+  date_time_of_timestamp(timestamp, time_zone).map(|date_time| date_time.date())
+}
@@ -0,0 +1,640 @@
+use chrono::{Days, Duration, NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use collab_database::database::{gen_database_id, gen_row_id, timestamp};
+use collab_database::entity::{CreateDatabaseParams, CreateViewParams};
+use collab_database::fields::date_type_option::DateTypeOption;
+use collab_database::fields::Field;
+use collab_database::rows::CreateRowParams;
+use collab_database::views::{DatabaseLayout, LayoutSettings};
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::box_any::BoxAny;
+
+use crate::entities::FieldType;
+use crate::services::cell::{apply_cell_changeset, insert_text_cell};
+use crate::services::field::{
+  bounded_interval, time_zone_from_id, timestamp_of_date_time, DateCellChangeset, FieldBuilder,
+  RecurrenceFrequency, RecurrenceRule,
+};
+use crate::services::field_settings::default_field_settings_for_fields;
+use crate::services::setting::CalendarLayoutSetting;
+use crate::services::share::ics::format::{
+  date_of_timestamp, DATE_FORMAT, DATE_TIME_FORMAT, WEEKDAYS,
+};
+
+#[derive(Default)]
+pub struct ICSImporter;
+
+impl ICSImporter {
+  /// Imports the events of the iCalendar content as the rows of a calendar database. The SUMMARY
+  /// is imported as the title, the DTSTART and the DTEND or the DURATION as the date, and the
+  /// DESCRIPTION and the LOCATION as text fields. The RRULE is kept if its frequency is supported.
+  pub fn import_ics_from_string(
+    &self,
+    view_id: String,
+    content: String,
+  ) -> FlowyResult<CreateDatabaseParams> {
+    let calendar = parse_calendar(&content)?;
+    Ok(database_from_calendar(&view_id, calendar))
+  }
+}
+
+#[derive(Default)]
+struct ICSCalendar {
+  name: String,
+  time_zone_id: Option<String>,
+  events: Vec<ICSEvent>,
+}
+
+#[derive(Default)]
+struct ICSEvent {
+  summary: String,
+  description: String,
+  location: String,
+  start: Option<ICSDate>,
+  end: Option<ICSDate>,
+  duration: Option<Duration>,
+  rrule: Option<String>,
+  exceptions: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ICSDate {
+  timestamp: i64,
+  all_day: bool,
+}
+
+/// A property of a component, such as `DTSTART;TZID=Europe/Paris:20240101T100000`
+struct ContentLine {
+  name: String,
+  params: Vec<(String, String)>,
+  value: String,
+}
+
+impl ContentLine {
+  fn param(&self, name: &str) -> Option<&str> {
+    self
+      .params
+      .iter()
+      .find(|(param_name, _)| param_name.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.trim_matches('"'))
+  }
+}
+
+fn parse_calendar(content: &str) -> FlowyResult<ICSCalendar> {
+  let lines = unfold_lines(content)
+    .iter()
+    .filter_map(|line| parse_content_line(line))
+    .collect::<Vec<_>>();
+  if !lines
+    .iter()
+    .any(|line| line.name == "BEGIN" && line.value.eq_ignore_ascii_case("VCALENDAR"))
+  {
+    return Err(FlowyError::invalid_data().with_context("The content is not an iCalendar file"));
+  }
+
+  // The time zone of the calendar is used for the floating times and the all-day dates. It's the
+  // time zone of the first event if the calendar doesn't have one.
+  let mut calendar = ICSCalendar::default();
+  for line in lines.iter() {
+    match line.name.as_str() {
+      "X-WR-CALNAME" if calendar.name.is_empty() => calendar.name = unescape_text(&line.value),
+      "X-WR-TIMEZONE" => calendar.time_zone_id = Some(line.value.trim().to_string()),
+      _ => {},
+    }
+  }
+  if calendar.time_zone_id.is_none() {
+    calendar.time_zone_id = lines
+      .iter()
+      .filter(|line| line.name == "DTSTART")
+      .find_map(|line| line.param("TZID"))
+      .map(|id| id.to_string());
+  }
+  let default_time_zone = calendar.time_zone_id.as_deref().and_then(time_zone_from_id);
+
+  // The properties of the components nested in the events, such as the alarms, are ignored
+  let mut components: Vec<String> = vec![];
+  let mut event: Option<ICSEvent> = None;
+  for line in lines {
+    match line.name.as_str() {
+      "BEGIN" => {
+        let component = line.value.to_uppercase();
+        if component == "VEVENT" {
+          event = Some(ICSEvent::default());
+        }
+        components.push(component);
+        continue;
+      },
+      "END" => {
+        if components.pop().as_deref() == Some("VEVENT") {
+          calendar.events.extend(event.take());
+        }
+        continue;
+      },
+      _ => {},
+    }
+    if components.last().map(String::as_str) != Some("VEVENT") {
+      continue;
+    }
+    let event = match event.as_mut() {
+      None => continue,
+      Some(event) => event,
+    };
+
+    match line.name.as_str() {
+      "SUMMARY" => event.summary = unescape_text(&line.value),
+      "DESCRIPTION" => event.description = unescape_text(&line.value),
+      "LOCATION" => event.location = unescape_text(&line.value),
+      "DTSTART" => event.start = parse_dates(&line, default_time_zone).into_iter().next(),
+      "DTEND" => event.end = parse_dates(&line, default_time_zone).into_iter().next(),
+      "DURATION" => event.duration = parse_duration(&line.value),
+      "RRULE" => event.rrule = Some(line.value.clone()),
+      "EXDATE" => event.exceptions.extend(
+        parse_dates(&line, default_time_zone)
+          .into_iter()
+          .map(|date| date.timestamp),
+      ),
+      _ => {},
+    }
+  }
+  Ok(calendar)
+}
+
+/// Joins the lines that were folded, a line that starts with a space or a tab continues the
+/// previous one.
+fn unfold_lines(content: &str) -> Vec<String> {
+  let mut lines: Vec<String> = vec![];
+  for line in content.lines() {
+    match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+      (Some(continuation), Some(last)) => last.push_str(continuation),
+      _ => lines.push(line.to_string()),
+    }
+  }
+  lines
+}
+
+/// Returns None if the line doesn't have a value. The colons and the semicolons in the quoted
+/// parameter values don't split the line.
+fn parse_content_line(line: &str) -> Option<ContentLine> {
+  let mut in_quotes = false;
+  let mut parts: Vec<String> = vec![String::new()];
+  let mut value = None;
+  for (index, c) in line.char_indices() {
+    match c {
+      '"' => in_quotes = !in_quotes,
+      ';' if !in_quotes => {
+        parts.push(String::new());
+        continue;
+      },
+      ':' if !in_quotes => {
+        value = Some(line[index + 1..].to_string());
+        break;
+      },
+      _ => {},
+    }
+    if let Some(part) = parts.last_mut() {
+      part.push(c);
+    }
+  }
+
+  let mut parts = parts.into_iter();
+  let name = parts.next()?.trim().to_uppercase();
+  let params = parts
+    .filter_map(|param| {
+      let (name, value) = param.split_once('=')?;
+      Some((name.trim().to_uppercase(), value.to_string()))
+    })
+    .collect();
+  Some(ContentLine {
+    name,
+    params,
+    value: value?,
+  })
+}
+
+fn unescape_text(s: &str) -> String {
+  let mut text = String::with_capacity(s.len());
+  let mut chars = s.chars();
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      text.push(c);
+      continue;
+    }
+    match chars.next() {
+      Some('n') | Some('N') => text.push('\n'),
+      Some(c) => text.push(c),
+      None => text.push('\\'),
+    }
+  }
+  text
+}
+
+/// Parses the comma-separated dates of the line. The times with a `TZID` are converted from their
+/// time zone, the floating times from the default time zone, or the local time zone if there is
+/// none.
+fn parse_dates(line: &ContentLine, default_time_zone: Option<Tz>) -> Vec<ICSDate> {
+  let time_zone = line
+    .param("TZID")
+    .and_then(time_zone_from_id)
+    .or(default_time_zone);
+  let is_date = line
+    .param("VALUE")
+    .map_or(false, |value| value.eq_ignore_ascii_case("DATE"));
+  line
+    .value
+    .split(',')
+    .filter_map(|value| parse_date(value.trim(), is_date, time_zone))
+    .collect()
+}
+
+fn parse_date(value: &str, is_date: bool, time_zone: Option<Tz>) -> Option<ICSDate> {
+  if is_date || value.len() == 8 {
+    let date = NaiveDate::parse_from_str(value, DATE_FORMAT).ok()?;
+    return Some(ICSDate {
+      timestamp: timestamp_of_date_time(date.and_hms_opt(0, 0, 0)?, time_zone)?,
+      all_day: true,
+    });
+  }
+
+  let timestamp = match value.strip_suffix(['Z', 'z']) {
+    Some(value) => NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+      .ok()?
+      .and_utc()
+      .timestamp(),
+    None => timestamp_of_date_time(
+      NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT).ok()?,
+      time_zone,
+    )?,
+  };
+  Some(ICSDate {
+    timestamp,
+    all_day: false,
+  })
+}
+
+/// Parses a duration such as `P1D`, `PT1H30M` or `-P2W`. Returns None if the duration is invalid
+/// or out of range.
+fn parse_duration(value: &str) -> Option<Duration> {
+  let value = value.trim();
+  let (sign, value) = match value.strip_prefix('-') {
+    Some(value) => (-1, value),
+    None => (1, value.strip_prefix('+').unwrap_or(value)),
+  };
+  let value = value.strip_prefix('P')?;
+
+  let mut seconds = 0_i64;
+  let mut number = String::new();
+  for c in value.chars() {
+    match c {
+      'T' => continue,
+      '0'..='9' => number.push(c),
+      _ => {
+        let unit = match c {
+          'W' => 7 * 24 * 60 * 60,
+          'D' => 24 * 60 * 60,
+          'H' => 60 * 60,
+          'M' => 60,
+          'S' => 1,
+          _ => return None,
+        };
+        let unit_seconds = number.parse::<i64>().ok()?.checked_mul(unit)?;
+        seconds = seconds.checked_add(unit_seconds)?;
+        number.clear();
+      },
+    }
+  }
+  Duration::try_seconds(sign * seconds)
+}
+
+/// Returns None if the frequency isn't supported, such as `HOURLY`. The ordinal of the `BYDAY`
+/// values, such as the 1 of `1MO`, is ignored.
+fn parse_rrule(value: &str, time_zone: Option<Tz>) -> Option<RecurrenceRule> {
+  let mut recurrence: Option<RecurrenceRule> = None;
+  let parts = value
+    .split(';')
+    .filter_map(|part| part.split_once('='))
+    .map(|(name, value)| (name.trim().to_uppercase(), value.trim()))
+    .collect::<Vec<_>>();
+
+  for (name, value) in parts.iter() {
+    if name == "FREQ" {
+      let frequency = match value.to_uppercase().as_str() {
+        "DAILY" => RecurrenceFrequency::Daily,
+        "WEEKLY" => RecurrenceFrequency::Weekly,
+        "MONTHLY" => RecurrenceFrequency::Monthly,
+        "YEARLY" => RecurrenceFrequency::Yearly,
+        _ => return None,
+      };
+      recurrence = Some(RecurrenceRule::new(frequency));
+    }
+  }
+
+  let mut recurrence = recurrence?;
+  for (name, value) in parts {
+    match name.as_str() {
+      "INTERVAL" => recurrence.interval = bounded_interval(value.parse::<u32>().ok()?),
+      "COUNT" => recurrence.count = value.parse::<u32>().ok(),
+      "BYDAY" => {
+        recurrence.by_weekday = value
+          .split(',')
+          .filter_map(|weekday| {
+            let weekday = weekday.trim().to_uppercase();
+            let weekday = weekday.get(weekday.len().checked_sub(2)?..)?;
+            WEEKDAYS.iter().position(|day| *day == weekday)
+          })
+          .map(|weekday| weekday as u32)
+          .collect()
+      },
+      // An UNTIL date includes the whole day
+      "UNTIL" => {
+        recurrence.until = parse_date(value, false, time_zone).map(|date| {
+          if date.all_day {
+            date.timestamp + 24 * 60 * 60 - 1
+          } else {
+            date.timestamp
+          }
+        })
+      },
+      _ => {},
+    }
+  }
+  Some(recurrence)
+}
+
+fn database_from_calendar(view_id: &str, calendar: ICSCalendar) -> CreateDatabaseParams {
+  let database_id = gen_database_id();
+  let time_zone_id = calendar.time_zone_id.clone().unwrap_or_default();
+  let time_zone = time_zone_from_id(&time_zone_id);
+
+  let title_field = FieldBuilder::from_field_type(FieldType::RichText)
+    .name("Title")
+    .primary(true)
+    .build();
+  let date_type_option = DateTypeOption {
+    timezone_id: time_zone
+      .map(|time_zone| time_zone.name().to_string())
+      .unwrap_or_default(),
+    ..DateTypeOption::new()
+  };
+  let date_field = FieldBuilder::new(FieldType::DateTime, date_type_option)
+    .name("Date")
+    .build();
+  let description_field = FieldBuilder::from_field_type(FieldType::RichText)
+    .name("Description")
+    .build();
+  let location_field = calendar
+    .events
+    .iter()
+    .any(|event| !event.location.is_empty())
+    .then(|| {
+      FieldBuilder::from_field_type(FieldType::RichText)
+        .name("Location")
+        .build()
+    });
+
+  let rows = calendar
+    .events
+    .iter()
+    .map(|event| {
+      let mut params = CreateRowParams::new(gen_row_id(), database_id.clone());
+      let mut insert_text = |field: &Field, s: &str| {
+        if !s.is_empty() {
+          params
+            .cells
+            .insert(field.id.clone(), insert_text_cell(s.to_string(), field));
+        }
+      };
+      insert_text(&title_field, &event.summary);
+      insert_text(&description_field, &event.description);
+      if let Some(location_field) = &location_field {
+        insert_text(location_field, &event.location);
+      }
+      if let Some(changeset) = date_changeset_from_event(event, time_zone) {
+        if let Ok(cell) = apply_cell_changeset(BoxAny::new(changeset), None, &date_field, None) {
+          params.cells.insert(date_field.id.clone(), cell);
+        }
+      }
+      params
+    })
+    .collect::<Vec<CreateRowParams>>();
+
+  let date_field_id = date_field.id.clone();
+  let mut fields = vec![title_field, date_field, description_field];
+  fields.extend(location_field);
+  let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Calendar);
+
+  let mut layout_settings = LayoutSettings::default();
+  layout_settings.insert(
+    DatabaseLayout::Calendar,
+    CalendarLayoutSetting::new(date_field_id).into(),
+  );
+
+  let timestamp = timestamp();
+  CreateDatabaseParams {
+    database_id: database_id.clone(),
+    rows,
+    fields,
+    views: vec![CreateViewParams {
+      database_id,
+      view_id: view_id.to_string(),
+      name: calendar.name,
+      layout: DatabaseLayout::Calendar,
+      layout_settings,
+      field_settings,
+      created_at: timestamp,
+      modified_at: timestamp,
+      ..Default::default()
+    }],
+  }
+}
+
+/// The end date of an all-day event is exclusive, an event that ends on the day after its start
+/// lasts a single day.
+fn date_changeset_from_event(event: &ICSEvent, time_zone: Option<Tz>) -> Option<DateCellChangeset> {
+  let start = event.start?;
+  let end = match (event.end, event.duration) {
+    (Some(end), _) => Some(end.timestamp),
+    (None, Some(duration)) => start.timestamp.checked_add(duration.num_seconds()),
+    (None, None) => None,
+  };
+  let end = end.map(|end| {
+    if start.all_day {
+      last_day_of_all_day_event(start.timestamp, end, time_zone)
+    } else {
+      end
+    }
+  });
+  let end = end.filter(|end| *end > start.timestamp);
+
+  let recurrence = event
+    .rrule
+    .as_deref()
+    .and_then(|rrule| parse_rrule(rrule, time_zone))
+    .map(|recurrence| RecurrenceRule {
+      exceptions: event.exceptions.clone(),
+      ..recurrence
+    });
+  Some(DateCellChangeset {
+    timestamp: Some(start.timestamp),
+    end_timestamp: end,
+    include_time: Some(!start.all_day),
+    is_range: Some(end.is_some()),
+    recurrence,
+    ..Default::default()
+  })
+}
+
+fn last_day_of_all_day_event(start: i64, end: i64, time_zone: Option<Tz>) -> i64 {
+  date_of_timestamp(end, time_zone)
+    .and_then(|end_date| end_date.checked_sub_days(Days::new(1)))
+    .and_then(|last_date| last_date.and_hms_opt(0, 0, 0))
+    .and_then(|last_date| timestamp_of_date_time(last_date, time_zone))
+    .unwrap_or(end)
+    .max(start)
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{NaiveDate, TimeZone};
+  use chrono_tz::Tz;
+  use collab_database::fields::date_type_option::{DateCellData, DateTypeOption};
+
+  use crate::entities::FieldType;
+  use crate::services::cell::stringify_cell;
+  use crate::services::field::{RecurrenceFrequency, RecurrenceRule, MAX_INTERVAL};
+  use crate::services::share::ics::ICSImporter;
+
+  const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//EN\r\n\
+X-WR-CALNAME:Team\r\n\
+BEGIN:VEVENT\r\n\
+UID:1\r\n\
+DTSTART;TZID=Europe/Paris:20240102T100000\r\n\
+DTEND;TZID=Europe/Paris:20240102T113000\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=TU,TH;COUNT=4\r\n\
+EXDATE;TZID=Europe/Paris:20240104T100000\r\n\
+SUMMARY:Stand\\, up\r\n\
+DESCRIPTION:First line\\nsecond line that is long enough to be folded by th\r\n \
+e exporter\r\n\
+BEGIN:VALARM\r\n\
+DESCRIPTION:Reminder\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:2\r\n\
+DTSTART;VALUE=DATE:20240105\r\n\
+DTEND;VALUE=DATE:20240107\r\n\
+SUMMARY:Offsite\r\n\
+LOCATION:Paris\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+  #[test]
+  fn import_ics_test() {
+    let params = ICSImporter
+      .import_ics_from_string("view".to_string(), ICS.to_string())
+      .unwrap();
+    assert_eq!(params.views[0].name, "Team");
+    let field_names = params
+      .fields
+      .iter()
+      .map(|field| field.name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      field_names,
+      vec!["Title", "Date", "Description", "Location"]
+    );
+    let date_field = &params.fields[1];
+    let type_option = date_field
+      .get_type_option::<DateTypeOption>(FieldType::DateTime)
+      .unwrap();
+    assert_eq!(type_option.timezone_id, "Europe/Paris");
+
+    assert_eq!(params.rows.len(), 2);
+    let text = |row: usize, field: usize| {
+      let field = &params.fields[field];
+      params.rows[row]
+        .cells
+        .get(&field.id)
+        .map(|cell| stringify_cell(cell, field))
+        .unwrap_or_default()
+    };
+    assert_eq!(text(0, 0), "Stand, up");
+    assert_eq!(
+      text(0, 2),
+      "First line\nsecond line that is long enough to be folded by the exporter"
+    );
+    assert_eq!(text(1, 3), "Paris");
+
+    // The times are converted from the time zone of the event
+    let paris = "Europe/Paris".parse::<Tz>().unwrap();
+    let paris_timestamp = |day: u32, hour: u32, minute: u32| {
+      paris
+        .with_ymd_and_hms(2024, 1, day, hour, minute, 0)
+        .unwrap()
+        .timestamp()
+    };
+    let date_cell = params.rows[0].cells.get(&date_field.id).unwrap();
+    let cell_data = DateCellData::from(date_cell);
+    assert_eq!(cell_data.timestamp, Some(paris_timestamp(2, 10, 0)));
+    assert_eq!(cell_data.end_timestamp, Some(paris_timestamp(2, 11, 30)));
+    assert!(cell_data.is_range && cell_data.include_time);
+    assert_eq!(
+      RecurrenceRule::from_cell(date_cell),
+      Some(RecurrenceRule {
+        by_weekday: vec![1, 3],
+        count: Some(4),
+        exceptions: vec![paris_timestamp(4, 10, 0)],
+        ..RecurrenceRule::new(RecurrenceFrequency::Weekly)
+      })
+    );
+
+    // The exclusive end of the all-day event is its last day
+    let cell_data = DateCellData::from(params.rows[1].cells.get(&date_field.id).unwrap());
+    let midnight = |day: u32| {
+      paris
+        .from_local_datetime(
+          &NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap(),
+        )
+        .unwrap()
+        .timestamp()
+    };
+    assert_eq!(cell_data.timestamp, Some(midnight(5)));
+    assert_eq!(cell_data.end_timestamp, Some(midnight(6)));
+    assert!(!cell_data.include_time);
+  }
+
+  #[test]
+  fn import_out_of_range_ics_test() {
+    let ics = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+DTSTART:20240102T100000Z\r\n\
+DURATION:P99999999999999999W\r\n\
+RRULE:FREQ=DAILY;INTERVAL=4294967295\r\n\
+SUMMARY:Forever\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+    let params = ICSImporter
+      .import_ics_from_string("view".to_string(), ics.to_string())
+      .unwrap();
+    let date_field = &params.fields[1];
+    let date_cell = params.rows[0].cells.get(&date_field.id).unwrap();
+
+    // The invalid duration is skipped and the interval is clamped
+    assert_eq!(DateCellData::from(date_cell).end_timestamp, None);
+    assert_eq!(
+      RecurrenceRule::from_cell(date_cell).unwrap().interval,
+      MAX_INTERVAL
+    );
+  }
+
+  #[test]
+  fn import_invalid_ics_test() {
+    assert!(ICSImporter
+      .import_ics_from_string("view".to_string(), "Name,Date\nA,B".to_string())
+      .is_err());
+  }
+}
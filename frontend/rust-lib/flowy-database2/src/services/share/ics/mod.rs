@@ -0,0 +1,6 @@
+mod export;
+mod format;
+mod import;
+
+pub use export::*;
+pub use import::*;
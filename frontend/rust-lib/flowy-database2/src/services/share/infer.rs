@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use collab_database::fields::number_type_option::NumberFormat;
+use collab_database::fields::select_type_option::SelectTypeOption;
+use collab_database::fields::Field;
+use collab_database::rows::{new_cell_builder, Cell};
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+use lib_infra::box_any::BoxAny;
+
+use crate::entities::FieldType;
+use crate::services::cell::{
+  apply_cell_changeset, insert_checkbox_cell, insert_checklist_cell, insert_date_cell,
+  insert_select_option_cell, insert_url_cell,
+};
+use crate::services::field::{ChecklistCellInsertChangeset, CELL_DATA};
+
+/// A column whose distinct values don't exceed this number is imported as a select field.
+pub(crate) const MAX_NUM_OF_SELECT_OPTIONS: usize = 20;
+
+const DATE_FORMATS: [&str; 6] = [
+  "%Y-%m-%d",
+  "%Y/%m/%d",
+  "%m/%d/%Y",
+  "%d.%m.%Y",
+  "%b %d, %Y",
+  "%B %d, %Y",
+];
+const DATE_TIME_FORMATS: [&str; 6] = [
+  "%Y-%m-%d %H:%M:%S",
+  "%Y-%m-%d %H:%M",
+  "%Y-%m-%dT%H:%M:%S",
+  "%Y/%m/%d %H:%M",
+  "%m/%d/%Y %H:%M",
+  "%b %d, %Y %H:%M",
+];
+
+lazy_static! {
+  static ref NUMBER_REGEX: Regex = Regex::new(r"^(\d{1,3}(,\d{3})+|\d+)(\.\d+)?$").unwrap();
+  static ref CHECKLIST_ITEM_REGEX: Regex = Regex::new(r"\[([ xX])\]\s*([^\[]*)").unwrap();
+}
+
+/// Infers the field type from the non-empty text values of a column. The number format is only
+/// used when the field type is [FieldType::Number].
+pub(crate) fn infer_field_type(values: &[&str]) -> (FieldType, NumberFormat) {
+  if values.is_empty() {
+    return (FieldType::RichText, NumberFormat::Num);
+  }
+  if values.iter().all(|value| parse_checkbox(value).is_some()) {
+    return (FieldType::Checkbox, NumberFormat::Num);
+  }
+  if let Some(format) = infer_number_format(values) {
+    return (FieldType::Number, format);
+  }
+  if values.iter().all(|value| parse_date(value).is_some()) {
+    return (FieldType::DateTime, NumberFormat::Num);
+  }
+  if values.iter().all(|value| is_url(value)) {
+    return (FieldType::URL, NumberFormat::Num);
+  }
+  if values.iter().all(|value| parse_checklist(value).is_some()) {
+    return (FieldType::Checklist, NumberFormat::Num);
+  }
+  (infer_select_type(values), NumberFormat::Num)
+}
+
+/// All the values must be numbers with the same currency symbol.
+fn infer_number_format(values: &[&str]) -> Option<NumberFormat> {
+  let mut formats = values
+    .iter()
+    .map(|value| parse_number(value).map(|(_, format)| format));
+  let first = formats.next()??;
+  for format in formats {
+    if format? != first {
+      return None;
+    }
+  }
+  Some(first)
+}
+
+/// Text with few distinct values becomes a select field. A column in which every value is distinct
+/// is more likely to be a free-text column.
+pub(crate) fn infer_select_type(values: &[&str]) -> FieldType {
+  let field_type = if values.iter().any(|value| value.contains(',')) {
+    FieldType::MultiSelect
+  } else {
+    FieldType::SingleSelect
+  };
+  let options = values
+    .iter()
+    .flat_map(|value| split_select_options(value, field_type))
+    .collect::<Vec<_>>();
+  let distinct_options = options.iter().collect::<HashSet<_>>();
+  if distinct_options.len() <= MAX_NUM_OF_SELECT_OPTIONS && distinct_options.len() < options.len() {
+    field_type
+  } else {
+    FieldType::RichText
+  }
+}
+
+pub(crate) fn parse_checkbox(value: &str) -> Option<bool> {
+  match value.trim().to_lowercase().as_str() {
+    "yes" | "true" | "checked" => Some(true),
+    "no" | "false" | "unchecked" => Some(false),
+    _ => None,
+  }
+}
+
+/// Returns the number without the currency symbol and the thousands separators, along with the
+/// format implied by the symbol.
+pub(crate) fn parse_number(value: &str) -> Option<(String, NumberFormat)> {
+  let value = value.trim();
+  let (sign, value) = match value.strip_prefix('-') {
+    Some(value) => ("-", value.trim()),
+    None => ("", value),
+  };
+  let (number, format) = match value.strip_suffix('%') {
+    Some(number) => (number, NumberFormat::Percent),
+    None => strip_currency_symbol(value),
+  };
+
+  let number = number.trim();
+  if !NUMBER_REGEX.is_match(number).unwrap_or(false) {
+    return None;
+  }
+  Some((format!("{}{}", sign, number.replace(',', "")), format))
+}
+
+fn strip_currency_symbol(value: &str) -> (&str, NumberFormat) {
+  // The longer symbols must be checked first, "R$" contains "$".
+  const SYMBOLS: [(&str, NumberFormat); 10] = [
+    ("R$", NumberFormat::Real),
+    ("CA$", NumberFormat::CanadianDollar),
+    ("$", NumberFormat::USD),
+    ("€", NumberFormat::EUR),
+    ("£", NumberFormat::Pound),
+    ("¥", NumberFormat::Yen),
+    ("₹", NumberFormat::Rupee),
+    ("₩", NumberFormat::Won),
+    ("₽", NumberFormat::Ruble),
+    ("₺", NumberFormat::Lira),
+  ];
+  for (symbol, format) in SYMBOLS {
+    if let Some(number) = value.strip_prefix(symbol) {
+      return (number, format);
+    }
+    if let Some(number) = value.strip_suffix(symbol) {
+      return (number, format);
+    }
+  }
+  (value, NumberFormat::Num)
+}
+
+/// Returns the timestamp of the date and whether the value contains the time.
+pub(crate) fn parse_date(value: &str) -> Option<(i64, bool)> {
+  let value = value.trim();
+  if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(value) {
+    return Some((datetime.timestamp(), true));
+  }
+  for format in DATE_TIME_FORMATS {
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+      return Some((datetime.and_utc().timestamp(), true));
+    }
+  }
+  for format in DATE_FORMATS {
+    if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+      return Some((date.and_time(NaiveTime::MIN).and_utc().timestamp(), false));
+    }
+  }
+  None
+}
+
+pub(crate) fn is_url(value: &str) -> bool {
+  let value = value.trim();
+  if value.contains(char::is_whitespace) {
+    return false;
+  }
+  match url::Url::parse(value) {
+    Ok(url) => matches!(url.scheme(), "http" | "https") && url.host().is_some(),
+    Err(_) => value.starts_with("www.") && value.len() > 4,
+  }
+}
+
+/// Parses the tasks written as `[x] done, [ ] todo`. Returns the name of each task and whether the
+/// task is complete.
+pub(crate) fn parse_checklist(value: &str) -> Option<Vec<(String, bool)>> {
+  let value = value.trim();
+  if !value.starts_with('[') {
+    return None;
+  }
+  let tasks = CHECKLIST_ITEM_REGEX
+    .captures_iter(value)
+    .filter_map(|captures| captures.ok())
+    .filter_map(|captures| {
+      let is_complete = captures.get(1)?.as_str() != " ";
+      let name = captures
+        .get(2)?
+        .as_str()
+        .trim()
+        .trim_end_matches([',', ';'])
+        .trim()
+        .to_string();
+      Some((name, is_complete))
+    })
+    .filter(|(name, _)| !name.is_empty())
+    .collect::<Vec<_>>();
+  if tasks.is_empty() {
+    None
+  } else {
+    Some(tasks)
+  }
+}
+
+/// Returns the names of the options of a select cell. The options of a multi-select cell are
+/// separated by commas.
+pub(crate) fn split_select_options(value: &str, field_type: FieldType) -> Vec<String> {
+  if field_type.is_multi_select() {
+    value
+      .split(',')
+      .map(|name| name.trim().to_string())
+      .filter(|name| !name.is_empty())
+      .collect()
+  } else {
+    vec![value.trim().to_string()]
+  }
+}
+
+/// Converts the content to the cell of the field. The content is kept as it is if it can't be
+/// parsed as the field type, which happens when the caller overrides the inferred type.
+pub(crate) fn cell_from_content(field: &Field, content: &str) -> Cell {
+  let field_type = FieldType::from(field.field_type);
+  let content = content.trim();
+  let cell = if content.is_empty() {
+    None
+  } else {
+    match field_type {
+      FieldType::Number => parse_number(content)
+        .and_then(|(number, _)| apply_cell_changeset(BoxAny::new(number), None, field, None).ok()),
+      FieldType::DateTime => parse_date(content).map(|(timestamp, include_time)| {
+        insert_date_cell(timestamp, None, Some(include_time), field)
+      }),
+      FieldType::Checkbox => {
+        parse_checkbox(content).map(|is_checked| insert_checkbox_cell(is_checked, field))
+      },
+      FieldType::URL => Some(insert_url_cell(content.to_string(), field)),
+      FieldType::SingleSelect | FieldType::MultiSelect => {
+        let options = field
+          .get_any_type_option(field_type)
+          .map(|data| SelectTypeOption::from(data).options)
+          .unwrap_or_default();
+        let option_ids = split_select_options(content, field_type)
+          .into_iter()
+          .filter_map(|name| {
+            options
+              .iter()
+              .find(|option| option.name == name)
+              .map(|option| option.id.clone())
+          })
+          .collect::<Vec<String>>();
+        Some(insert_select_option_cell(option_ids, field))
+      },
+      FieldType::Checklist => parse_checklist(content).map(|tasks| {
+        let tasks = tasks
+          .into_iter()
+          .map(|(name, is_complete)| ChecklistCellInsertChangeset::new(name, is_complete))
+          .collect();
+        insert_checklist_cell(tasks, field)
+      }),
+      _ => None,
+    }
+  };
+
+  cell.unwrap_or_else(|| {
+    let mut cell = new_cell_builder(field_type);
+    cell.insert(CELL_DATA.into(), content.into());
+    cell
+  })
+}
@@ -1 +1,5 @@
 pub mod csv;
+pub mod html;
+pub mod ics;
+mod infer;
+pub mod xlsx;
@@ -0,0 +1,222 @@
+use collab_database::database::Database;
+use collab_database::fields::date_type_option::DateCellData;
+use collab_database::fields::select_type_option::SingleSelectTypeOption;
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+use futures::StreamExt;
+use rust_xlsxwriter::{ColNum, DataValidation, ExcelDateTime, Format, RowNum, Workbook, Worksheet};
+
+use flowy_error::{internal_error, FlowyResult};
+
+use crate::entities::{CheckboxCellDataPB, FieldType};
+use crate::services::cell::stringify_cell;
+use crate::services::field::TypeOptionCellExt;
+
+const DATE_FORMAT: &str = "yyyy-mm-dd";
+const DATE_TIME_FORMAT: &str = "yyyy-mm-dd hh:mm";
+
+pub struct XLSXExport;
+impl XLSXExport {
+  /// Exports the rows of the view as a single worksheet. Numbers, dates and checkboxes are written
+  /// as native spreadsheet values and the options of single select fields are kept as a list
+  /// validation, so they survive a round-trip through a spreadsheet application.
+  pub async fn export_database(&self, database: &Database, view_id: &str) -> FlowyResult<Vec<u8>> {
+    let fields = database.get_fields_in_view(view_id, None);
+    let rows = database
+      .get_rows_for_view(view_id, None)
+      .await
+      .filter_map(|result| async { result.ok() })
+      .collect::<Vec<_>>()
+      .await;
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    if let Some(view) = database.get_view(view_id) {
+      let sheet_name = sheet_name_from_view_name(&view.name);
+      if !sheet_name.is_empty() {
+        worksheet.set_name(sheet_name).map_err(internal_error)?;
+      }
+    }
+
+    let formats = CellFormats::new();
+    for (col, field) in fields.iter().enumerate() {
+      worksheet
+        .write_string_with_format(0, col as ColNum, &field.name, &formats.header)
+        .map_err(internal_error)?;
+    }
+    worksheet.set_freeze_panes(1, 0).map_err(internal_error)?;
+
+    for (index, row) in rows.iter().enumerate() {
+      let row_num = (index + 1) as RowNum;
+      for (col, field) in fields.iter().enumerate() {
+        write_cell(worksheet, row_num, col as ColNum, row, field, &formats)?;
+      }
+    }
+
+    for (col, field) in fields.iter().enumerate() {
+      if FieldType::from(field.field_type).is_single_select() {
+        add_select_option_validation(worksheet, col as ColNum, rows.len() as RowNum, field);
+      }
+    }
+
+    workbook.save_to_buffer().map_err(internal_error)
+  }
+}
+
+struct CellFormats {
+  header: Format,
+  date: Format,
+  date_time: Format,
+}
+
+impl CellFormats {
+  fn new() -> Self {
+    Self {
+      header: Format::new().set_bold(),
+      date: Format::new().set_num_format(DATE_FORMAT),
+      date_time: Format::new().set_num_format(DATE_TIME_FORMAT),
+    }
+  }
+}
+
+fn write_cell(
+  worksheet: &mut Worksheet,
+  row_num: RowNum,
+  col: ColNum,
+  row: &Row,
+  field: &Field,
+  formats: &CellFormats,
+) -> FlowyResult<()> {
+  let field_type = FieldType::from(field.field_type);
+  match field_type {
+    FieldType::CreatedTime | FieldType::LastEditedTime => {
+      let timestamp = if field_type.is_created_time() {
+        row.created_at
+      } else {
+        row.modified_at
+      };
+      return write_timestamp(worksheet, row_num, col, timestamp, true, formats);
+    },
+    _ => {},
+  }
+
+  let cell = match row.cells.get(&field.id) {
+    None => return Ok(()),
+    Some(cell) => cell,
+  };
+  match field_type {
+    FieldType::Number | FieldType::Time | FieldType::Formula | FieldType::Rollup => {
+      let number = TypeOptionCellExt::new(field, None)
+        .get_type_option_cell_data_handler()
+        .and_then(|handler| handler.handle_numeric_cell(cell));
+      match number {
+        Some(number) => {
+          worksheet
+            .write_number(row_num, col, number)
+            .map_err(internal_error)?;
+        },
+        None => write_text(worksheet, row_num, col, stringify_cell(cell, field))?,
+      }
+    },
+    FieldType::Checkbox => {
+      worksheet
+        .write_boolean(row_num, col, CheckboxCellDataPB::from(cell).is_checked)
+        .map_err(internal_error)?;
+    },
+    FieldType::DateTime => {
+      let cell_data = DateCellData::from(cell);
+      if let Some(timestamp) = cell_data.timestamp {
+        write_timestamp(
+          worksheet,
+          row_num,
+          col,
+          timestamp,
+          cell_data.include_time,
+          formats,
+        )?;
+      }
+    },
+    _ => write_text(worksheet, row_num, col, stringify_cell(cell, field))?,
+  }
+  Ok(())
+}
+
+fn write_text(
+  worksheet: &mut Worksheet,
+  row_num: RowNum,
+  col: ColNum,
+  s: String,
+) -> FlowyResult<()> {
+  if !s.is_empty() {
+    worksheet
+      .write_string(row_num, col, s)
+      .map_err(internal_error)?;
+  }
+  Ok(())
+}
+
+fn write_timestamp(
+  worksheet: &mut Worksheet,
+  row_num: RowNum,
+  col: ColNum,
+  timestamp: i64,
+  include_time: bool,
+  formats: &CellFormats,
+) -> FlowyResult<()> {
+  let datetime = ExcelDateTime::from_timestamp(timestamp).map_err(internal_error)?;
+  let format = if include_time {
+    &formats.date_time
+  } else {
+    &formats.date
+  };
+  worksheet
+    .write_datetime_with_format(row_num, col, &datetime, format)
+    .map_err(internal_error)?;
+  Ok(())
+}
+
+/// Restricts the cells of the column to the options of the single select field. Excel limits the
+/// length of such a list, so fields with many options are exported without the validation.
+fn add_select_option_validation(
+  worksheet: &mut Worksheet,
+  col: ColNum,
+  num_of_rows: RowNum,
+  field: &Field,
+) {
+  let options = field
+    .get_type_option::<SingleSelectTypeOption>(FieldType::SingleSelect)
+    .map(|type_option| type_option.options.clone())
+    .unwrap_or_default();
+  if options.is_empty() {
+    return;
+  }
+
+  let names = options
+    .iter()
+    .map(|option| option.name.as_str())
+    .collect::<Vec<_>>();
+  let result = DataValidation::new()
+    .allow_list_strings(&names)
+    .and_then(|validation| {
+      worksheet.add_data_validation(1, col, num_of_rows.max(1), col, &validation)?;
+      Ok(())
+    });
+  if let Err(err) = result {
+    tracing::warn!(
+      "[XLSX]: skip the validation of field {}: {}",
+      field.name,
+      err
+    );
+  }
+}
+
+/// Excel limits the sheet name to 31 characters and doesn't allow some special characters.
+fn sheet_name_from_view_name(name: &str) -> String {
+  name
+    .chars()
+    .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+    .take(31)
+    .collect::<String>()
+    .trim_matches('\'')
+    .to_string()
+}
@@ -0,0 +1,329 @@
+use std::io::Cursor;
+
+use calamine::{open_workbook_auto_from_rs, Data, DataType, Reader};
+use collab_database::database::{gen_database_id, gen_database_view_id, gen_row_id, timestamp};
+use collab_database::entity::{CreateDatabaseParams, CreateViewParams};
+use collab_database::fields::number_type_option::{NumberFormat, NumberTypeOption};
+use collab_database::fields::select_type_option::{SelectOption, SelectTypeOption};
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, CreateRowParams};
+use collab_database::views::DatabaseLayout;
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::box_any::BoxAny;
+
+use crate::entities::FieldType;
+use crate::services::cell::{
+  apply_cell_changeset, insert_checkbox_cell, insert_date_cell, insert_text_cell,
+};
+use crate::services::field::{new_select_option_color, FieldBuilder};
+use crate::services::field_settings::default_field_settings_for_fields;
+use crate::services::share::infer::{
+  cell_from_content, infer_field_type as infer_text_field_type, split_select_options,
+};
+
+pub struct ImportedSheet {
+  pub name: String,
+  pub params: CreateDatabaseParams,
+}
+
+#[derive(Default)]
+pub struct XLSXImporter;
+
+impl XLSXImporter {
+  /// Imports every non-empty sheet of the workbook as a separate database. The first row of a
+  /// sheet is used as the field names and the type of each field is inferred from its values.
+  pub fn import_xlsx_from_bytes(&self, bytes: Vec<u8>) -> FlowyResult<Vec<ImportedSheet>> {
+    let mut workbook = open_workbook_auto_from_rs(Cursor::new(bytes))
+      .map_err(|err| FlowyError::invalid_data().with_context(err))?;
+
+    let sheets = workbook
+      .worksheets()
+      .into_iter()
+      .filter(|(_, range)| !range.is_empty())
+      .map(|(name, range)| {
+        let mut rows = range.rows();
+        let header = rows.next().map(|row| row.to_vec()).unwrap_or_default();
+        let rows = rows.map(|row| row.to_vec()).collect::<Vec<_>>();
+        ImportedSheet {
+          params: database_from_sheet(&gen_database_view_id(), &name, header, rows),
+          name,
+        }
+      })
+      .collect::<Vec<_>>();
+
+    if sheets.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("The workbook doesn't contain any data"));
+    }
+    Ok(sheets)
+  }
+}
+
+fn database_from_sheet(
+  view_id: &str,
+  sheet_name: &str,
+  header: Vec<Data>,
+  rows: Vec<Vec<Data>>,
+) -> CreateDatabaseParams {
+  let database_id = gen_database_id();
+  let columns = header
+    .iter()
+    .enumerate()
+    .map(|(index, name)| {
+      let values = rows
+        .iter()
+        .map(|row| row.get(index).unwrap_or(&Data::Empty));
+      let name = data_to_string(name);
+      let name = if name.is_empty() {
+        format!("Column {}", index + 1)
+      } else {
+        name
+      };
+      ImportedColumn::new(&name, index == 0, values)
+    })
+    .collect::<Vec<_>>();
+  let fields = columns
+    .iter()
+    .map(|column| column.field.clone())
+    .collect::<Vec<Field>>();
+  let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
+
+  let rows = rows
+    .iter()
+    .map(|row| {
+      let mut params = CreateRowParams::new(gen_row_id(), database_id.clone());
+      for (column, data) in columns.iter().zip(row.iter()) {
+        if let Some(cell) = column.cell_from_data(data) {
+          params.cells.insert(column.field.id.clone(), cell);
+        }
+      }
+      params
+    })
+    .collect::<Vec<CreateRowParams>>();
+
+  let timestamp = timestamp();
+  CreateDatabaseParams {
+    database_id: database_id.clone(),
+    rows,
+    fields,
+    views: vec![CreateViewParams {
+      database_id,
+      view_id: view_id.to_string(),
+      name: sheet_name.to_string(),
+      layout: DatabaseLayout::Grid,
+      field_settings,
+      created_at: timestamp,
+      modified_at: timestamp,
+      ..Default::default()
+    }],
+  }
+}
+
+struct ImportedColumn {
+  field: Field,
+  include_time: bool,
+}
+
+impl ImportedColumn {
+  fn new<'a>(name: &str, is_primary: bool, values: impl Iterator<Item = &'a Data>) -> Self {
+    let values = values.filter(|data| !data.is_empty()).collect::<Vec<_>>();
+    // A column that only contains text is inferred the same way as a column of a CSV file
+    let texts = values
+      .iter()
+      .map(|data| match data {
+        Data::String(text) => Some(text.trim()),
+        _ => None,
+      })
+      .collect::<Option<Vec<_>>>();
+    let (field_type, number_format) = match texts {
+      _ if is_primary => (FieldType::RichText, NumberFormat::Num),
+      Some(texts) => infer_text_field_type(&texts),
+      None => (infer_field_type(&values), NumberFormat::Num),
+    };
+    let include_time = field_type.is_date()
+      && values
+        .iter()
+        .filter_map(|data| data.as_datetime())
+        .any(|datetime| datetime.time() != chrono::NaiveTime::MIN);
+
+    let field = match field_type {
+      FieldType::Number => {
+        let mut type_option = NumberTypeOption::default();
+        type_option.set_format(number_format);
+        FieldBuilder::new(field_type, type_option)
+      },
+      FieldType::SingleSelect | FieldType::MultiSelect => {
+        let mut options: Vec<SelectOption> = vec![];
+        for name in values
+          .iter()
+          .flat_map(|data| split_select_options(&data_to_string(data), field_type))
+        {
+          if options.iter().all(|option| option.name != name) {
+            let color = new_select_option_color(&options);
+            options.push(SelectOption::with_color(&name, color));
+          }
+        }
+        let type_option = SelectTypeOption {
+          options,
+          disable_color: false,
+        };
+        FieldBuilder::new(field_type, type_option)
+      },
+      _ => FieldBuilder::from_field_type(field_type),
+    }
+    .name(name)
+    .primary(is_primary)
+    .build();
+
+    Self {
+      field,
+      include_time,
+    }
+  }
+
+  fn cell_from_data(&self, data: &Data) -> Option<Cell> {
+    if data.is_empty() {
+      return None;
+    }
+
+    if let Data::String(text) = data {
+      return Some(cell_from_content(&self.field, text));
+    }
+
+    let field_type = FieldType::from(self.field.field_type);
+    let cell = match field_type {
+      FieldType::Number => {
+        let number = data.as_f64()?;
+        apply_cell_changeset(BoxAny::new(number.to_string()), None, &self.field, None).ok()?
+      },
+      FieldType::Checkbox => insert_checkbox_cell(data.get_bool()?, &self.field),
+      FieldType::DateTime => {
+        let timestamp = data.as_datetime()?.and_utc().timestamp();
+        insert_date_cell(timestamp, None, Some(self.include_time), &self.field)
+      },
+      _ => insert_text_cell(data_to_string(data), &self.field),
+    };
+    Some(cell)
+  }
+}
+
+/// Infers the field type from the non-empty values of a column whose values are not all text.
+/// Numbers, booleans and dates keep their type only if the whole column agrees.
+fn infer_field_type(values: &[&Data]) -> FieldType {
+  if values.is_empty() {
+    return FieldType::RichText;
+  }
+  if values
+    .iter()
+    .all(|data| matches!(data, Data::Int(_) | Data::Float(_)))
+  {
+    return FieldType::Number;
+  }
+  if values.iter().all(|data| matches!(data, Data::Bool(_))) {
+    return FieldType::Checkbox;
+  }
+  if values
+    .iter()
+    .all(|data| data.is_datetime() || data.is_datetime_iso())
+  {
+    return FieldType::DateTime;
+  }
+  FieldType::RichText
+}
+
+fn data_to_string(data: &Data) -> String {
+  match data {
+    Data::DateTime(_) | Data::DateTimeIso(_) => data
+      .as_datetime()
+      .map(|datetime| datetime.format("%Y-%m-%d %H:%M").to_string())
+      .unwrap_or_default(),
+    _ => data.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::select_type_option::SelectTypeOption;
+  use rust_xlsxwriter::{ExcelDateTime, Workbook};
+
+  use crate::entities::FieldType;
+  use crate::services::cell::stringify_cell;
+  use crate::services::share::xlsx::XLSXImporter;
+
+  fn workbook_bytes() -> Vec<u8> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Tasks").unwrap();
+    for (col, name) in ["Name", "Estimate", "Done", "Due", "Status", "Tags", "Price"]
+      .iter()
+      .enumerate()
+    {
+      worksheet.write_string(0, col as u16, *name).unwrap();
+    }
+    let rows = [
+      ("Write", 1.5, true, 1704067200, "Done", "docs", "$1,200.50"),
+      ("Review", 2.0, false, 1704153600, "Todo", "docs, code", "$3"),
+      ("Ship", 3.0, false, 1704240000, "Todo", "code", "$7.25"),
+    ];
+    for (index, (name, estimate, done, due, status, tags, price)) in rows.iter().enumerate() {
+      let row = (index + 1) as u32;
+      worksheet.write_string(row, 0, *name).unwrap();
+      worksheet.write_number(row, 1, *estimate).unwrap();
+      worksheet.write_boolean(row, 2, *done).unwrap();
+      let due = ExcelDateTime::from_timestamp(*due).unwrap();
+      worksheet
+        .write_datetime_with_format(row, 3, &due, &"yyyy-mm-dd".into())
+        .unwrap();
+      worksheet.write_string(row, 4, *status).unwrap();
+      worksheet.write_string(row, 5, *tags).unwrap();
+      // Numbers written as text are inferred from their content
+      worksheet.write_string(row, 6, *price).unwrap();
+    }
+    workbook.add_worksheet().set_name("Empty").unwrap();
+    workbook.save_to_buffer().unwrap()
+  }
+
+  #[test]
+  fn import_xlsx_infer_field_type_test() {
+    let sheets = XLSXImporter
+      .import_xlsx_from_bytes(workbook_bytes())
+      .unwrap();
+    assert_eq!(sheets.len(), 1);
+    assert_eq!(sheets[0].name, "Tasks");
+
+    let params = &sheets[0].params;
+    let field_types = params
+      .fields
+      .iter()
+      .map(|field| FieldType::from(field.field_type))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::Number,
+        FieldType::Checkbox,
+        FieldType::DateTime,
+        FieldType::SingleSelect,
+        FieldType::MultiSelect,
+        FieldType::Number,
+      ]
+    );
+    assert!(params.fields[0].is_primary);
+
+    let tags = &params.fields[5];
+    let options = SelectTypeOption::from(tags.get_any_type_option(FieldType::MultiSelect).unwrap());
+    assert_eq!(options.options.len(), 2);
+
+    assert_eq!(params.rows.len(), 3);
+    let row = &params.rows[1];
+    let cell_string = |index: usize| {
+      let field = &params.fields[index];
+      stringify_cell(row.cells.get(&field.id).unwrap(), field)
+    };
+    assert_eq!(cell_string(0), "Review");
+    assert_eq!(cell_string(1), "2");
+    assert_eq!(cell_string(4), "Todo");
+    assert_eq!(cell_string(5), "docs,code");
+    assert_eq!(cell_string(6), "$3");
+  }
+}
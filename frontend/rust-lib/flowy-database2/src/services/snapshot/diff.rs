@@ -0,0 +1,124 @@
+use collab_database::fields::Field;
+use collab_database::rows::Row;
+
+use crate::services::cell::stringify_cell;
+use crate::services::snapshot::entities::{
+  CellSnapshotDiff, DatabaseSnapshotContent, DatabaseSnapshotDiff, FieldSnapshotDiff,
+  RowSnapshotDiff, SnapshotDiffKind,
+};
+
+/// Compares the snapshot with the current state of the database. The cells are compared by their
+/// content, so a cell that was cleared and an empty cell are considered the same.
+pub fn diff_database_snapshot(
+  snapshot: &DatabaseSnapshotContent,
+  current: &DatabaseSnapshotContent,
+) -> DatabaseSnapshotDiff {
+  let mut fields = vec![];
+  for field in snapshot.fields.iter() {
+    match current.fields.iter().find(|current| current.id == field.id) {
+      None => fields.push(FieldSnapshotDiff {
+        field_id: field.id.clone(),
+        name: field.name.clone(),
+        kind: SnapshotDiffKind::Removed,
+      }),
+      Some(current) if is_field_changed(field, current) => fields.push(FieldSnapshotDiff {
+        field_id: current.id.clone(),
+        name: current.name.clone(),
+        kind: SnapshotDiffKind::Modified,
+      }),
+      Some(_) => {},
+    }
+  }
+  for field in current.fields.iter() {
+    if snapshot.fields.iter().all(|old| old.id != field.id) {
+      fields.push(FieldSnapshotDiff {
+        field_id: field.id.clone(),
+        name: field.name.clone(),
+        kind: SnapshotDiffKind::Added,
+      });
+    }
+  }
+
+  // The deleted fields are still needed to show the content of their cells.
+  let all_fields = current
+    .fields
+    .iter()
+    .chain(
+      snapshot
+        .fields
+        .iter()
+        .filter(|field| current.fields.iter().all(|current| current.id != field.id)),
+    )
+    .cloned()
+    .collect::<Vec<Field>>();
+
+  let mut rows = snapshot
+    .rows
+    .iter()
+    .filter_map(|row| diff_row_snapshot(Some(row), current.get_row(&row.id), &all_fields))
+    .collect::<Vec<_>>();
+  rows.extend(
+    current
+      .rows
+      .iter()
+      .filter(|row| snapshot.get_row(&row.id).is_none())
+      .filter_map(|row| diff_row_snapshot(None, Some(row), &all_fields)),
+  );
+
+  DatabaseSnapshotDiff { fields, rows }
+}
+
+/// Returns None if the row is the same in the snapshot and in the database.
+pub fn diff_row_snapshot(
+  snapshot_row: Option<&Row>,
+  current_row: Option<&Row>,
+  fields: &[Field],
+) -> Option<RowSnapshotDiff> {
+  let (row_id, kind) = match (snapshot_row, current_row) {
+    (Some(row), Some(_)) => (row.id.clone(), SnapshotDiffKind::Modified),
+    (Some(row), None) => (row.id.clone(), SnapshotDiffKind::Removed),
+    (None, Some(row)) => (row.id.clone(), SnapshotDiffKind::Added),
+    (None, None) => return None,
+  };
+
+  let cells = fields
+    .iter()
+    .filter_map(|field| {
+      let old_cell = snapshot_row.and_then(|row| row.cells.get(&field.id));
+      let new_cell = current_row.and_then(|row| row.cells.get(&field.id));
+      if old_cell == new_cell {
+        return None;
+      }
+
+      let old_content = old_cell
+        .map(|cell| stringify_cell(cell, field))
+        .unwrap_or_default();
+      let new_content = new_cell
+        .map(|cell| stringify_cell(cell, field))
+        .unwrap_or_default();
+      if old_content == new_content {
+        return None;
+      }
+      Some(CellSnapshotDiff {
+        field_id: field.id.clone(),
+        old_content,
+        new_content,
+      })
+    })
+    .collect::<Vec<_>>();
+
+  if kind == SnapshotDiffKind::Modified && cells.is_empty() {
+    return None;
+  }
+  Some(RowSnapshotDiff {
+    row_id,
+    kind,
+    cells,
+  })
+}
+
+pub(crate) fn is_field_changed(old: &Field, new: &Field) -> bool {
+  old.name != new.name
+    || old.field_type != new.field_type
+    || serde_json::to_value(&old.type_options).ok() != serde_json::to_value(&new.type_options).ok()
+}
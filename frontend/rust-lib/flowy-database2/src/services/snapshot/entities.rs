@@ -1 +1,82 @@
+use collab_database::fields::Field;
+use collab_database::rows::{Row, RowId};
+use flowy_error::FlowyResult;
+use serde::{Deserialize, Serialize};
 
+#[derive(Debug, Clone)]
+pub struct DatabaseSnapshotMeta {
+  pub snapshot_id: String,
+  pub object_id: String,
+  pub created_at: i64,
+}
+
+pub struct DatabaseSnapshotData {
+  pub object_id: String,
+  pub data: Vec<u8>,
+}
+
+/// The fields and the rows of a database at the time the snapshot was taken. The history of a row
+/// is made of the versions of the row found in these snapshots. The views are not included,
+/// restoring a snapshot keeps the current filters, sorts and groups.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatabaseSnapshotContent {
+  pub fields: Vec<Field>,
+  pub rows: Vec<Row>,
+}
+
+impl DatabaseSnapshotContent {
+  pub fn from_json_bytes(bytes: &[u8]) -> FlowyResult<Self> {
+    Ok(serde_json::from_slice(bytes)?)
+  }
+
+  pub fn to_json_bytes(&self) -> FlowyResult<Vec<u8>> {
+    Ok(serde_json::to_vec(self)?)
+  }
+
+  pub fn get_row(&self, row_id: &RowId) -> Option<&Row> {
+    self.rows.iter().find(|row| &row.id == row_id)
+  }
+}
+
+/// How the current state differs from the snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotDiffKind {
+  /// Exists now but not in the snapshot.
+  Added,
+  /// Exists in the snapshot but has been deleted since.
+  Removed,
+  Modified,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldSnapshotDiff {
+  pub field_id: String,
+  pub name: String,
+  pub kind: SnapshotDiffKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct CellSnapshotDiff {
+  pub field_id: String,
+  pub old_content: String,
+  pub new_content: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RowSnapshotDiff {
+  pub row_id: RowId,
+  pub kind: SnapshotDiffKind,
+  pub cells: Vec<CellSnapshotDiff>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseSnapshotDiff {
+  pub fields: Vec<FieldSnapshotDiff>,
+  pub rows: Vec<RowSnapshotDiff>,
+}
+
+impl DatabaseSnapshotDiff {
+  pub fn is_empty(&self) -> bool {
+    self.fields.is_empty() && self.rows.is_empty()
+  }
+}
@@ -1 +1,5 @@
+mod diff;
 pub mod entities;
+
+pub use diff::*;
+pub use entities::*;
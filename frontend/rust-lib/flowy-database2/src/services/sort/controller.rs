@@ -19,10 +19,12 @@ use crate::entities::{FieldType, SortWithIndexPB};
 use crate::services::cell::CellCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
 use crate::services::field::{
-  default_order, TimestampCellData, TimestampCellDataWrapper, TypeOptionCellExt,
+  default_order, RelationCellData, RelationTypeOption, TimestampCellData, TimestampCellDataWrapper,
+  TypeOptionCellExt,
 };
 use crate::services::sort::{
-  ReorderAllRowsResult, ReorderSingleRowResult, Sort, SortChangeset, SortCondition,
+  EmptyCellsPosition, RelationSortBy, ReorderAllRowsResult, ReorderSingleRowResult, Sort,
+  SortChangeset,
 };
 
 #[async_trait]
@@ -33,6 +35,9 @@ pub trait SortDelegate: Send + Sync {
   async fn filter_row(&self, row_detail: &Row) -> bool;
   async fn get_field(&self, field_id: &str) -> Option<Field>;
   async fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Vec<Field>;
+  /// Returns the primary text of the rows of the related database, or None if the related
+  /// database isn't opened
+  async fn get_related_row_names(&self, database_id: &str) -> Option<HashMap<RowId, String>>;
 }
 
 pub struct SortController {
@@ -206,14 +211,74 @@ impl SortController {
 
   pub async fn sort_rows(&mut self, rows: &mut Vec<Arc<Row>>) {
     let fields = self.delegate.get_fields(&self.view_id, None).await;
-    for sort in self.sorts.iter().rev() {
-      rows.par_sort_by(|left, right| cmp_row(left, right, sort, &fields, &self.cell_cache));
-    }
+    let related_row_names = self.get_related_row_names(&fields).await;
+    // Each sort breaks the ties of the previous ones, and the rows that are equal for all the
+    // sorts keep their order because the sort is stable.
+    rows.par_sort_by(|left, right| {
+      self
+        .sorts
+        .iter()
+        .map(|sort| {
+          cmp_row(
+            left,
+            right,
+            sort,
+            &fields,
+            &related_row_names,
+            &self.cell_cache,
+          )
+        })
+        .find(|order| order.is_ne())
+        .unwrap_or(Ordering::Equal)
+    });
     rows.iter().enumerate().for_each(|(index, row)| {
       self.row_index_cache.insert(row.id.clone(), index);
     });
   }
 
+  /// Returns the primary text of the related rows of each relation field sorted by
+  /// [RelationSortBy::PrimaryText]. The fields whose related database isn't opened are skipped.
+  async fn get_related_row_names(
+    &self,
+    fields: &[Field],
+  ) -> HashMap<String, HashMap<RowId, String>> {
+    let mut related_row_names = HashMap::new();
+    for sort in self.sorts.iter() {
+      if sort.relation_sort_by != RelationSortBy::PrimaryText {
+        continue;
+      }
+      if let Some(database_id) = get_related_database_id(fields, &sort.field_id) {
+        if let Some(names) = self.delegate.get_related_row_names(&database_id).await {
+          related_row_names.insert(sort.field_id.clone(), names);
+        }
+      }
+    }
+    related_row_names
+  }
+
+  /// Sorts the rows again if any of the sorts depends on the primary text of the rows of the
+  /// given database
+  pub async fn did_update_related_database(&self, database_id: &str) {
+    if !self
+      .sorts
+      .iter()
+      .any(|sort| sort.relation_sort_by == RelationSortBy::PrimaryText)
+    {
+      return;
+    }
+
+    let fields = self.delegate.get_fields(&self.view_id, None).await;
+    let is_related = self.sorts.iter().any(|sort| {
+      sort.relation_sort_by == RelationSortBy::PrimaryText
+        && get_related_database_id(&fields, &sort.field_id).as_deref() == Some(database_id)
+    });
+    if is_related {
+      self
+        .gen_task(SortEvent::SortDidChanged, QualityOfService::Background)
+        .await;
+    }
+  }
+
   pub async fn delete_all_sorts(&mut self) {
     self.sorts.clear();
     self
@@ -286,18 +351,42 @@ impl SortController {
   }
 }
 
+fn get_related_database_id(fields: &[Field], field_id: &str) -> Option<String> {
+  fields
+    .iter()
+    .find(|field| field.id == field_id && FieldType::from(field.field_type) == FieldType::Relation)?
+    .get_type_option::<RelationTypeOption>(FieldType::Relation)
+    .map(|type_option| type_option.database_id)
+}
+
 fn cmp_row(
   left: &Row,
   right: &Row,
-  sort: &Arc<Sort>,
+  sort: &Sort,
   fields: &[Field],
+  related_row_names: &HashMap<String, HashMap<RowId, String>>,
   cell_data_cache: &CellCache,
 ) -> Ordering {
+  if let Some(names) = related_row_names.get(&sort.field_id) {
+    return cmp_related_row_names(
+      left.cells.get(&sort.field_id),
+      right.cells.get(&sort.field_id),
+      names,
+      sort,
+    );
+  }
+
   match fields
     .iter()
     .find(|field_rev| field_rev.id == sort.field_id)
   {
     None => default_order(),
+    Some(field_rev)
+      if FieldType::from(field_rev.field_type) == FieldType::Relation
+        && sort.relation_sort_by == RelationSortBy::Unsorted =>
+    {
+      default_order()
+    },
     Some(field_rev) => {
       let field_type = field_rev.field_type.into();
       let timestamp_cells = match field_type {
@@ -325,24 +414,71 @@ fn cmp_row(
           .map_or_else(|| right.cells.get(&sort.field_id), |cell| cell.1.as_ref()),
         field_rev,
         cell_data_cache,
-        sort.condition,
+        sort,
       )
     },
   }
 }
 
+/// Compares the primary text of the related rows of the relation cells, the empty cells are last
+/// unless the sort places them first
+fn cmp_related_row_names(
+  left_cell: Option<&Cell>,
+  right_cell: Option<&Cell>,
+  names: &HashMap<RowId, String>,
+  sort: &Sort,
+) -> Ordering {
+  let related_row_names = |cell: Option<&Cell>| {
+    cell
+      .map(RelationCellData::from)
+      .unwrap_or_default()
+      .row_ids
+      .iter()
+      .filter_map(|row_id| names.get(row_id))
+      .filter(|name| !name.is_empty())
+      .cloned()
+      .collect::<Vec<String>>()
+      .join(", ")
+  };
+  let (left, right) = (related_row_names(left_cell), related_row_names(right_cell));
+  if let Some(order) = sort
+    .empty_cells
+    .cmp_empty(left.is_empty(), right.is_empty())
+  {
+    return order;
+  }
+  match (left.is_empty(), right.is_empty()) {
+    (true, true) => Ordering::Equal,
+    (true, false) => Ordering::Greater,
+    (false, true) => Ordering::Less,
+    (false, false) => sort.condition.evaluate_order(sort.cmp_text(&left, &right)),
+  }
+}
+
 fn cmp_cell(
   left_cell: Option<&Cell>,
   right_cell: Option<&Cell>,
   field: &Field,
   cell_data_cache: &CellCache,
-  sort_condition: SortCondition,
+  sort: &Sort,
 ) -> Ordering {
   match TypeOptionCellExt::new(field, Some(cell_data_cache.clone()))
     .get_type_option_cell_data_handler()
   {
     None => default_order(),
-    Some(handler) => handler.handle_cell_compare(left_cell, right_cell, field, sort_condition),
+    Some(handler) => {
+      if sort.empty_cells != EmptyCellsPosition::Default {
+        let is_cell_empty =
+          |cell: Option<&Cell>| cell.map_or(true, |cell| handler.handle_is_cell_empty(cell, field));
+        if let Some(order) = sort
+          .empty_cells
+          .cmp_empty(is_cell_empty(left_cell), is_cell_empty(right_cell))
+        {
+          return order;
+        }
+      }
+      handler.handle_cell_compare(left_cell, right_cell, field, sort)
+    },
   }
 }
 
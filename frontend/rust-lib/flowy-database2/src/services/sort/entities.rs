@@ -6,16 +6,39 @@ use collab::util::AnyMapExt;
 use collab_database::rows::RowId;
 use collab_database::views::{SortMap, SortMapBuilder};
 
-#[derive(Debug, Clone)]
+use crate::services::sort::cmp_text;
+
+#[derive(Debug, Clone, Default)]
 pub struct Sort {
   pub id: String,
   pub field_id: String,
   pub condition: SortCondition,
+  /// Only used by the relation fields
+  pub relation_sort_by: RelationSortBy,
+  /// Compares the numbers in the texts by their value, "item2" is before "item10"
+  pub natural_order: bool,
+  /// Compares the texts ignoring their accents and case first, see [cmp_text]
+  pub locale_collation: bool,
+  /// Only used by the single and multi select fields
+  pub select_sort_by: SelectSortBy,
+  pub empty_cells: EmptyCellsPosition,
+}
+
+impl Sort {
+  /// Compares two texts according to the text options of the sort, regardless of its condition
+  pub fn cmp_text(&self, left: &str, right: &str) -> Ordering {
+    cmp_text(left, right, self.natural_order, self.locale_collation)
+  }
 }
 
 const SORT_ID: &str = "id";
 const FIELD_ID: &str = "field_id";
 const SORT_CONDITION: &str = "condition";
+const RELATION_SORT_BY: &str = "relation_sort_by";
+const NATURAL_ORDER: &str = "natural_order";
+const LOCALE_COLLATION: &str = "locale_collation";
+const SELECT_SORT_BY: &str = "select_sort_by";
+const EMPTY_CELLS: &str = "empty_cells";
 
 impl TryFrom<SortMap> for Sort {
   type Error = anyhow::Error;
@@ -30,10 +53,27 @@ impl TryFrom<SortMap> for Sort {
           .get_as::<i64>(SORT_CONDITION)
           .map(SortCondition::from)
           .unwrap_or_default();
+        let relation_sort_by = value
+          .get_as::<i64>(RELATION_SORT_BY)
+          .map(RelationSortBy::from)
+          .unwrap_or_default();
+        let select_sort_by = value
+          .get_as::<i64>(SELECT_SORT_BY)
+          .map(SelectSortBy::from)
+          .unwrap_or_default();
+        let empty_cells = value
+          .get_as::<i64>(EMPTY_CELLS)
+          .map(EmptyCellsPosition::from)
+          .unwrap_or_default();
         Ok(Self {
           id,
           field_id,
           condition,
+          relation_sort_by,
+          natural_order: value.get_as::<bool>(NATURAL_ORDER).unwrap_or_default(),
+          locale_collation: value.get_as::<bool>(LOCALE_COLLATION).unwrap_or_default(),
+          select_sort_by,
+          empty_cells,
         })
       },
       _ => {
@@ -49,6 +89,17 @@ impl From<Sort> for SortMap {
       (SORT_ID.into(), data.id.into()),
       (FIELD_ID.into(), data.field_id.into()),
       (SORT_CONDITION.into(), Any::BigInt(data.condition.value())),
+      (
+        RELATION_SORT_BY.into(),
+        Any::BigInt(data.relation_sort_by as i64),
+      ),
+      (NATURAL_ORDER.into(), Any::Bool(data.natural_order)),
+      (LOCALE_COLLATION.into(), Any::Bool(data.locale_collation)),
+      (
+        SELECT_SORT_BY.into(),
+        Any::BigInt(data.select_sort_by as i64),
+      ),
+      (EMPTY_CELLS.into(), Any::BigInt(data.empty_cells as i64)),
     ])
   }
 }
@@ -87,6 +138,87 @@ impl From<i64> for SortCondition {
   }
 }
 
+/// How the cells of a relation field are compared
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RelationSortBy {
+  /// The cells aren't compared and the rows keep their order, as for the sorts created before
+  /// the relation sort options
+  #[default]
+  Unsorted = 0,
+  /// Compares the primary text of the related rows, or their number if the related database isn't
+  /// opened
+  PrimaryText = 1,
+  RowCount = 2,
+}
+
+impl From<i64> for RelationSortBy {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => RelationSortBy::PrimaryText,
+      2 => RelationSortBy::RowCount,
+      _ => RelationSortBy::Unsorted,
+    }
+  }
+}
+
+/// How the cells of a single or multi select field are compared
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SelectSortBy {
+  #[default]
+  OptionName = 0,
+  /// Compares the position of the options in the field
+  OptionOrder = 1,
+}
+
+impl From<i64> for SelectSortBy {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => SelectSortBy::OptionOrder,
+      _ => SelectSortBy::OptionName,
+    }
+  }
+}
+
+/// Where the empty cells are placed, regardless of the sort condition
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EmptyCellsPosition {
+  /// Each field type decides, most of them place the empty cells last
+  #[default]
+  Default = 0,
+  First = 1,
+  Last = 2,
+}
+
+impl EmptyCellsPosition {
+  /// Returns the order of two cells if any of them is empty, or None if the field type decides
+  pub fn cmp_empty(&self, is_left_empty: bool, is_right_empty: bool) -> Option<Ordering> {
+    let empty_order = match self {
+      EmptyCellsPosition::Default => return None,
+      EmptyCellsPosition::First => Ordering::Less,
+      EmptyCellsPosition::Last => Ordering::Greater,
+    };
+    match (is_left_empty, is_right_empty) {
+      (true, true) => Some(Ordering::Equal),
+      (true, false) => Some(empty_order),
+      (false, true) => Some(empty_order.reverse()),
+      (false, false) => None,
+    }
+  }
+}
+
+impl From<i64> for EmptyCellsPosition {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => EmptyCellsPosition::First,
+      2 => EmptyCellsPosition::Last,
+      _ => EmptyCellsPosition::Default,
+    }
+  }
+}
+
 #[derive(Clone)]
 pub struct ReorderAllRowsResult {
   pub view_id: String,
@@ -1,7 +1,9 @@
 mod controller;
 mod entities;
 mod task;
+mod text_order;
 
 pub use controller::*;
 pub use entities::*;
 pub use task::*;
+pub use text_order::*;
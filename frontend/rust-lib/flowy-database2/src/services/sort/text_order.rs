@@ -0,0 +1,131 @@
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Compares two texts.
+///
+/// With `natural_order`, the runs of digits are compared by their numeric value, so "item2" is
+/// before "item10".
+///
+/// With `locale_collation`, the texts are compared like the default collation of the Unicode
+/// Collation Algorithm: first by their letters ignoring the accents and the case, then by their
+/// accents, and finally by their exact text. For example, "école" is between "ecole" and "ecrit"
+/// instead of after "zoo".
+pub fn cmp_text(left: &str, right: &str, natural_order: bool, locale_collation: bool) -> Ordering {
+  if !locale_collation {
+    return cmp_str(left, right, natural_order);
+  }
+
+  let base_letters = |s: &str| {
+    s.nfd()
+      .filter(|c| !is_combining_mark(*c))
+      .flat_map(char::to_lowercase)
+      .collect::<String>()
+  };
+  let accented_letters = |s: &str| s.nfd().flat_map(char::to_lowercase).collect::<String>();
+  cmp_str(&base_letters(left), &base_letters(right), natural_order)
+    .then_with(|| {
+      cmp_str(
+        &accented_letters(left),
+        &accented_letters(right),
+        natural_order,
+      )
+    })
+    .then_with(|| cmp_str(left, right, natural_order))
+}
+
+fn cmp_str(left: &str, right: &str, natural_order: bool) -> Ordering {
+  if !natural_order {
+    return left.cmp(right);
+  }
+
+  let mut left = left.chars().peekable();
+  let mut right = right.chars().peekable();
+  loop {
+    let order = match (left.peek().copied(), right.peek().copied()) {
+      (None, None) => return Ordering::Equal,
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+        cmp_digits(&take_digits(&mut left), &take_digits(&mut right))
+      },
+      (Some(l), Some(r)) => {
+        left.next();
+        right.next();
+        l.cmp(&r)
+      },
+    };
+    if order.is_ne() {
+      return order;
+    }
+  }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+  let mut digits = String::new();
+  while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+    digits.push(c);
+  }
+  digits
+}
+
+/// Compares two runs of digits by their value, the run with fewer leading zeros is first if the
+/// values are equal
+fn cmp_digits(left: &str, right: &str) -> Ordering {
+  let (left_value, right_value) = (left.trim_start_matches('0'), right.trim_start_matches('0'));
+  left_value
+    .len()
+    .cmp(&right_value.len())
+    .then_with(|| left_value.cmp(right_value))
+    .then_with(|| left.len().cmp(&right.len()))
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cmp::Ordering;
+
+  use crate::services::sort::cmp_text;
+
+  fn sorted(texts: &[&str], natural_order: bool, locale_collation: bool) -> Vec<String> {
+    let mut texts = texts.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    texts.sort_by(|left, right| cmp_text(left, right, natural_order, locale_collation));
+    texts
+  }
+
+  #[test]
+  fn natural_order_test() {
+    let texts = ["item10", "item2", "item1", "item02", "item"];
+    assert_eq!(
+      sorted(&texts, false, false),
+      vec!["item", "item02", "item1", "item10", "item2"]
+    );
+    assert_eq!(
+      sorted(&texts, true, false),
+      vec!["item", "item1", "item2", "item02", "item10"]
+    );
+    assert_eq!(
+      cmp_text("v1.10.0", "v1.9.3", true, false),
+      Ordering::Greater
+    );
+  }
+
+  #[test]
+  fn locale_collation_test() {
+    let texts = ["zoo", "école", "Ecole", "ecrit", "ecole"];
+    assert_eq!(
+      sorted(&texts, false, false),
+      vec!["Ecole", "ecole", "ecrit", "zoo", "école"]
+    );
+    assert_eq!(
+      sorted(&texts, false, true),
+      vec!["Ecole", "ecole", "école", "ecrit", "zoo"]
+    );
+    assert_eq!(
+      sorted(&["File 10", "file 9", "File 1"], true, true),
+      vec!["File 1", "file 9", "File 10"]
+    );
+  }
+}
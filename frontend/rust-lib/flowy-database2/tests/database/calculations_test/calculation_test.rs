@@ -33,6 +33,7 @@ async fn calculations_test() {
       field_id: field_id.to_owned(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::Sum,
+      percentile: None,
     })
     .await;
 
@@ -45,6 +46,7 @@ async fn calculations_test() {
       field_id: field_id.to_owned(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::Min,
+      percentile: None,
     })
     .await;
 
@@ -57,6 +59,7 @@ async fn calculations_test() {
       field_id: field_id.to_owned(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::Average,
+      percentile: None,
     })
     .await;
 
@@ -69,6 +72,7 @@ async fn calculations_test() {
       field_id: field_id.to_owned(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::Max,
+      percentile: None,
     })
     .await;
 
@@ -81,6 +85,7 @@ async fn calculations_test() {
       field_id: field_id.to_owned(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::Median,
+      percentile: None,
     })
     .await;
 
@@ -107,6 +112,7 @@ async fn calculations_empty_test() {
       field_id: field_id.clone(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::CountEmpty,
+      percentile: None,
     })
     .await;
   test.assert_calculation_value("1").await;
@@ -146,6 +152,7 @@ async fn calculations_non_empty_test() {
       field_id: field_id.clone(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::CountNonEmpty,
+      percentile: None,
     })
     .await;
   test.assert_calculation_value("6").await;
@@ -185,6 +192,7 @@ async fn calculations_count_test() {
       field_id: field_id.clone(),
       calculation_id: Some(calculation_id.clone()),
       calculation_type: CalculationType::Count,
+      percentile: None,
     })
     .await;
   test.assert_calculation_value("7").await;
@@ -193,3 +201,157 @@ async fn calculations_count_test() {
   tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
   test.assert_calculation_value("8").await;
 }
+
+#[tokio::test]
+async fn calculations_number_distribution_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+
+  let view_id = &test.view_id();
+  let field_id = &test.get_first_field(FieldType::Number).await.id;
+  let calculation_id = "calc_id".to_owned();
+
+  // The numbers of the cells are 1, 2, 3, 5 and 14
+  let expected = [
+    (CalculationType::Variance, None, 22.0),
+    (CalculationType::StdDev, None, 22.0_f64.sqrt()),
+    (CalculationType::Percentile, None, 3.0),
+    (CalculationType::Percentile, Some(90), 10.4),
+    (CalculationType::Percentile, Some(100), 14.0),
+  ];
+  for (calculation_type, percentile, expected) in expected {
+    test
+      .insert_calculation(UpdateCalculationChangesetPB {
+        view_id: view_id.clone(),
+        field_id: field_id.clone(),
+        calculation_id: Some(calculation_id.clone()),
+        calculation_type,
+        percentile,
+      })
+      .await;
+    test.assert_calculation_float_value(expected).await;
+  }
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field_id.clone(),
+      calculation_id: Some(calculation_id.clone()),
+      calculation_type: CalculationType::CountUnique,
+      percentile: None,
+    })
+    .await;
+  test.assert_calculation_value("5").await;
+}
+
+#[tokio::test]
+async fn calculations_percent_empty_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+
+  let view_id = &test.view_id();
+  let field_id = &test.get_first_field(FieldType::RichText).await.id;
+  let calculation_id = "calc_id".to_owned();
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field_id.clone(),
+      calculation_id: Some(calculation_id.clone()),
+      calculation_type: CalculationType::PercentEmpty,
+      percentile: None,
+    })
+    .await;
+  test.assert_calculation_float_value(100.0 / 7.0).await;
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field_id.clone(),
+      calculation_id: Some(calculation_id.clone()),
+      calculation_type: CalculationType::PercentNotEmpty,
+      percentile: None,
+    })
+    .await;
+  test.assert_calculation_float_value(600.0 / 7.0).await;
+
+  // Update the empty cell with a non-empty value
+  test
+    .update_cell(
+      field_id,
+      test.rows[1].id.clone(),
+      BoxAny::new("change".to_string()),
+    )
+    .await
+    .unwrap();
+
+  // sleep for 3 seconds to wait for the calculation to update
+  tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+  test.assert_calculation_float_value(100.0).await;
+}
+
+#[tokio::test]
+async fn calculations_checkbox_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+
+  let view_id = &test.view_id();
+  let field_id = &test.get_first_field(FieldType::Checkbox).await.id;
+  let calculation_id = "calc_id".to_owned();
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field_id.clone(),
+      calculation_id: Some(calculation_id.clone()),
+      calculation_type: CalculationType::CountChecked,
+      percentile: None,
+    })
+    .await;
+  test.assert_calculation_value("3").await;
+
+  test
+    .insert_calculation(UpdateCalculationChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field_id.clone(),
+      calculation_id: Some(calculation_id.clone()),
+      calculation_type: CalculationType::PercentChecked,
+      percentile: None,
+    })
+    .await;
+  test.assert_calculation_float_value(50.0).await;
+}
+
+#[tokio::test]
+async fn calculations_date_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+
+  let view_id = &test.view_id();
+  let field_id = &test.get_first_field(FieldType::DateTime).await.id;
+  let calculation_id = "calc_id".to_owned();
+
+  let expected = [
+    (CalculationType::EarliestDate, "2022/03/14"),
+    (CalculationType::LatestDate, "2022/12/25"),
+    (CalculationType::DateRange, "285"),
+  ];
+  for (calculation_type, expected) in expected {
+    test
+      .insert_calculation(UpdateCalculationChangesetPB {
+        view_id: view_id.clone(),
+        field_id: field_id.clone(),
+        calculation_id: Some(calculation_id.clone()),
+        calculation_type,
+        percentile: None,
+      })
+      .await;
+    test.assert_calculation_value(expected).await;
+  }
+}
+
+#[test]
+fn calculation_type_is_allowed_test() {
+  assert!(CalculationType::Percentile.is_allowed(FieldType::Number));
+  assert!(!CalculationType::Percentile.is_allowed(FieldType::RichText));
+  assert!(CalculationType::PercentChecked.is_allowed(FieldType::Checkbox));
+  assert!(!CalculationType::CountUnique.is_allowed(FieldType::Checkbox));
+  assert!(CalculationType::DateRange.is_allowed(FieldType::LastEditedTime));
+  assert!(!CalculationType::EarliestDate.is_allowed(FieldType::Number));
+}
@@ -3,11 +3,15 @@ use collab_database::fields::date_type_option::DateCellData;
 use collab_database::fields::media_type_option::{MediaFile, MediaFileType, MediaUploadType};
 use collab_database::fields::select_type_option::{MultiSelectTypeOption, SingleSelectTypeOption};
 use collab_database::fields::url_type_option::URLCellData;
-use flowy_database2::entities::{FieldType, MediaCellChangeset};
+use flowy_database2::entities::{
+  CreateRowPayloadPB, FieldChangesetPB, FieldType, MediaCellChangeset,
+};
 use flowy_database2::services::field::{
-  ChecklistCellChangeset, ChecklistCellInsertChangeset, DateCellChangeset, RelationCellChangeset,
-  SelectOptionCellChangeset, StringCellData, TimeCellData,
+  ChecklistCellChangeset, ChecklistCellInsertChangeset, ComputedCellData, DateCellChangeset,
+  FieldValidation, RelationCellChangeset, RelationCellData, RelationTypeOption,
+  SelectOptionCellChangeset, StringCellData, TimeCellData, ValidationSeverity,
 };
+use flowy_error::ErrorCode;
 use lib_infra::box_any::BoxAny;
 use std::time::Duration;
 
@@ -200,3 +204,305 @@ async fn time_cell_data_test() {
     assert_eq!(cell.0.unwrap_or_default(), 75);
   }
 }
+
+#[tokio::test]
+async fn formula_cell_recompute_on_dependency_update_test() {
+  let test = DatabaseCellTest::new().await;
+  let number_field = test.get_first_field(FieldType::Number).await;
+  let formula_field = test.get_first_field(FieldType::Formula).await;
+
+  test
+    .update_cell(
+      &test.view_id,
+      &number_field.id,
+      &test.rows[0].id,
+      BoxAny::new("21".to_string()),
+    )
+    .await;
+
+  let cells = test
+    .editor
+    .get_cells_for_field(&test.view_id, &formula_field.id)
+    .await;
+  let cell = ComputedCellData::from(cells[0].cell.as_ref().unwrap());
+  assert_eq!(cell.as_ref(), "42");
+}
+
+#[tokio::test]
+async fn formula_cell_follows_renamed_and_deleted_field_test() {
+  let test = DatabaseCellTest::new().await;
+  let number_field = test.get_first_field(FieldType::Number).await;
+  let formula_field = test.get_first_field(FieldType::Formula).await;
+  let formula_cell = || async {
+    let cells = test
+      .editor
+      .get_cells_for_field(&test.view_id, &formula_field.id)
+      .await;
+    cells[0]
+      .cell
+      .as_ref()
+      .map(ComputedCellData::from)
+      .unwrap_or_default()
+  };
+
+  // The formula keeps reading the renamed field
+  test
+    .editor
+    .update_field(FieldChangesetPB {
+      field_id: number_field.id.clone(),
+      view_id: test.view_id.clone(),
+      name: Some("Cost".to_string()),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  test
+    .update_cell(
+      &test.view_id,
+      &number_field.id,
+      &test.rows[0].id,
+      BoxAny::new("21".to_string()),
+    )
+    .await;
+  assert_eq!(formula_cell().await.as_ref(), "42");
+
+  // The formula can't be evaluated once the field is deleted
+  test.editor.delete_field(&number_field.id).await.unwrap();
+  assert_eq!(formula_cell().await.as_ref(), "");
+}
+
+#[tokio::test]
+async fn number_cell_validation_test() {
+  let test = DatabaseCellTest::new().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  let row_id = test.rows[0].id.clone();
+  let mut validation = FieldValidation {
+    min: Some(0.0),
+    severity: ValidationSeverity::Error,
+    ..Default::default()
+  };
+  test
+    .editor
+    .update_field_validation(&field.id, validation.clone())
+    .await
+    .unwrap();
+  assert_eq!(
+    test.editor.get_field_validation(&field.id).await,
+    Some(validation.clone())
+  );
+
+  // the cells that break the rules of the Error severity are rejected
+  let error = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &field.id,
+      BoxAny::new("-1".to_string()),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::CellValidationFailed);
+  test
+    .update_cell(
+      &test.view_id,
+      &field.id,
+      &row_id,
+      BoxAny::new("1".to_string()),
+    )
+    .await;
+  assert!(test
+    .editor
+    .get_validation_errors(&test.view_id)
+    .await
+    .unwrap()
+    .is_empty());
+
+  // the cells that break the rules of the Warning severity are saved and reported
+  validation.severity = ValidationSeverity::Warning;
+  test
+    .editor
+    .update_field_validation(&field.id, validation)
+    .await
+    .unwrap();
+  test
+    .update_cell(
+      &test.view_id,
+      &field.id,
+      &row_id,
+      BoxAny::new("-1".to_string()),
+    )
+    .await;
+  let errors = test
+    .editor
+    .get_validation_errors(&test.view_id)
+    .await
+    .unwrap();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].row_id, row_id);
+  assert_eq!(errors[0].field_id, field.id);
+}
+
+#[tokio::test]
+async fn create_row_with_required_field_test() {
+  let test = DatabaseCellTest::new().await;
+  let field = test.get_first_field(FieldType::Number).await;
+  test
+    .editor
+    .update_field_validation(
+      &field.id,
+      FieldValidation {
+        required: true,
+        severity: ValidationSeverity::Error,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  // the blank row is created, its empty required cell is reported
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  let errors = test
+    .editor
+    .get_validation_errors(&test.view_id)
+    .await
+    .unwrap();
+  assert!(errors
+    .iter()
+    .any(|error| error.row_id == row_detail.row.id && error.field_id == field.id));
+}
+
+#[tokio::test]
+async fn unique_text_cell_test() {
+  let test = DatabaseCellTest::new().await;
+  let field = test.get_first_field(FieldType::RichText).await;
+  let row_id = test.rows[0].id.clone();
+  test
+    .editor
+    .update_field_validation(
+      &field.id,
+      FieldValidation {
+        unique: true,
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+
+  let error = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &field.id,
+      BoxAny::new(" C ".to_string()),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::DuplicateCellValue);
+
+  // the empty and the new values are accepted
+  test
+    .update_cell(
+      &test.view_id,
+      &field.id,
+      &row_id,
+      BoxAny::new("".to_string()),
+    )
+    .await;
+  test
+    .update_cell(
+      &test.view_id,
+      &field.id,
+      &row_id,
+      BoxAny::new("Z".to_string()),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn find_and_merge_duplicate_rows_test() {
+  let test = DatabaseCellTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let number_field = test.get_first_field(FieldType::Number).await;
+  let row_count = test.rows.len();
+
+  let groups = test
+    .editor
+    .find_duplicate_rows(&test.view_id, vec![text_field.id.clone()], false)
+    .await
+    .unwrap();
+  assert_eq!(
+    groups,
+    vec![vec![test.rows[4].id.clone(), test.rows[5].id.clone()]]
+  );
+
+  // the empty number of the kept row is filled from its duplicate
+  let expected_number = test.rows[5].cells.get(&number_field.id).cloned();
+  test
+    .editor
+    .merge_duplicate_rows(&test.view_id, &test.rows[4].id, &[test.rows[5].id.clone()])
+    .await
+    .unwrap();
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), row_count - 1);
+  let row = rows.iter().find(|row| row.id == test.rows[4].id).unwrap();
+  assert_eq!(row.cells.get(&number_field.id).cloned(), expected_number);
+  assert!(test
+    .editor
+    .find_duplicate_rows(&test.view_id, vec![text_field.id], false)
+    .await
+    .unwrap()
+    .is_empty());
+}
+
+#[tokio::test]
+async fn merge_duplicate_rows_moves_relations_test() {
+  let test = DatabaseCellTest::new().await;
+  let relation_field = test.get_first_field(FieldType::Relation).await;
+  let type_option = RelationTypeOption {
+    database_id: test.editor.get_database_id(),
+  };
+  test
+    .editor
+    .update_field_type_option(
+      &relation_field.id,
+      type_option.into(),
+      relation_field.clone(),
+    )
+    .await
+    .unwrap();
+  test
+    .update_cell(
+      &test.view_id,
+      &relation_field.id,
+      &test.rows[0].id,
+      BoxAny::new(RelationCellChangeset {
+        inserted_row_ids: vec![test.rows[4].id.clone(), test.rows[5].id.clone()],
+        ..Default::default()
+      }),
+    )
+    .await;
+
+  // the relations to the duplicate row link to the kept row instead
+  test
+    .editor
+    .merge_duplicate_rows(&test.view_id, &test.rows[4].id, &[test.rows[5].id.clone()])
+    .await
+    .unwrap();
+  let rows = test.get_rows().await;
+  let row = rows.iter().find(|row| row.id == test.rows[0].id).unwrap();
+  let cell = row.cells.get(&relation_field.id).unwrap();
+  assert_eq!(
+    RelationCellData::from(cell).row_ids,
+    vec![test.rows[4].id.clone()]
+  );
+}
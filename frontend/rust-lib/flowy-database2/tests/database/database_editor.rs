@@ -19,7 +19,9 @@ use flowy_database2::services::database::DatabaseEditor;
 use flowy_database2::services::field::checklist_type_option::{
   ChecklistCellChangeset, ChecklistTypeOption,
 };
-use flowy_database2::services::field::SelectOptionCellChangeset;
+use flowy_database2::services::field::{
+  RelationCellChangeset, RelationTypeOption, SelectOptionCellChangeset,
+};
 use flowy_database2::services::share::csv::{CSVFormat, ImportResult};
 use flowy_error::FlowyResult;
 
@@ -258,6 +260,34 @@ impl DatabaseEditorTest {
       .await
   }
 
+  pub(crate) async fn update_relation_cell(
+    &mut self,
+    row_id: RowId,
+    related_row_ids: Vec<RowId>,
+  ) -> FlowyResult<()> {
+    let field = self.get_first_field(FieldType::Relation).await;
+    let cell_changeset = RelationCellChangeset {
+      inserted_row_ids: related_row_ids,
+      ..Default::default()
+    };
+    self
+      .update_cell(&field.id, row_id, BoxAny::new(cell_changeset))
+      .await
+  }
+
+  /// Makes the first relation field link to the rows of the database of the test
+  pub(crate) async fn link_relation_to_own_database(&self) {
+    let field = self.get_first_field(FieldType::Relation).await;
+    let type_option = RelationTypeOption {
+      database_id: self.editor.get_database_id(),
+    };
+    self
+      .editor
+      .update_field_type_option(&field.id, type_option.into(), field.clone())
+      .await
+      .unwrap();
+  }
+
   pub(crate) async fn update_single_select_cell(
     &mut self,
     row_id: RowId,
@@ -290,6 +320,15 @@ impl DatabaseEditorTest {
       .unwrap()
   }
 
+  pub async fn import_ics(&self, s: String) -> ImportResult {
+    self
+      .sdk
+      .database_manager
+      .import_ics(gen_database_view_id(), s)
+      .await
+      .unwrap()
+  }
+
   pub async fn get_database(&self, database_id: &str) -> Option<Arc<DatabaseEditor>> {
     self
       .sdk
@@ -49,6 +49,7 @@ async fn create_advanced_filter_test() {
       filter_type: FilterType::Or,
       children: vec![],
       data: None,
+      ..Default::default()
     }])
     .await;
 
@@ -89,15 +90,18 @@ async fn create_advanced_filter_test() {
             field_type: FieldType::Checkbox,
             data: checkbox_filter_bytes.clone(),
           }),
+          ..Default::default()
         },
         FilterPB {
           id: "".to_string(),
           filter_type: FilterType::And,
           children: vec![],
           data: None,
+          ..Default::default()
         },
       ],
       data: None,
+      ..Default::default()
     }])
     .await;
 
@@ -145,6 +149,7 @@ async fn create_advanced_filter_test() {
             field_type: FieldType::Checkbox,
             data: checkbox_filter_bytes,
           }),
+          ..Default::default()
         },
         FilterPB {
           id: "".to_string(),
@@ -159,6 +164,7 @@ async fn create_advanced_filter_test() {
                 field_type: FieldType::DateTime,
                 data: date_filter_bytes,
               }),
+              ..Default::default()
             },
             FilterPB {
               id: "".to_string(),
@@ -169,12 +175,15 @@ async fn create_advanced_filter_test() {
                 field_type: FieldType::Number,
                 data: number_filter_bytes,
               }),
+              ..Default::default()
             },
           ],
           data: None,
+          ..Default::default()
         },
       ],
       data: None,
+      ..Default::default()
     }])
     .await;
 
@@ -277,6 +286,7 @@ async fn create_advanced_filter_with_conversion_test() {
             field_type: FieldType::Checkbox,
             data: checkbox_filter_bytes,
           }),
+          ..Default::default()
         },
         FilterPB {
           id: "".to_string(),
@@ -291,6 +301,7 @@ async fn create_advanced_filter_with_conversion_test() {
                 field_type: FieldType::DateTime,
                 data: date_filter_bytes,
               }),
+              ..Default::default()
             },
             FilterPB {
               id: "".to_string(),
@@ -301,12 +312,15 @@ async fn create_advanced_filter_with_conversion_test() {
                 field_type: FieldType::Number,
                 data: number_filter_bytes,
               }),
+              ..Default::default()
             },
           ],
           data: None,
+          ..Default::default()
         },
       ],
       data: None,
+      ..Default::default()
     }])
     .await;
 
@@ -18,6 +18,7 @@ async fn grid_filter_date_is_test() {
         start: None,
         end: None,
         timestamp: Some(1647251762),
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -46,6 +47,7 @@ async fn grid_filter_date_after_test() {
         start: None,
         end: None,
         timestamp: Some(1647251762),
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -74,6 +76,7 @@ async fn grid_filter_date_on_or_after_test() {
         start: None,
         end: None,
         timestamp: Some(1668359085),
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -102,6 +105,7 @@ async fn grid_filter_date_on_or_before_test() {
         start: None,
         end: None,
         timestamp: Some(1668359085),
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -130,6 +134,7 @@ async fn grid_filter_date_within_test() {
         start: Some(1647251762),
         end: Some(1668704685),
         timestamp: None,
+        num_of_days: None,
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -141,3 +146,49 @@ async fn grid_filter_date_within_test() {
   // Assert the number of visible rows
   test.assert_number_of_visible_rows(expected).await;
 }
+
+#[tokio::test]
+async fn grid_filter_created_time_today_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+
+  // The rows of the test database are created today
+  test
+    .create_data_filter(
+      None,
+      FieldType::CreatedTime,
+      BoxAny::new(DateFilterPB {
+        condition: DateFilterConditionPB::DateStartsToday,
+        ..Default::default()
+      }),
+      None,
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(row_count).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_within_next_days_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+
+  // The dates of the test database are all in the past
+  test
+    .create_data_filter(
+      None,
+      FieldType::DateTime,
+      BoxAny::new(DateFilterPB {
+        condition: DateFilterConditionPB::DateStartsWithinNextDays,
+        num_of_days: Some(7),
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(0).await;
+}
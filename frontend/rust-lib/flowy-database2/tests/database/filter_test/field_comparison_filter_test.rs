@@ -0,0 +1,106 @@
+use flowy_database2::entities::{
+  FieldComparisonConditionPB, FieldType, FilterDataPB, FilterType, FormulaFilterPB, InsertFilterPB,
+};
+use flowy_database2::services::filter::FilterChangeset;
+use flowy_error::ErrorCode;
+
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
+
+#[tokio::test]
+async fn grid_filter_date_before_created_time_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  // Every date is before the creation of the rows, except for the last row that has no date
+  let expected = 6;
+
+  test
+    .create_field_comparison_filter(
+      FieldType::DateTime,
+      FieldType::CreatedTime,
+      FieldComparisonConditionPB::LessThan,
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(expected).await;
+
+  let filter = test.get_filter(FilterType::FieldComparison, None).await;
+  let field_comparison = filter.unwrap().field_comparison.unwrap();
+  assert_eq!(
+    field_comparison.condition,
+    FieldComparisonConditionPB::LessThan
+  );
+}
+
+#[tokio::test]
+async fn grid_filter_number_greater_than_number_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  // Only the first row has an estimated time, which is greater than its price
+  let expected = 1;
+
+  test
+    .create_field_comparison_filter(
+      FieldType::Time,
+      FieldType::Number,
+      FieldComparisonConditionPB::GreaterThan,
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(expected).await;
+}
+
+#[tokio::test]
+async fn grid_filter_formula_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let expected = 3;
+
+  test
+    .create_formula_filter(
+      "{Price} >= 3 && !empty({Name})",
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(expected).await;
+}
+
+#[tokio::test]
+async fn grid_filter_formula_with_checkbox_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.rows.len();
+  let expected = 1;
+
+  test
+    .create_formula_filter(
+      "{Price} > 2 && {is urgent}",
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(expected).await;
+}
+
+#[tokio::test]
+async fn grid_filter_invalid_formula_test() {
+  let payload = InsertFilterPB {
+    parent_filter_id: None,
+    data: FilterDataPB::default(),
+    field_comparison: None,
+    formula: Some(FormulaFilterPB {
+      expression: "{Price} >".to_string(),
+    }),
+  };
+  let result: Result<FilterChangeset, ErrorCode> = payload.try_into();
+  assert_eq!(result.unwrap_err(), ErrorCode::InvalidFormula);
+}
@@ -0,0 +1,189 @@
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
+use collab_database::rows::RowId;
+use flowy_database2::entities::{
+  FieldType, FilterDataPB, RelationFilterConditionPB, RelationFilterPB, TextFilterConditionPB,
+  TextFilterPB,
+};
+use lib_infra::box_any::BoxAny;
+
+#[tokio::test]
+async fn grid_filter_relation_is_empty_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  set_relation_cells(&mut test).await;
+  let row_count = test.rows.len();
+  let expected = row_count - 2;
+
+  // Create Relation "Is Empty" filter
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationIsEmpty,
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(expected).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_is_not_empty_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  set_relation_cells(&mut test).await;
+  let row_count = test.rows.len();
+  let expected = 2;
+
+  // Create Relation "Is Not Empty" filter
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationIsNotEmpty,
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(expected).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_contains_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  set_relation_cells(&mut test).await;
+  let row_count = test.rows.len();
+
+  // Create Relation "Contains" filter with a row that only the first row relates to
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationContains,
+        row_ids: vec!["related_row_a".to_string()],
+        related_filter: None,
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - 1,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_does_not_contain_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  set_relation_cells(&mut test).await;
+  let row_count = test.rows.len();
+
+  // Both related rows relate to "related_row_b"
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelationDoesNotContain,
+        row_ids: vec!["related_row_b".to_string()],
+        related_filter: None,
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 2,
+      }),
+    )
+    .await;
+
+  test.assert_number_of_visible_rows(row_count - 2).await;
+}
+
+#[tokio::test]
+async fn grid_filter_relation_related_row_matches_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  test.link_relation_to_own_database().await;
+  let row_count = test.rows.len();
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let (first_row_id, second_row_id) = (test.rows[0].id.clone(), test.rows[1].id.clone());
+  let (related_row_c, related_row_da) = (test.rows[2].id.clone(), test.rows[3].id.clone());
+  test
+    .update_relation_cell(first_row_id, vec![related_row_c.clone()])
+    .await
+    .unwrap();
+  test
+    .update_relation_cell(second_row_id, vec![related_row_da])
+    .await
+    .unwrap();
+
+  // Only the first row relates to a row whose text is "C"
+  test
+    .create_data_filter(
+      None,
+      FieldType::Relation,
+      BoxAny::new(RelationFilterPB {
+        condition: RelationFilterConditionPB::RelatedRowMatches,
+        row_ids: vec![],
+        related_filter: Some(FilterDataPB {
+          field_id: text_field.id.clone(),
+          field_type: FieldType::RichText,
+          data: TextFilterPB {
+            condition: TextFilterConditionPB::TextIs,
+            content: "C".to_string(),
+            ..Default::default()
+          }
+          .try_into()
+          .unwrap(),
+        }),
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - 1,
+      }),
+    )
+    .await;
+  test.assert_number_of_visible_rows(1).await;
+
+  // The related row doesn't match the filter anymore, so the first row is hidden as well
+  test
+    .update_text_cell(related_row_c, "changed")
+    .await
+    .unwrap();
+  test.wait(200).await;
+  test.assert_number_of_visible_rows(0).await;
+}
+
+/// Relates the first row to `related_row_a` and `related_row_b`, and the second row to
+/// `related_row_b`. The other rows have no related rows.
+async fn set_relation_cells(test: &mut DatabaseFilterTest) {
+  let first_row_id = test.rows[0].id.clone();
+  let second_row_id = test.rows[1].id.clone();
+  test
+    .update_relation_cell(
+      first_row_id,
+      vec![
+        RowId::from("related_row_a".to_string()),
+        RowId::from("related_row_b".to_string()),
+      ],
+    )
+    .await
+    .unwrap();
+  test
+    .update_relation_cell(
+      second_row_id,
+      vec![RowId::from("related_row_b".to_string())],
+    )
+    .await
+    .unwrap();
+}
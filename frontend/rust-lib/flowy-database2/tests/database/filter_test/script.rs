@@ -8,7 +8,8 @@ use lib_infra::box_any::BoxAny;
 use tokio::sync::broadcast::Receiver;
 
 use flowy_database2::entities::{
-  DatabaseViewSettingPB, FieldType, FilterPB, FilterType, TextFilterConditionPB, TextFilterPB,
+  DatabaseViewSettingPB, FieldComparisonConditionPB, FieldType, FilterPB, FilterType,
+  TextFilterConditionPB, TextFilterPB,
 };
 use flowy_database2::services::database_view::DatabaseViewChanged;
 use lib_dispatch::prelude::af_spawn;
@@ -78,6 +79,9 @@ impl DatabaseFilterTest {
       {
         Some(filter.clone())
       },
+      FilterType::FieldComparison | FilterType::Formula if filter.filter_type == filter_type => {
+        Some(filter.clone())
+      },
       _ => None,
     }
   }
@@ -139,6 +143,52 @@ impl DatabaseFilterTest {
       .unwrap();
   }
 
+  pub async fn create_field_comparison_filter(
+    &mut self,
+    field_type: FieldType,
+    other_field_type: FieldType,
+    condition: FieldComparisonConditionPB,
+    changed: Option<FilterRowChanged>,
+  ) {
+    self.subscribe_view_changed().await;
+    self.assert_future_changed(changed).await;
+    let field = self.get_first_field(field_type).await;
+    let other_field = self.get_first_field(other_field_type).await;
+    let params = FilterChangeset::Insert {
+      parent_filter_id: None,
+      data: FilterInner::FieldComparison {
+        field_id: field.id,
+        other_field_id: other_field.id,
+        condition,
+      },
+    };
+    self
+      .editor
+      .modify_view_filters(&self.view_id, params)
+      .await
+      .unwrap();
+  }
+
+  pub async fn create_formula_filter(
+    &mut self,
+    expression: &str,
+    changed: Option<FilterRowChanged>,
+  ) {
+    self.subscribe_view_changed().await;
+    self.assert_future_changed(changed).await;
+    let params = FilterChangeset::Insert {
+      parent_filter_id: None,
+      data: FilterInner::Formula {
+        expression: expression.to_string(),
+      },
+    };
+    self
+      .editor
+      .modify_view_filters(&self.view_id, params)
+      .await
+      .unwrap();
+  }
+
   pub async fn update_text_filter(
     &mut self,
     filter: FilterPB,
@@ -154,7 +204,11 @@ impl DatabaseFilterTest {
       data: FilterInner::Data {
         field_id: current_filter.field_id,
         field_type: current_filter.field_type,
-        condition_and_content: BoxAny::new(TextFilterPB { condition, content }),
+        condition_and_content: BoxAny::new(TextFilterPB {
+          condition,
+          content,
+          ..Default::default()
+        }),
       },
     };
     self
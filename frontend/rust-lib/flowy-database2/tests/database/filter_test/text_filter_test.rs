@@ -1,5 +1,5 @@
 use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
-use flowy_database2::entities::{FieldType, TextFilterConditionPB, TextFilterPB};
+use flowy_database2::entities::{FieldType, FilterType, TextFilterConditionPB, TextFilterPB};
 use lib_infra::box_any::BoxAny;
 
 #[tokio::test]
@@ -14,6 +14,7 @@ async fn grid_filter_text_is_empty_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -38,6 +39,7 @@ async fn grid_filter_text_is_not_empty_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsNotEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -77,6 +79,7 @@ async fn grid_filter_is_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIs,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -98,6 +101,7 @@ async fn grid_filter_contain_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -120,6 +124,7 @@ async fn grid_filter_contain_text_test2() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -153,6 +158,7 @@ async fn grid_filter_does_not_contain_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextDoesNotContain,
         content: "AB".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -174,6 +180,7 @@ async fn grid_filter_start_with_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextStartsWith,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -195,6 +202,7 @@ async fn grid_filter_ends_with_text_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextEndsWith,
         content: "A".to_string(),
+        ..Default::default()
       }),
       None,
     )
@@ -216,6 +224,7 @@ async fn grid_update_text_filter_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextEndsWith,
         content: "A".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -258,6 +267,7 @@ async fn grid_filter_delete_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       None,
     )
@@ -289,6 +299,7 @@ async fn grid_filter_update_empty_text_cell_test() {
       BoxAny::new(TextFilterPB {
         condition: TextFilterConditionPB::TextIsEmpty,
         content: "".to_string(),
+        ..Default::default()
       }),
       Some(FilterRowChanged {
         showing_num_of_rows: 0,
@@ -312,3 +323,39 @@ async fn grid_filter_update_empty_text_cell_test() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn grid_filter_text_matches_regex_test() {
+  let mut test = DatabaseFilterTest::new().await;
+
+  // Create a case sensitive Text "Matches Regex" filter, which keeps the rows starting with "A"
+  test
+    .create_data_filter(
+      None,
+      FieldType::RichText,
+      BoxAny::new(TextFilterPB {
+        condition: TextFilterConditionPB::TextMatchesRegex,
+        content: "^A".to_string(),
+        case_sensitive: true,
+        ..Default::default()
+      }),
+      Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 3,
+      }),
+    )
+    .await;
+
+  // The options of the filter are persisted
+  let filter = test
+    .get_filter(FilterType::Data, Some(FieldType::RichText))
+    .await
+    .unwrap();
+  let text_filter = TextFilterPB::try_from(filter.data.unwrap().data.as_ref()).unwrap();
+  assert_eq!(
+    text_filter.condition,
+    TextFilterConditionPB::TextMatchesRegex
+  );
+  assert!(text_filter.case_sensitive);
+  assert!(!text_filter.whole_word);
+}
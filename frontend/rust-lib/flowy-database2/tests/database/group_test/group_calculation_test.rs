@@ -0,0 +1,72 @@
+use flowy_database2::entities::{CalculationType, FieldType, UpdateCalculationChangesetPB};
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+async fn insert_sum_calculation(test: &DatabaseGroupTest) {
+  let number_field = test.get_field(FieldType::Number).await;
+  test
+    .editor
+    .update_calculation(UpdateCalculationChangesetPB {
+      view_id: test.view_id.clone(),
+      calculation_id: None,
+      field_id: number_field.id.clone(),
+      calculation_type: CalculationType::Sum,
+      percentile: None,
+    })
+    .await
+    .unwrap();
+}
+
+async fn assert_group_calculation_value(test: &DatabaseGroupTest, group_index: usize, value: &str) {
+  let group = test.group_at_index(group_index).await;
+  assert_eq!(group.calculations.len(), 1);
+  assert_eq!(group.calculations[0].value, value);
+}
+
+#[tokio::test]
+async fn group_calculations_test() {
+  let test = DatabaseGroupTest::new().await;
+  assert!(test.group_at_index(1).await.calculations.is_empty());
+
+  insert_sum_calculation(&test).await;
+  assert_group_calculation_value(&test, 1, "3.00000").await;
+  assert_group_calculation_value(&test, 2, "7.00000").await;
+  // The only row of the group doesn't have a number
+  assert_group_calculation_value(&test, 3, "").await;
+}
+
+#[tokio::test]
+async fn group_calculations_after_moving_row_test() {
+  let test = DatabaseGroupTest::new().await;
+  insert_sum_calculation(&test).await;
+
+  // Move the row with the number 1 to the next group
+  test.move_row(1, 0, 2, 1).await;
+  test.assert_group_row_count(2, 3).await;
+  assert_group_calculation_value(&test, 1, "2.00000").await;
+  assert_group_calculation_value(&test, 2, "8.00000").await;
+}
+
+#[tokio::test]
+async fn group_calculations_on_created_time_field_test() {
+  let test = DatabaseGroupTest::new().await;
+  let created_time_field = test.get_field(FieldType::CreatedTime).await;
+  test
+    .editor
+    .update_calculation(UpdateCalculationChangesetPB {
+      view_id: test.view_id.clone(),
+      calculation_id: None,
+      field_id: created_time_field.id.clone(),
+      calculation_type: CalculationType::Count,
+      percentile: None,
+    })
+    .await
+    .unwrap();
+
+  // The cells of the timestamp fields are built from the rows, every row is counted
+  for group_index in 1..=2 {
+    let group = test.group_at_index(group_index).await;
+    assert!(!group.rows.is_empty());
+    assert_group_calculation_value(&test, group_index, &group.rows.len().to_string()).await;
+  }
+}
@@ -1,4 +1,9 @@
 mod date_group_test;
+mod group_calculation_test;
+mod number_group_test;
+mod relation_group_test;
 mod script;
+mod sub_group_test;
 mod test;
+mod timestamp_group_test;
 mod url_group_test;
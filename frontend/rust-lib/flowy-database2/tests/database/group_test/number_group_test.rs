@@ -0,0 +1,63 @@
+use bytes::Bytes;
+use flowy_database2::entities::{FieldType, NumberGroupConfigurationPB};
+use flowy_database2::services::cell::stringify_cell;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn group_by_number_test() {
+  let test = DatabaseGroupTest::new().await;
+  let number_field = test.get_field(FieldType::Number).await;
+  test.group_by_field(&number_field.id).await;
+
+  // The ranges are generated from the numbers 1, 2, 3 and 4
+  test.assert_group_count(4).await;
+  test.assert_group_row_count(0, 1).await; // No status group
+  test.assert_group_id(1, "..2").await;
+  test.assert_group_row_count(1, 1).await;
+  test.assert_group_id(2, "2..3").await;
+  test.assert_group_row_count(2, 1).await;
+  test.assert_group_id(3, "3..").await;
+  test.assert_group_row_count(3, 2).await;
+}
+
+#[tokio::test]
+async fn group_by_number_with_boundaries_test() {
+  let test = DatabaseGroupTest::new().await;
+  let number_field = test.get_field(FieldType::Number).await;
+  let config = NumberGroupConfigurationPB {
+    boundaries: vec![2.5],
+    ..Default::default()
+  };
+  let data: Bytes = config.try_into().unwrap();
+  test
+    .editor
+    .set_group_by_field(&test.view_id, &number_field.id, data.to_vec())
+    .await
+    .unwrap();
+
+  test.assert_group_count(3).await;
+  test.assert_group_id(1, "..2.5").await;
+  test.assert_group_row_count(1, 2).await;
+  test.assert_group_id(2, "2.5..").await;
+  test.assert_group_row_count(2, 2).await;
+}
+
+#[tokio::test]
+async fn move_row_to_number_group_test() {
+  let test = DatabaseGroupTest::new().await;
+  let number_field = test.get_field(FieldType::Number).await;
+  test.group_by_field(&number_field.id).await;
+
+  // Moving the row sets its number to the start of the range
+  let row = test.row_at_index(3, 0).await;
+  test.move_row(3, 0, 2, 0).await;
+  test.assert_group_row_count(2, 2).await;
+  test.assert_group_row_count(3, 1).await;
+  assert_eq!(test.row_at_index(2, 0).await.id, row.id);
+
+  let rows = test.get_rows().await;
+  let moved_row = rows.iter().find(|r| r.id.as_str() == row.id).unwrap();
+  let cell = moved_row.cells.get(&number_field.id).unwrap();
+  assert_eq!(stringify_cell(cell, &number_field), "2");
+}
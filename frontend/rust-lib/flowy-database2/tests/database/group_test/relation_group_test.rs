@@ -0,0 +1,72 @@
+use collab_database::rows::RowId;
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::field::{RelationCellChangeset, RelationCellData};
+use lib_infra::box_any::BoxAny;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn move_row_with_multiple_relations_test() {
+  let test = DatabaseGroupTest::new().await;
+  let relation_field = test.get_field(FieldType::Relation).await;
+  let rows = test.get_rows().await;
+  let row_id = rows[0].id.clone();
+  test
+    .update_cell(
+      &relation_field.id,
+      row_id.clone(),
+      BoxAny::new(RelationCellChangeset {
+        inserted_row_ids: vec![
+          "related_a".to_string().into(),
+          "related_b".to_string().into(),
+        ],
+        ..Default::default()
+      }),
+    )
+    .await
+    .unwrap();
+  test
+    .update_cell(
+      &relation_field.id,
+      rows[1].id.clone(),
+      BoxAny::new(RelationCellChangeset {
+        inserted_row_ids: vec!["related_c".to_string().into()],
+        ..Default::default()
+      }),
+    )
+    .await
+    .unwrap();
+  test.group_by_field(&relation_field.id).await;
+
+  // Move the row from the group of related_a to the group of related_c
+  let groups = test.editor.load_groups(&test.view_id).await.unwrap().items;
+  let group_index = |group_id: &str| {
+    groups
+      .iter()
+      .position(|group| group.group_id == group_id)
+      .unwrap()
+  };
+  let (a_index, b_index, c_index) = (
+    group_index("related_a"),
+    group_index("related_b"),
+    group_index("related_c"),
+  );
+  test.move_row(a_index, 0, c_index, 0).await;
+
+  // The row keeps its relation to related_b
+  test.assert_group_row_count(a_index, 0).await;
+  test.assert_group_row_count(b_index, 1).await;
+  test.assert_group_row_count(c_index, 2).await;
+  assert_eq!(test.row_at_index(b_index, 0).await.id, row_id.as_str());
+
+  let rows = test.get_rows().await;
+  let moved_row = rows.iter().find(|row| row.id == row_id).unwrap();
+  let cell = moved_row.cells.get(&relation_field.id).unwrap();
+  assert_eq!(
+    RelationCellData::from(cell).row_ids,
+    vec![
+      RowId::from("related_b".to_string()),
+      RowId::from("related_c".to_string())
+    ]
+  );
+}
@@ -54,6 +54,7 @@ impl DatabaseGroupTest {
         &to_group.group_id,
         from_row,
         Some(to_row),
+        None,
       )
       .await
       .unwrap();
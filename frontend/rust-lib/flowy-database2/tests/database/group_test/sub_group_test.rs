@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use collab_database::rows::RowId;
+use flowy_database2::entities::{FieldType, GroupPB};
+use flowy_database2::services::field::{CHECK, UNCHECK};
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+fn sub_group<'a>(group: &'a GroupPB, sub_group_id: &str) -> &'a GroupPB {
+  group
+    .sub_groups
+    .iter()
+    .find(|sub_group| sub_group.group_id == sub_group_id)
+    .unwrap()
+}
+
+#[tokio::test]
+async fn sub_group_by_checkbox_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test
+    .editor
+    .set_sub_group_by_field(&test.view_id, Some(&checkbox_field.id), vec![])
+    .await
+    .unwrap();
+
+  let groups = test.editor.load_groups(&test.view_id).await.unwrap().items;
+  for group in groups.iter() {
+    let num_of_rows = group
+      .sub_groups
+      .iter()
+      .map(|sub_group| sub_group.rows.len())
+      .sum::<usize>();
+    assert_eq!(num_of_rows, group.rows.len());
+  }
+  assert_eq!(sub_group(&groups[1], CHECK).rows.len(), 2);
+  assert_eq!(sub_group(&groups[1], UNCHECK).rows.len(), 0);
+  assert_eq!(sub_group(&groups[2], CHECK).rows.len(), 0);
+  assert_eq!(sub_group(&groups[2], UNCHECK).rows.len(), 2);
+
+  // The sub groups are kept in the group settings of the view
+  let setting = test
+    .editor
+    .get_database_view_setting(&test.view_id)
+    .await
+    .unwrap();
+  assert_eq!(setting.group_settings.items.len(), 2);
+  assert_eq!(setting.group_settings.items[1].field_id, checkbox_field.id);
+
+  test
+    .editor
+    .set_sub_group_by_field(&test.view_id, None, vec![])
+    .await
+    .unwrap();
+  let group = test.group_at_index(1).await;
+  assert!(group.sub_groups.is_empty());
+}
+
+#[tokio::test]
+async fn move_row_to_another_sub_group_test() {
+  let test = DatabaseGroupTest::new().await;
+  let checkbox_field = test.get_field(FieldType::Checkbox).await;
+  test
+    .editor
+    .set_sub_group_by_field(&test.view_id, Some(&checkbox_field.id), vec![])
+    .await
+    .unwrap();
+
+  let from_group = test.group_at_index(1).await;
+  let to_group = test.group_at_index(2).await;
+  let row_id = sub_group(&from_group, CHECK).rows[0].id.clone();
+  test
+    .editor
+    .move_group_row(
+      &test.view_id,
+      &from_group.group_id,
+      &to_group.group_id,
+      RowId::from(row_id.clone()),
+      None,
+      Some(UNCHECK),
+    )
+    .await
+    .unwrap();
+  tokio::time::sleep(Duration::from_secs(1)).await; // Sleep to allow the row update to propagate
+
+  let to_group = test.group_at_index(2).await;
+  let to_sub_group = sub_group(&to_group, UNCHECK);
+  assert_eq!(to_sub_group.rows.len(), 3);
+  assert!(to_sub_group.rows.iter().any(|row| row.id == row_id));
+  let from_group = test.group_at_index(1).await;
+  assert_eq!(sub_group(&from_group, CHECK).rows.len(), 1);
+}
@@ -0,0 +1,42 @@
+use chrono::offset;
+use flowy_database2::entities::FieldType;
+
+use crate::database::group_test::script::DatabaseGroupTest;
+
+#[tokio::test]
+async fn group_by_created_time_test() {
+  let test = DatabaseGroupTest::new().await;
+  let created_time_field = test.get_field(FieldType::CreatedTime).await;
+  test.group_by_field(&created_time_field.id).await;
+
+  // All the rows were created today
+  let today = offset::Local::now().format("%Y/%m/%d").to_string();
+  test.assert_group_count(2).await;
+  test.assert_group_row_count(0, 0).await; // No status group
+  test.assert_group_id(1, &today).await;
+  test.assert_group_row_count(1, 5).await;
+}
+
+#[tokio::test]
+async fn move_row_to_another_created_time_group_test() {
+  let test = DatabaseGroupTest::new().await;
+  let created_time_field = test.get_field(FieldType::CreatedTime).await;
+  test.group_by_field(&created_time_field.id).await;
+
+  // The created time can't be edited, so the row stays in its group
+  let row = test.row_at_index(1, 0).await;
+  test
+    .editor
+    .move_group_row(
+      &test.view_id,
+      &test.group_at_index(1).await.group_id,
+      &created_time_field.id,
+      row.id.clone().into(),
+      None,
+      None,
+    )
+    .await
+    .unwrap();
+  test.assert_group_row_count(0, 0).await;
+  test.assert_group_row_count(1, 5).await;
+}
@@ -1,8 +1,16 @@
 use collab_database::fields::Field;
 use collab_database::views::DatabaseLayout;
 
-use flowy_database2::entities::{FieldType, LayoutSettingChangeset, LayoutSettingParams};
-use flowy_database2::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use flowy_database2::entities::{
+  CalendarEventPB, CellIdParams, DatabaseLayoutPB, FieldType, LayoutSettingChangeset,
+  LayoutSettingParams, MoveCalendarEventParams, RecurrenceEditScopePB, TimelineBarPB,
+};
+use flowy_database2::services::field::DateCellChangeset;
+use flowy_database2::services::setting::{
+  BoardLayoutSetting, CalendarLayoutSetting, TimelineLayoutSetting,
+};
+use flowy_error::FlowyResult;
+use lib_infra::box_any::BoxAny;
 
 use crate::database::database_editor::DatabaseEditorTest;
 
@@ -27,10 +35,11 @@ impl DatabaseLayoutTest {
   }
 
   pub async fn get_first_date_field(&self) -> Field {
-    self
-      .database_test
-      .get_first_field(FieldType::DateTime)
-      .await
+    self.get_first_field(FieldType::DateTime).await
+  }
+
+  pub async fn get_first_field(&self, field_type: FieldType) -> Field {
+    self.database_test.get_first_field(field_type).await
   }
 
   async fn get_layout_setting(
@@ -47,6 +56,10 @@ impl DatabaseLayoutTest {
   }
 
   pub async fn update_database_layout(&mut self, layout: DatabaseLayout) {
+    self.update_database_layout_pb(layout.into()).await;
+  }
+
+  pub async fn update_database_layout_pb(&mut self, layout: DatabaseLayoutPB) {
     self
       .database_test
       .editor
@@ -55,11 +68,105 @@ impl DatabaseLayoutTest {
       .unwrap();
   }
 
+  pub async fn get_timeline_layout_setting(&self) -> Option<TimelineLayoutSetting> {
+    let view_id = self.database_test.view_id.clone();
+    self
+      .get_layout_setting(&view_id, DatabaseLayout::Grid)
+      .await
+      .timeline
+  }
+
+  pub async fn update_timeline_layout_setting(
+    &mut self,
+    new_setting: TimelineLayoutSetting,
+  ) -> FlowyResult<()> {
+    let changeset = LayoutSettingChangeset {
+      view_id: self.database_test.view_id.clone(),
+      layout_type: DatabaseLayout::Grid,
+      board: None,
+      calendar: None,
+      timeline: Some(new_setting),
+    };
+    self
+      .database_test
+      .editor
+      .set_layout_setting(&self.database_test.view_id, changeset)
+      .await
+  }
+
+  pub async fn get_timeline_bars(&self, start: i64, end: i64) -> Vec<TimelineBarPB> {
+    self
+      .database_test
+      .editor
+      .get_timeline_bars(&self.database_test.view_id, start, end)
+      .await
+      .unwrap()
+  }
+
+  pub async fn update_first_timeline_bar(&mut self, start: i64, end: i64) {
+    let row_id = self.database_test.rows[0].id.clone();
+    self
+      .database_test
+      .editor
+      .update_timeline_bar(&self.database_test.view_id, &row_id, start, end)
+      .await
+      .unwrap();
+  }
+
+  pub async fn get_calendar_events(&self, start: i64, end: i64) -> Vec<CalendarEventPB> {
+    self
+      .database_test
+      .editor
+      .get_all_calendar_events(&self.database_test.view_id, Some((start, end)))
+      .await
+  }
+
+  pub async fn update_first_row_date(&mut self, changeset: DateCellChangeset) {
+    let date_field = self.get_first_date_field().await;
+    let row_id = self.database_test.rows[0].id.clone();
+    self
+      .database_test
+      .editor
+      .update_cell_with_changeset(
+        &self.database_test.view_id,
+        &row_id,
+        &date_field.id,
+        BoxAny::new(changeset),
+      )
+      .await
+      .unwrap();
+  }
+
+  pub async fn move_first_calendar_event(
+    &mut self,
+    timestamp: i64,
+    occurrence: i64,
+    scope: RecurrenceEditScopePB,
+  ) {
+    let date_field = self.get_first_date_field().await;
+    let params = MoveCalendarEventParams {
+      cell_id: CellIdParams {
+        view_id: self.database_test.view_id.clone(),
+        field_id: date_field.id,
+        row_id: self.database_test.rows[0].id.clone(),
+      },
+      timestamp,
+      occurrence: Some(occurrence),
+      scope,
+    };
+    self
+      .database_test
+      .editor
+      .move_calendar_event(params)
+      .await
+      .unwrap();
+  }
+
   pub async fn assert_all_calendar_events_count(&self, expected: usize) {
     let events = self
       .database_test
       .editor
-      .get_all_calendar_events(&self.database_test.view_id)
+      .get_all_calendar_events(&self.database_test.view_id, None)
       .await;
     assert_eq!(events.len(), expected);
   }
@@ -100,6 +207,7 @@ impl DatabaseLayoutTest {
       layout_type: DatabaseLayout::Board,
       board: Some(new_setting),
       calendar: None,
+      timeline: None,
     };
     self
       .database_test
@@ -113,7 +221,7 @@ impl DatabaseLayoutTest {
     let events = self
       .database_test
       .editor
-      .get_all_calendar_events(&self.database_test.view_id)
+      .get_all_calendar_events(&self.database_test.view_id, None)
       .await;
     assert_eq!(events.len(), 5);
 
@@ -1,6 +1,12 @@
 use crate::database::layout_test::script::DatabaseLayoutTest;
 use collab_database::views::DatabaseLayout;
-use flowy_database2::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use flowy_database2::entities::{
+  CalendarEventPB, DatabaseLayoutPB, FieldType, RecurrenceEditScopePB,
+};
+use flowy_database2::services::field::{DateCellChangeset, RecurrenceFrequency, RecurrenceRule};
+use flowy_database2::services::setting::{
+  BoardLayoutSetting, CalendarLayoutSetting, TimelineLayoutSetting, TimelineZoomLevel,
+};
 
 #[tokio::test]
 async fn board_layout_setting_test() {
@@ -43,6 +49,89 @@ async fn calendar_get_events_test() {
   test.assert_default_all_calendar_events().await;
 }
 
+#[tokio::test]
+async fn calendar_recurring_events_test() {
+  const DAY: i64 = 24 * 60 * 60;
+  let mut test = DatabaseLayoutTest::new_calendar().await;
+  // 2023-11-14, after the dates of the other rows
+  let first = 1700000000;
+  test
+    .update_first_row_date(DateCellChangeset {
+      timestamp: Some(first),
+      recurrence: Some(RecurrenceRule {
+        count: Some(3),
+        ..RecurrenceRule::new(RecurrenceFrequency::Daily)
+      }),
+      ..Default::default()
+    })
+    .await;
+
+  let timestamps = |events: Vec<CalendarEventPB>| {
+    let mut timestamps = events
+      .into_iter()
+      .map(|event| (event.timestamp.unwrap(), event.is_recurring))
+      .collect::<Vec<_>>();
+    timestamps.sort();
+    timestamps
+  };
+  let events = test.get_calendar_events(first, first + 10 * DAY).await;
+  assert_eq!(
+    timestamps(events),
+    vec![(first, true), (first + DAY, true), (first + 2 * DAY, true)]
+  );
+
+  // Move the second occurrence an hour later, it becomes a row on its own
+  test
+    .move_first_calendar_event(
+      first + DAY + 3600,
+      first + DAY,
+      RecurrenceEditScopePB::ThisOccurrence,
+    )
+    .await;
+  let events = test.get_calendar_events(first, first + 10 * DAY).await;
+  assert_eq!(
+    timestamps(events),
+    vec![
+      (first, true),
+      (first + DAY + 3600, false),
+      (first + 2 * DAY, true)
+    ]
+  );
+
+  // Move the last occurrence and the following ones two hours later
+  test
+    .move_first_calendar_event(
+      first + 2 * DAY + 7200,
+      first + 2 * DAY,
+      RecurrenceEditScopePB::AllFollowing,
+    )
+    .await;
+  let events = test.get_calendar_events(first, first + 10 * DAY).await;
+  assert_eq!(
+    timestamps(events),
+    vec![
+      (first, true),
+      (first + DAY + 3600, false),
+      (first + 2 * DAY + 7200, true)
+    ]
+  );
+  test.assert_all_calendar_events_count(7).await;
+
+  // Move the whole series
+  test
+    .move_first_calendar_event(first + 3600, first, RecurrenceEditScopePB::All)
+    .await;
+  let events = test.get_calendar_events(first, first + 10 * DAY).await;
+  assert_eq!(
+    timestamps(events),
+    vec![
+      (first + 3600, true),
+      (first + DAY + 3600, false),
+      (first + 2 * DAY + 7200, true)
+    ]
+  );
+}
+
 #[tokio::test]
 async fn grid_to_calendar_layout_test() {
   let mut test = DatabaseLayoutTest::new_no_date_grid().await;
@@ -51,3 +140,87 @@ async fn grid_to_calendar_layout_test() {
   test.update_database_layout(DatabaseLayout::Calendar).await;
   test.assert_all_calendar_events_count(3).await;
 }
+
+#[tokio::test]
+async fn grid_to_timeline_layout_test() {
+  let mut test = DatabaseLayoutTest::new_no_date_grid().await;
+  assert!(test.get_timeline_layout_setting().await.is_none());
+
+  // A date field is created for the timeline
+  test
+    .update_database_layout_pb(DatabaseLayoutPB::Timeline)
+    .await;
+  let date_field = test.get_first_date_field().await;
+  let setting = test.get_timeline_layout_setting().await.unwrap();
+  assert_eq!(setting.start_field_id, date_field.id);
+  assert!(setting.end_field_id.is_none());
+  assert!(test.get_timeline_bars(0, i64::MAX).await.is_empty());
+
+  // Switching back to the grid removes the timeline setting
+  test.update_database_layout_pb(DatabaseLayoutPB::Grid).await;
+  assert!(test.get_timeline_layout_setting().await.is_none());
+
+  // Switching to the timeline again reuses the date field
+  test
+    .update_database_layout_pb(DatabaseLayoutPB::Timeline)
+    .await;
+  let setting = test.get_timeline_layout_setting().await.unwrap();
+  assert_eq!(setting.start_field_id, date_field.id);
+}
+
+#[tokio::test]
+async fn timeline_move_and_resize_bar_test() {
+  let mut test = DatabaseLayoutTest::new_no_date_grid().await;
+  test
+    .update_database_layout_pb(DatabaseLayoutPB::Timeline)
+    .await;
+
+  test.update_first_timeline_bar(1000, 5000).await;
+  let bars = test.get_timeline_bars(0, 2000).await;
+  assert_eq!(bars.len(), 1);
+  assert_eq!((bars[0].start, bars[0].end), (1000, 5000));
+  assert!(test.get_timeline_bars(6000, 7000).await.is_empty());
+
+  // Move the bar
+  test.update_first_timeline_bar(6000, 10000).await;
+  let bars = test.get_timeline_bars(6000, 7000).await;
+  assert_eq!((bars[0].start, bars[0].end), (6000, 10000));
+}
+
+#[tokio::test]
+async fn timeline_layout_setting_test() {
+  let mut test = DatabaseLayoutTest::new_no_date_grid().await;
+  test
+    .update_database_layout_pb(DatabaseLayoutPB::Timeline)
+    .await;
+  let date_field = test.get_first_date_field().await;
+
+  let new_setting = TimelineLayoutSetting {
+    zoom_level: TimelineZoomLevel::Quarter,
+    ..TimelineLayoutSetting::new(date_field.id.clone())
+  };
+  test
+    .update_timeline_layout_setting(new_setting)
+    .await
+    .unwrap();
+  let setting = test.get_timeline_layout_setting().await.unwrap();
+  assert_eq!(setting.zoom_level, TimelineZoomLevel::Quarter);
+
+  // The dependency field must be a relation field
+  let invalid_setting = TimelineLayoutSetting {
+    dependency_field_id: Some(date_field.id.clone()),
+    ..TimelineLayoutSetting::new(date_field.id.clone())
+  };
+  assert!(test
+    .update_timeline_layout_setting(invalid_setting)
+    .await
+    .is_err());
+
+  // The start field must be a date field
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let invalid_setting = TimelineLayoutSetting::new(text_field.id);
+  assert!(test
+    .update_timeline_layout_setting(invalid_setting)
+    .await
+    .is_err());
+}
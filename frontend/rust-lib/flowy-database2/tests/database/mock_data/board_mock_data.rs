@@ -143,7 +143,7 @@ pub fn make_test_board() -> DatabaseData {
           .build();
         fields.push(time_field);
       },
-      FieldType::Translate | FieldType::Media => {},
+      FieldType::Translate | FieldType::Media | FieldType::Formula | FieldType::Rollup => {},
     }
   }
 
@@ -18,7 +18,8 @@ use flowy_database2::entities::FieldType;
 use flowy_database2::services::field::summary_type_option::summary::SummarizationTypeOption;
 use flowy_database2::services::field::translate_type_option::translate::TranslateTypeOption;
 use flowy_database2::services::field::{
-  ChecklistCellInsertChangeset, ChecklistTypeOption, FieldBuilder, RelationTypeOption,
+  ChecklistCellInsertChangeset, ChecklistTypeOption, FieldBuilder, FormulaTypeOption,
+  RelationTypeOption, RollupTypeOption,
 };
 use flowy_database2::services::field_settings::default_field_settings_for_fields;
 
@@ -168,6 +169,22 @@ pub fn make_test_grid() -> DatabaseData {
           .build();
         fields.push(media_field);
       },
+      FieldType::Formula => {
+        let type_option = FormulaTypeOption {
+          expression: "{Price} * 2".to_string(),
+        };
+        let formula_field = FieldBuilder::new(field_type, type_option)
+          .name("Formula")
+          .build();
+        fields.push(formula_field);
+      },
+      FieldType::Rollup => {
+        let type_option = RollupTypeOption::default();
+        let rollup_field = FieldBuilder::new(field_type, type_option)
+          .name("Rollup")
+          .build();
+        fields.push(rollup_field);
+      },
     }
   }
 
@@ -10,4 +10,5 @@ mod layout_test;
 mod mock_data;
 mod pre_fill_cell_test;
 mod share_test;
+mod snapshot_test;
 mod sort_test;
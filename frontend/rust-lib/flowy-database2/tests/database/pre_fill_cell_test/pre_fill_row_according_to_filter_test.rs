@@ -17,6 +17,7 @@ async fn according_to_text_contains_filter_test() {
       data: TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "sample".to_string(),
+        ..Default::default()
       }
       .try_into()
       .unwrap(),
@@ -47,6 +48,7 @@ async fn according_to_empty_text_contains_filter_test() {
       data: TextFilterPB {
         condition: TextFilterConditionPB::TextContains,
         content: "".to_string(),
+        ..Default::default()
       }
       .try_into()
       .unwrap(),
@@ -76,6 +78,7 @@ async fn according_to_text_is_not_empty_filter_test() {
       data: TextFilterPB {
         condition: TextFilterConditionPB::TextIsNotEmpty,
         content: "".to_string(),
+        ..Default::default()
       }
       .try_into()
       .unwrap(),
@@ -43,6 +43,7 @@ impl DatabasePreFillRowCellTest {
         InsertFilterPB {
           parent_filter_id: None,
           data: filter,
+          ..Default::default()
         }
         .try_into()
         .unwrap(),
@@ -1,3 +1,5 @@
+use chrono::{Local, TimeZone};
+use collab_database::fields::date_type_option::DateCellData;
 use flowy_database2::entities::FieldType;
 use flowy_database2::services::cell::stringify_cell;
 use flowy_database2::services::field::CHECK;
@@ -85,7 +87,9 @@ async fn export_and_then_import_meta_csv_test() {
           | FieldType::Summary
           | FieldType::Time
           | FieldType::Translate
-          | FieldType::Media => {},
+          | FieldType::Media
+          | FieldType::Formula
+          | FieldType::Rollup => {},
         }
       } else {
         panic!(
@@ -97,6 +101,46 @@ async fn export_and_then_import_meta_csv_test() {
   }
 }
 
+#[tokio::test]
+async fn export_and_then_import_ics_test() {
+  let test = DatabaseEditorTest::new_calendar().await;
+  let ics = test.editor.export_ics(&test.view_id).await.unwrap();
+  assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+  let num_of_events = ics.matches("BEGIN:VEVENT").count();
+  assert!(num_of_events > 0);
+
+  let result = test.import_ics(ics).await;
+  let database = test.get_database(&result.database_id).await.unwrap();
+  let fields = database.get_fields(&result.view_id, None).await;
+  let rows = database.get_all_rows(&result.view_id).await.unwrap();
+  assert_eq!(rows.len(), num_of_events);
+  assert_eq!(fields[0].name, "Title");
+  assert_eq!(FieldType::from(fields[1].field_type), FieldType::DateTime);
+
+  // The first event keeps its title and its day, the mock dates don't include the time
+  let title = stringify_cell(rows[0].cells.get(&fields[0].id).unwrap(), &fields[0]);
+  assert_eq!(title, "A");
+  let date = DateCellData::from(rows[0].cells.get(&fields[1].id).unwrap());
+  assert!(!date.include_time);
+  let local_date = |timestamp: i64| Local.timestamp_opt(timestamp, 0).unwrap().date_naive();
+  assert_eq!(local_date(date.timestamp.unwrap()), local_date(1678090778));
+}
+
+#[tokio::test]
+async fn export_html_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let html = test.editor.export_html(&test.view_id).await.unwrap();
+  assert!(html.starts_with("<table><thead><tr>"));
+  assert!(html.ends_with("</tbody></table>"));
+
+  // One header row, then one row for each row of the view
+  assert_eq!(html.matches("<tr>").count(), test.rows.len() + 1);
+  assert_eq!(html.matches("<th>").count(), test.fields.len());
+  for field in &test.fields {
+    assert!(html.contains(&format!("<th>{}</th>", field.name)));
+  }
+}
+
 #[tokio::test]
 async fn history_database_import_test() {
   let format = CSVFormat::META;
@@ -171,7 +215,9 @@ async fn history_database_import_test() {
           | FieldType::Summary
           | FieldType::Time
           | FieldType::Translate
-          | FieldType::Media => {},
+          | FieldType::Media
+          | FieldType::Formula
+          | FieldType::Rollup => {},
         }
       } else {
         panic!(
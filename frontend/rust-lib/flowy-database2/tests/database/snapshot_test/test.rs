@@ -0,0 +1,110 @@
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database2::services::snapshot::SnapshotDiffKind;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+async fn create_snapshot(test: &DatabaseEditorTest) -> String {
+  let manager = test.sdk.database_manager.clone();
+  manager
+    .create_database_snapshot(&test.view_id)
+    .await
+    .unwrap();
+  let metas = manager
+    .get_database_snapshot_metas(&test.view_id)
+    .await
+    .unwrap();
+  metas[0].snapshot_id.clone()
+}
+
+#[tokio::test]
+async fn diff_and_restore_database_snapshot_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let manager = test.sdk.database_manager.clone();
+  let snapshot_id = create_snapshot(&test).await;
+
+  let primary_field = test.get_first_field(FieldType::RichText).await;
+  let rows = test.get_rows().await;
+  let row_count = rows.len();
+  let edited_row_id = rows[0].id.clone();
+  let deleted_row_id = rows[1].id.clone();
+  let original_content = stringify_cell(
+    rows[0].cells.get(&primary_field.id).unwrap(),
+    &primary_field,
+  );
+  test
+    .update_text_cell(edited_row_id.clone(), "changed")
+    .await
+    .unwrap();
+  test.editor.delete_rows(&[deleted_row_id.clone()]).await;
+
+  let diff = manager
+    .get_database_snapshot_diff(&test.view_id, &snapshot_id)
+    .await
+    .unwrap();
+  assert!(diff.fields.is_empty());
+  assert_eq!(diff.rows.len(), 2);
+  let edited_row = diff
+    .rows
+    .iter()
+    .find(|row| row.row_id == edited_row_id)
+    .unwrap();
+  assert_eq!(edited_row.kind, SnapshotDiffKind::Modified);
+  assert_eq!(edited_row.cells.len(), 1);
+  assert_eq!(edited_row.cells[0].old_content, original_content);
+  assert_eq!(edited_row.cells[0].new_content, "changed");
+  let deleted_row = diff
+    .rows
+    .iter()
+    .find(|row| row.row_id == deleted_row_id)
+    .unwrap();
+  assert_eq!(deleted_row.kind, SnapshotDiffKind::Removed);
+
+  manager
+    .restore_database_snapshot(&test.view_id, &snapshot_id)
+    .await
+    .unwrap();
+  assert_eq!(test.get_rows().await.len(), row_count);
+  let diff = manager
+    .get_database_snapshot_diff(&test.view_id, &snapshot_id)
+    .await
+    .unwrap();
+  assert!(diff.is_empty());
+}
+
+#[tokio::test]
+async fn diff_and_restore_row_snapshot_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let manager = test.sdk.database_manager.clone();
+  let snapshot_id = create_snapshot(&test).await;
+
+  let row_id = test.get_rows().await[0].id.clone();
+  test
+    .update_text_cell(row_id.clone(), "changed")
+    .await
+    .unwrap();
+  let diff = manager
+    .get_row_snapshot_diff(&test.view_id, &row_id, &snapshot_id)
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(diff.kind, SnapshotDiffKind::Modified);
+  assert_eq!(diff.cells[0].new_content, "changed");
+
+  manager
+    .restore_row_snapshot(&test.view_id, &row_id, &snapshot_id)
+    .await
+    .unwrap();
+  assert!(manager
+    .get_row_snapshot_diff(&test.view_id, &row_id, &snapshot_id)
+    .await
+    .unwrap()
+    .is_none());
+
+  // The edited version was saved before the row was restored.
+  let metas = manager
+    .get_row_snapshot_metas(&test.view_id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(metas.len(), 2);
+}
@@ -32,6 +32,16 @@ impl DatabaseSortTest {
   }
 
   pub async fn insert_sort(&mut self, field: Field, condition: SortCondition) {
+    let params = UpdateSortPayloadPB {
+      field_id: field.id.clone(),
+      condition: condition.into(),
+      ..Default::default()
+    };
+    self.insert_sort_with_params(params).await;
+  }
+
+  /// Inserts a new sort with the options of the params in the view of the test
+  pub async fn insert_sort_with_params(&mut self, params: UpdateSortPayloadPB) {
     self.recv = Some(
       self
         .editor
@@ -41,9 +51,8 @@ impl DatabaseSortTest {
     );
     let params = UpdateSortPayloadPB {
       view_id: self.view_id.clone(),
-      field_id: field.id.clone(),
       sort_id: None,
-      condition: condition.into(),
+      ..params
     };
     self.editor.create_or_update_sort(params).await.unwrap();
   }
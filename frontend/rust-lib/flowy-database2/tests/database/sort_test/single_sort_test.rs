@@ -1,5 +1,9 @@
+use crate::database::mock_data::{COMPLETED, PAUSED, PLANNED};
 use crate::database::sort_test::script::DatabaseSortTest;
-use flowy_database2::entities::{CheckboxFilterConditionPB, CheckboxFilterPB, FieldType};
+use flowy_database2::entities::{
+  CheckboxFilterConditionPB, CheckboxFilterPB, EmptyCellsPositionPB, FieldType, RelationSortByPB,
+  SelectSortByPB, SortConditionPB, UpdateSortPayloadPB,
+};
 use flowy_database2::services::sort::SortCondition;
 use lib_infra::box_any::BoxAny;
 
@@ -310,3 +314,137 @@ async fn sort_number_by_descending_test() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn sort_text_by_natural_order_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let rows = test.get_rows().await;
+  for (row, text) in rows.iter().zip(["item10", "item2", "item1"]) {
+    test
+      .update_text_cell(row.id.clone(), text.to_string())
+      .await;
+  }
+
+  test
+    .insert_sort_with_params(UpdateSortPayloadPB {
+      field_id: text_field.id.clone(),
+      condition: SortConditionPB::Ascending,
+      natural_order: true,
+      ..Default::default()
+    })
+    .await;
+  test
+    .assert_cell_content_order(
+      text_field.id.clone(),
+      vec!["AE", "AE", "CB", "DA", "item1", "item2", "item10"],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn sort_text_with_empty_cells_first_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+
+  test
+    .insert_sort_with_params(UpdateSortPayloadPB {
+      field_id: text_field.id.clone(),
+      condition: SortConditionPB::Descending,
+      empty_cells: EmptyCellsPositionPB::First,
+      ..Default::default()
+    })
+    .await;
+  test
+    .assert_cell_content_order(
+      text_field.id.clone(),
+      vec!["", "DA", "CB", "C", "AE", "AE", "A"],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn sort_single_select_by_option_order_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let select_field = test.get_first_field(FieldType::SingleSelect).await;
+  let options = test.get_single_select_type_option(&select_field.id).await;
+  let paused = options.iter().find(|option| option.name == PAUSED).unwrap();
+  let rows = test.get_rows().await;
+  test
+    .update_single_select_cell(rows[0].id.clone(), &paused.id)
+    .await
+    .unwrap();
+
+  // The options are ordered as Completed, Planned and Paused in the field
+  test
+    .insert_sort_with_params(UpdateSortPayloadPB {
+      field_id: select_field.id.clone(),
+      condition: SortConditionPB::Ascending,
+      select_sort_by: SelectSortByPB::OptionOrder,
+      ..Default::default()
+    })
+    .await;
+  test
+    .assert_cell_content_order(
+      select_field.id.clone(),
+      vec![COMPLETED, COMPLETED, PLANNED, PLANNED, PAUSED, "", ""],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn sort_relation_by_primary_text_test() {
+  let mut test = DatabaseSortTest::new().await;
+  test.link_relation_to_own_database().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let relation_field = test.get_first_field(FieldType::Relation).await;
+  let rows = test.get_rows().await;
+  // The rows "A", "" and "C" relate to the rows "CB", "A" and "DA"
+  for (row_index, related_row_index) in [(0, 6), (1, 0), (2, 3)] {
+    test
+      .update_relation_cell(
+        rows[row_index].id.clone(),
+        vec![rows[related_row_index].id.clone()],
+      )
+      .await
+      .unwrap();
+  }
+
+  test
+    .insert_sort_with_params(UpdateSortPayloadPB {
+      field_id: relation_field.id.clone(),
+      condition: SortConditionPB::Ascending,
+      relation_sort_by: RelationSortByPB::PrimaryText,
+      ..Default::default()
+    })
+    .await;
+  test
+    .assert_cell_content_order(
+      text_field.id.clone(),
+      vec!["", "A", "C", "DA", "AE", "AE", "CB"],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn sort_relation_keeps_row_order_by_default_test() {
+  let mut test = DatabaseSortTest::new().await;
+  test.link_relation_to_own_database().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let relation_field = test.get_first_field(FieldType::Relation).await;
+  let rows = test.get_rows().await;
+  test
+    .update_relation_cell(rows[3].id.clone(), vec![rows[0].id.clone()])
+    .await
+    .unwrap();
+
+  test
+    .insert_sort(relation_field.clone(), SortCondition::Ascending)
+    .await;
+  test
+    .assert_cell_content_order(
+      text_field.id.clone(),
+      vec!["A", "", "C", "DA", "AE", "AE", "CB"],
+    )
+    .await;
+}
@@ -2,10 +2,18 @@ use crate::entities::{
   DocEventPB, DocumentAwarenessStatesPB, DocumentSnapshotStatePB, DocumentSyncStatePB,
 };
 use crate::notification::{send_notification, DocumentNotification};
+use crate::parser::utils::{delta_to_text, get_delta_for_block};
+use collab::lock::RwLock;
 use collab::preclude::Collab;
+use collab_document::blocks::DocumentData;
 use collab_document::document::Document;
+use flowy_error::{internal_error, FlowyResult};
+use flowy_search_pub::entities::{ContentIndexManager, IndexableContent};
 use futures::StreamExt;
 use lib_dispatch::prelude::af_spawn;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+use tokio::sync::mpsc;
 
 pub fn subscribe_document_changed(doc_id: &str, document: &mut Document) {
   let doc_id_clone_for_block_changed = doc_id.to_owned();
@@ -67,3 +75,79 @@ pub fn subscribe_document_sync_state(collab: &Collab) {
     }
   });
 }
+
+/// Returns the text of each block of the document that has text, which is indexed by the local
+/// search.
+pub fn document_block_contents(
+  document_id: &str,
+  workspace_id: &str,
+  data: &DocumentData,
+) -> Vec<IndexableContent> {
+  data
+    .blocks
+    .keys()
+    .filter_map(|block_id| {
+      let content = delta_to_text(&get_delta_for_block(block_id, data)?);
+      if content.trim().is_empty() {
+        return None;
+      }
+      Some(IndexableContent {
+        id: block_id.clone(),
+        object_id: document_id.to_string(),
+        view_id: document_id.to_string(),
+        content,
+        workspace_id: workspace_id.to_string(),
+      })
+    })
+    .collect()
+}
+
+/// Replaces the indexed blocks of the document. The index is committed on a blocking thread.
+pub async fn index_document_blocks(
+  indexer: Arc<dyn ContentIndexManager>,
+  document_id: String,
+  contents: Vec<IndexableContent>,
+) -> FlowyResult<()> {
+  tokio::task::spawn_blocking(move || indexer.index_object(&document_id, contents))
+    .await
+    .map_err(internal_error)?
+}
+
+/// Indexes the blocks of the document, and indexes them again whenever they change. The changes
+/// are debounced, so typing in a block doesn't re-index the document after every keystroke.
+pub fn subscribe_document_index(
+  document_id: &str,
+  workspace_id: &str,
+  document: &mut Document,
+  weak_document: Weak<RwLock<Document>>,
+  indexer: Arc<dyn ContentIndexManager>,
+) {
+  let (tx, mut rx) = mpsc::channel::<()>(1);
+  let _ = tx.try_send(());
+  document.subscribe_block_changed("index", move |_, _| {
+    let _ = tx.try_send(());
+  });
+
+  let document_id = document_id.to_string();
+  let workspace_id = workspace_id.to_string();
+  af_spawn(async move {
+    while rx.recv().await.is_some() {
+      tokio::time::sleep(Duration::from_secs(1)).await;
+      let document = match weak_document.upgrade() {
+        Some(document) => document,
+        None => break,
+      };
+      let data = document.read().await.get_document_data();
+      match data {
+        Ok(data) => {
+          let contents = document_block_contents(&document_id, &workspace_id, &data);
+          let result = index_document_blocks(indexer.clone(), document_id.clone(), contents).await;
+          if let Err(err) = result {
+            tracing::error!("Failed to index document {}: {}", document_id, err);
+          }
+        },
+        Err(err) => tracing::error!("Failed to get document data {}: {}", document_id, err),
+      }
+    }
+  });
+}
@@ -372,12 +372,14 @@ impl From<(&Vec<BlockEvent>, bool, Option<DocumentData>)> for DocEventPB {
 ///     json: true,
 ///     text: true,
 ///     html: true,
+///     markdown: true,
 ///   },
 /// };
 /// let result = test.convert_document(payload).await;
 /// assert_eq!(result.json, Some("[{ \"block_id\": \"1\", \"type\": \"paragraph\", \"data\": {\"delta\": [{ \"insert\": \"Hello\" }] } }, { \"block_id\": \"2\", \"type\": \"paragraph\", \"data\": {\"delta\": [{ \"insert\": \" World!\" }] } }".to_string()));
 /// assert_eq!(result.text, Some("Hello\n World!".to_string()));
 /// assert_eq!(result.html, Some("<p>Hello</p><p> World!</p>".to_string()));
+/// assert_eq!(result.markdown, Some("Hello\n\n&#32;World!\n".to_string()));
 /// ```
 /// #
 pub async fn convert_document_handler(
@@ -409,6 +411,10 @@ pub async fn convert_document_handler(
       .parse_types
       .text
       .then(|| parser.to_text_with_json(root)),
+    markdown: params
+      .parse_types
+      .markdown
+      .then(|| parser.to_markdown_with_json(root)),
   })
 }
 
@@ -21,13 +21,15 @@ use tracing::{event, instrument};
 use tracing::{info, trace};
 
 use crate::document::{
-  subscribe_document_changed, subscribe_document_snapshot_state, subscribe_document_sync_state,
+  document_block_contents, index_document_blocks, subscribe_document_changed,
+  subscribe_document_index, subscribe_document_snapshot_state, subscribe_document_sync_state,
 };
 use collab_integrate::collab_builder::{
   AppFlowyCollabBuilder, CollabBuilderConfig, CollabPersistenceImpl,
 };
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_search_pub::entities::ContentIndexManager;
 use flowy_storage_pub::storage::{CreatedUpload, StorageService};
 use lib_dispatch::prelude::af_spawn;
 
@@ -60,6 +62,7 @@ pub struct DocumentManager {
   cloud_service: Arc<dyn DocumentCloudService>,
   storage_service: Weak<dyn StorageService>,
   snapshot_service: Arc<dyn DocumentSnapshotService>,
+  document_indexer: Arc<dyn ContentIndexManager>,
 }
 
 impl DocumentManager {
@@ -69,6 +72,7 @@ impl DocumentManager {
     cloud_service: Arc<dyn DocumentCloudService>,
     storage_service: Weak<dyn StorageService>,
     snapshot_service: Arc<dyn DocumentSnapshotService>,
+    document_indexer: Arc<dyn ContentIndexManager>,
   ) -> Self {
     Self {
       user_service,
@@ -78,6 +82,7 @@ impl DocumentManager {
       cloud_service,
       storage_service,
       snapshot_service,
+      document_indexer,
     }
   }
 
@@ -250,6 +255,13 @@ impl DocumentManager {
             subscribe_document_changed(doc_id, &mut lock);
             subscribe_document_snapshot_state(&lock);
             subscribe_document_sync_state(&lock);
+            subscribe_document_index(
+              doc_id,
+              &self.user_service.workspace_id()?,
+              &mut lock,
+              Arc::downgrade(&document),
+              self.document_indexer.clone(),
+            );
           }
           self.documents.insert(doc_id.to_string(), document.clone());
         }
@@ -338,9 +350,22 @@ impl DocumentManager {
       // When deleting a document, we need to remove it from the cache.
       self.documents.remove(doc_id);
     }
+    self.document_indexer.remove_object(doc_id)?;
     Ok(())
   }
 
+  /// Indexes the blocks of the document for the local search, unless the document is already
+  /// indexed or doesn't exist on the local disk. The opened documents keep their index current.
+  pub async fn index_document_if_needed(&self, doc_id: &str) -> FlowyResult<()> {
+    if self.document_indexer.is_object_indexed(doc_id) || !self.is_doc_exist(doc_id).await? {
+      return Ok(());
+    }
+
+    let data = self.get_document_data(doc_id).await?;
+    let contents = document_block_contents(doc_id, &self.user_service.workspace_id()?, &data);
+    index_document_blocks(self.document_indexer.clone(), doc_id.to_string(), contents).await
+  }
+
   #[instrument(level = "debug", skip_all, err)]
   pub async fn set_document_awareness_local_state(
     &self,
@@ -424,6 +449,14 @@ impl DocumentManager {
     Ok(())
   }
 
+  /// Returns the content of a file uploaded to the file storage, for example, an image of a
+  /// document.
+  pub async fn get_file(&self, url: String) -> FlowyResult<Vec<u8>> {
+    let storage_service = self.storage_service_upgrade()?;
+    let object = storage_service.get_object(url).await?;
+    Ok(object.raw.to_vec())
+  }
+
   async fn is_doc_exist(&self, doc_id: &str) -> FlowyResult<bool> {
     let uid = self.user_service.user_id()?;
     if let Some(collab_db) = self.user_service.collab_db(uid)?.upgrade() {
@@ -12,6 +12,10 @@ pub const HEIGHT: &str = "height";
 pub const URL: &str = "url";
 pub const CAPTION: &str = "caption";
 pub const ALIGN: &str = "align";
+pub const ROWS_LEN: &str = "rowsLen";
+pub const COLS_LEN: &str = "colsLen";
+pub const ROW_POSITION: &str = "rowPosition";
+pub const COL_POSITION: &str = "colPosition";
 
 pub const PAGE: &str = "page";
 pub const HEADING: &str = "heading";
@@ -25,6 +29,8 @@ pub const CALLOUT: &str = "callout";
 pub const IMAGE: &str = "image";
 pub const DIVIDER: &str = "divider";
 pub const MATH_EQUATION: &str = "math_equation";
+pub const TABLE: &str = "table";
+pub const TABLE_CELL: &str = "table/cell";
 pub const BOLD: &str = "bold";
 pub const ITALIC: &str = "italic";
 pub const STRIKETHROUGH: &str = "strikethrough";
@@ -35,6 +41,12 @@ pub const BG_COLOR: &str = "bg_color";
 
 pub const FORMULA: &str = "formula";
 pub const MENTION: &str = "mention";
+pub const MENTION_TYPE: &str = "type";
+pub const MENTION_PAGE_ID: &str = "page_id";
+pub const MENTION_DATE: &str = "date";
+pub const MENTION_TYPE_PAGE: &str = "page";
+pub const MENTION_TYPE_CHILD_PAGE: &str = "childPage";
+pub const MENTION_TYPE_DATE: &str = "date";
 
 pub const TEXT_DIRECTION: &str = "text_direction";
 
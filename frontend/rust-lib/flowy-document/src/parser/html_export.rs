@@ -0,0 +1,150 @@
+use crate::parser::constant::*;
+use crate::parser::parser_entities::{InsertDelta, NestedBlock};
+use crate::parser::utils::convert_insert_delta_from_json;
+use lib_infra::util::escape_html;
+use serde_json::Value;
+
+/// A page mentioned in a document that is exported as a static page.
+pub struct MentionedPage {
+  /// The name of the page, which replaces the mention character.
+  pub name: String,
+  /// The link to the exported page, `None` if the page is not part of the export.
+  pub href: Option<String>,
+}
+
+/// Returns the urls of the images of the block and of its descendants.
+pub fn image_urls(block: &NestedBlock) -> Vec<String> {
+  let mut urls = vec![];
+  if block.ty == IMAGE {
+    if let Some(url) = block.data.get(URL).and_then(|url| url.as_str()) {
+      if !url.is_empty() {
+        urls.push(url.to_string());
+      }
+    }
+  }
+  for child in &block.children {
+    urls.extend(image_urls(child));
+  }
+  urls
+}
+
+/// Prepares the block and its descendants to be converted to a standalone HTML page: the text is
+/// escaped, the mentions of the pages become links to the exported pages, and the urls of the
+/// images are replaced by the links returned by `asset_href`.
+pub fn prepare_html_export<P, A>(block: &mut NestedBlock, mentioned_page: &P, asset_href: &A)
+where
+  P: Fn(&str) -> Option<MentionedPage>,
+  A: Fn(&str) -> Option<String>,
+{
+  match block.ty.as_str() {
+    IMAGE => {
+      let url = block
+        .data
+        .get(URL)
+        .and_then(|url| url.as_str())
+        .unwrap_or_default();
+      let url = asset_href(url).unwrap_or_else(|| url.to_string());
+      block
+        .data
+        .insert(URL.to_string(), Value::String(escape_html(&url)));
+    },
+    MATH_EQUATION => {
+      if let Some(formula) = block.data.get(FORMULA).and_then(|formula| formula.as_str()) {
+        let formula = escape_html(formula);
+        block
+          .data
+          .insert(FORMULA.to_string(), Value::String(formula));
+      }
+    },
+    _ => {},
+  }
+
+  if let Some(delta) = block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+  {
+    let delta = delta
+      .into_iter()
+      .map(|insert| prepare_insert(insert, mentioned_page))
+      .collect::<Vec<_>>();
+    if let Ok(delta) = serde_json::to_value(delta) {
+      block.data.insert(DELTA.to_string(), delta);
+    }
+  }
+
+  for child in &mut block.children {
+    prepare_html_export(child, mentioned_page, asset_href);
+  }
+}
+
+fn prepare_insert<P>(mut insert: InsertDelta, mentioned_page: &P) -> InsertDelta
+where
+  P: Fn(&str) -> Option<MentionedPage>,
+{
+  let mut attributes = match insert.attributes.take() {
+    None => {
+      insert.insert = escape_html(&insert.insert);
+      return insert;
+    },
+    Some(attributes) => attributes,
+  };
+
+  if let Some(mention) = attributes.remove(MENTION) {
+    let mention_type = mention.get(MENTION_TYPE).and_then(|ty| ty.as_str());
+    insert.insert = match mention_type {
+      Some(MENTION_TYPE_PAGE) | Some(MENTION_TYPE_CHILD_PAGE) => {
+        let page = mention
+          .get(MENTION_PAGE_ID)
+          .and_then(|page_id| page_id.as_str())
+          .and_then(mentioned_page);
+        match page {
+          Some(page) => {
+            if let Some(href) = page.href {
+              attributes.insert(HREF.to_string(), Value::String(href));
+            }
+            page.name
+          },
+          None => String::new(),
+        }
+      },
+      // The date is stored in the ISO 8601 format, only the day is shown
+      Some(MENTION_TYPE_DATE) => mention
+        .get(MENTION_DATE)
+        .and_then(|date| date.as_str())
+        .map(|date| date.chars().take(10).collect())
+        .unwrap_or_default(),
+      _ => String::new(),
+    };
+  }
+
+  if let Some(href) = attributes.remove(HREF) {
+    // Links that could run scripts in the exported page, like `javascript:`, are dropped and
+    // their text is kept.
+    if let Some(href) = href.as_str().filter(|href| is_safe_href(href)) {
+      attributes.insert(HREF.to_string(), Value::String(escape_html(href)));
+    }
+  }
+  insert.insert = escape_html(&insert.insert);
+  insert.attributes = Some(attributes);
+  insert
+}
+
+/// Returns true if the link is relative or uses the http(s) or mailto scheme.
+fn is_safe_href(href: &str) -> bool {
+  // Browsers ignore the whitespace and the control characters in a scheme, so `java\tscript:` is
+  // the same as `javascript:`.
+  let href = href
+    .chars()
+    .filter(|c| !c.is_whitespace() && !c.is_control())
+    .collect::<String>()
+    .to_lowercase();
+  match href.find(':') {
+    // A colon after a path, query or fragment delimiter isn't part of a scheme
+    Some(colon) if !href[..colon].contains(['/', '?', '#']) => {
+      let scheme = &href[..colon];
+      scheme == "http" || scheme == "https" || scheme == "mailto"
+    },
+    _ => true,
+  }
+}
@@ -0,0 +1,6 @@
+pub mod parser;
+pub mod serializer;
+
+/// The mentions are written as links whose url starts with this prefix, followed by the fields
+/// of the mention as query parameters.
+pub const MENTION_URL_PREFIX: &str = "appflowy://mention?";
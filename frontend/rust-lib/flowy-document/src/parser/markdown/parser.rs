@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use ::markdown::mdast::{ListItem, Node};
+use ::markdown::{Constructs, ParseOptions};
+use serde_json::{json, Map, Value};
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::entities::DocumentDataPB;
+use crate::parser::constant::*;
+use crate::parser::json::parser::JsonToDocumentParser;
+use crate::parser::markdown::MENTION_URL_PREFIX;
+use crate::parser::parser_entities::{InsertDelta, NestedBlock};
+
+type Attributes = HashMap<String, Value>;
+
+/// Parses CommonMark with the GitHub Flavored Markdown extensions and the math syntax, which is
+/// the format written by [nested_block_to_markdown](super::serializer::nested_block_to_markdown).
+pub struct MarkdownToDocumentParser;
+
+impl MarkdownToDocumentParser {
+  pub fn markdown_to_document(markdown: &str) -> FlowyResult<DocumentDataPB> {
+    let root = Self::markdown_to_nested_block(markdown)?;
+    let json = serde_json::to_string(&root)?;
+    JsonToDocumentParser::json_str_to_document(&json)
+  }
+
+  /// Format to nested block.
+  ///
+  /// Example:
+  /// - input markdown: `**Hello** World!`
+  /// - output json:
+  /// ```json
+  /// { "type": "page", "data": {}, "children": [{ "type": "paragraph", "children": [], "data": { "delta": [{ "insert": "Hello", attributes: { "bold": true } }, { "insert": " World!", attributes: null }] } }] }
+  /// ```
+  pub fn markdown_to_nested_block(markdown: &str) -> FlowyResult<NestedBlock> {
+    let options = ParseOptions {
+      constructs: Constructs {
+        math_flow: true,
+        math_text: true,
+        // The urls in the text stay plain text, as they were written
+        gfm_autolink_literal: false,
+        ..Constructs::gfm()
+      },
+      ..ParseOptions::gfm()
+    };
+    let root = ::markdown::to_mdast(markdown, &options)
+      .map_err(|err| FlowyError::invalid_data().with_context(err.to_string()))?;
+    let children = root
+      .children()
+      .map(|nodes| blocks(nodes))
+      .unwrap_or_default();
+    Ok(NestedBlock::new(PAGE.to_string(), HashMap::new(), children))
+  }
+}
+
+fn blocks(nodes: &[Node]) -> Vec<NestedBlock> {
+  let mut blocks = vec![];
+  for node in nodes {
+    match node {
+      Node::Heading(heading) => {
+        let mut data = text_data(&heading.children);
+        data.insert(LEVEL.to_string(), json!(heading.depth));
+        blocks.push(NestedBlock::new(HEADING.to_string(), data, vec![]));
+      },
+      Node::Paragraph(paragraph) => match paragraph.children.as_slice() {
+        [Node::Image(image)] => {
+          let data = HashMap::from([(URL.to_string(), json!(image.url))]);
+          blocks.push(NestedBlock::new(IMAGE.to_string(), data, vec![]));
+        },
+        [Node::Html(html)] if is_line_break(&html.value) => blocks.push(empty_paragraph()),
+        children => blocks.push(NestedBlock::new(
+          PARAGRAPH.to_string(),
+          text_data(children),
+          vec![],
+        )),
+      },
+      // The empty paragraphs are written as line breaks
+      Node::Html(html) if is_line_break(&html.value) => blocks.push(empty_paragraph()),
+      Node::Html(html) => {
+        let data = delta_data(vec![InsertDelta {
+          insert: html.value.clone(),
+          attributes: None,
+        }]);
+        blocks.push(NestedBlock::new(PARAGRAPH.to_string(), data, vec![]));
+      },
+      Node::Code(code) => {
+        let mut delta = vec![];
+        push_insert(&mut delta, &code.value, &Attributes::new());
+        let mut data = delta_data(delta);
+        if let Some(language) = &code.lang {
+          data.insert(LANGUAGE.to_string(), json!(language));
+        }
+        blocks.push(NestedBlock::new(CODE.to_string(), data, vec![]));
+      },
+      Node::Math(math) => {
+        let data = HashMap::from([(FORMULA.to_string(), json!(math.value))]);
+        blocks.push(NestedBlock::new(MATH_EQUATION.to_string(), data, vec![]));
+      },
+      Node::ThematicBreak(_) => {
+        blocks.push(NestedBlock::new(
+          DIVIDER.to_string(),
+          HashMap::new(),
+          vec![],
+        ));
+      },
+      // The first paragraph of the quote is its text, the other blocks are its children
+      Node::Blockquote(quote) => {
+        let (data, children) = text_data_and_children(&quote.children);
+        blocks.push(NestedBlock::new(QUOTE.to_string(), data, children));
+      },
+      Node::List(list) => {
+        for (i, item) in list.children.iter().enumerate() {
+          if let Node::ListItem(item) = item {
+            blocks.push(list_item(
+              item,
+              list.ordered,
+              (i == 0).then_some(list.start).flatten(),
+            ));
+          }
+        }
+      },
+      Node::Table(table) => blocks.push(table_block(&table.children)),
+      _ => {},
+    }
+  }
+  blocks
+}
+
+fn list_item(item: &ListItem, ordered: bool, start: Option<u32>) -> NestedBlock {
+  let (mut data, children) = text_data_and_children(&item.children);
+  let ty = if ordered {
+    // The number of the first item is the start of the list
+    if let Some(start) = start.filter(|start| *start != 1) {
+      data.insert(NUMBER.to_string(), json!(start));
+    }
+    NUMBERED_LIST
+  } else if let Some(checked) = item.checked {
+    data.insert(CHECKED.to_string(), json!(checked));
+    TODO_LIST
+  } else {
+    BULLETED_LIST
+  };
+  NestedBlock::new(ty.to_string(), data, children)
+}
+
+/// The cells of the blocks of the tables are ordered by column.
+fn table_block(rows: &[Node]) -> NestedBlock {
+  let rows = rows
+    .iter()
+    .filter_map(|row| match row {
+      Node::TableRow(row) => Some(&row.children),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  let cols_len = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+  let mut cells = vec![];
+  for col in 0..cols_len {
+    for (row, cols) in rows.iter().enumerate() {
+      let data = match cols.get(col) {
+        Some(Node::TableCell(cell)) => text_data(&cell.children),
+        _ => delta_data(vec![]),
+      };
+      let paragraph = NestedBlock::new(PARAGRAPH.to_string(), data, vec![]);
+      let data = HashMap::from([
+        (ROW_POSITION.to_string(), json!(row)),
+        (COL_POSITION.to_string(), json!(col)),
+      ]);
+      cells.push(NestedBlock::new(
+        TABLE_CELL.to_string(),
+        data,
+        vec![paragraph],
+      ));
+    }
+  }
+
+  let data = HashMap::from([
+    (ROWS_LEN.to_string(), json!(rows.len())),
+    (COLS_LEN.to_string(), json!(cols_len)),
+  ]);
+  NestedBlock::new(TABLE.to_string(), data, cells)
+}
+
+fn text_data_and_children(nodes: &[Node]) -> (HashMap<String, Value>, Vec<NestedBlock>) {
+  match nodes.split_first() {
+    Some((Node::Paragraph(paragraph), rest)) => (text_data(&paragraph.children), blocks(rest)),
+    _ => (delta_data(vec![]), blocks(nodes)),
+  }
+}
+
+fn empty_paragraph() -> NestedBlock {
+  NestedBlock::new(PARAGRAPH.to_string(), delta_data(vec![]), vec![])
+}
+
+fn text_data(nodes: &[Node]) -> HashMap<String, Value> {
+  let mut delta = vec![];
+  inline_nodes(nodes, &mut Attributes::new(), &mut delta);
+  delta_data(delta)
+}
+
+fn delta_data(delta: Vec<InsertDelta>) -> HashMap<String, Value> {
+  let delta = serde_json::to_value(delta).unwrap_or_else(|_| json!([]));
+  HashMap::from([(DELTA.to_string(), delta)])
+}
+
+fn is_line_break(html: &str) -> bool {
+  matches!(
+    html.trim().to_lowercase().as_str(),
+    "<br>" | "<br/>" | "<br />"
+  )
+}
+
+/// Converts the inline nodes to inserts. The HTML tags of the marks are siblings of the nodes they
+/// apply to, so they update `attributes` for the following nodes.
+fn inline_nodes(nodes: &[Node], attributes: &mut Attributes, delta: &mut Vec<InsertDelta>) {
+  for node in nodes {
+    match node {
+      Node::Text(text) => push_insert(delta, &text.value, attributes),
+      Node::Break(_) => push_insert(delta, "\n", attributes),
+      Node::Strong(strong) => marked_nodes(&strong.children, attributes, BOLD, delta),
+      Node::Emphasis(emphasis) => marked_nodes(&emphasis.children, attributes, ITALIC, delta),
+      Node::Delete(delete) => marked_nodes(&delete.children, attributes, STRIKETHROUGH, delta),
+      Node::InlineCode(code) => {
+        let mut attributes = attributes.clone();
+        attributes.insert(CODE.to_string(), json!(true));
+        push_insert(delta, &code.value, &attributes);
+      },
+      Node::InlineMath(math) => {
+        let mut attributes = attributes.clone();
+        attributes.insert(FORMULA.to_string(), json!(math.value));
+        push_insert(delta, "$", &attributes);
+      },
+      Node::Link(link) => match link.url.strip_prefix(MENTION_URL_PREFIX) {
+        Some(query) => {
+          let mut attributes = attributes.clone();
+          attributes.insert(MENTION.to_string(), mention(query));
+          push_insert(delta, "$", &attributes);
+        },
+        None => {
+          let mut attributes = attributes.clone();
+          attributes.insert(HREF.to_string(), json!(link.url));
+          inline_nodes(&link.children, &mut attributes, delta);
+        },
+      },
+      // The images can't be inside a text, they become links
+      Node::Image(image) => {
+        let mut attributes = attributes.clone();
+        attributes.insert(HREF.to_string(), json!(image.url));
+        let text = if image.alt.is_empty() {
+          &image.url
+        } else {
+          &image.alt
+        };
+        push_insert(delta, text, &attributes);
+      },
+      Node::Html(html) => {
+        let tag = html.value.trim().to_lowercase();
+        if is_line_break(&tag) {
+          push_insert(delta, "\n", attributes);
+          continue;
+        }
+        let (name, is_closing) = match tag.strip_prefix("</").and_then(|tag| tag.strip_suffix('>'))
+        {
+          Some(name) => (name.to_string(), true),
+          None => (
+            tag
+              .trim_start_matches('<')
+              .trim_end_matches('>')
+              .to_string(),
+            false,
+          ),
+        };
+        let key = match name.as_str() {
+          STRONG_TAG_NAME | B_TAG_NAME => Some(BOLD),
+          EM_TAG_NAME | I_TAG_NAME => Some(ITALIC),
+          S_TAG_NAME | DEL_TAG_NAME => Some(STRIKETHROUGH),
+          U_TAG_NAME => Some(UNDERLINE),
+          _ => None,
+        };
+        match key {
+          Some(key) if is_closing => {
+            attributes.remove(key);
+          },
+          Some(key) => {
+            attributes.insert(key.to_string(), json!(true));
+          },
+          // The other tags are kept as text
+          None => push_insert(delta, &html.value, attributes),
+        }
+      },
+      node => {
+        if let Some(children) = node.children() {
+          inline_nodes(children, attributes, delta);
+        }
+      },
+    }
+  }
+}
+
+fn marked_nodes(nodes: &[Node], attributes: &Attributes, key: &str, delta: &mut Vec<InsertDelta>) {
+  let mut attributes = attributes.clone();
+  attributes.insert(key.to_string(), json!(true));
+  inline_nodes(nodes, &mut attributes, delta);
+}
+
+/// Appends the text to the delta, merging it with the last insert if they have the same attributes.
+fn push_insert(delta: &mut Vec<InsertDelta>, text: &str, attributes: &Attributes) {
+  if text.is_empty() {
+    return;
+  }
+  let attributes = (!attributes.is_empty()).then(|| attributes.clone());
+  match delta.last_mut() {
+    // The mentions and the formulas are single characters that can't be merged
+    Some(last)
+      if last.attributes == attributes
+        && !attributes.as_ref().is_some_and(|attributes| {
+          attributes.contains_key(MENTION) || attributes.contains_key(FORMULA)
+        }) =>
+    {
+      last.insert.push_str(text);
+    },
+    _ => delta.push(InsertDelta {
+      insert: text.to_string(),
+      attributes,
+    }),
+  }
+}
+
+/// The fields of the mention are the parameters of the query of its url.
+fn mention(query: &str) -> Value {
+  let mention = url::form_urlencoded::parse(query.as_bytes())
+    .map(|(key, value)| {
+      let value = match value.as_ref() {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => Value::String(value.into_owned()),
+      };
+      (key.into_owned(), value)
+    })
+    .collect::<Map<_, _>>();
+  Value::Object(mention)
+}
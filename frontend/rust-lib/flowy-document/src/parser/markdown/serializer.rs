@@ -0,0 +1,712 @@
+use serde_json::Value;
+
+use crate::parser::constant::*;
+use crate::parser::markdown::MENTION_URL_PREFIX;
+use crate::parser::parser_entities::{InsertDelta, NestedBlock};
+use crate::parser::utils::convert_insert_delta_from_json;
+
+/// The character used in place of the delimiters of the marks when their inner content is written
+/// before the delimiters are chosen. All the delimiters start and end with a punctuation.
+const DELIMITER_PLACEHOLDER: char = '*';
+
+/// Converts the block and its descendants to CommonMark with the GitHub Flavored Markdown
+/// extensions (tables, task lists and strikethrough) and the math syntax.
+///
+/// Example:
+/// - input json: { "type": "page", "children": [{ "type": "heading", "data": { "level": 1, "delta": [{ "insert": "Hello" }] } }] }
+/// - output markdown: `# Hello`
+///
+/// The conversion is lossless for the blocks and the attributes that have a Markdown equivalent.
+/// The others are converted to the closest equivalent: the callouts become quotes, the toggle lists
+/// become bulleted lists, the children of the paragraphs and of the headings are written after them,
+/// and the colors and the alignments are dropped.
+pub fn nested_block_to_markdown(root: &NestedBlock) -> String {
+  let markdown = if root.ty == PAGE {
+    // The root of a range of the document may contain the text of the first block of the range
+    let text = block_text(root, InlineContext::Block);
+    join_non_empty(text, blocks_to_markdown(&root.children))
+  } else {
+    blocks_to_markdown(std::slice::from_ref(root))
+  };
+  if markdown.is_empty() {
+    markdown
+  } else {
+    format!("{}\n", markdown)
+  }
+}
+
+fn blocks_to_markdown(blocks: &[NestedBlock]) -> String {
+  let mut markdown = String::new();
+  let mut number = 1;
+  for (i, block) in blocks.iter().enumerate() {
+    let prev = i.checked_sub(1).map(|i| &blocks[i]);
+    // The items of the same list are separated by a line break, so the list stays tight
+    let same_list =
+      prev.is_some_and(|prev| list_kind(prev).is_some() && list_kind(prev) == list_kind(block));
+    if prev.is_some() {
+      markdown.push_str(if same_list { "\n" } else { "\n\n" });
+    }
+    if block.ty == NUMBERED_LIST {
+      number = if same_list {
+        number + 1
+      } else {
+        block
+          .data
+          .get(NUMBER)
+          .and_then(|number| number.as_u64())
+          .unwrap_or(1)
+      };
+    }
+    markdown.push_str(&block_to_markdown(block, number));
+  }
+  markdown
+}
+
+/// The numbered lists and the other lists use different markers, so they can't share a list.
+fn list_kind(block: &NestedBlock) -> Option<bool> {
+  match block.ty.as_str() {
+    NUMBERED_LIST => Some(true),
+    BULLETED_LIST | TODO_LIST | TOGGLE_LIST => Some(false),
+    _ => None,
+  }
+}
+
+fn block_to_markdown(block: &NestedBlock, number: u64) -> String {
+  match block.ty.as_str() {
+    HEADING => {
+      let level = block
+        .data
+        .get(LEVEL)
+        .and_then(|level| level.as_u64())
+        .unwrap_or(1)
+        .clamp(1, 6) as usize;
+      let text = block_text(block, InlineContext::Heading);
+      let heading = format!("{} {}", "#".repeat(level), text);
+      join_non_empty(
+        heading.trim_end().to_string(),
+        blocks_to_markdown(&block.children),
+      )
+    },
+    BULLETED_LIST | TOGGLE_LIST => list_item_to_markdown(block, "- ", String::new()),
+    TODO_LIST => {
+      let checked = block
+        .data
+        .get(CHECKED)
+        .and_then(|checked| checked.as_bool())
+        .unwrap_or_default();
+      let task = if checked { "[x] " } else { "[ ] " };
+      list_item_to_markdown(block, "- ", task.to_string())
+    },
+    NUMBERED_LIST => list_item_to_markdown(block, &format!("{}. ", number), String::new()),
+    QUOTE | CALLOUT => {
+      let mut text = block_text(block, InlineContext::Block);
+      if block.ty == CALLOUT {
+        if let Some(icon) = block.data.get(ICON).and_then(|icon| icon.as_str()) {
+          if !icon.is_empty() {
+            text = format!("{} {}", icon, text);
+          }
+        }
+      }
+      let content = join_non_empty(text, blocks_to_markdown(&block.children));
+      prefix_lines(&content, "> ", ">")
+    },
+    CODE => {
+      let language = block
+        .data
+        .get(LANGUAGE)
+        .and_then(|language| language.as_str())
+        .unwrap_or_default();
+      let code = block_plain_text(block);
+      let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+      if code.is_empty() {
+        format!("{}{}\n{}", fence, language, fence)
+      } else {
+        format!("{}{}\n{}\n{}", fence, language, code, fence)
+      }
+    },
+    MATH_EQUATION => {
+      let formula = block
+        .data
+        .get(FORMULA)
+        .and_then(|formula| formula.as_str())
+        .unwrap_or_default();
+      if formula.is_empty() {
+        "$$\n$$".to_string()
+      } else {
+        format!("$$\n{}\n$$", formula)
+      }
+    },
+    DIVIDER => "---".to_string(),
+    IMAGE => {
+      let url = block
+        .data
+        .get(URL)
+        .and_then(|url| url.as_str())
+        .unwrap_or_default();
+      format!("![]({})", link_destination(url, InlineContext::Block))
+    },
+    TABLE => table_to_markdown(block),
+    _ => {
+      // An empty paragraph would be dropped, so it is written as a line break
+      let mut text = block_text(block, InlineContext::Block);
+      if text.is_empty() {
+        text = "<br>".to_string();
+      }
+      join_non_empty(text, blocks_to_markdown(&block.children))
+    },
+  }
+}
+
+fn list_item_to_markdown(block: &NestedBlock, marker: &str, task: String) -> String {
+  let indent = " ".repeat(marker.len());
+  let text = format!("{}{}", task, block_text(block, InlineContext::Block));
+  let mut markdown = format!("{}{}", marker, text.replace('\n', &format!("\n{}", indent)));
+  markdown.truncate(markdown.trim_end().len());
+  if let Some(first_child) = block.children.first() {
+    // A nested list can follow the text directly, the other blocks need a blank line
+    markdown.push_str(if list_kind(first_child).is_some() {
+      "\n"
+    } else {
+      "\n\n"
+    });
+    markdown.push_str(&prefix_lines(
+      &blocks_to_markdown(&block.children),
+      &indent,
+      "",
+    ));
+  }
+  markdown
+}
+
+/// Writes the table in the GitHub Flavored Markdown syntax, whose first row is the header.
+fn table_to_markdown(block: &NestedBlock) -> String {
+  let position = |cell: &NestedBlock, key: &str| {
+    cell
+      .data
+      .get(key)
+      .and_then(|position| position.as_u64())
+      .map(|position| position as usize)
+  };
+  let cells = block
+    .children
+    .iter()
+    .filter(|cell| cell.ty == TABLE_CELL)
+    .filter_map(|cell| {
+      let row = position(cell, ROW_POSITION)?;
+      let col = position(cell, COL_POSITION)?;
+      Some((row, col, cell))
+    })
+    .collect::<Vec<_>>();
+  // The grid is sized from the cells that exist. The stored lengths can't be trusted to allocate
+  // the grid, but they still drop the cells that were left behind by removed rows or columns.
+  let len = |key: &str| {
+    block
+      .data
+      .get(key)
+      .and_then(|len| len.as_u64())
+      .map(|len| len as usize)
+      .unwrap_or(usize::MAX)
+  };
+  let (max_rows, max_cols) = (len(ROWS_LEN), len(COLS_LEN));
+  let cells = cells
+    .into_iter()
+    .filter(|(row, col, _)| *row < max_rows && *col < max_cols)
+    .collect::<Vec<_>>();
+  let rows_len = cells.iter().map(|(row, _, _)| row + 1).max().unwrap_or(0);
+  let cols_len = cells.iter().map(|(_, col, _)| col + 1).max().unwrap_or(0);
+  if rows_len == 0 || cols_len == 0 {
+    return String::new();
+  }
+
+  let mut rows = vec![vec![String::new(); cols_len]; rows_len];
+  for (row, col, cell) in cells {
+    rows[row][col] = cell
+      .children
+      .iter()
+      .map(|child| block_text(child, InlineContext::TableCell))
+      .collect::<Vec<_>>()
+      .join("<br>");
+  }
+
+  let mut lines = rows
+    .iter()
+    .map(|row| format!("| {} |", row.join(" | ")))
+    .collect::<Vec<_>>();
+  lines.insert(1, format!("|{}", " --- |".repeat(cols_len)));
+  lines.join("\n")
+}
+
+fn join_non_empty(first: String, second: String) -> String {
+  match (first.is_empty(), second.is_empty()) {
+    (_, true) => first,
+    (true, false) => second,
+    (false, false) => format!("{}\n\n{}", first, second),
+  }
+}
+
+fn prefix_lines(text: &str, prefix: &str, empty_line_prefix: &str) -> String {
+  text
+    .split('\n')
+    .map(|line| {
+      if line.is_empty() {
+        empty_line_prefix.to_string()
+      } else {
+        format!("{}{}", prefix, line)
+      }
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+  let mut longest = 0;
+  let mut run = 0;
+  for char in text.chars() {
+    if char == c {
+      run += 1;
+      longest = longest.max(run);
+    } else {
+      run = 0;
+    }
+  }
+  longest
+}
+
+fn block_delta(block: &NestedBlock) -> Vec<InsertDelta> {
+  block
+    .data
+    .get(DELTA)
+    .and_then(convert_insert_delta_from_json)
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|insert| !insert.insert.is_empty())
+    .collect()
+}
+
+fn block_plain_text(block: &NestedBlock) -> String {
+  block_delta(block)
+    .into_iter()
+    .map(|insert| insert.insert)
+    .collect()
+}
+
+fn block_text(block: &NestedBlock, context: InlineContext) -> String {
+  let delta = block_delta(block);
+  let mut writer = InlineWriter::new(true);
+  write_inline(&mut writer, &delta, 0, None, None, context);
+  writer.text
+}
+
+/// Where the text is written, which decides how the line breaks and the pipes are written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InlineContext {
+  Block,
+  /// The headings and the cells of the tables must fit on one line.
+  Heading,
+  TableCell,
+}
+
+impl InlineContext {
+  fn is_single_line(&self) -> bool {
+    !matches!(self, InlineContext::Block)
+  }
+}
+
+struct InlineWriter {
+  text: String,
+  /// Whether the text starts at the start of a line.
+  starts_line: bool,
+}
+
+impl InlineWriter {
+  fn new(starts_line: bool) -> Self {
+    Self {
+      text: String::new(),
+      starts_line,
+    }
+  }
+
+  fn at_line_start(&self) -> bool {
+    if self.text.is_empty() {
+      self.starts_line
+    } else {
+      self.text.ends_with('\n')
+    }
+  }
+}
+
+/// The marks are nested in this order, the first one being the outermost.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+  Link,
+  Bold,
+  Italic,
+  Strikethrough,
+  Underline,
+}
+
+const MARKS: [Mark; 5] = [
+  Mark::Link,
+  Mark::Bold,
+  Mark::Italic,
+  Mark::Strikethrough,
+  Mark::Underline,
+];
+
+impl Mark {
+  /// Returns the value of the mark in the insert, `None` if the insert doesn't have the mark.
+  fn value(&self, insert: &InsertDelta) -> Option<String> {
+    let attributes = insert.attributes.as_ref()?;
+    let key = match self {
+      Mark::Link => {
+        // The mentions are written as links, so they can't be in a link
+        if attributes.contains_key(MENTION) {
+          return None;
+        }
+        return attributes
+          .get(HREF)
+          .and_then(|href| href.as_str())
+          .map(|href| href.to_string());
+      },
+      Mark::Bold => BOLD,
+      Mark::Italic => ITALIC,
+      Mark::Strikethrough => STRIKETHROUGH,
+      Mark::Underline => UNDERLINE,
+    };
+    match attributes.get(key).and_then(|value| value.as_bool()) {
+      Some(true) => Some(String::new()),
+      _ => None,
+    }
+  }
+
+  /// Wraps the content of the mark, `before` and `after` being the characters around it.
+  ///
+  /// The emphasis delimiters only work when they are flanking the content, as defined by
+  /// CommonMark, otherwise the equivalent HTML tags are used.
+  fn wrap(
+    &self,
+    value: &str,
+    content: &str,
+    before: Option<char>,
+    after: Option<char>,
+    context: InlineContext,
+  ) -> String {
+    let delimited = |delimiter: &str| format!("{}{}{}", delimiter, content, delimiter);
+    let tagged = |tag: &str| format!("<{}>{}</{}>", tag, content, tag);
+    match self {
+      Mark::Link => format!("[{}]({})", content, link_destination(value, context)),
+      Mark::Bold if can_delimit(content, '*', before, after) => delimited("**"),
+      Mark::Bold => tagged(STRONG_TAG_NAME),
+      Mark::Italic if can_delimit(content, '_', before, after) => delimited("_"),
+      Mark::Italic if can_delimit(content, '*', before, after) => delimited("*"),
+      Mark::Italic => tagged(EM_TAG_NAME),
+      Mark::Strikethrough if can_delimit(content, '~', before, after) => delimited("~~"),
+      Mark::Strikethrough => tagged(S_TAG_NAME),
+      Mark::Underline => tagged(U_TAG_NAME),
+    }
+  }
+}
+
+fn is_whitespace(c: Option<char>) -> bool {
+  c.map_or(true, |c| c.is_whitespace())
+}
+
+fn is_punctuation(c: Option<char>) -> bool {
+  c.is_some_and(|c| c.is_ascii_punctuation() || (!c.is_alphanumeric() && !c.is_whitespace()))
+}
+
+fn is_left_flanking(before: Option<char>, after: Option<char>) -> bool {
+  !is_whitespace(after)
+    && (!is_punctuation(after) || is_whitespace(before) || is_punctuation(before))
+}
+
+fn is_right_flanking(before: Option<char>, after: Option<char>) -> bool {
+  !is_whitespace(before)
+    && (!is_punctuation(before) || is_whitespace(after) || is_punctuation(after))
+}
+
+/// Whether the delimiters made of `delimiter` can open and close the emphasis around `content`.
+fn can_delimit(content: &str, delimiter: char, before: Option<char>, after: Option<char>) -> bool {
+  let first = content.chars().next();
+  let last = content.chars().last();
+  // The delimiters would merge with the ones of the content
+  if first.is_none() || first == Some(delimiter) || last == Some(delimiter) {
+    return false;
+  }
+  let can_open = is_left_flanking(before, first);
+  let can_close = is_right_flanking(last, after);
+  if delimiter == '_' {
+    // The underscores can't be used inside a word
+    can_open
+      && (!is_right_flanking(before, first) || is_punctuation(before))
+      && can_close
+      && (!is_left_flanking(last, after) || is_punctuation(after))
+  } else {
+    can_open && can_close
+  }
+}
+
+/// The text of the delta as it is seen by its neighbours.
+fn display_text(insert: &InsertDelta) -> &str {
+  let attributes = insert.attributes.as_ref();
+  if attributes
+    .is_some_and(|attributes| attributes.contains_key(MENTION) || attributes.contains_key(FORMULA))
+  {
+    "$"
+  } else {
+    &insert.insert
+  }
+}
+
+/// Writes the delta grouping the consecutive inserts that share the mark of `level`, so the marks
+/// spanning several inserts are only written once.
+fn write_inline(
+  writer: &mut InlineWriter,
+  delta: &[InsertDelta],
+  level: usize,
+  before: Option<char>,
+  after: Option<char>,
+  context: InlineContext,
+) {
+  let mark = match MARKS.get(level) {
+    Some(mark) => mark,
+    None => {
+      for (i, insert) in delta.iter().enumerate() {
+        let after = delta
+          .get(i + 1)
+          .and_then(|next| display_text(next).chars().next())
+          .or(after);
+        write_insert(writer, insert, after, context);
+      }
+      return;
+    },
+  };
+
+  let mut groups: Vec<(Option<String>, &[InsertDelta])> = vec![];
+  let mut start = 0;
+  for i in 1..=delta.len() {
+    if i == delta.len() || mark.value(&delta[i]) != mark.value(&delta[start]) {
+      groups.push((mark.value(&delta[start]), &delta[start..i]));
+      start = i;
+    }
+  }
+
+  // The content of each group is written first, the delimiters of the marks depend on the
+  // characters around them.
+  let mut contents: Vec<String> = vec![];
+  let mut starts_line = writer.at_line_start();
+  for (i, (value, inserts)) in groups.iter().enumerate() {
+    let content = if value.is_some() {
+      let mut inner = InlineWriter::new(false);
+      write_inline(
+        &mut inner,
+        inserts,
+        level + 1,
+        Some(DELIMITER_PLACEHOLDER),
+        Some(DELIMITER_PLACEHOLDER),
+        context,
+      );
+      starts_line = false;
+      inner.text
+    } else {
+      let before = match i.checked_sub(1) {
+        None => before,
+        Some(j) if groups[j].0.is_some() => Some(DELIMITER_PLACEHOLDER),
+        Some(j) => contents[j].chars().last().or(before),
+      };
+      let after = match groups.get(i + 1) {
+        None => after,
+        Some((Some(_), _)) => Some(DELIMITER_PLACEHOLDER),
+        Some((None, next)) => next
+          .first()
+          .and_then(|next| display_text(next).chars().next()),
+      };
+      let mut inner = InlineWriter::new(starts_line);
+      write_inline(&mut inner, inserts, level + 1, before, after, context);
+      starts_line = inner.at_line_start();
+      inner.text
+    };
+    contents.push(content);
+  }
+
+  for (i, (value, _)) in groups.iter().enumerate() {
+    match value {
+      None => writer.text.push_str(&contents[i]),
+      Some(value) => {
+        let before = match i.checked_sub(1) {
+          None => before,
+          Some(j) if groups[j].0.is_some() => Some(DELIMITER_PLACEHOLDER),
+          Some(j) => contents[j].chars().last().or(before),
+        };
+        let after = match groups.get(i + 1) {
+          None => after,
+          Some((Some(_), _)) => Some(DELIMITER_PLACEHOLDER),
+          Some((None, _)) => contents[i + 1].chars().next().or(after),
+        };
+        let wrapped = mark.wrap(value, &contents[i], before, after, context);
+        writer.text.push_str(&wrapped);
+      },
+    }
+  }
+}
+
+fn write_insert(
+  writer: &mut InlineWriter,
+  insert: &InsertDelta,
+  after: Option<char>,
+  context: InlineContext,
+) {
+  let attributes = insert.attributes.as_ref();
+  if let Some(mention) = attributes.and_then(|attributes| attributes.get(MENTION)) {
+    writer.text.push_str(&format!(
+      "[\\$]({})",
+      link_destination(&mention_url(mention), context)
+    ));
+    return;
+  }
+  if let Some(formula) = attributes
+    .and_then(|attributes| attributes.get(FORMULA))
+    .and_then(|formula| formula.as_str())
+  {
+    writer.text.push_str(&code_span(formula, '$', context));
+    return;
+  }
+  let is_code = attributes
+    .and_then(|attributes| attributes.get(CODE))
+    .and_then(|code| code.as_bool())
+    .unwrap_or_default();
+  if is_code {
+    writer
+      .text
+      .push_str(&code_span(&insert.insert, '`', context));
+    return;
+  }
+  write_escaped_text(writer, &insert.insert, after, context);
+}
+
+/// Writes the code or the inline math, whose content is not escaped.
+fn code_span(content: &str, fence: char, context: InlineContext) -> String {
+  // The parsers strip one space around the content, and the fence must be longer than the runs
+  // of its character in the content
+  let padding = content.starts_with(fence)
+    || content.ends_with(fence)
+    || (content.starts_with(' ') && content.ends_with(' ') && !content.trim().is_empty());
+  let padding = if padding { " " } else { "" };
+  let fence = fence.to_string().repeat(longest_run(content, fence) + 1);
+  let mut content = content.to_string();
+  if context == InlineContext::TableCell {
+    content = content.replace('|', "\\|");
+  }
+  format!("{}{}{}{}{}", fence, padding, content, padding, fence)
+}
+
+/// Escapes the characters of the text that would be read as Markdown. The whitespaces at the
+/// start and at the end of the lines, which are trimmed by the parsers, are written as
+/// character references.
+fn write_escaped_text(
+  writer: &mut InlineWriter,
+  text: &str,
+  after: Option<char>,
+  context: InlineContext,
+) {
+  let chars = text.chars().collect::<Vec<_>>();
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    let prev = i.checked_sub(1).map(|i| chars[i]);
+    let next = chars.get(i + 1).copied();
+    let at_line_start = writer.at_line_start();
+    let ends_line = match next {
+      Some(next) => next == '\n',
+      None => after.is_none() || after == Some('\n'),
+    };
+    match c {
+      '\n' => {
+        // A backslash at the start or at the end of a paragraph is not a line break
+        if context.is_single_line() || at_line_start || (next.is_none() && after.is_none()) {
+          writer.text.push_str("<br>");
+        } else {
+          writer.text.push_str("\\\n");
+        }
+      },
+      ' ' | '\t' if at_line_start || ends_line => {
+        writer.text.push_str(&format!("&#{};", c as u32));
+      },
+      '\\' | '`' | '*' | '[' | ']' | '<' | '$' | '~' => {
+        writer.text.push('\\');
+        writer.text.push(c);
+      },
+      '_'
+        if !(prev.is_some_and(char::is_alphanumeric)
+          && next.is_some_and(char::is_alphanumeric)) =>
+      {
+        writer.text.push_str("\\_");
+      },
+      '&' if next.map_or(true, |next| next.is_alphanumeric() || next == '#') => {
+        writer.text.push_str("\\&");
+      },
+      '|' if context == InlineContext::TableCell => writer.text.push_str("\\|"),
+      '#' if at_line_start || prev.map_or(true, char::is_whitespace) => {
+        writer.text.push_str("\\#");
+      },
+      '>' | '-' | '+' | '=' if at_line_start => {
+        writer.text.push('\\');
+        writer.text.push(c);
+      },
+      '0'..='9' if at_line_start => {
+        // The numbers followed by a dot or a parenthesis would start an ordered list
+        while i < chars.len() && chars[i].is_ascii_digit() {
+          writer.text.push(chars[i]);
+          i += 1;
+        }
+        if let Some(&punctuation @ ('.' | ')')) = chars.get(i) {
+          writer.text.push('\\');
+          writer.text.push(punctuation);
+          i += 1;
+        }
+        continue;
+      },
+      _ => writer.text.push(c),
+    }
+    i += 1;
+  }
+}
+
+/// The destination of a link, which is enclosed in angle brackets when it contains characters
+/// that would end it.
+fn link_destination(url: &str, context: InlineContext) -> String {
+  let mut destination = url.replace('\\', "\\\\");
+  if context == InlineContext::TableCell {
+    destination = destination.replace('|', "\\|");
+  }
+  if url.is_empty()
+    || url.contains(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | '<' | '>'))
+  {
+    format!("<{}>", destination.replace('<', "\\<").replace('>', "\\>"))
+  } else {
+    destination
+  }
+}
+
+/// The url of the mention, whose query contains the fields of the mention sorted by name.
+fn mention_url(mention: &Value) -> String {
+  let mut fields = mention
+    .as_object()
+    .map(|mention| mention.iter().collect::<Vec<_>>())
+    .unwrap_or_default();
+  fields.sort_by(|a, b| a.0.cmp(b.0));
+
+  let mut query = url::form_urlencoded::Serializer::new(String::new());
+  for (key, value) in fields {
+    match value {
+      Value::Null => {},
+      Value::String(value) => {
+        query.append_pair(key, value);
+      },
+      value => {
+        query.append_pair(key, &value.to_string());
+      },
+    }
+  }
+  format!("{}{}", MENTION_URL_PREFIX, query.finish())
+}
@@ -1,6 +1,8 @@
 pub mod constant;
 pub mod document_data_parser;
 pub mod external;
+pub mod html_export;
 pub mod json;
+pub mod markdown;
 pub mod parser_entities;
 pub mod utils;
@@ -38,6 +38,7 @@ pub struct RangePB {
  * @field json: bool // export json data
  * @field html: bool // export html data
  * @field text: bool // export text data
+ * @field markdown: bool // export markdown data
  */
 #[derive(Default, ProtoBuf, Debug, Clone)]
 pub struct ParseTypePB {
@@ -49,6 +50,9 @@ pub struct ParseTypePB {
 
   #[pb(index = 3)]
   pub text: bool,
+
+  #[pb(index = 4)]
+  pub markdown: bool,
 }
 /**
 * ConvertDocumentPayloadPB
@@ -76,6 +80,8 @@ pub struct ConvertDocumentResponsePB {
   pub html: Option<String>,
   #[pb(index = 3, one_of)]
   pub text: Option<String>,
+  #[pb(index = 4, one_of)]
+  pub markdown: Option<String>,
 }
 
 pub struct Selection {
@@ -93,6 +99,7 @@ pub struct ParseType {
   pub json: bool,
   pub html: bool,
   pub text: bool,
+  pub markdown: bool,
 }
 
 pub struct ConvertDocumentParams {
@@ -103,7 +110,7 @@ pub struct ConvertDocumentParams {
 
 impl ParseType {
   pub fn any_enabled(&self) -> bool {
-    self.json || self.html || self.text
+    self.json || self.html || self.text || self.markdown
   }
 }
 
@@ -132,6 +139,7 @@ impl From<ParseTypePB> for ParseType {
       json: data.json,
       html: data.html,
       text: data.text,
+      markdown: data.markdown,
     }
   }
 }
@@ -503,6 +511,7 @@ pub enum InputType {
   #[default]
   Html = 0,
   PlainText = 1,
+  Markdown = 2,
 }
 
 #[derive(Default, ProtoBuf, Debug, Validate)]
@@ -4,6 +4,8 @@ use collab_document::blocks::{Block, BlockAction, BlockActionPayload, BlockActio
 use collab_document::document_data::{default_document_data, PARAGRAPH_BLOCK_TYPE};
 use serde_json::{json, to_value, Value};
 
+use flowy_document::document::document_block_contents;
+
 use crate::document::util::{gen_document_id, gen_id, DocumentTest};
 
 #[tokio::test]
@@ -228,3 +230,34 @@ async fn document_apply_update_action() {
   // close a document
   _ = test.close_document(&doc_id).await;
 }
+
+#[test]
+fn document_block_contents_test() {
+  let doc_id = gen_document_id();
+  let mut data = default_document_data(&doc_id);
+  let text_id = gen_id();
+  let text_block = Block {
+    id: gen_id(),
+    ty: PARAGRAPH_BLOCK_TYPE.to_string(),
+    parent: data.page_id.clone(),
+    children: gen_id(),
+    external_id: Some(text_id.clone()),
+    external_type: Some("text".to_string()),
+    data: HashMap::new(),
+  };
+  data
+    .blocks
+    .insert(text_block.id.clone(), text_block.clone());
+  data.meta.text_map.get_or_insert_with(HashMap::new).insert(
+    text_id,
+    json!([{ "insert": "Hello " }, { "insert": "world", "attributes": { "bold": true } }])
+      .to_string(),
+  );
+
+  // Only the blocks with text are indexed
+  let contents = document_block_contents(&doc_id, "workspace", &data);
+  assert_eq!(contents.len(), 1);
+  assert_eq!(contents[0].id, text_block.id);
+  assert_eq!(contents[0].object_id, doc_id);
+  assert_eq!(contents[0].content, "Hello world");
+}
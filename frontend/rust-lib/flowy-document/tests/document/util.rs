@@ -21,7 +21,9 @@ use flowy_document::entities::{DocumentSnapshotData, DocumentSnapshotMeta};
 use flowy_document::manager::{DocumentManager, DocumentSnapshotService, DocumentUserService};
 use flowy_document_pub::cloud::*;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_search_pub::entities::{ContentIndexManager, IndexableContent};
 use flowy_storage_pub::chunked_byte::ChunkedBytes;
+use flowy_storage_pub::cloud::ObjectValue;
 use flowy_storage_pub::storage::{CreatedUpload, FileProgressReceiver, StorageService};
 use lib_infra::async_trait::async_trait;
 use lib_infra::box_any::BoxAny;
@@ -50,6 +52,7 @@ impl DocumentTest {
       cloud_service,
       Arc::downgrade(&file_storage),
       document_snapshot,
+      Arc::new(DocumentTestIndexer),
     );
     Self { inner: manager }
   }
@@ -195,6 +198,10 @@ impl StorageService for DocumentTestFileStorageService {
     todo!()
   }
 
+  async fn get_object(&self, _url: String) -> FlowyResult<ObjectValue> {
+    todo!()
+  }
+
   async fn create_upload(
     &self,
     _workspace_id: &str,
@@ -258,6 +265,33 @@ impl DocumentSnapshotService for DocumentTestSnapshot {
   }
 }
 
+struct DocumentTestIndexer;
+impl ContentIndexManager for DocumentTestIndexer {
+  fn index_object(
+    &self,
+    _object_id: &str,
+    _contents: Vec<IndexableContent>,
+  ) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn update_contents(&self, _contents: Vec<IndexableContent>) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn remove_contents(&self, _ids: Vec<String>) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn remove_object(&self, _object_id: &str) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn is_object_indexed(&self, _object_id: &str) -> bool {
+    false
+  }
+}
+
 struct WorkspaceCollabIntegrateImpl {
   workspace_id: String,
 }
@@ -0,0 +1,110 @@
+use flowy_document::parser::html_export::{image_urls, prepare_html_export, MentionedPage};
+use flowy_document::parser::parser_entities::{ConvertBlockToHtmlParams, NestedBlock};
+use serde_json::json;
+
+fn mock_page() -> NestedBlock {
+  serde_json::from_value(json!({
+    "type": "page",
+    "children": [
+      {
+        "type": "paragraph",
+        "data": {
+          "delta": [
+            { "insert": "a < b, see " },
+            { "insert": "$", "attributes": { "mention": { "type": "page", "page_id": "p1" } } },
+            { "insert": " and " },
+            { "insert": "$", "attributes": { "mention": { "type": "page", "page_id": "p2" } } },
+            { "insert": " on " },
+            { "insert": "$", "attributes": { "mention": { "type": "date", "date": "2024-05-01T10:00:00.000" } } }
+          ]
+        }
+      },
+      {
+        "type": "image",
+        "data": { "url": "/tmp/image.png" }
+      },
+      {
+        "type": "image",
+        "data": { "url": "https://example.com/a.png?w=1&h=2" }
+      }
+    ]
+  }))
+  .unwrap()
+}
+
+#[test]
+fn image_urls_test() {
+  let page = mock_page();
+  assert_eq!(
+    image_urls(&page),
+    vec![
+      "/tmp/image.png".to_string(),
+      "https://example.com/a.png?w=1&h=2".to_string()
+    ]
+  );
+}
+
+#[test]
+fn prepare_html_export_test() {
+  let mut page = mock_page();
+  let mentioned_page = |page_id: &str| match page_id {
+    "p1" => Some(MentionedPage {
+      name: "First".to_string(),
+      href: Some("first-p1.html".to_string()),
+    }),
+    "p2" => Some(MentionedPage {
+      name: "Second".to_string(),
+      href: None,
+    }),
+    _ => None,
+  };
+  let asset_href = |url: &str| (url == "/tmp/image.png").then(|| "assets/1-image.png".to_string());
+  prepare_html_export(&mut page, &mentioned_page, &asset_href);
+
+  let html = page.convert_to_html(ConvertBlockToHtmlParams {
+    prev_block_ty: None,
+    next_block_ty: None,
+  });
+  assert_eq!(
+    html,
+    "<p>a &lt; b, see <a href=\"first-p1.html\">First</a> and Second on 2024-05-01</p>\
+    <img src=\"assets/1-image.png\" alt=AppFlowy-Image />\
+    <img src=\"https://example.com/a.png?w=1&amp;h=2\" alt=AppFlowy-Image />"
+  );
+}
+
+#[test]
+fn prepare_html_export_unsafe_link_test() {
+  let mut page: NestedBlock = serde_json::from_value(json!({
+    "type": "page",
+    "children": [
+      {
+        "type": "paragraph",
+        "data": {
+          "delta": [
+            { "insert": "a", "attributes": { "href": "javascript:alert(1)" } },
+            { "insert": "b", "attributes": { "href": " JavaScript:alert(1)" } },
+            { "insert": "c", "attributes": { "href": "java\tscript:alert(1)" } },
+            { "insert": "d", "attributes": { "href": "data:text/html,<script>alert(1)</script>" } },
+            { "insert": "e", "attributes": { "href": "https://appflowy.io" } },
+            { "insert": "f", "attributes": { "href": "mailto:hello@appflowy.io" } },
+            { "insert": "g", "attributes": { "href": "pages/a:b.html" } }
+          ]
+        }
+      }
+    ]
+  }))
+  .unwrap();
+  prepare_html_export(&mut page, &|_: &str| None, &|_: &str| None);
+
+  let html = page.convert_to_html(ConvertBlockToHtmlParams {
+    prev_block_ty: None,
+    next_block_ty: None,
+  });
+  assert_eq!(
+    html,
+    "<p>abcd<a href=\"https://appflowy.io\">e</a>\
+    <a href=\"mailto:hello@appflowy.io\">f</a>\
+    <a href=\"pages/a:b.html\">g</a></p>"
+  );
+}
@@ -0,0 +1,99 @@
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_document::parser::markdown::parser::MarkdownToDocumentParser;
+use flowy_document::parser::markdown::serializer::nested_block_to_markdown;
+use flowy_document::parser::parser_entities::NestedBlock;
+use serde_json::json;
+use std::sync::Arc;
+
+const MARKDOWN: &str = include_str!("../assets/markdown/document.md");
+const JSON: &str = include_str!("../assets/json/markdown.json");
+
+#[test]
+fn nested_block_to_markdown_test() {
+  let block = serde_json::from_str::<NestedBlock>(JSON).unwrap();
+  assert_eq!(nested_block_to_markdown(&block), MARKDOWN);
+}
+
+#[test]
+fn markdown_to_nested_block_test() {
+  let expected = serde_json::from_str::<NestedBlock>(JSON).unwrap();
+  let block = MarkdownToDocumentParser::markdown_to_nested_block(MARKDOWN).unwrap();
+  assert_eq!(block, expected);
+  assert_eq!(expected, block);
+}
+
+#[test]
+fn markdown_round_trip_test() {
+  let block = MarkdownToDocumentParser::markdown_to_nested_block(MARKDOWN).unwrap();
+  assert_eq!(nested_block_to_markdown(&block), MARKDOWN);
+}
+
+#[test]
+fn document_data_to_markdown_test() {
+  let document_data = JsonToDocumentParser::json_str_to_document(JSON)
+    .unwrap()
+    .into();
+  let parser = DocumentDataParser::new(Arc::new(document_data), None);
+  assert_eq!(parser.to_markdown(), MARKDOWN);
+
+  let document_data = MarkdownToDocumentParser::markdown_to_document(MARKDOWN)
+    .unwrap()
+    .into();
+  let parser = DocumentDataParser::new(Arc::new(document_data), None);
+  assert_eq!(parser.to_markdown(), MARKDOWN);
+}
+
+// The emphasis delimiters that wouldn't be read back are replaced by HTML tags
+#[test]
+fn markdown_emphasis_fallback_test() {
+  let block = serde_json::from_value::<NestedBlock>(json!({
+    "type": "page",
+    "children": [{
+      "type": "paragraph",
+      "data": {
+        "delta": [
+          { "insert": "f" },
+          { "insert": "(x)", "attributes": { "bold": true } },
+          { "insert": "y " },
+          { "insert": "bold ", "attributes": { "bold": true } },
+          { "insert": "end" }
+        ]
+      }
+    }]
+  }))
+  .unwrap();
+
+  let markdown = nested_block_to_markdown(&block);
+  assert_eq!(
+    markdown,
+    "f<strong>(x)</strong>y <strong>bold </strong>end\n"
+  );
+  let parsed = MarkdownToDocumentParser::markdown_to_nested_block(&markdown).unwrap();
+  assert_eq!(parsed, block);
+}
+
+// The blocks without a Markdown equivalent are written as the closest one
+#[test]
+fn markdown_lossy_blocks_test() {
+  let block = serde_json::from_value::<NestedBlock>(json!({
+    "type": "page",
+    "children": [
+      {
+        "type": "callout",
+        "data": { "icon": "💡", "delta": [{ "insert": "Note" }] }
+      },
+      {
+        "type": "toggle_list",
+        "data": { "delta": [{ "insert": "Toggle" }] },
+        "children": [{ "type": "paragraph", "data": { "delta": [{ "insert": "Hidden" }] } }]
+      }
+    ]
+  }))
+  .unwrap();
+
+  assert_eq!(
+    nested_block_to_markdown(&block),
+    "> 💡 Note\n\n- Toggle\n\n  Hidden\n"
+  );
+}
@@ -1,4 +1,6 @@
 mod document_data_parser_test;
 mod html;
+mod html_export_test;
 mod json;
+mod markdown_test;
 mod parse_to_html_text;
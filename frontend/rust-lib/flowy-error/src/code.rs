@@ -317,6 +317,18 @@ pub enum ErrorCode {
 
   #[error("Group name is empty")]
   GroupNameIsEmpty = 109,
+
+  #[error("Invalid formula")]
+  InvalidFormula = 110,
+
+  #[error("The cell doesn't pass the validation of its field")]
+  CellValidationFailed = 111,
+
+  #[error("Another row has the same value in the unique field")]
+  DuplicateCellValue = 112,
+
+  #[error("ContentIndexManager or its dependencies are unavailable")]
+  ContentIndexManagerUnavailable = 113,
 }
 
 impl ErrorCode {
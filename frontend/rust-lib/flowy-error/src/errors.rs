@@ -126,11 +126,16 @@ impl FlowyError {
     folder_index_manager_unavailable,
     ErrorCode::FolderIndexManagerUnavailable
   );
+  static_flowy_error!(
+    content_index_manager_unavailable,
+    ErrorCode::ContentIndexManagerUnavailable
+  );
   static_flowy_error!(workspace_data_not_match, ErrorCode::WorkspaceDataNotMatch);
   static_flowy_error!(local_ai, ErrorCode::LocalAIError);
   static_flowy_error!(local_ai_unavailable, ErrorCode::LocalAIUnavailable);
   static_flowy_error!(response_timeout, ErrorCode::ResponseTimeout);
   static_flowy_error!(file_storage_limit, ErrorCode::FileStorageLimitExceeded);
+  static_flowy_error!(invalid_formula, ErrorCode::InvalidFormula);
 }
 
 impl std::convert::From<ErrorCode> for FlowyError {
@@ -0,0 +1,25 @@
+use flowy_derive::ProtoBuf;
+use lib_infra::validator_fn::required_not_empty_str;
+use validator::Validate;
+
+#[derive(Clone, Debug, Validate, ProtoBuf, Default)]
+pub struct ExportViewToHtmlPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub view_id: String,
+
+  /// The directory where the site is written, created if it doesn't exist.
+  #[pb(index = 2)]
+  #[validate(custom(function = "required_not_empty_str"))]
+  pub output_dir: String,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ExportViewToHtmlResponsePB {
+  /// The path of the page of the exported view, the entry point of the site.
+  #[pb(index = 1)]
+  pub index_file_path: String,
+
+  #[pb(index = 2)]
+  pub page_count: i32,
+}
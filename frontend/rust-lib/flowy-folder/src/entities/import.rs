@@ -1,6 +1,6 @@
 use crate::entities::parser::empty_str::NotEmptyStr;
 use crate::entities::ViewLayoutPB;
-use crate::share::{ImportParams, ImportType, ImportValue};
+use crate::share::{ImportOptions, ImportParams, ImportType, ImportValue};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::FlowyError;
 use lib_infra::validator_fn::required_not_empty_str;
@@ -13,6 +13,8 @@ pub enum ImportTypePB {
   Markdown = 2,
   AFDatabase = 3,
   CSV = 4,
+  XLSX = 5,
+  ICS = 6,
 }
 
 impl From<ImportTypePB> for ImportType {
@@ -23,6 +25,8 @@ impl From<ImportTypePB> for ImportType {
       ImportTypePB::Markdown => ImportType::Markdown,
       ImportTypePB::AFDatabase => ImportType::AFDatabase,
       ImportTypePB::CSV => ImportType::CSV,
+      ImportTypePB::XLSX => ImportType::XLSX,
+      ImportTypePB::ICS => ImportType::ICS,
     }
   }
 }
@@ -56,6 +60,15 @@ pub struct ImportValuePayloadPB {
   // the type of the import page
   #[pb(index = 5)]
   pub import_type: ImportTypePB,
+
+  // the id that the progress and the report of the import are sent to
+  #[pb(index = 6, one_of)]
+  pub import_id: Option<String>,
+
+  // the encoded CSVImportPreviewPB with the field types of the columns of a CSV import. If it's
+  // empty, the field types are inferred from the values.
+  #[pb(index = 7, one_of)]
+  pub csv_preview: Option<Vec<u8>>,
 }
 
 #[derive(Clone, Debug, Validate, ProtoBuf, Default)]
@@ -100,6 +113,10 @@ impl TryInto<ImportParams> for ImportPayloadPB {
         file_path,
         view_layout: value.view_layout.into(),
         import_type: value.import_type.into(),
+        options: ImportOptions {
+          import_id: value.import_id,
+          csv_preview: value.csv_preview,
+        },
       };
 
       values.push(params);
@@ -394,6 +394,19 @@ pub(crate) async fn import_zip_file_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_view_to_html_handler(
+  data: AFPluginData<ExportViewToHtmlPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<ExportViewToHtmlResponsePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.try_into_inner()?;
+  let response = folder
+    .export_view_to_html(&data.view_id, &data.output_dir)
+    .await?;
+  data_result_ok(response)
+}
+
 #[tracing::instrument(level = "debug", skip(folder), err)]
 pub(crate) async fn get_folder_snapshots_handler(
   data: AFPluginData<WorkspaceIdPB>,
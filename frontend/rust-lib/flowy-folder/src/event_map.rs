@@ -33,6 +33,7 @@ pub fn init(folder: Weak<FolderManager>) -> AFPlugin {
     .event(FolderEvent::PermanentlyDeleteAllTrashItem, delete_my_trash_handler)
     .event(FolderEvent::ImportData, import_data_handler)
     .event(FolderEvent::ImportZipFile, import_zip_file_handler)
+    .event(FolderEvent::ExportViewToHtml, export_view_to_html_handler)
     .event(FolderEvent::GetFolderSnapshots, get_folder_snapshots_handler)
     .event(FolderEvent::UpdateViewIcon, update_view_icon_handler)
     .event(FolderEvent::ReadFavorites, read_favorites_handler)
@@ -200,4 +201,11 @@ pub enum FolderEvent {
 
   #[event(input = "ImportZipPB")]
   ImportZipFile = 48,
+
+  /// Export the view and its child views to a static HTML site
+  #[event(
+    input = "ExportViewToHtmlPayloadPB",
+    output = "ExportViewToHtmlResponsePB"
+  )]
+  ExportViewToHtml = 49,
 }
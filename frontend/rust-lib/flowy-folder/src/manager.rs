@@ -1,9 +1,10 @@
 use crate::entities::icon::UpdateViewIconParams;
 use crate::entities::{
   view_pb_with_child_views, view_pb_without_child_views, view_pb_without_child_views_from_arc,
-  CreateViewParams, CreateWorkspaceParams, DeletedViewPB, DuplicateViewParams, FolderSnapshotPB,
-  MoveNestedViewParams, RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams,
-  ViewLayoutPB, ViewPB, ViewSectionPB, WorkspacePB, WorkspaceSettingPB,
+  CreateViewParams, CreateWorkspaceParams, DeletedViewPB, DuplicateViewParams,
+  ExportViewToHtmlResponsePB, FolderSnapshotPB, MoveNestedViewParams, RepeatedTrashPB,
+  RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams, ViewLayoutPB, ViewPB, ViewSectionPB,
+  WorkspacePB, WorkspaceSettingPB,
 };
 use crate::manager_observer::{
   notify_child_views_changed, notify_did_update_workspace, notify_parent_view_did_change,
@@ -13,10 +14,13 @@ use crate::notification::{
   send_current_workspace_notification, send_notification, FolderNotification,
 };
 use crate::publish_util::{generate_publish_name, view_pb_to_publish_view};
-use crate::share::{ImportParams, ImportValue};
+use crate::share::{
+  HtmlExportContext, HtmlExportPage, ImportParams, ImportType, ImportValue, HTML_EXPORT_UNAVAILABLE,
+};
 use crate::util::{folder_not_init_error, workspace_data_not_sync_error};
 use crate::view_operation::{
-  create_view, EncodedCollabWrapper, FolderOperationHandler, FolderOperationHandlers, ViewData,
+  create_view, EncodedCollabWrapper, FolderOperationHandler, FolderOperationHandlers, ImportedData,
+  ViewData,
 };
 use arc_swap::ArcSwapOption;
 use collab::core::collab::DataSource;
@@ -42,9 +46,10 @@ use flowy_sqlite::kv::KVStorePreferences;
 use futures::future;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLockWriteGuard;
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
 
 pub trait FolderUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
@@ -1452,6 +1457,77 @@ impl FolderManager {
     Ok(payload)
   }
 
+  /// Exports the view and its child views to a static HTML site written to the `output_dir`.
+  ///
+  /// Each view becomes a page with a navigation sidebar that mirrors the folder tree. The links
+  /// between the exported views are relative and the media of the views are copied to the site,
+  /// so it can be served by any web server, unlike the published views that require AppFlowy
+  /// Cloud.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn export_view_to_html(
+    &self,
+    view_id: &str,
+    output_dir: &str,
+  ) -> FlowyResult<ExportViewToHtmlResponsePB> {
+    let root = self
+      .build_html_export_page(view_id, true)
+      .await
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("Can't find the view with ID: {}", view_id))
+      })?;
+    let view_names = self
+      .get_all_views_pb()
+      .await?
+      .into_iter()
+      .map(|view| (view.id, view.name))
+      .collect::<HashMap<_, _>>();
+    let mut context = HtmlExportContext::new(PathBuf::from(output_dir), &root, view_names)?;
+
+    let pages = root.flatten();
+    for page in &pages {
+      let content = match self.get_handler(&page.layout) {
+        Ok(handler) => {
+          handler
+            .export_view_to_html(&page.view_id, &mut context)
+            .await
+        },
+        Err(err) => Err(err),
+      };
+      // A view that can't be exported doesn't prevent the export of the other views
+      let content = content.unwrap_or_else(|err| {
+        warn!("[Export] failed to export view {}: {}", page.view_id, err);
+        HTML_EXPORT_UNAVAILABLE.to_string()
+      });
+      context.write_page(&root, page, &content)?;
+    }
+
+    Ok(ExportViewToHtmlResponsePB {
+      index_file_path: context.page_path(&root).to_string_lossy().to_string(),
+      page_count: pages.len() as i32,
+    })
+  }
+
+  async fn build_html_export_page(&self, view_id: &str, is_root: bool) -> Option<HtmlExportPage> {
+    let view_pb = self.get_view_pb(view_id).await.ok()?;
+    if view_pb.layout == ViewLayoutPB::Chat {
+      return None;
+    }
+
+    let child_pages_futures = view_pb
+      .child_views
+      .iter()
+      .map(|child| self.build_html_export_page(&child.id, false))
+      .collect::<Vec<_>>();
+    let child_pages = future::join_all(child_pages_futures)
+      .await
+      .into_iter()
+      .flatten()
+      .collect::<Vec<_>>();
+
+    Some(HtmlExportPage::new(&view_pb, is_root, child_pages))
+  }
+
   // Used by toggle_favorites to send notification to frontend, after the favorite status of view has been changed.It sends two distinct notifications: one to correctly update the concerned view's is_favorite status, and another to update the list of favorites that is to be displayed.
   async fn send_toggle_favorite_notification(&self, view_id: &str) {
     if let Ok(view) = self.get_view_pb(view_id).await {
@@ -1563,8 +1639,8 @@ impl FolderManager {
   pub(crate) async fn import_single_file(
     &self,
     parent_view_id: String,
-    import_data: ImportValue,
-  ) -> FlowyResult<(View, Vec<(String, CollabType, EncodedCollab)>)> {
+    mut import_data: ImportValue,
+  ) -> FlowyResult<(Vec<View>, Vec<ImportedData>)> {
     // Ensure either data or file_path is provided
     if import_data.data.is_none() && import_data.file_path.is_none() {
       return Err(FlowyError::new(
@@ -1573,6 +1649,17 @@ impl FolderManager {
       ));
     }
 
+    if matches!(import_data.import_type, ImportType::XLSX) {
+      return self
+        .import_multiple_views(parent_view_id, import_data)
+        .await;
+    }
+
+    // The events of an iCalendar file are always imported as a calendar
+    if matches!(import_data.import_type, ImportType::ICS) {
+      import_data.view_layout = ViewLayout::Calendar;
+    }
+
     let handler = self.get_handler(&import_data.view_layout)?;
     let view_id = gen_view_id().to_string();
     let uid = self.user.user_id()?;
@@ -1587,6 +1674,7 @@ impl FolderManager {
           &import_data.name,
           import_data.import_type,
           data,
+          import_data.options.clone(),
         )
         .await?;
     }
@@ -1594,15 +1682,82 @@ impl FolderManager {
     // Import data from file path if available
     if let Some(file_path) = import_data.file_path {
       handler
-        .import_from_file_path(&view_id, &import_data.name, file_path)
+        .import_from_file_path(
+          &view_id,
+          &import_data.name,
+          import_data.import_type,
+          file_path,
+          import_data.options,
+        )
+        .await?;
+    }
+
+    let view = self
+      .insert_imported_view(
+        parent_view_id,
+        view_id,
+        import_data.name,
+        import_data.view_layout,
+      )
+      .await?;
+    Ok((vec![view], encoded_collab))
+  }
+
+  /// Imports a file that contains multiple views, such as a XLSX workbook in which each sheet is
+  /// imported as a separate grid. The views are named after the file and their sheet.
+  async fn import_multiple_views(
+    &self,
+    parent_view_id: String,
+    import_data: ImportValue,
+  ) -> FlowyResult<(Vec<View>, Vec<ImportedData>)> {
+    let data = match (import_data.data, import_data.file_path) {
+      (Some(data), _) => data,
+      (None, Some(file_path)) => std::fs::read(file_path)?,
+      (None, None) => return Err(FlowyError::invalid_data()),
+    };
+
+    let view_layout = ViewLayout::Grid;
+    let handler = self.get_handler(&view_layout)?;
+    let uid = self.user.user_id()?;
+    let imported_views = handler
+      .import_views_from_bytes(uid, import_data.import_type, data)
+      .await?;
+
+    let has_multiple_views = imported_views.len() > 1;
+    let mut views = vec![];
+    let mut encoded_collabs = vec![];
+    for imported_view in imported_views {
+      let name = if has_multiple_views {
+        format!("{} - {}", import_data.name, imported_view.name)
+      } else {
+        import_data.name.clone()
+      };
+      let view = self
+        .insert_imported_view(
+          parent_view_id.clone(),
+          imported_view.view_id,
+          name,
+          view_layout.clone(),
+        )
         .await?;
+      views.push(view);
+      encoded_collabs.extend(imported_view.imported_data);
     }
+    Ok((views, encoded_collabs))
+  }
 
+  async fn insert_imported_view(
+    &self,
+    parent_view_id: String,
+    view_id: String,
+    name: String,
+    view_layout: ViewLayout,
+  ) -> FlowyResult<View> {
     let params = CreateViewParams {
       parent_view_id,
-      name: import_data.name,
+      name,
       desc: "".to_string(),
-      layout: import_data.view_layout.clone().into(),
+      layout: view_layout.clone().into(),
       initial_data: ViewData::Empty,
       view_id,
       meta: Default::default(),
@@ -1613,7 +1768,7 @@ impl FolderManager {
       icon: None,
     };
 
-    let view = create_view(self.user.user_id()?, params, import_data.view_layout);
+    let view = create_view(self.user.user_id()?, params, view_layout);
 
     // Insert the new view into the folder
     if let Some(lock) = self.mutex_folder.load_full() {
@@ -1621,7 +1776,7 @@ impl FolderManager {
       folder.insert_view(view.clone(), None);
     }
 
-    Ok((view, encoded_collab))
+    Ok(view)
   }
 
   pub(crate) async fn import_zip_file(&self, zip_file_path: &str) -> FlowyResult<()> {
@@ -1636,10 +1791,10 @@ impl FolderManager {
     let mut views = vec![];
     for data in import_data.values {
       // Import a single file and get the view and encoded collab data
-      let (view, encoded_collabs) = self
+      let (imported_views, encoded_collabs) = self
         .import_single_file(import_data.parent_view_id.clone(), data)
         .await?;
-      views.push(view_pb_without_child_views(view));
+      views.extend(imported_views.into_iter().map(view_pb_without_child_views));
 
       for (object_id, collab_type, encode_collab) in encoded_collabs {
         match self.get_folder_collab_params(object_id, collab_type, encode_collab) {
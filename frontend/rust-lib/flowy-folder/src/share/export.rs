@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use collab_folder::ViewLayout;
+use flowy_error::{FlowyError, FlowyResult};
+use lib_infra::util::escape_html;
+
+use crate::entities::{ViewIconTypePB, ViewPB};
+
+pub const HTML_EXPORT_ASSETS_DIR: &str = "assets";
+const HTML_EXPORT_INDEX_FILE_NAME: &str = "index.html";
+const MAX_FILE_NAME_LEN: usize = 40;
+
+/// The content of the pages whose view can't be exported.
+pub(crate) const HTML_EXPORT_UNAVAILABLE: &str = "<p>This page can't be exported.</p>";
+
+const HTML_EXPORT_STYLE: &str = "\
+body{margin:0;display:flex;font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif;color:#1f2329;line-height:1.6}\
+nav{position:sticky;top:0;height:100vh;overflow-y:auto;box-sizing:border-box;width:260px;flex-shrink:0;padding:16px;background:#f7f8fc;border-right:1px solid #e5e5e5}\
+nav ul{list-style:none;margin:0;padding-left:12px}\
+nav>ul{padding-left:0}\
+nav a{display:block;padding:2px 6px;border-radius:4px;color:inherit;text-decoration:none;overflow:hidden;text-overflow:ellipsis;white-space:nowrap}\
+nav a:hover{background:#e9ecf5}\
+nav a.current{background:#e0e8ff;font-weight:600}\
+main{flex-grow:1;min-width:0;max-width:960px;padding:32px 48px}\
+img{max-width:100%}\
+pre{padding:12px;background:#f7f8fc;border-radius:4px;overflow-x:auto}\
+blockquote{margin-left:0;padding-left:12px;border-left:4px solid #00bcf0}\
+aside{padding:12px;background:#f7f8fc;border-radius:4px}\
+table{border-collapse:collapse}\
+th,td{padding:4px 8px;border:1px solid #e5e5e5;text-align:left;vertical-align:top}\
+th{background:#f7f8fc}";
+
+/// A view of the exported subtree. Each view is written to its own page at the root of the site.
+pub struct HtmlExportPage {
+  pub view_id: String,
+  pub name: String,
+  pub icon: Option<String>,
+  pub layout: ViewLayout,
+  pub file_name: String,
+  pub children: Vec<HtmlExportPage>,
+}
+
+impl HtmlExportPage {
+  /// The root of the export is the entry point of the site, so it is written to the index page.
+  pub(crate) fn new(view: &ViewPB, is_root: bool, children: Vec<HtmlExportPage>) -> Self {
+    let file_name = if is_root {
+      HTML_EXPORT_INDEX_FILE_NAME.to_string()
+    } else {
+      page_file_name(&view.id, &view.name)
+    };
+    let icon = view
+      .icon
+      .as_ref()
+      .filter(|icon| icon.ty == ViewIconTypePB::Emoji && !icon.value.is_empty())
+      .map(|icon| icon.value.clone());
+    Self {
+      view_id: view.id.clone(),
+      name: view.name.clone(),
+      icon,
+      layout: view.layout.clone().into(),
+      file_name,
+      children,
+    }
+  }
+
+  fn title(&self) -> String {
+    let name = if self.name.is_empty() {
+      "Untitled"
+    } else {
+      self.name.as_str()
+    };
+    match &self.icon {
+      Some(icon) => format!("{} {}", icon, name),
+      None => name.to_string(),
+    }
+  }
+
+  /// Returns the page and all its descendants, parents first.
+  pub fn flatten(&self) -> Vec<&HtmlExportPage> {
+    let mut pages = vec![self];
+    for child in &self.children {
+      pages.extend(child.flatten());
+    }
+    pages
+  }
+}
+
+/// The context of an export of views to a static HTML site. The handlers use it to link to the
+/// other pages of the site and to copy the media of their views into the site.
+pub struct HtmlExportContext {
+  output_dir: PathBuf,
+  /// The names of all the views of the workspace, used for the mentions of the pages that are not
+  /// part of the export.
+  view_names: HashMap<String, String>,
+  page_file_names: HashMap<String, String>,
+  /// The links to the copied assets by their original url.
+  assets: HashMap<String, String>,
+}
+
+impl HtmlExportContext {
+  pub(crate) fn new(
+    output_dir: PathBuf,
+    root: &HtmlExportPage,
+    view_names: HashMap<String, String>,
+  ) -> FlowyResult<Self> {
+    fs::create_dir_all(&output_dir)?;
+    let page_file_names = root
+      .flatten()
+      .into_iter()
+      .map(|page| (page.view_id.clone(), page.file_name.clone()))
+      .collect();
+    Ok(Self {
+      output_dir,
+      view_names,
+      page_file_names,
+      assets: HashMap::new(),
+    })
+  }
+
+  /// Returns the relative link to the page of the view, `None` if the view is not exported.
+  pub fn page_href(&self, view_id: &str) -> Option<String> {
+    self.page_file_names.get(view_id).cloned()
+  }
+
+  pub fn view_name(&self, view_id: &str) -> Option<String> {
+    self.view_names.get(view_id).cloned()
+  }
+
+  /// Returns the relative link to the copy of the asset, `None` if it hasn't been copied.
+  pub fn asset_href(&self, url: &str) -> Option<String> {
+    self.assets.get(url).cloned()
+  }
+
+  /// Copies the file at `path` to the assets of the site and returns the link to the copy.
+  pub fn copy_asset(&mut self, url: &str, path: &Path) -> FlowyResult<String> {
+    if let Some(href) = self.asset_href(url) {
+      return Ok(href);
+    }
+    let (href, asset_path) = self.new_asset_path(url)?;
+    fs::copy(path, asset_path)?;
+    self.assets.insert(url.to_string(), href.clone());
+    Ok(href)
+  }
+
+  /// Writes the content of the asset downloaded from `url` to the assets of the site and returns
+  /// the link to the copy.
+  pub fn write_asset(&mut self, url: &str, data: &[u8]) -> FlowyResult<String> {
+    if let Some(href) = self.asset_href(url) {
+      return Ok(href);
+    }
+    let (href, asset_path) = self.new_asset_path(url)?;
+    fs::write(asset_path, data)?;
+    self.assets.insert(url.to_string(), href.clone());
+    Ok(href)
+  }
+
+  /// The file name of an asset is made unique by prefixing the name of the original file with the
+  /// number of the asset.
+  fn new_asset_path(&self, url: &str) -> FlowyResult<(String, PathBuf)> {
+    let name = url
+      .split(['?', '#'])
+      .next()
+      .and_then(|path| path.rsplit(['/', '\\']).next())
+      .map(sanitize_file_name)
+      .filter(|name| !name.is_empty())
+      .unwrap_or_else(|| "asset".to_string());
+    let file_name = format!("{}-{}", self.assets.len() + 1, name);
+
+    let assets_dir = self.output_dir.join(HTML_EXPORT_ASSETS_DIR);
+    fs::create_dir_all(&assets_dir)?;
+    let href = format!("{}/{}", HTML_EXPORT_ASSETS_DIR, file_name);
+    Ok((href, assets_dir.join(file_name)))
+  }
+
+  pub fn page_path(&self, page: &HtmlExportPage) -> PathBuf {
+    self.output_dir.join(&page.file_name)
+  }
+
+  /// Writes the page with the navigation sidebar of the site, whose root is `root`.
+  pub(crate) fn write_page(
+    &self,
+    root: &HtmlExportPage,
+    page: &HtmlExportPage,
+    content: &str,
+  ) -> FlowyResult<()> {
+    let html = render_page(root, page, content);
+    fs::write(self.page_path(page), html).map_err(FlowyError::from)
+  }
+}
+
+fn render_page(root: &HtmlExportPage, page: &HtmlExportPage, content: &str) -> String {
+  let title = escape_html(&page.title());
+  let mut navigation = String::from("<ul>");
+  render_navigation(root, &page.view_id, &mut navigation);
+  navigation.push_str("</ul>");
+
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n\
+    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+    <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<nav>{}</nav>\n\
+    <main>\n<h1>{}</h1>\n{}\n</main>\n</body>\n</html>\n",
+    title, HTML_EXPORT_STYLE, navigation, title, content
+  )
+}
+
+fn render_navigation(page: &HtmlExportPage, current_view_id: &str, html: &mut String) {
+  let class = if page.view_id == current_view_id {
+    " class=\"current\""
+  } else {
+    ""
+  };
+  html.push_str(&format!(
+    "<li><a href=\"{}\"{}>{}</a>",
+    escape_html(&page.file_name),
+    class,
+    escape_html(&page.title())
+  ));
+  if !page.children.is_empty() {
+    html.push_str("<ul>");
+    for child in &page.children {
+      render_navigation(child, current_view_id, html);
+    }
+    html.push_str("</ul>");
+  }
+  html.push_str("</li>");
+}
+
+/// The file name of the page is made of the name of the view, so the links are readable, and of
+/// the id of the view, so it is unique.
+fn page_file_name(view_id: &str, name: &str) -> String {
+  let mut slug = String::new();
+  for c in name.chars() {
+    if c.is_ascii_alphanumeric() {
+      slug.push(c.to_ascii_lowercase());
+    } else if !slug.is_empty() && !slug.ends_with('-') {
+      slug.push('-');
+    }
+    if slug.len() >= MAX_FILE_NAME_LEN {
+      break;
+    }
+  }
+  let slug = slug.trim_end_matches('-');
+  if slug.is_empty() {
+    format!("{}.html", view_id)
+  } else {
+    format!("{}-{}.html", slug, view_id)
+  }
+}
+
+fn sanitize_file_name(name: &str) -> String {
+  name
+    .chars()
+    .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+    .take(MAX_FILE_NAME_LEN)
+    .collect::<String>()
+    .trim_start_matches('.')
+    .to_string()
+}
@@ -7,6 +7,8 @@ pub enum ImportType {
   Markdown = 2,
   AFDatabase = 3,
   CSV = 4,
+  XLSX = 5,
+  ICS = 6,
 }
 
 #[derive(Clone, Debug)]
@@ -16,6 +18,17 @@ pub struct ImportValue {
   pub file_path: Option<String>,
   pub view_layout: ViewLayout,
   pub import_type: ImportType,
+  pub options: ImportOptions,
+}
+
+/// The options of an import that are only used by some of the handlers.
+#[derive(Clone, Debug, Default)]
+pub struct ImportOptions {
+  /// The id that the progress and the report of the import are sent to. The view id is generated
+  /// during the import, so the caller can't use it to observe the import.
+  pub import_id: Option<String>,
+  /// The encoded `CSVImportPreviewPB` with the field types chosen for the columns of a CSV file.
+  pub csv_preview: Option<Vec<u8>>,
 }
 
 #[derive(Clone, Debug)]
@@ -1,3 +1,5 @@
+mod export;
 mod import;
 
+pub use export::*;
 pub use import::*;
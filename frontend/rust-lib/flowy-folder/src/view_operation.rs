@@ -15,7 +15,7 @@ use lib_infra::util::timestamp;
 
 use crate::entities::{CreateViewParams, ViewLayoutPB};
 use crate::manager::FolderUser;
-use crate::share::ImportType;
+use crate::share::{HtmlExportContext, ImportOptions, ImportType};
 
 #[derive(Debug, Clone)]
 pub enum EncodedCollabWrapper {
@@ -39,6 +39,14 @@ pub struct DatabaseEncodedCollab {
 
 pub type ImportedData = (String, CollabType, EncodedCollab);
 
+/// One of the views created by importing a file that contains multiple views, for example, a
+/// sheet of a XLSX workbook.
+pub struct ImportedView {
+  pub view_id: String,
+  pub name: String,
+  pub imported_data: Vec<ImportedData>,
+}
+
 /// The handler will be used to handler the folder operation for a specific
 /// view layout. Each [ViewLayout] will have a handler. So when creating a new
 /// view, the [ViewLayout] will be used to get the handler.
@@ -76,6 +84,16 @@ pub trait FolderOperationHandler {
     Err(FlowyError::not_support())
   }
 
+  /// Renders the content of the view as an HTML fragment for the static site export. The links
+  /// to the other exported views and the copies of the media are provided by the `context`.
+  async fn export_view_to_html(
+    &self,
+    _view_id: &str,
+    _context: &mut HtmlExportContext,
+  ) -> Result<String, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
   /// Create a view with the data.
   ///
   /// # Arguments
@@ -121,14 +139,28 @@ pub trait FolderOperationHandler {
     name: &str,
     import_type: ImportType,
     bytes: Vec<u8>,
+    options: ImportOptions,
   ) -> Result<Vec<ImportedData>, FlowyError>;
 
+  /// Create multiple views by importing data that contains more than one view. Unlike the
+  /// [FolderOperationHandler::import_from_bytes], the handler generates the view ids.
+  async fn import_views_from_bytes(
+    &self,
+    _uid: i64,
+    _import_type: ImportType,
+    _bytes: Vec<u8>,
+  ) -> Result<Vec<ImportedView>, FlowyError> {
+    Err(FlowyError::not_support())
+  }
+
   /// Create a view by importing data from a file
   async fn import_from_file_path(
     &self,
     view_id: &str,
     name: &str,
+    import_type: ImportType,
     path: String,
+    options: ImportOptions,
   ) -> Result<(), FlowyError>;
 
   /// Called when the view is updated. The handler is the `old` registered handler.
@@ -45,3 +45,30 @@ pub trait FolderIndexManager: IndexManager {
     workspace_id: String,
   );
 }
+
+/// The text of a block of a document, or of the cells of a row of a database.
+pub struct IndexableContent {
+  /// The id of the block or the row
+  pub id: String,
+  /// The id of the document or the database that the block or the row belongs to
+  pub object_id: String,
+  /// The id of the view that displays the content
+  pub view_id: String,
+  pub content: String,
+  pub workspace_id: String,
+}
+
+/// Indexes the content of the documents or the databases, so the local search can find the
+/// block or the row that contains the query.
+pub trait ContentIndexManager: Send + Sync {
+  /// Replaces all the indexed content of the object with the given contents.
+  fn index_object(
+    &self,
+    object_id: &str,
+    contents: Vec<IndexableContent>,
+  ) -> Result<(), FlowyError>;
+  fn update_contents(&self, contents: Vec<IndexableContent>) -> Result<(), FlowyError>;
+  fn remove_contents(&self, ids: Vec<String>) -> Result<(), FlowyError>;
+  fn remove_object(&self, object_id: &str) -> Result<(), FlowyError>;
+  fn is_object_indexed(&self, object_id: &str) -> bool;
+}
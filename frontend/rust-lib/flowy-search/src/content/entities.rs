@@ -0,0 +1,53 @@
+use flowy_folder::entities::ViewPB;
+use flowy_folder::ViewLayout;
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{IndexTypePB, ResultIconPB, ResultIconTypePB, SearchResultPB};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContentIndexData {
+  pub id: String,
+  pub object_id: String,
+  pub view_id: String,
+  pub workspace_id: String,
+  pub content: String,
+}
+
+/// A block or a row that matches the query of the local search.
+#[derive(Debug)]
+pub struct ContentSearchResult {
+  pub data: ContentIndexData,
+  /// The part of the content around the matched terms
+  pub preview: String,
+  pub score: f64,
+}
+
+impl ContentSearchResult {
+  /// Returns the result that points to the block or the row displayed in the view.
+  pub fn into_search_result(self, index_type: IndexTypePB, view: &ViewPB) -> SearchResultPB {
+    SearchResultPB {
+      index_type,
+      view_id: self.data.view_id,
+      id: self.data.id,
+      data: view.name.clone(),
+      icon: Some(view_result_icon(view)),
+      score: self.score,
+      workspace_id: self.data.workspace_id,
+      preview: Some(self.preview),
+    }
+  }
+}
+
+/// Returns the icon of the view, or the icon of its layout if the view has no icon.
+pub fn view_result_icon(view: &ViewPB) -> ResultIconPB {
+  match view.icon.clone() {
+    Some(view_icon) => ResultIconPB::from(view_icon),
+    None => {
+      let view_layout_ty: i64 = ViewLayout::from(view.layout.clone()).into();
+      ResultIconPB {
+        ty: ResultIconTypePB::Icon,
+        value: view_layout_ty.to_string(),
+      }
+    },
+  }
+}
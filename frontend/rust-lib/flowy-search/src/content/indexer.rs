@@ -0,0 +1,325 @@
+use std::{
+  collections::HashMap,
+  fs,
+  ops::Deref,
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex, MutexGuard, Weak},
+};
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::{ContentIndexManager, IndexableContent};
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use tantivy::{
+  collector::TopDocs,
+  directory::MmapDirectory,
+  doc,
+  query::{BooleanQuery, Occur, Query, QueryParser, TermQuery},
+  schema::{Field, IndexRecordOption},
+  snippet::SnippetGenerator,
+  Document, Index, IndexReader, IndexWriter, TantivyDocument, Term,
+};
+
+use super::{
+  entities::{ContentIndexData, ContentSearchResult},
+  schema::{
+    ContentSchema, CONTENT_FIELD_NAME, CONTENT_ID_FIELD_NAME, CONTENT_OBJECT_ID_FIELD_NAME,
+    CONTENT_VIEW_ID_FIELD_NAME, CONTENT_WORKSPACE_ID_FIELD_NAME,
+  },
+};
+
+pub const DOCUMENT_INDEX_DIR: &str = "document_index";
+pub const DATABASE_INDEX_DIR: &str = "database_index";
+
+const SEARCH_LIMIT: usize = 20;
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// Indexes the text of the blocks of the documents, or of the rows of the databases, in a
+/// tantivy index stored in the given directory of the user's index path.
+#[derive(Clone)]
+pub struct ContentIndexManagerImpl {
+  content_schema: Option<ContentSchema>,
+  index: Option<Index>,
+  index_reader: Option<IndexReader>,
+  index_writer: Option<Arc<Mutex<IndexWriter>>>,
+}
+
+struct ContentFields {
+  id: Field,
+  object_id: Field,
+  view_id: Field,
+  workspace_id: Field,
+  content: Field,
+}
+
+impl ContentIndexManagerImpl {
+  pub fn new(auth_user: Option<Weak<AuthenticateUser>>, index_dir: &str) -> Self {
+    // AuthenticateUser is required to get the index path
+    let storage_path = match auth_user.and_then(|auth_user| auth_user.upgrade()) {
+      Some(auth_user) => auth_user.get_index_path(),
+      None => {
+        tracing::error!("ContentIndexManager: AuthenticateUser is not available");
+        return ContentIndexManagerImpl::empty();
+      },
+    };
+
+    Self::open(storage_path.join(Path::new(index_dir)))
+  }
+
+  /// Opens the index stored in the directory, or creates it if the directory is empty.
+  pub fn open(index_path: PathBuf) -> Self {
+    if !index_path.exists() {
+      if let Err(e) = fs::create_dir_all(&index_path) {
+        tracing::error!(
+          "ContentIndexManager failed to create index directory: {:?}",
+          e
+        );
+        return ContentIndexManagerImpl::empty();
+      }
+    }
+
+    let content_schema = ContentSchema::new();
+    let index = match MmapDirectory::open(index_path) {
+      Ok(dir) => match Index::open_or_create(dir, content_schema.schema.clone()) {
+        Ok(index) => index,
+        Err(e) => {
+          tracing::error!("ContentIndexManager failed to open index: {:?}", e);
+          return ContentIndexManagerImpl::empty();
+        },
+      },
+      Err(e) => {
+        tracing::error!(
+          "ContentIndexManager failed to open index directory: {:?}",
+          e
+        );
+        return ContentIndexManagerImpl::empty();
+      },
+    };
+
+    let (index_reader, index_writer) = match (index.reader(), index.writer(50_000_000)) {
+      (Ok(reader), Ok(writer)) => (reader, writer),
+      _ => {
+        tracing::error!("ContentIndexManager failed to instantiate index writer and/or reader");
+        return ContentIndexManagerImpl::empty();
+      },
+    };
+
+    Self {
+      content_schema: Some(content_schema),
+      index: Some(index),
+      index_reader: Some(index_reader),
+      index_writer: Some(Arc::new(Mutex::new(index_writer))),
+    }
+  }
+
+  fn empty() -> Self {
+    Self {
+      content_schema: None,
+      index: None,
+      index_reader: None,
+      index_writer: None,
+    }
+  }
+
+  pub fn num_docs(&self) -> u64 {
+    self
+      .index_reader
+      .as_ref()
+      .map(|reader| reader.searcher().num_docs())
+      .unwrap_or(0)
+  }
+
+  fn get_index_writer(&self) -> FlowyResult<MutexGuard<IndexWriter>> {
+    match &self.index_writer {
+      Some(index_writer) => match index_writer.deref().lock() {
+        Ok(writer) => Ok(writer),
+        Err(e) => {
+          tracing::error!("ContentIndexManager failed to lock index writer: {:?}", e);
+          Err(FlowyError::content_index_manager_unavailable())
+        },
+      },
+      None => Err(FlowyError::content_index_manager_unavailable()),
+    }
+  }
+
+  fn get_content_schema(&self) -> FlowyResult<&ContentSchema> {
+    self
+      .content_schema
+      .as_ref()
+      .ok_or_else(FlowyError::content_index_manager_unavailable)
+  }
+
+  fn get_schema_fields(&self) -> FlowyResult<ContentFields> {
+    let schema = &self.get_content_schema()?.schema;
+    Ok(ContentFields {
+      id: schema.get_field(CONTENT_ID_FIELD_NAME)?,
+      object_id: schema.get_field(CONTENT_OBJECT_ID_FIELD_NAME)?,
+      view_id: schema.get_field(CONTENT_VIEW_ID_FIELD_NAME)?,
+      workspace_id: schema.get_field(CONTENT_WORKSPACE_ID_FIELD_NAME)?,
+      content: schema.get_field(CONTENT_FIELD_NAME)?,
+    })
+  }
+
+  /// Commits the changes and reloads the reader, so the changes are visible to the next search.
+  fn commit(&self, index_writer: &mut IndexWriter) -> FlowyResult<()> {
+    index_writer.commit()?;
+    if let Some(index_reader) = &self.index_reader {
+      index_reader.reload()?;
+    }
+    Ok(())
+  }
+
+  fn add_contents(
+    &self,
+    index_writer: &mut IndexWriter,
+    fields: &ContentFields,
+    contents: Vec<IndexableContent>,
+  ) {
+    for data in contents {
+      let _ = index_writer.add_document(doc![
+        fields.id => data.id,
+        fields.object_id => data.object_id,
+        fields.view_id => data.view_id,
+        fields.workspace_id => data.workspace_id,
+        fields.content => data.content,
+      ]);
+    }
+  }
+
+  /// Returns the blocks or the rows whose text matches the query, ordered by relevance.
+  pub fn search(
+    &self,
+    query: &str,
+    workspace_id: Option<&str>,
+  ) -> FlowyResult<Vec<ContentSearchResult>> {
+    let content_schema = self.get_content_schema()?;
+    let (index, index_reader) = self
+      .index
+      .as_ref()
+      .zip(self.index_reader.as_ref())
+      .ok_or_else(FlowyError::content_index_manager_unavailable)?;
+    let fields = self.get_schema_fields()?;
+
+    // The content is typed by the user, so the query is parsed leniently instead of failing on
+    // the characters that have a meaning in the query syntax.
+    let query_parser = QueryParser::for_index(index, vec![fields.content]);
+    let (content_query, _) = query_parser.parse_query_lenient(query);
+
+    let searcher = index_reader.searcher();
+    let mut snippet_generator =
+      SnippetGenerator::create(&searcher, &*content_query, fields.content)?;
+    snippet_generator.set_max_num_chars(PREVIEW_MAX_CHARS);
+
+    // The workspace is part of the query, so that the results of the other workspaces don't
+    // take the places of the search limit.
+    let built_query: Box<dyn Query> = match workspace_id {
+      Some(workspace_id) => {
+        let workspace_query = TermQuery::new(
+          Term::from_field_text(fields.workspace_id, workspace_id),
+          IndexRecordOption::Basic,
+        );
+        Box::new(BooleanQuery::new(vec![
+          (Occur::Must, content_query),
+          (Occur::Must, Box::new(workspace_query)),
+        ]))
+      },
+      None => content_query,
+    };
+
+    let mut search_results = vec![];
+    let top_docs = searcher.search(&built_query, &TopDocs::with_limit(SEARCH_LIMIT))?;
+    for (score, doc_address) in top_docs {
+      let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+
+      let mut content = HashMap::new();
+      let named_doc = retrieved_doc.to_named_doc(&content_schema.schema);
+      for (k, v) in named_doc.0 {
+        content.insert(k, v[0].clone());
+      }
+
+      let s = serde_json::to_string(&content)?;
+      let data = serde_json::from_str::<ContentIndexData>(&s)?;
+
+      let snippet = snippet_generator.snippet_from_doc(&retrieved_doc);
+      let preview = if snippet.fragment().is_empty() {
+        data.content.chars().take(PREVIEW_MAX_CHARS).collect()
+      } else {
+        snippet.fragment().to_string()
+      };
+
+      // The scores of tantivy are not bounded, so they are mapped into (0, 1) to be comparable
+      // with the scores of the other search handlers.
+      let score = score as f64;
+      search_results.push(ContentSearchResult {
+        data,
+        preview,
+        score: score / (score + 1.0),
+      });
+    }
+
+    Ok(search_results)
+  }
+}
+
+impl ContentIndexManager for ContentIndexManagerImpl {
+  fn index_object(
+    &self,
+    object_id: &str,
+    contents: Vec<IndexableContent>,
+  ) -> Result<(), FlowyError> {
+    let fields = self.get_schema_fields()?;
+    let mut index_writer = self.get_index_writer()?;
+    index_writer.delete_term(Term::from_field_text(fields.object_id, object_id));
+    self.add_contents(&mut index_writer, &fields, contents);
+    self.commit(&mut index_writer)?;
+    Ok(())
+  }
+
+  fn update_contents(&self, contents: Vec<IndexableContent>) -> Result<(), FlowyError> {
+    if contents.is_empty() {
+      return Ok(());
+    }
+
+    let fields = self.get_schema_fields()?;
+    let mut index_writer = self.get_index_writer()?;
+    for data in &contents {
+      index_writer.delete_term(Term::from_field_text(fields.id, &data.id));
+    }
+    self.add_contents(&mut index_writer, &fields, contents);
+    self.commit(&mut index_writer)?;
+    Ok(())
+  }
+
+  fn remove_contents(&self, ids: Vec<String>) -> Result<(), FlowyError> {
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let fields = self.get_schema_fields()?;
+    let mut index_writer = self.get_index_writer()?;
+    for id in ids {
+      index_writer.delete_term(Term::from_field_text(fields.id, &id));
+    }
+    self.commit(&mut index_writer)?;
+    Ok(())
+  }
+
+  fn remove_object(&self, object_id: &str) -> Result<(), FlowyError> {
+    let fields = self.get_schema_fields()?;
+    let mut index_writer = self.get_index_writer()?;
+    index_writer.delete_term(Term::from_field_text(fields.object_id, object_id));
+    self.commit(&mut index_writer)?;
+    Ok(())
+  }
+
+  fn is_object_indexed(&self, object_id: &str) -> bool {
+    let (Some(index_reader), Ok(fields)) = (self.index_reader.as_ref(), self.get_schema_fields())
+    else {
+      return false;
+    };
+    let term = Term::from_field_text(fields.object_id, object_id);
+    index_reader
+      .searcher()
+      .doc_freq(&term)
+      .map_or(false, |doc_freq| doc_freq > 0)
+  }
+}
@@ -0,0 +1,51 @@
+use tantivy::schema::Schema;
+
+pub const CONTENT_ID_FIELD_NAME: &str = "id";
+pub const CONTENT_OBJECT_ID_FIELD_NAME: &str = "object_id";
+pub const CONTENT_VIEW_ID_FIELD_NAME: &str = "view_id";
+pub const CONTENT_WORKSPACE_ID_FIELD_NAME: &str = "workspace_id";
+pub const CONTENT_FIELD_NAME: &str = "content";
+
+#[derive(Clone)]
+pub struct ContentSchema {
+  pub schema: Schema,
+}
+
+/// Do not change the schema after the index has been created.
+/// See [crate::folder::schema::FolderSchema] for the details.
+///
+impl ContentSchema {
+  pub fn new() -> Self {
+    let mut schema_builder = Schema::builder();
+    schema_builder.add_text_field(
+      CONTENT_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      CONTENT_OBJECT_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      CONTENT_VIEW_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      CONTENT_WORKSPACE_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      CONTENT_FIELD_NAME,
+      tantivy::schema::TEXT | tantivy::schema::STORED,
+    );
+
+    let schema = schema_builder.build();
+
+    Self { schema }
+  }
+}
+
+impl Default for ContentSchema {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use tracing::trace;
+
+use flowy_error::FlowyResult;
+use flowy_folder::manager::FolderManager;
+use lib_infra::async_trait::async_trait;
+
+use crate::{
+  content::indexer::ContentIndexManagerImpl,
+  entities::{IndexTypePB, SearchFilterPB, SearchResultPB},
+  services::manager::{SearchHandler, SearchType},
+};
+
+pub struct DatabaseSearchHandler {
+  pub folder_manager: Arc<FolderManager>,
+  pub index_manager: Arc<ContentIndexManagerImpl>,
+}
+
+impl DatabaseSearchHandler {
+  pub fn new(
+    folder_manager: Arc<FolderManager>,
+    index_manager: Arc<ContentIndexManagerImpl>,
+  ) -> Self {
+    Self {
+      folder_manager,
+      index_manager,
+    }
+  }
+}
+
+#[async_trait]
+impl SearchHandler for DatabaseSearchHandler {
+  fn search_type(&self) -> SearchType {
+    SearchType::Database
+  }
+
+  async fn perform_search(
+    &self,
+    query: String,
+    filter: Option<SearchFilterPB>,
+  ) -> FlowyResult<Vec<SearchResultPB>> {
+    let workspace_id = filter.and_then(|filter| filter.workspace_id);
+    let results = self.index_manager.search(&query, workspace_id.as_deref())?;
+    trace!("[Search] database search results: {:?}", results);
+
+    // The rows of the databases whose views are trashed or private are not shown
+    let views = self.folder_manager.get_all_views_pb().await?;
+    let search_results = results
+      .into_iter()
+      .filter_map(|result| {
+        let view = views.iter().find(|v| v.id == result.data.view_id)?;
+        Some(result.into_search_result(IndexTypePB::DatabaseRow, view))
+      })
+      .collect();
+    Ok(search_results)
+  }
+
+  fn index_count(&self) -> u64 {
+    self.index_manager.num_docs()
+  }
+}
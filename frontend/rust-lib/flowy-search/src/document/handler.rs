@@ -2,28 +2,32 @@ use std::sync::Arc;
 use tracing::{trace, warn};
 
 use flowy_error::FlowyResult;
-use flowy_folder::{manager::FolderManager, ViewLayout};
+use flowy_folder::manager::FolderManager;
 use flowy_search_pub::cloud::SearchCloudService;
 use lib_infra::async_trait::async_trait;
 
 use crate::{
-  entities::{IndexTypePB, ResultIconPB, ResultIconTypePB, SearchFilterPB, SearchResultPB},
+  content::{entities::view_result_icon, indexer::ContentIndexManagerImpl},
+  entities::{IndexTypePB, SearchFilterPB, SearchResultPB},
   services::manager::{SearchHandler, SearchType},
 };
 
 pub struct DocumentSearchHandler {
   pub cloud_service: Arc<dyn SearchCloudService>,
   pub folder_manager: Arc<FolderManager>,
+  pub index_manager: Arc<ContentIndexManagerImpl>,
 }
 
 impl DocumentSearchHandler {
   pub fn new(
     cloud_service: Arc<dyn SearchCloudService>,
     folder_manager: Arc<FolderManager>,
+    index_manager: Arc<ContentIndexManagerImpl>,
   ) -> Self {
     Self {
       cloud_service,
       folder_manager,
+      index_manager,
     }
   }
 }
@@ -39,48 +43,59 @@ impl SearchHandler for DocumentSearchHandler {
     query: String,
     filter: Option<SearchFilterPB>,
   ) -> FlowyResult<Vec<SearchResultPB>> {
-    let filter = match filter {
-      Some(filter) => filter,
-      None => return Ok(vec![]),
-    };
+    let workspace_id = filter.and_then(|filter| filter.workspace_id);
+
+    // Grab all views from folder cache
+    // Notice that `get_all_view_pb` returns Views that don't include trashed and private views
+    let views = self.folder_manager.get_all_views_pb().await?;
+    let mut search_results: Vec<SearchResultPB> = vec![];
+
+    // The local index points to the blocks that contain the query
+    let local_results = self.index_manager.search(&query, workspace_id.as_deref())?;
+    trace!("[Search] local search results: {:?}", local_results);
+    for result in local_results {
+      if let Some(view) = views.iter().find(|v| v.id == result.data.view_id) {
+        search_results.push(result.into_search_result(IndexTypePB::DocumentBlock, view));
+      }
+    }
 
-    let workspace_id = match filter.workspace_id {
+    let workspace_id = match workspace_id {
       Some(workspace_id) => workspace_id,
-      None => return Ok(vec![]),
+      None => return Ok(search_results),
     };
 
-    let results = self
+    // The cloud search is not available for the local server, in which case only the local
+    // results are returned.
+    let results = match self
       .cloud_service
       .document_search(&workspace_id, query)
-      .await?;
+      .await
+    {
+      Ok(results) => results,
+      Err(err) => {
+        trace!("[Search] remote search is unavailable: {}", err);
+        return Ok(search_results);
+      },
+    };
     trace!("[Search] remote search results: {:?}", results);
 
-    // Grab all views from folder cache
-    // Notice that `get_all_view_pb` returns Views that don't include trashed and private views
-    let mut views = self.folder_manager.get_all_views_pb().await?.into_iter();
-    let mut search_results: Vec<SearchResultPB> = vec![];
-
     for result in results {
-      if let Some(view) = views.find(|v| v.id == result.object_id) {
-        // If there is no View for the result, we don't add it to the results
-        // If possible we will extract the icon to display for the result
-        let icon: Option<ResultIconPB> = match view.icon.clone() {
-          Some(view_icon) => Some(ResultIconPB::from(view_icon)),
-          None => {
-            let view_layout_ty: i64 = ViewLayout::from(view.layout.clone()).into();
-            Some(ResultIconPB {
-              ty: ResultIconTypePB::Icon,
-              value: view_layout_ty.to_string(),
-            })
-          },
-        };
+      // The local results of the document already point to its blocks
+      if search_results
+        .iter()
+        .any(|search_result| search_result.view_id == result.object_id)
+      {
+        continue;
+      }
 
+      if let Some(view) = views.iter().find(|v| v.id == result.object_id) {
+        // If there is no View for the result, we don't add it to the results
         search_results.push(SearchResultPB {
           index_type: IndexTypePB::Document,
           view_id: result.object_id.clone(),
           id: result.object_id.clone(),
           data: view.name.clone(),
-          icon,
+          icon: Some(view_result_icon(view)),
           // We reverse the score, the cloud search score is based on
           // 1 being the worst result, and closer to 0 being good result, that is
           // the opposite of local search.
@@ -97,8 +112,7 @@ impl SearchHandler for DocumentSearchHandler {
     Ok(search_results)
   }
 
-  /// Ignore for [DocumentSearchHandler]
   fn index_count(&self) -> u64 {
-    0
+    self.index_manager.num_docs()
   }
 }
@@ -1,3 +1,5 @@
+pub mod content;
+pub mod database;
 pub mod document;
 pub mod entities;
 pub mod event_handler;
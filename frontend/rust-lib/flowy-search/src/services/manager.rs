@@ -12,6 +12,7 @@ use tokio::sync::broadcast;
 pub enum SearchType {
   Folder,
   Document,
+  Database,
 }
 
 #[async_trait]
@@ -0,0 +1,107 @@
+use flowy_search::content::indexer::ContentIndexManagerImpl;
+use flowy_search_pub::entities::{ContentIndexManager, IndexableContent};
+
+fn content(id: &str, object_id: &str, content: &str) -> IndexableContent {
+  workspace_content("w1", id, object_id, content)
+}
+
+fn workspace_content(
+  workspace_id: &str,
+  id: &str,
+  object_id: &str,
+  content: &str,
+) -> IndexableContent {
+  IndexableContent {
+    id: id.to_string(),
+    object_id: object_id.to_string(),
+    view_id: object_id.to_string(),
+    content: content.to_string(),
+    workspace_id: workspace_id.to_string(),
+  }
+}
+
+#[test]
+fn search_content_index_test() {
+  let dir = tempfile::tempdir().unwrap();
+  let indexer = ContentIndexManagerImpl::open(dir.path().to_path_buf());
+  indexer
+    .index_object(
+      "doc_1",
+      vec![
+        content("block_1", "doc_1", "The quick brown fox"),
+        content("block_2", "doc_1", "jumps over the lazy dog"),
+      ],
+    )
+    .unwrap();
+  indexer
+    .index_object(
+      "doc_2",
+      vec![content("block_3", "doc_2", "A lazy afternoon")],
+    )
+    .unwrap();
+  assert!(indexer.is_object_indexed("doc_1"));
+
+  // The results point to the blocks that contain the query
+  let mut ids = indexer
+    .search("lazy", Some("w1"))
+    .unwrap()
+    .into_iter()
+    .map(|result| result.data.id)
+    .collect::<Vec<_>>();
+  ids.sort();
+  assert_eq!(ids, vec!["block_2", "block_3"]);
+  assert!(indexer.search("lazy", Some("w2")).unwrap().is_empty());
+
+  // Indexing the object again replaces its blocks
+  indexer
+    .index_object(
+      "doc_1",
+      vec![content("block_1", "doc_1", "The quick brown cat")],
+    )
+    .unwrap();
+  let results = indexer.search("lazy", None).unwrap();
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].data.id, "block_3");
+  assert!(results[0].preview.contains("lazy"));
+
+  indexer
+    .update_contents(vec![content("block_3", "doc_2", "A busy afternoon")])
+    .unwrap();
+  assert!(indexer.search("lazy", None).unwrap().is_empty());
+
+  indexer.remove_object("doc_2").unwrap();
+  assert!(indexer.search("afternoon", None).unwrap().is_empty());
+}
+
+#[test]
+fn search_content_index_of_workspace_test() {
+  let dir = tempfile::tempdir().unwrap();
+  let indexer = ContentIndexManagerImpl::open(dir.path().to_path_buf());
+  // The other workspace has more matches than the search limit
+  let other_contents = (0..30)
+    .map(|i| workspace_content("w2", &format!("w2_block_{}", i), "w2_doc", "lazy lazy lazy"))
+    .collect();
+  indexer.index_object("w2_doc", other_contents).unwrap();
+  indexer
+    .index_object(
+      "w1_doc",
+      vec![
+        content("w1_block_1", "w1_doc", "A lazy afternoon"),
+        content(
+          "w1_block_2",
+          "w1_doc",
+          "The lazy dog and the quick brown fox",
+        ),
+      ],
+    )
+    .unwrap();
+
+  let mut ids = indexer
+    .search("lazy", Some("w1"))
+    .unwrap()
+    .into_iter()
+    .map(|result| result.data.id)
+    .collect::<Vec<_>>();
+  ids.sort();
+  assert_eq!(ids, vec!["w1_block_1", "w1_block_2"]);
+}
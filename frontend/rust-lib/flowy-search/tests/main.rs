@@ -1,3 +1,4 @@
 // mod search;
 
+mod content_index_test;
 mod tantivy_test;
@@ -7,8 +7,13 @@ use collab_user::core::default_user_awareness_data;
 use flowy_database_pub::cloud::{DatabaseCloudService, DatabaseSnapshot, EncodeCollabByOid};
 use flowy_error::FlowyError;
 use lib_infra::async_trait::async_trait;
+use std::sync::Arc;
 
-pub(crate) struct LocalServerDatabaseCloudServiceImpl();
+use crate::local_server::LocalServerDB;
+
+pub(crate) struct LocalServerDatabaseCloudServiceImpl {
+  pub db: Arc<dyn LocalServerDB>,
+}
 
 #[async_trait]
 impl DatabaseCloudService for LocalServerDatabaseCloudServiceImpl {
@@ -56,9 +61,10 @@ impl DatabaseCloudService for LocalServerDatabaseCloudServiceImpl {
 
   async fn get_database_collab_object_snapshots(
     &self,
-    _object_id: &str,
-    _limit: usize,
+    object_id: &str,
+    limit: usize,
   ) -> Result<Vec<DatabaseSnapshot>, FlowyError> {
-    Ok(vec![])
+    let uid = self.db.get_current_uid()?;
+    self.db.get_database_snapshots(uid, object_id, limit)
   }
 }
@@ -0,0 +1,608 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use bytes::Bytes;
+use mime_guess::mime::{self, Mime};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{error, trace};
+
+use flowy_error::{internal_error, FlowyError, FlowyResult};
+use flowy_storage_pub::cloud::{ObjectIdentity, ObjectValue, StorageCloudService};
+use flowy_storage_pub::storage::{CompletedPartRequest, CreateUploadResponse, UploadPartResponse};
+use lib_dispatch::prelude::af_spawn;
+use lib_infra::async_trait::async_trait;
+
+use crate::local_server::LocalServerDB;
+
+pub const LOCAL_STORAGE_URL_PREFIX: &str = "appflowy-local://storage/";
+
+/// The uploads that are not completed within this duration are removed by the garbage collection.
+const STAGING_EXPIRATION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const UPLOAD_META_FILE: &str = "upload.json";
+/// The garbage collection runs this long after the first deletion that follows the last
+/// collection, so deleting many files walks the storage only once.
+const GARBAGE_COLLECTION_DELAY: Duration = Duration::from_secs(10 * 60);
+static IS_GARBAGE_COLLECTION_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+/// Serializes the writes of the objects and the references with the garbage collection, so an
+/// object can't be collected between being written and being referenced.
+static STORAGE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Stores the files of the local server under the user's data directory.
+pub(crate) struct LocalServerFileStorageImpl {
+  pub db: Arc<dyn LocalServerDB>,
+}
+
+impl LocalServerFileStorageImpl {
+  fn storage(&self) -> FlowyResult<LocalFileStorage> {
+    let root = self.db.get_user_data_dir()?.join("storage");
+    Ok(LocalFileStorage::new(root))
+  }
+
+  /// Runs the operation on the storage on a blocking thread, so the file system calls don't block
+  /// the async runtime.
+  async fn with_storage<T, F>(&self, f: F) -> FlowyResult<T>
+  where
+    T: Send + 'static,
+    F: FnOnce(LocalFileStorage) -> FlowyResult<T> + Send + 'static,
+  {
+    let storage = self.storage()?;
+    tokio::task::spawn_blocking(move || f(storage))
+      .await
+      .map_err(internal_error)?
+  }
+}
+
+/// Schedules a garbage collection of the storage, unless one is already scheduled.
+fn schedule_garbage_collection(storage: LocalFileStorage) {
+  if IS_GARBAGE_COLLECTION_SCHEDULED.swap(true, Ordering::AcqRel) {
+    return;
+  }
+  af_spawn(async move {
+    tokio::time::sleep(GARBAGE_COLLECTION_DELAY).await;
+    IS_GARBAGE_COLLECTION_SCHEDULED.store(false, Ordering::Release);
+    let result = tokio::task::spawn_blocking(move || storage.collect_garbage())
+      .await
+      .map_err(internal_error)
+      .and_then(|result| result);
+    match result {
+      Ok(num_of_removed_objects) => trace!(
+        "[File] removed {} objects from local storage",
+        num_of_removed_objects
+      ),
+      Err(err) => error!("[File] collect garbage of local storage failed: {}", err),
+    }
+  });
+}
+
+#[async_trait]
+impl StorageCloudService for LocalServerFileStorageImpl {
+  async fn get_object_url(&self, object_id: ObjectIdentity) -> Result<String, FlowyError> {
+    let file_name = format!("{}.{}", object_id.file_id, object_id.ext);
+    object_url(&[&object_id.workspace_id, &file_name])
+  }
+
+  async fn put_object(&self, url: String, object_value: ObjectValue) -> Result<(), FlowyError> {
+    let path = parse_object_url(&url)?;
+    self
+      .with_storage(move |storage| {
+        storage.put_object(&path, &object_value.raw, object_value.mime.as_ref())
+      })
+      .await
+  }
+
+  async fn delete_object(&self, url: &str) -> Result<(), FlowyError> {
+    let path = parse_object_url(url)?;
+    self
+      .with_storage(move |storage| storage.delete_object(&path))
+      .await?;
+    schedule_garbage_collection(self.storage()?);
+    Ok(())
+  }
+
+  async fn get_object(&self, url: String) -> Result<ObjectValue, FlowyError> {
+    let path = parse_object_url(&url)?;
+    self
+      .with_storage(move |storage| storage.get_object(&path))
+      .await
+  }
+
+  async fn get_object_url_v1(
+    &self,
+    workspace_id: &str,
+    parent_dir: &str,
+    file_id: &str,
+  ) -> FlowyResult<String> {
+    object_url(&[workspace_id, parent_dir, file_id])
+  }
+
+  async fn parse_object_url_v1(&self, url: &str) -> Option<(String, String, String)> {
+    let mut path = parse_object_url(url).ok()?;
+    if path.len() != 3 {
+      return None;
+    }
+    let file_id = path.pop()?;
+    let parent_dir = path.pop()?;
+    let workspace_id = path.pop()?;
+    Some((workspace_id, parent_dir, file_id))
+  }
+
+  async fn create_upload(
+    &self,
+    workspace_id: &str,
+    parent_dir: &str,
+    file_id: &str,
+    content_type: &str,
+  ) -> Result<CreateUploadResponse, FlowyError> {
+    let meta = UploadMeta {
+      workspace_id: workspace_id.to_string(),
+      parent_dir: parent_dir.to_string(),
+      file_id: file_id.to_string(),
+      content_type: content_type.to_string(),
+    };
+    let upload_id = self
+      .with_storage(move |storage| storage.create_upload(meta))
+      .await?;
+    Ok(CreateUploadResponse {
+      file_id: file_id.to_string(),
+      upload_id,
+    })
+  }
+
+  async fn upload_part(
+    &self,
+    _workspace_id: &str,
+    _parent_dir: &str,
+    upload_id: &str,
+    file_id: &str,
+    part_number: i32,
+    body: Vec<u8>,
+  ) -> Result<UploadPartResponse, FlowyError> {
+    let (upload_id, file_id) = (upload_id.to_string(), file_id.to_string());
+    let e_tag = self
+      .with_storage(move |storage| storage.upload_part(&upload_id, &file_id, part_number, &body))
+      .await?;
+    Ok(UploadPartResponse {
+      e_tag,
+      part_num: part_number,
+    })
+  }
+
+  async fn complete_upload(
+    &self,
+    _workspace_id: &str,
+    _parent_dir: &str,
+    upload_id: &str,
+    file_id: &str,
+    parts: Vec<CompletedPartRequest>,
+  ) -> Result<(), FlowyError> {
+    let (upload_id, file_id) = (upload_id.to_string(), file_id.to_string());
+    self
+      .with_storage(move |storage| storage.complete_upload(&upload_id, &file_id, parts))
+      .await
+  }
+}
+
+fn object_url(path: &[&str]) -> FlowyResult<String> {
+  for component in path {
+    validate_path_component(component)?;
+  }
+  Ok(format!("{}{}", LOCAL_STORAGE_URL_PREFIX, path.join("/")))
+}
+
+/// Returns the path components of the url, which are `[workspace_id, file_name]` for the urls
+/// of [StorageCloudService::get_object_url] and `[workspace_id, parent_dir, file_id]` for the
+/// urls of [StorageCloudService::get_object_url_v1].
+fn parse_object_url(url: &str) -> FlowyResult<Vec<String>> {
+  let path = url
+    .strip_prefix(LOCAL_STORAGE_URL_PREFIX)
+    .ok_or_else(|| FlowyError::invalid_data().with_context(format!("Invalid url: {}", url)))?;
+  let components = path.split('/').map(str::to_string).collect::<Vec<_>>();
+  if !(2..=3).contains(&components.len()) {
+    return Err(FlowyError::invalid_data().with_context(format!("Invalid url: {}", url)));
+  }
+  for component in &components {
+    validate_path_component(component)?;
+  }
+  Ok(components)
+}
+
+fn validate_path_component(component: &str) -> FlowyResult<()> {
+  if component.is_empty()
+    || component == "."
+    || component == ".."
+    || component.contains(['/', '\\'])
+  {
+    return Err(
+      FlowyError::invalid_data().with_context(format!("Invalid path component: {}", component)),
+    );
+  }
+  Ok(())
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+  hex::encode(Sha256::digest(data))
+}
+
+#[derive(Serialize, Deserialize)]
+struct UploadMeta {
+  workspace_id: String,
+  parent_dir: String,
+  file_id: String,
+  content_type: String,
+}
+
+/// Points a file at the object that holds its content.
+#[derive(Serialize, Deserialize)]
+struct ObjectRef {
+  hash: String,
+  content_type: String,
+}
+
+/// A content-addressed store on the local disk:
+/// - `objects/<sha256>`: the content of the files. The files with the same content share an object.
+/// - `files/<workspace_id>/[<parent_dir>/]<file_id>`: the [ObjectRef] of each file.
+/// - `staging/<upload_id>/`: the parts of the uploads that are not completed.
+/// - `tmp/`: the objects being written.
+struct LocalFileStorage {
+  root: PathBuf,
+}
+
+impl LocalFileStorage {
+  fn new(root: PathBuf) -> Self {
+    Self { root }
+  }
+
+  fn objects_dir(&self) -> PathBuf {
+    self.root.join("objects")
+  }
+
+  fn files_dir(&self) -> PathBuf {
+    self.root.join("files")
+  }
+
+  fn staging_dir(&self, upload_id: &str) -> FlowyResult<PathBuf> {
+    validate_path_component(upload_id)?;
+    Ok(self.root.join("staging").join(upload_id))
+  }
+
+  fn tmp_dir(&self) -> PathBuf {
+    self.root.join("tmp")
+  }
+
+  fn ref_path(&self, path: &[String]) -> PathBuf {
+    path
+      .iter()
+      .fold(self.files_dir(), |dir, component| dir.join(component))
+  }
+
+  fn read_ref(&self, path: &[String]) -> FlowyResult<ObjectRef> {
+    let ref_path = self.ref_path(path);
+    if !ref_path.exists() {
+      return Err(
+        FlowyError::record_not_found().with_context(format!("File not found: {}", path.join("/"))),
+      );
+    }
+    Ok(serde_json::from_slice(&fs::read(ref_path)?)?)
+  }
+
+  fn write_ref(&self, path: &[String], object_ref: &ObjectRef) -> FlowyResult<()> {
+    let ref_path = self.ref_path(path);
+    if let Some(parent) = ref_path.parent() {
+      fs::create_dir_all(parent)?;
+    }
+    fs::write(ref_path, serde_json::to_vec(object_ref)?)?;
+    Ok(())
+  }
+
+  /// Moves the file written in the tmp directory into the objects directory, unless an object
+  /// with the same content already exists.
+  fn commit_object(&self, tmp_path: &Path, hash: &str) -> FlowyResult<()> {
+    let objects_dir = self.objects_dir();
+    fs::create_dir_all(&objects_dir)?;
+    let object_path = objects_dir.join(hash);
+    if object_path.exists() {
+      fs::remove_file(tmp_path)?;
+    } else {
+      fs::rename(tmp_path, object_path)?;
+    }
+    Ok(())
+  }
+
+  fn new_tmp_file(&self) -> FlowyResult<(PathBuf, fs::File)> {
+    let tmp_dir = self.tmp_dir();
+    fs::create_dir_all(&tmp_dir)?;
+    let tmp_path = tmp_dir.join(uuid::Uuid::new_v4().to_string());
+    let file = fs::File::create(&tmp_path)?;
+    Ok((tmp_path, file))
+  }
+
+  fn put_object(&self, path: &[String], data: &[u8], content_type: &str) -> FlowyResult<()> {
+    let hash = sha256_hex(data);
+    let (tmp_path, mut file) = self.new_tmp_file()?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    let _guard = STORAGE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    self.commit_object(&tmp_path, &hash)?;
+    self.write_ref(
+      path,
+      &ObjectRef {
+        hash,
+        content_type: content_type.to_string(),
+      },
+    )
+  }
+
+  fn get_object(&self, path: &[String]) -> FlowyResult<ObjectValue> {
+    let object_ref = self.read_ref(path)?;
+    let raw = fs::read(self.objects_dir().join(&object_ref.hash))?;
+    let mime = object_ref
+      .content_type
+      .parse::<Mime>()
+      .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+    Ok(ObjectValue {
+      raw: Bytes::from(raw),
+      mime,
+    })
+  }
+
+  /// Removes the file. Its object is removed by the next [LocalFileStorage::collect_garbage].
+  fn delete_object(&self, path: &[String]) -> FlowyResult<()> {
+    let ref_path = self.ref_path(path);
+    if ref_path.exists() {
+      fs::remove_file(ref_path)?;
+    }
+    Ok(())
+  }
+
+  fn create_upload(&self, meta: UploadMeta) -> FlowyResult<String> {
+    for component in [&meta.workspace_id, &meta.parent_dir, &meta.file_id] {
+      validate_path_component(component)?;
+    }
+    let upload_id = uuid::Uuid::new_v4().to_string();
+    let staging_dir = self.staging_dir(&upload_id)?;
+    fs::create_dir_all(&staging_dir)?;
+    fs::write(
+      staging_dir.join(UPLOAD_META_FILE),
+      serde_json::to_vec(&meta)?,
+    )?;
+    Ok(upload_id)
+  }
+
+  fn read_upload_meta(&self, upload_id: &str, file_id: &str) -> FlowyResult<UploadMeta> {
+    let meta_path = self.staging_dir(upload_id)?.join(UPLOAD_META_FILE);
+    if !meta_path.exists() {
+      return Err(
+        FlowyError::record_not_found().with_context(format!("Upload not found: {}", upload_id)),
+      );
+    }
+    let meta: UploadMeta = serde_json::from_slice(&fs::read(meta_path)?)?;
+    if meta.file_id != file_id {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "Upload {} doesn't belong to file {}",
+        upload_id, file_id
+      )));
+    }
+    Ok(meta)
+  }
+
+  /// Writes the part into the staging area and returns its e-tag, the sha256 of its content.
+  /// Uploading the same part again replaces it.
+  fn upload_part(
+    &self,
+    upload_id: &str,
+    file_id: &str,
+    part_number: i32,
+    body: &[u8],
+  ) -> FlowyResult<String> {
+    self.read_upload_meta(upload_id, file_id)?;
+    let part_path = self
+      .staging_dir(upload_id)?
+      .join(format!("part-{}", part_number));
+    fs::write(part_path, body)?;
+    Ok(sha256_hex(body))
+  }
+
+  /// Concatenates the parts in the order of their part numbers into an object, and points the
+  /// file of the upload at it.
+  fn complete_upload(
+    &self,
+    upload_id: &str,
+    file_id: &str,
+    mut parts: Vec<CompletedPartRequest>,
+  ) -> FlowyResult<()> {
+    let meta = self.read_upload_meta(upload_id, file_id)?;
+    let staging_dir = self.staging_dir(upload_id)?;
+    parts.sort_by_key(|part| part.part_number);
+
+    let (tmp_path, mut file) = self.new_tmp_file()?;
+    let mut hasher = Sha256::new();
+    for part in &parts {
+      let body =
+        fs::read(staging_dir.join(format!("part-{}", part.part_number))).map_err(|err| {
+          FlowyError::record_not_found()
+            .with_context(format!("Part {} not found: {}", part.part_number, err))
+        })?;
+      if sha256_hex(&body) != part.e_tag {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(FlowyError::invalid_data().with_context(format!(
+          "The e-tag of part {} doesn't match",
+          part.part_number
+        )));
+      }
+      hasher.update(&body);
+      file.write_all(&body)?;
+    }
+    file.sync_all()?;
+    let hash = hex::encode(hasher.finalize());
+
+    {
+      let _guard = STORAGE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+      self.commit_object(&tmp_path, &hash)?;
+      let path = [meta.workspace_id, meta.parent_dir, meta.file_id];
+      self.write_ref(
+        &path,
+        &ObjectRef {
+          hash,
+          content_type: meta.content_type,
+        },
+      )?;
+    }
+    fs::remove_dir_all(staging_dir)?;
+    trace!("[File] completed local upload: {}", upload_id);
+    Ok(())
+  }
+
+  /// Removes the objects that no file refers to, and the uploads that are not completed within
+  /// [STAGING_EXPIRATION]. Returns the number of removed objects.
+  fn collect_garbage(&self) -> FlowyResult<usize> {
+    let _guard = STORAGE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let mut referenced_hashes = HashSet::new();
+    collect_referenced_hashes(&self.files_dir(), &mut referenced_hashes)?;
+
+    let mut num_of_removed_objects = 0;
+    for entry in read_dir_if_exists(&self.objects_dir())? {
+      let entry = entry?;
+      let hash = entry.file_name().to_string_lossy().to_string();
+      if !referenced_hashes.contains(&hash) {
+        fs::remove_file(entry.path())?;
+        num_of_removed_objects += 1;
+      }
+    }
+
+    let now = SystemTime::now();
+    let is_expired = |path: &Path| {
+      fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(|modified| {
+          now
+            .duration_since(modified)
+            .map_or(false, |age| age > STAGING_EXPIRATION)
+        })
+        .unwrap_or(false)
+    };
+    for entry in read_dir_if_exists(&self.root.join("staging"))? {
+      let path = entry?.path();
+      if is_expired(&path) {
+        fs::remove_dir_all(path)?;
+      }
+    }
+    for entry in read_dir_if_exists(&self.tmp_dir())? {
+      let path = entry?.path();
+      if is_expired(&path) {
+        fs::remove_file(path)?;
+      }
+    }
+    Ok(num_of_removed_objects)
+  }
+}
+
+fn read_dir_if_exists(dir: &Path) -> FlowyResult<Vec<std::io::Result<fs::DirEntry>>> {
+  if !dir.exists() {
+    return Ok(vec![]);
+  }
+  Ok(fs::read_dir(dir)?.collect())
+}
+
+fn collect_referenced_hashes(dir: &Path, hashes: &mut HashSet<String>) -> FlowyResult<()> {
+  for entry in read_dir_if_exists(dir)? {
+    let path = entry?.path();
+    if path.is_dir() {
+      collect_referenced_hashes(&path, hashes)?;
+    } else {
+      match serde_json::from_slice::<ObjectRef>(&fs::read(&path)?) {
+        Ok(object_ref) => {
+          hashes.insert(object_ref.hash);
+        },
+        Err(err) => error!("[File] invalid file reference {:?}: {}", path, err),
+      }
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use flowy_storage_pub::storage::CompletedPartRequest;
+
+  use super::{LocalFileStorage, UploadMeta};
+
+  fn temp_storage() -> (PathBuf, LocalFileStorage) {
+    let root = std::env::temp_dir().join(format!("local_storage_{}", uuid::Uuid::new_v4()));
+    (root.clone(), LocalFileStorage::new(root))
+  }
+
+  fn file_path(file_id: &str) -> Vec<String> {
+    vec!["w1".to_string(), "dir".to_string(), file_id.to_string()]
+  }
+
+  #[test]
+  fn multipart_upload_test() {
+    let (root, storage) = temp_storage();
+    let upload_id = storage
+      .create_upload(UploadMeta {
+        workspace_id: "w1".to_string(),
+        parent_dir: "dir".to_string(),
+        file_id: "f1".to_string(),
+        content_type: "text/plain".to_string(),
+      })
+      .unwrap();
+    let e_tag_2 = storage.upload_part(&upload_id, "f1", 2, b"world").unwrap();
+    let e_tag_1 = storage.upload_part(&upload_id, "f1", 1, b"hello ").unwrap();
+    assert!(storage.upload_part(&upload_id, "f2", 3, b"!").is_err());
+
+    let parts = vec![
+      CompletedPartRequest {
+        e_tag: e_tag_2,
+        part_number: 2,
+      },
+      CompletedPartRequest {
+        e_tag: e_tag_1,
+        part_number: 1,
+      },
+    ];
+    storage.complete_upload(&upload_id, "f1", parts).unwrap();
+    assert!(!storage.staging_dir(&upload_id).unwrap().exists());
+
+    let object = storage.get_object(&file_path("f1")).unwrap();
+    assert_eq!(object.raw.as_ref(), b"hello world");
+    assert_eq!(object.mime.as_ref(), "text/plain");
+    std::fs::remove_dir_all(root).unwrap();
+  }
+
+  #[test]
+  fn collect_orphaned_objects_test() {
+    let (root, storage) = temp_storage();
+    storage
+      .put_object(&file_path("f1"), b"same", "text/plain")
+      .unwrap();
+    storage
+      .put_object(&file_path("f2"), b"same", "text/plain")
+      .unwrap();
+    storage
+      .put_object(&file_path("f3"), b"other", "text/plain")
+      .unwrap();
+
+    storage.delete_object(&file_path("f1")).unwrap();
+    storage.delete_object(&file_path("f3")).unwrap();
+    assert_eq!(storage.collect_garbage().unwrap(), 1);
+    assert!(storage.get_object(&file_path("f1")).is_err());
+    assert_eq!(
+      storage.get_object(&file_path("f2")).unwrap().raw.as_ref(),
+      b"same"
+    );
+
+    storage.delete_object(&file_path("f2")).unwrap();
+    assert_eq!(storage.collect_garbage().unwrap(), 1);
+    std::fs::remove_dir_all(root).unwrap();
+  }
+}
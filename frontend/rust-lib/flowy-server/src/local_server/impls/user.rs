@@ -1,13 +1,21 @@
+use chrono::Utc;
+use collab::core::collab::DataSource;
 use collab::core::origin::CollabOrigin;
+use collab::entity::EncodedCollab;
 use collab::preclude::Collab;
+use collab_database::database::mut_database_views_with_collab;
+use collab_database::rows::database_row_document_id_from_row_id;
+use collab_database::workspace_database::WorkspaceDatabase;
 use collab_entity::CollabObject;
 use collab_user::core::UserAwareness;
 use lazy_static::lazy_static;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use flowy_error::FlowyError;
+use flowy_error::{internal_error, FlowyError};
+use flowy_folder_pub::cloud::{gen_workspace_id, Folder, FolderData, Workspace};
 use flowy_user_pub::cloud::{UserCloudService, UserCollabParams};
 use flowy_user_pub::entities::*;
 use flowy_user_pub::DEFAULT_USER_NAME;
@@ -24,7 +32,6 @@ lazy_static! {
 }
 
 pub(crate) struct LocalServerUserAuthServiceImpl {
-  #[allow(dead_code)]
   pub db: Arc<dyn LocalServerDB>,
 }
 
@@ -134,15 +141,19 @@ impl UserCloudService for LocalServerUserAuthServiceImpl {
     }
   }
 
-  async fn open_workspace(&self, _workspace_id: &str) -> Result<UserWorkspace, FlowyError> {
-    Err(
-      FlowyError::local_version_not_support()
-        .with_context("local server doesn't support open workspace"),
-    )
+  async fn open_workspace(&self, workspace_id: &str) -> Result<UserWorkspace, FlowyError> {
+    let uid = self.db.get_current_uid()?;
+    self
+      .db
+      .get_user_workspace_by_id(uid, workspace_id)?
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("workspace {} doesn't exist", workspace_id))
+      })
   }
 
-  async fn get_all_workspace(&self, _uid: i64) -> Result<Vec<UserWorkspace>, FlowyError> {
-    Ok(vec![])
+  async fn get_all_workspace(&self, uid: i64) -> Result<Vec<UserWorkspace>, FlowyError> {
+    self.db.get_all_user_workspaces(uid)
   }
 
   async fn get_user_awareness_doc_state(
@@ -180,33 +191,149 @@ impl UserCloudService for LocalServerUserAuthServiceImpl {
     )
   }
 
-  async fn create_workspace(&self, _workspace_name: &str) -> Result<UserWorkspace, FlowyError> {
-    Err(
-      FlowyError::local_version_not_support()
-        .with_context("local server doesn't support multiple workspaces"),
-    )
+  /// Creates the folder of the new workspace in the collab key-value store. The workspace itself
+  /// is saved in the sqlite database by the caller, and its workspace database is created empty
+  /// when the workspace is opened.
+  async fn create_workspace(&self, workspace_name: &str) -> Result<UserWorkspace, FlowyError> {
+    let uid = self.db.get_current_uid()?;
+    let workspace_id = gen_workspace_id().to_string();
+    let encoded_folder = empty_folder_encoded_collab(uid, &workspace_id, workspace_name)?;
+    self.db.save_collab(uid, &workspace_id, encoded_folder)?;
+
+    Ok(UserWorkspace {
+      id: workspace_id,
+      name: workspace_name.to_string(),
+      created_at: Utc::now(),
+      workspace_database_id: Uuid::new_v4().to_string(),
+      icon: "".to_string(),
+    })
   }
 
-  async fn delete_workspace(&self, _workspace_id: &str) -> Result<(), FlowyError> {
-    Err(
-      FlowyError::local_version_not_support()
-        .with_context("local server doesn't support multiple workspaces"),
-    )
+  /// Removes the collabs of the workspace from the collab key-value store: the documents of the
+  /// folder's views, the databases with their rows and row documents, the folder and the
+  /// workspace database. The workspace itself is removed from the sqlite database by the caller.
+  async fn delete_workspace(&self, workspace_id: &str) -> Result<(), FlowyError> {
+    let uid = self.db.get_current_uid()?;
+    if self.db.get_current_workspace_id()? == workspace_id {
+      return Err(
+        FlowyError::invalid_data().with_context("Can't delete the workspace that is open"),
+      );
+    }
+    let workspace = self
+      .db
+      .get_user_workspace_by_id(uid, workspace_id)?
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("workspace {} doesn't exist", workspace_id))
+      })?;
+
+    let mut object_ids = vec![];
+    if let Some(collab) = open_collab(self.db.as_ref(), uid, &workspace.id)? {
+      let folder = Folder::open(uid, collab, None).map_err(internal_error)?;
+      if let Some(folder_data) = folder.get_folder_data(&workspace.id) {
+        object_ids.extend(
+          folder_data
+            .views
+            .into_iter()
+            .filter(|view| !view.layout.is_database())
+            .map(|view| view.id),
+        );
+      }
+    }
+
+    if let Some(collab) = open_collab(self.db.as_ref(), uid, &workspace.workspace_database_id)? {
+      let workspace_database = WorkspaceDatabase::open(collab).map_err(internal_error)?;
+      for database_meta in workspace_database.get_all_database_meta() {
+        let database_id = database_meta.database_id;
+        if let Some(mut collab) = open_collab(self.db.as_ref(), uid, &database_id)? {
+          mut_database_views_with_collab(&mut collab, |database_view| {
+            for row_order in database_view.row_orders.iter() {
+              let row_id = String::from(row_order.id.clone());
+              object_ids.push(database_row_document_id_from_row_id(&row_id));
+              object_ids.push(row_id);
+            }
+          });
+        }
+        object_ids.push(database_id);
+      }
+    }
+
+    object_ids.push(workspace.id.clone());
+    object_ids.push(workspace.workspace_database_id.clone());
+    let object_ids = object_ids.into_iter().collect::<HashSet<_>>();
+    for object_id in object_ids {
+      self.db.delete_collab(uid, &object_id)?;
+    }
+    Ok(())
   }
 
+  /// The name and the icon are saved in the sqlite database by the caller
   async fn patch_workspace(
     &self,
-    _workspace_id: &str,
-    _new_workspace_name: Option<&str>,
+    workspace_id: &str,
+    new_workspace_name: Option<&str>,
     _new_workspace_icon: Option<&str>,
   ) -> Result<(), FlowyError> {
-    Err(
-      FlowyError::local_version_not_support()
-        .with_context("local server doesn't support multiple workspaces"),
-    )
+    if new_workspace_name.map_or(false, |name| name.trim().is_empty()) {
+      return Err(FlowyError::invalid_data().with_context("The workspace name is empty"));
+    }
+    let uid = self.db.get_current_uid()?;
+    if self
+      .db
+      .get_user_workspace_by_id(uid, workspace_id)?
+      .is_none()
+    {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("workspace {} doesn't exist", workspace_id)),
+      );
+    }
+    Ok(())
   }
 }
 
+/// Opens the collab stored in the user's collab key-value store, if any
+fn open_collab(
+  db: &dyn LocalServerDB,
+  uid: i64,
+  object_id: &str,
+) -> Result<Option<Collab>, FlowyError> {
+  match db.get_collab(uid, object_id)? {
+    None => Ok(None),
+    Some(encoded_collab) => {
+      let collab = Collab::new_with_source(
+        CollabOrigin::Empty,
+        object_id,
+        DataSource::DocStateV1(encoded_collab.doc_state.to_vec()),
+        vec![],
+        false,
+      )
+      .map_err(internal_error)?;
+      Ok(Some(collab))
+    },
+  }
+}
+
+/// Returns the folder of a new workspace that doesn't have any view yet
+fn empty_folder_encoded_collab(
+  uid: i64,
+  workspace_id: &str,
+  workspace_name: &str,
+) -> Result<EncodedCollab, FlowyError> {
+  let collab = Collab::new_with_origin(CollabOrigin::Empty, workspace_id, vec![], false);
+  let folder_data = FolderData {
+    workspace: Workspace::new(workspace_id.to_string(), workspace_name.to_string(), uid),
+    current_view: "".to_string(),
+    views: vec![],
+    favorites: Default::default(),
+    recent: Default::default(),
+    trash: Default::default(),
+    private: Default::default(),
+  };
+  let folder = Folder::create(uid, collab, None, folder_data);
+  folder.encode_collab().map_err(internal_error)
+}
+
 fn make_user_workspace() -> UserWorkspace {
   UserWorkspace {
     id: uuid::Uuid::new_v4().to_string(),
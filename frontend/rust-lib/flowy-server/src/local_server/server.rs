@@ -1,9 +1,11 @@
+use collab::entity::EncodedCollab;
 use flowy_search_pub::cloud::SearchCloudService;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use tokio::sync::mpsc;
 
-use flowy_database_pub::cloud::{DatabaseAIService, DatabaseCloudService};
+use flowy_database_pub::cloud::{DatabaseAIService, DatabaseCloudService, DatabaseSnapshot};
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::FlowyError;
 use flowy_folder_pub::cloud::FolderCloudService;
@@ -16,13 +18,45 @@ use flowy_user_pub::entities::*;
 
 use crate::local_server::impls::{
   LocalServerDatabaseCloudServiceImpl, LocalServerDocumentCloudServiceImpl,
-  LocalServerFolderCloudServiceImpl, LocalServerUserAuthServiceImpl,
+  LocalServerFileStorageImpl, LocalServerFolderCloudServiceImpl, LocalServerUserAuthServiceImpl,
 };
 use crate::AppFlowyServer;
 
 pub trait LocalServerDB: Send + Sync + 'static {
   fn get_user_profile(&self, uid: i64) -> Result<UserProfile, FlowyError>;
   fn get_user_workspace(&self, uid: i64) -> Result<Option<UserWorkspace>, FlowyError>;
+
+  /// Returns the id of the signed in user
+  fn get_current_uid(&self) -> Result<i64, FlowyError>;
+  /// Returns the id of the workspace that is open
+  fn get_current_workspace_id(&self) -> Result<String, FlowyError>;
+  /// Returns the workspaces of the user stored in the user's sqlite database
+  fn get_all_user_workspaces(&self, uid: i64) -> Result<Vec<UserWorkspace>, FlowyError>;
+  fn get_user_workspace_by_id(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+  ) -> Result<Option<UserWorkspace>, FlowyError>;
+  /// Writes the collab into the user's collab key-value store
+  fn save_collab(
+    &self,
+    uid: i64,
+    object_id: &str,
+    encoded_collab: EncodedCollab,
+  ) -> Result<(), FlowyError>;
+  /// Returns the collab stored in the user's collab key-value store, if any
+  fn get_collab(&self, uid: i64, object_id: &str) -> Result<Option<EncodedCollab>, FlowyError>;
+  /// Removes the collab from the user's collab key-value store
+  fn delete_collab(&self, uid: i64, object_id: &str) -> Result<(), FlowyError>;
+  /// Returns the directory of the signed in user's data, where the local files are stored
+  fn get_user_data_dir(&self) -> Result<PathBuf, FlowyError>;
+  /// Returns the latest `limit` snapshots of the database, the latest first
+  fn get_database_snapshots(
+    &self,
+    uid: i64,
+    database_id: &str,
+    limit: usize,
+  ) -> Result<Vec<DatabaseSnapshot>, FlowyError>;
 }
 
 pub struct LocalServer {
@@ -60,7 +94,9 @@ impl AppFlowyServer for LocalServer {
   }
 
   fn database_service(&self) -> Arc<dyn DatabaseCloudService> {
-    Arc::new(LocalServerDatabaseCloudServiceImpl())
+    Arc::new(LocalServerDatabaseCloudServiceImpl {
+      db: self.local_db.clone(),
+    })
   }
 
   fn document_service(&self) -> Arc<dyn DocumentCloudService> {
@@ -68,7 +104,9 @@ impl AppFlowyServer for LocalServer {
   }
 
   fn file_storage(&self) -> Option<Arc<dyn StorageCloudService>> {
-    None
+    Some(Arc::new(LocalServerFileStorageImpl {
+      db: self.local_db.clone(),
+    }))
   }
 
   fn search_service(&self) -> Option<Arc<dyn SearchCloudService>> {
@@ -1,4 +1,5 @@
 use crate::chunked_byte::ChunkedBytes;
+use crate::cloud::ObjectValue;
 use async_trait::async_trait;
 pub use client_api_entity::{CompletedPartRequest, CreateUploadResponse, UploadPartResponse};
 use flowy_error::{FlowyError, FlowyResult};
@@ -15,6 +16,10 @@ pub trait StorageService: Send + Sync {
 
   fn download_object(&self, url: String, local_file_path: String) -> FlowyResult<()>;
 
+  /// Returns the content of the file stored at the url. Fails if the url doesn't belong to the
+  /// file storage.
+  async fn get_object(&self, url: String) -> FlowyResult<ObjectValue>;
+
   async fn create_upload(
     &self,
     workspace_id: &str,
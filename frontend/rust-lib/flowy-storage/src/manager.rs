@@ -14,7 +14,7 @@ use dashmap::DashMap;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_sqlite::DBConnection;
 use flowy_storage_pub::chunked_byte::{ChunkedBytes, MIN_CHUNK_SIZE};
-use flowy_storage_pub::cloud::StorageCloudService;
+use flowy_storage_pub::cloud::{ObjectValue, StorageCloudService};
 use flowy_storage_pub::storage::{
   CompletedPartRequest, CreatedUpload, FileProgress, FileProgressReceiver, FileUploadState,
   ProgressNotifier, StorageService, UploadPartResponse,
@@ -271,6 +271,17 @@ impl StorageService for StorageServiceImpl {
     Ok(())
   }
 
+  async fn get_object(&self, url: String) -> FlowyResult<ObjectValue> {
+    // The url is not sent to the cloud service if it points to another host, because the
+    // requests of the cloud service carry the credentials of the user.
+    if self.cloud_service.parse_object_url_v1(&url).await.is_none() {
+      return Err(
+        FlowyError::invalid_data().with_context(format!("Not a url of the file storage: {}", url)),
+      );
+    }
+    self.cloud_service.get_object(url).await
+  }
+
   async fn create_upload(
     &self,
     workspace_id: &str,
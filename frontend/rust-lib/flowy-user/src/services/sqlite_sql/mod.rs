@@ -1,3 +1,3 @@
 pub(crate) mod member_sql;
 pub(crate) mod user_sql;
-pub(crate) mod workspace_sql;
+pub mod workspace_sql;
@@ -54,7 +54,6 @@ impl BoxAny {
     }
   }
 
-  #[allow(dead_code)]
   pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
     self.0.downcast_ref()
   }